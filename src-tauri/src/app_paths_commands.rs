@@ -0,0 +1,89 @@
+//! Expose the app's well-known directories (app data, attachments, caches, skills) to the frontend.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::Manager;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppPaths {
+    pub app_data_dir: String,
+    pub attachments_dir: String,
+    pub pdf_cache_dir: String,
+    pub cove_skills_dir: String,
+    /// None when the home directory can't be resolved
+    pub officellm_home: Option<String>,
+    pub tmp_dir: String,
+}
+
+fn path_string(p: &Path) -> String {
+    p.to_string_lossy().into_owned()
+}
+
+/// Core logic, separated from the AppHandle for testability.
+pub(crate) fn app_paths_from(
+    app_data_dir: &Path,
+    officellm_home: Option<PathBuf>,
+) -> Result<AppPaths, String> {
+    Ok(AppPaths {
+        app_data_dir: path_string(app_data_dir),
+        attachments_dir: path_string(&crate::attachment_commands::attachments_dir(app_data_dir)),
+        pdf_cache_dir: path_string(&crate::docx_commands::pdf_cache_dir(app_data_dir)),
+        cove_skills_dir: path_string(&crate::skill_commands::cove_skills_dir()?),
+        officellm_home: officellm_home.as_deref().map(path_string),
+        tmp_dir: path_string(&crate::officellm::env::tmp_dir()),
+    })
+}
+
+/// Where attachments, the PDF cache, skills and officellm state live on this machine.
+#[tauri::command]
+pub fn get_app_paths(app: tauri::AppHandle) -> Result<AppPaths, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+    // Same resolution as `officellm_env_info`: without a binary this is the external home
+    let bin = crate::officellm::resolve::resolve_bin();
+    let officellm_home = crate::officellm::resolve::resolve_home_for_bin(bin.as_ref(), &app).ok();
+    app_paths_from(&app_data_dir, officellm_home)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::with_home;
+
+    #[test]
+    fn paths_are_absolute_and_non_empty() {
+        with_home(|home| {
+            let app_data = home.join("app-data");
+            let paths = app_paths_from(&app_data, Some(home.join(".officellm"))).unwrap();
+            let all = [
+                &paths.app_data_dir,
+                &paths.attachments_dir,
+                &paths.pdf_cache_dir,
+                &paths.cove_skills_dir,
+                paths.officellm_home.as_ref().unwrap(),
+                &paths.tmp_dir,
+            ];
+            for p in all {
+                assert!(!p.is_empty());
+                assert!(Path::new(p).is_absolute(), "{p}");
+            }
+            assert!(paths.attachments_dir.starts_with(&paths.app_data_dir));
+            assert!(Path::new(&paths.cove_skills_dir).starts_with(home));
+        });
+    }
+
+    #[test]
+    fn officellm_home_absent_when_unresolved() {
+        with_home(|home| {
+            let paths = app_paths_from(&home.join("app-data"), None).unwrap();
+            assert!(paths.officellm_home.is_none());
+            let json = serde_json::to_string(&paths).unwrap();
+            assert!(json.contains("\"pdfCacheDir\""));
+            assert!(json.contains("\"officellmHome\":null"));
+        });
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::Path;
+
+use tauri::Manager;
+
+use super::file_utils::{attachments_dir, read_image_preview_data_url, unique_file_name};
+use super::SaveAttachmentFileResult;
+
+const CLIPBOARD_IMAGE_NAME: &str = "clipboard.png";
+
+/// 将 RGBA 像素编码为 PNG
+fn encode_png(width: usize, height: usize, rgba: &[u8]) -> Result<Vec<u8>, String> {
+    if width == 0 || height == 0 || rgba.len() != width * height * 4 {
+        return Err("剪贴板图片数据无效".to_string());
+    }
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| format!("PNG 编码失败：{}", e))?;
+    writer
+        .write_image_data(rgba)
+        .map_err(|e| format!("PNG 编码失败：{}", e))?;
+    writer.finish().map_err(|e| format!("PNG 编码失败：{}", e))?;
+    Ok(out)
+}
+
+/// 将 RGBA 图片以 PNG 写入附件目录，返回附件信息（含预览 data URL）
+fn save_rgba_as_attachment(
+    attachment_dir: &Path,
+    width: usize,
+    height: usize,
+    rgba: &[u8],
+) -> Result<SaveAttachmentFileResult, String> {
+    let png = encode_png(width, height, rgba)?;
+    fs::create_dir_all(attachment_dir).map_err(|e| format!("创建附件目录失败：{}", e))?;
+    let dest_path = attachment_dir.join(unique_file_name(CLIPBOARD_IMAGE_NAME));
+    fs::write(&dest_path, &png).map_err(|e| format!("保存附件失败：{}", e))?;
+
+    Ok(SaveAttachmentFileResult {
+        path: dest_path.to_string_lossy().to_string(),
+        name: CLIPBOARD_IMAGE_NAME.to_string(),
+        size: png.len() as u64,
+        preview_data_url: read_image_preview_data_url(&dest_path, CLIPBOARD_IMAGE_NAME),
+    })
+}
+
+/// 直接从系统剪贴板读取图片并保存为 PNG 附件，避免前端 base64 往返。
+#[tauri::command]
+pub fn save_clipboard_image(app: tauri::AppHandle) -> Result<SaveAttachmentFileResult, String> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| format!("无法访问剪贴板：{}", e))?;
+    let image = clipboard.get_image().map_err(|e| match e {
+        arboard::Error::ContentNotAvailable => "剪贴板中没有图片".to_string(),
+        other => format!("读取剪贴板图片失败：{}", other),
+    })?;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用目录失败：{}", e))?;
+    save_rgba_as_attachment(
+        &attachments_dir(&app_data_dir),
+        image.width,
+        image.height,
+        &image.bytes,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_rgba_pixels_as_png_attachment() {
+        let dir = tempfile::tempdir().unwrap();
+        let attachments = dir.path().join("attachments");
+        // 2x1：红、半透明蓝
+        let rgba = [255, 0, 0, 255, 0, 0, 255, 128];
+
+        let r = save_rgba_as_attachment(&attachments, 2, 1, &rgba).unwrap();
+        assert_eq!(r.name, "clipboard.png");
+        let bytes = fs::read(&r.path).unwrap();
+        assert_eq!(r.size, bytes.len() as u64);
+        assert!(bytes.starts_with(&[0x89, b'P', b'N', b'G']));
+        assert!(r
+            .preview_data_url
+            .as_deref()
+            .is_some_and(|u| u.starts_with("data:image/png;base64,")));
+
+        let decoder = png::Decoder::new(fs::File::open(&r.path).unwrap());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (2, 1));
+    }
+
+    #[test]
+    fn rejects_mismatched_pixel_buffer() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(save_rgba_as_attachment(dir.path(), 2, 2, &[0; 4]).is_err());
+        assert!(save_rgba_as_attachment(dir.path(), 0, 0, &[]).is_err());
+    }
+
+    /// 需要图形会话，且会覆盖系统剪贴板：仅 macOS / Windows，手动运行
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    #[test]
+    #[ignore = "overwrites the system clipboard"]
+    fn reads_image_written_to_clipboard() {
+        let Ok(mut clipboard) = arboard::Clipboard::new() else { return };
+        let image = arboard::ImageData {
+            width: 1,
+            height: 1,
+            bytes: vec![0u8, 255, 0, 255].into(),
+        };
+        if clipboard.set_image(image).is_err() {
+            return;
+        }
+        let got = clipboard.get_image().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let r = save_rgba_as_attachment(dir.path(), got.width, got.height, &got.bytes).unwrap();
+        assert!(r.size > 0);
+    }
+}
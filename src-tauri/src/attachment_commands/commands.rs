@@ -1,19 +1,17 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 use tauri::Manager;
 
 use super::file_utils::{
-    get_extension, guess_image_mime_by_ext, is_text_like_extension, read_image_preview_data_url,
+    attachments_dir, get_extension, guess_image_mime_by_ext, read_image_preview_data_url,
     safe_file_name, unique_file_name,
 };
-use super::parsers::{parse_docx, parse_pdf, parse_plain_text, parse_pptx, parse_xlsx};
 use super::{
-    ParseDocumentTextResult, ReadAttachmentDataUrlArgs, ReadAttachmentDataUrlResult,
-    ReadAttachmentTextArgs, SaveAttachmentFileArgs, SaveAttachmentFileResult,
-    SaveAttachmentFromBase64Args,
+    ReadAttachmentDataUrlArgs, ReadAttachmentDataUrlResult, SaveAttachmentFileArgs,
+    SaveAttachmentFileResult, SaveAttachmentFromBase64Args,
 };
 
 /// 最大以 data URL 读取的附件大小（25MB），避免内存溢出
@@ -28,7 +26,7 @@ pub fn save_attachment_from_base64(
         .path()
         .app_data_dir()
         .map_err(|e| format!("获取应用目录失败：{}", e))?;
-    let attachment_dir = app_data_dir.join("attachments");
+    let attachment_dir = attachments_dir(&app_data_dir);
     fs::create_dir_all(&attachment_dir).map_err(|e| format!("创建附件目录失败：{}", e))?;
 
     let bytes = BASE64_STANDARD
@@ -59,7 +57,7 @@ pub fn read_attachment_as_data_url(
         .path()
         .app_data_dir()
         .map_err(|e| format!("获取应用目录失败：{}", e))?;
-    let attachment_dir = app_data_dir.join("attachments");
+    let attachment_dir = attachments_dir(&app_data_dir);
 
     let requested_path = Path::new(&args.path);
     let canonical_requested = requested_path
@@ -125,7 +123,7 @@ pub fn save_attachment_file(
         .path()
         .app_data_dir()
         .map_err(|e| format!("获取应用目录失败：{}", e))?;
-    let attachment_dir = app_data_dir.join("attachments");
+    let attachment_dir = attachments_dir(&app_data_dir);
     fs::create_dir_all(&attachment_dir).map_err(|e| format!("创建附件目录失败：{}", e))?;
 
     let origin_name = src_path
@@ -150,19 +148,9 @@ pub fn save_attachment_file(
     })
 }
 
-#[tauri::command]
-pub fn parse_document_text(
-    app: tauri::AppHandle,
-    args: ReadAttachmentTextArgs,
-) -> Result<ParseDocumentTextResult, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("获取应用目录失败：{}", e))?;
-    let attachment_dir = app_data_dir.join("attachments");
-
-    let requested_path = Path::new(&args.path);
-    let canonical_requested = requested_path
+/// 校验附件路径位于附件目录内且为文件，返回规范化路径。
+pub(super) fn resolve_attachment_file(attachment_dir: &Path, path: &str) -> Result<PathBuf, String> {
+    let canonical_requested = Path::new(path)
         .canonicalize()
         .map_err(|e| format!("读取附件失败：{}", e))?;
     let canonical_root = attachment_dir
@@ -175,40 +163,5 @@ pub fn parse_document_text(
     if !canonical_requested.is_file() {
         return Err("附件文件不存在".to_string());
     }
-
-    let file_name = canonical_requested
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or_default();
-    let extension = get_extension(&canonical_requested);
-    let can_parse = is_text_like_extension(file_name)
-        || extension == "pdf"
-        || extension == "docx"
-        || extension == "xlsx"
-        || extension == "pptx";
-    if !can_parse {
-        return Err("该附件不是可读取的文本文件".to_string());
-    }
-
-    let max_bytes = args.max_bytes.unwrap_or(128 * 1024).min(512 * 1024);
-    let max_chars = std::cmp::max(4096, max_bytes as usize);
-    let (content, truncated, mut warnings) = match extension.as_str() {
-        "pdf" => parse_pdf(&canonical_requested, max_chars, args.page_range.as_deref())?,
-        "docx" => parse_docx(&canonical_requested, max_chars)?,
-        "xlsx" => parse_xlsx(&canonical_requested, max_chars)?,
-        "pptx" => parse_pptx(&canonical_requested, max_chars)?,
-        "doc" => {
-            return Err("DOC 老格式暂未支持，请先转换为 DOCX 或 PDF。".to_string());
-        }
-        _ => parse_plain_text(&canonical_requested, max_bytes)?,
-    };
-    if content.trim().is_empty() {
-        warnings.push("解析结果为空文本".to_string());
-    }
-    Ok(ParseDocumentTextResult {
-        file_type: extension,
-        content,
-        truncated,
-        warnings,
-    })
+    Ok(canonical_requested)
 }
@@ -1,10 +1,15 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 
+/// 附件目录：<app_data_dir>/attachments/（不创建）
+pub(crate) fn attachments_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("attachments")
+}
+
 pub(super) fn safe_file_name(name: &str) -> String {
     let sanitized = name
         .chars()
@@ -77,6 +82,7 @@ pub(super) fn is_text_like_extension(file_name: &str) -> bool {
             | "qmd"
             | "json"
             | "csv"
+            | "tsv"
             | "ts"
             | "tsx"
             | "js"
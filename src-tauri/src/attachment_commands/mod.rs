@@ -1,10 +1,17 @@
+mod clipboard;
 mod commands;
 mod file_utils;
+mod parse_text;
 mod parsers;
+mod pdf_pages;
 mod preprocess;
 mod workspace_save;
 
+pub(crate) use file_utils::attachments_dir;
+pub use clipboard::*;
 pub use commands::*;
+pub use parse_text::*;
+pub use pdf_pages::*;
 pub use preprocess::*;
 pub use workspace_save::*;
 
@@ -60,6 +67,21 @@ pub struct ReadAttachmentTextArgs {
     pub max_bytes: Option<u64>,
     #[serde(default)]
     pub page_range: Option<String>,
+    /// 取消令牌：通过 `cancel_command` 中止长时间解析（PDF 按页检查）
+    #[serde(default)]
+    pub cancel_token: Option<String>,
+    /// Markdown 文件：从正文中去掉 YAML front-matter，改由 `frontmatter` 字段单独返回
+    #[serde(default)]
+    pub strip_frontmatter: bool,
+    /// 总超时（毫秒），默认 30s；超时后尽量返回已解析的部分内容
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// DOCX：将表格解析为 Markdown 表格（默认输出扁平文本）
+    #[serde(default)]
+    pub preserve_tables: bool,
+    /// DOCX / PDF：额外提取文档中的超链接，结果放在 `links` 字段
+    #[serde(default)]
+    pub extract_links: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -69,6 +91,39 @@ pub struct ParseDocumentTextResult {
     pub content: String,
     pub truncated: bool,
     pub warnings: Vec<String>,
+    /// 解析被取消时为 true，`content` 为已解析的部分内容
+    pub cancelled: bool,
+    /// 因总超时中止；此时 `cancelled` 也为 true
+    pub timed_out: bool,
+    /// `stripFrontmatter` 时剥离出的原始 YAML front-matter（不含 `---` 分隔行）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frontmatter: Option<String>,
+    /// `extractLinks` 时提取到的超链接（按出现顺序去重）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsePdfPagesArgs {
+    pub path: String,
+    #[serde(default)]
+    pub page_range: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfPageText {
+    /// 页码，从 1 开始
+    pub page: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsePdfPagesResult {
+    pub pages: Vec<PdfPageText>,
+    pub warnings: Vec<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -141,83 +196,4 @@ pub struct PreprocessAttachmentResult {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn serde_save_file_args() {
-        let json = r#"{"sourcePath":"/tmp/file.pdf"}"#;
-        let args: SaveAttachmentFileArgs = serde_json::from_str(json).unwrap();
-        assert_eq!(args.source_path, "/tmp/file.pdf");
-    }
-
-    #[test]
-    fn serde_read_text_args_full() {
-        let json = r#"{"path":"/tmp/doc.pdf","maxBytes":1024,"pageRange":"1-3"}"#;
-        let args: ReadAttachmentTextArgs = serde_json::from_str(json).unwrap();
-        assert_eq!(args.path, "/tmp/doc.pdf");
-        assert_eq!(args.max_bytes, Some(1024));
-        assert_eq!(args.page_range, Some("1-3".to_string()));
-    }
-
-    #[test]
-    fn serde_read_text_args_optional_defaults() {
-        let json = r#"{"path":"/tmp/doc.txt"}"#;
-        let args: ReadAttachmentTextArgs = serde_json::from_str(json).unwrap();
-        assert_eq!(args.path, "/tmp/doc.txt");
-        assert_eq!(args.max_bytes, None);
-        assert_eq!(args.page_range, None);
-    }
-
-    #[test]
-    fn serde_result_serializes_camel_case() {
-        let result = ParseDocumentTextResult {
-            file_type: "pdf".to_string(),
-            content: "hello".to_string(),
-            truncated: false,
-            warnings: vec![],
-        };
-        let json = serde_json::to_string(&result).unwrap();
-        assert!(json.contains("\"fileType\""));
-        assert!(json.contains("\"truncated\""));
-        assert!(!json.contains("file_type"));
-    }
-
-    #[test]
-    fn serde_workspace_save_args() {
-        let json = r#"{"sourcePath":"/tmp/file.pdf","workspaceRoot":"/project"}"#;
-        let args: SaveAttachmentToWorkspaceArgs = serde_json::from_str(json).unwrap();
-        assert_eq!(args.source_path, "/tmp/file.pdf");
-        assert_eq!(args.workspace_root, "/project");
-    }
-
-    #[test]
-    fn serde_preprocess_args() {
-        let json = r#"{"path":"/project/file.txt","maxChars":1000}"#;
-        let args: PreprocessAttachmentArgs = serde_json::from_str(json).unwrap();
-        assert_eq!(args.path, "/project/file.txt");
-        assert_eq!(args.max_chars, Some(1000));
-    }
-
-    #[test]
-    fn serde_preprocess_result() {
-        let result = PreprocessAttachmentResult {
-            file_type: "text".to_string(),
-            content: "hello".to_string(),
-            summary: "hello".to_string(),
-            char_count: 5,
-            truncated: false,
-            warnings: vec![],
-            metadata: AttachmentMetadata {
-                line_count: Some(1),
-                ..Default::default()
-            },
-        };
-        let json = serde_json::to_string(&result).unwrap();
-        assert!(json.contains("\"fileType\""));
-        assert!(json.contains("\"charCount\""));
-        assert!(json.contains("\"lineCount\""));
-        // Optional None fields should be skipped
-        assert!(!json.contains("\"pageCount\""));
-    }
-}
+mod tests;
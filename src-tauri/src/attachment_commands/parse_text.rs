@@ -0,0 +1,189 @@
+//! 附件文本解析：按扩展名分派解析器，支持取消与总超时。
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::Manager;
+
+use super::commands::resolve_attachment_file;
+use super::file_utils::{attachments_dir, get_extension, is_text_like_extension};
+use super::parsers::{
+    parse_delimited, parse_docx, parse_docx_with_tables, parse_ipynb, parse_pdf_cancelable,
+    parse_plain_text, parse_pptx, parse_xlsx,
+};
+use super::{ParseDocumentTextResult, ReadAttachmentTextArgs};
+use crate::document_parsers::frontmatter::split_frontmatter;
+use crate::document_parsers::links::{docx_links, pdf_links};
+use crate::shell_commands::{CancelRegistry, CancelToken};
+
+/// `parse_document_text` 默认总超时，防止畸形 PDF/XLSX 让解析器长时间空转
+const PARSE_TIMEOUT: Duration = Duration::from_secs(30);
+/// 超时后等待解析线程响应取消、交回部分内容的宽限期
+const PARSE_TIMEOUT_GRACE: Duration = Duration::from_secs(2);
+
+/// 解析附件文本。提供 `cancelToken` 时可通过 `cancel_command` 中止，返回已解析的部分内容。
+/// 超过总超时（`timeoutMs`，默认 30s）时同样中止并返回部分内容，`timedOut` 为 true。
+#[tauri::command]
+pub async fn parse_document_text(
+    app: tauri::AppHandle,
+    args: ReadAttachmentTextArgs,
+    state: tauri::State<'_, Arc<CancelRegistry>>,
+) -> Result<ParseDocumentTextResult, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用目录失败：{}", e))?;
+    let attachment_dir = attachments_dir(&app_data_dir);
+
+    let token = match args.cancel_token.as_deref() {
+        Some(key) => state.register(key),
+        None => CancelToken::new(),
+    };
+    let token_key = args.cancel_token.clone();
+    let registry = Arc::clone(&state);
+    let deadline = args.timeout_ms.map_or(PARSE_TIMEOUT, Duration::from_millis);
+
+    let result = parse_with_deadline(deadline, PARSE_TIMEOUT_GRACE, token, move |token| {
+        parse_document_text_inner(&attachment_dir, &args, Some(token))
+    })
+    .await;
+
+    if let Some(key) = token_key {
+        registry.remove(&key);
+    }
+    result
+}
+
+/// 在 blocking 线程池执行解析，超过 `deadline` 时触发 `token` 取消。
+///
+/// 解析器在 `grace` 内响应取消（PDF 按页检查）则返回部分内容并标记 `timed_out`；
+/// 否则返回超时错误，阻塞中的解析线程在结束后自行退出。
+pub(super) async fn parse_with_deadline<F>(
+    deadline: Duration,
+    grace: Duration,
+    token: CancelToken,
+    parse: F,
+) -> Result<ParseDocumentTextResult, String>
+where
+    F: FnOnce(&CancelToken) -> Result<ParseDocumentTextResult, String> + Send + 'static,
+{
+    let worker_token = token.clone();
+    let mut task = tauri::async_runtime::spawn_blocking(move || parse(&worker_token));
+    if let Ok(joined) = tokio::time::timeout(deadline, &mut task).await {
+        return joined.map_err(|e| format!("task join error: {e}"))?;
+    }
+
+    token.cancel();
+    let secs = deadline.as_secs_f32();
+    match tokio::time::timeout(grace, &mut task).await {
+        Ok(joined) => {
+            let mut result = joined.map_err(|e| format!("task join error: {e}"))??;
+            result.timed_out = true;
+            result.warnings.push(format!("解析超时（{secs:.1}s），仅返回部分内容"));
+            Ok(result)
+        }
+        Err(_) => Err(format!("解析超时（{secs:.1}s），文件可能已损坏或结构异常")),
+    }
+}
+
+pub(crate) fn parse_document_text_inner(
+    attachment_dir: &Path,
+    args: &ReadAttachmentTextArgs,
+    cancel: Option<&CancelToken>,
+) -> Result<ParseDocumentTextResult, String> {
+    let is_cancelled = || cancel.is_some_and(|t| t.is_cancelled());
+    let canonical_requested = resolve_attachment_file(attachment_dir, &args.path)?;
+
+    let file_name = canonical_requested
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let extension = get_extension(&canonical_requested);
+    let can_parse = is_text_like_extension(file_name)
+        || extension == "pdf"
+        || extension == "docx"
+        || extension == "xlsx"
+        || extension == "pptx"
+        || extension == "ipynb";
+    if !can_parse {
+        return Err("该附件不是可读取的文本文件".to_string());
+    }
+
+    let max_bytes = args.max_bytes.unwrap_or(128 * 1024).min(512 * 1024);
+    let max_chars = std::cmp::max(4096, max_bytes as usize);
+    if is_cancelled() {
+        return Ok(ParseDocumentTextResult {
+            file_type: extension,
+            content: String::new(),
+            truncated: false,
+            warnings: vec!["解析已取消".to_string()],
+            cancelled: true,
+            timed_out: false,
+            frontmatter: None,
+            links: Vec::new(),
+        });
+    }
+    let mut cancelled = false;
+    let (content, truncated, mut warnings) = match extension.as_str() {
+        "pdf" => {
+            let (content, truncated, warnings, was_cancelled) = parse_pdf_cancelable(
+                &canonical_requested,
+                max_chars,
+                args.page_range.as_deref(),
+                |_| is_cancelled(),
+            )?;
+            cancelled = was_cancelled;
+            (content, truncated, warnings)
+        }
+        "docx" if args.preserve_tables => parse_docx_with_tables(&canonical_requested, max_chars)?,
+        "docx" => parse_docx(&canonical_requested, max_chars)?,
+        "xlsx" => parse_xlsx(&canonical_requested, max_chars)?,
+        "pptx" => parse_pptx(&canonical_requested, max_chars)?,
+        "ipynb" => parse_ipynb(&canonical_requested, max_chars)?,
+        "csv" | "tsv" => parse_delimited(&canonical_requested, max_chars, &extension)?,
+        "doc" => {
+            return Err("DOC 老格式暂未支持，请先转换为 DOCX 或 PDF。".to_string());
+        }
+        _ => parse_plain_text(&canonical_requested, max_bytes)?,
+    };
+    let mut frontmatter = None;
+    let content = if args.strip_frontmatter && matches!(extension.as_str(), "md" | "markdown" | "mdx") {
+        let (fm, body) = split_frontmatter(&content);
+        frontmatter = fm;
+        body.to_string()
+    } else {
+        content
+    };
+    if content.trim().is_empty() && !cancelled {
+        warnings.push("解析结果为空文本".to_string());
+    }
+    let links = if args.extract_links {
+        extract_links(&canonical_requested, &extension, &mut warnings)
+    } else {
+        Vec::new()
+    };
+    Ok(ParseDocumentTextResult {
+        file_type: extension,
+        content,
+        truncated,
+        warnings,
+        cancelled,
+        timed_out: false,
+        frontmatter,
+        links,
+    })
+}
+
+/// 超链接提取失败不影响正文结果，仅记为警告
+fn extract_links(path: &Path, extension: &str, warnings: &mut Vec<String>) -> Vec<String> {
+    let result = match extension {
+        "docx" => docx_links(path),
+        "pdf" => pdf_links(path),
+        _ => return Vec::new(),
+    };
+    result.unwrap_or_else(|err| {
+        warnings.push(format!("提取超链接失败：{}", err));
+        Vec::new()
+    })
+}
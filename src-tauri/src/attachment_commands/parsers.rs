@@ -1,3 +1,6 @@
 pub(super) use crate::document_parsers::parsers::{
-    parse_docx, parse_pdf, parse_plain_text, parse_pptx, parse_xlsx,
+    parse_docx, parse_ipynb, parse_pdf, parse_pdf_by_pages, parse_pdf_cancelable, parse_plain_text,
+    parse_pptx, parse_xlsx,
 };
+pub(super) use crate::document_parsers::docx_tables::parse_docx_with_tables;
+pub(super) use crate::document_parsers::delimited::parse_delimited;
@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use tauri::Manager;
+
+use super::commands::resolve_attachment_file;
+use super::file_utils::{attachments_dir, get_extension};
+use super::parsers::parse_pdf_by_pages;
+use super::{ParsePdfPagesArgs, ParsePdfPagesResult, PdfPageText};
+
+/// 按页解析 PDF 附件，返回每页文本，供分块索引等需要页边界的场景使用。
+#[tauri::command]
+pub async fn parse_pdf_pages(
+    app: tauri::AppHandle,
+    args: ParsePdfPagesArgs,
+) -> Result<ParsePdfPagesResult, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用目录失败：{}", e))?;
+    let attachment_dir = attachments_dir(&app_data_dir);
+    tauri::async_runtime::spawn_blocking(move || parse_pdf_pages_inner(&attachment_dir, &args))
+        .await
+        .map_err(|e| format!("task join error: {e}"))?
+}
+
+pub(crate) fn parse_pdf_pages_inner(
+    attachment_dir: &Path,
+    args: &ParsePdfPagesArgs,
+) -> Result<ParsePdfPagesResult, String> {
+    let path = resolve_attachment_file(attachment_dir, &args.path)?;
+    if get_extension(&path) != "pdf" {
+        return Err("该附件不是 PDF 文件".to_string());
+    }
+    let (pages, warnings) = parse_pdf_by_pages(&path, args.page_range.as_deref())?;
+    Ok(ParsePdfPagesResult {
+        pages: pages
+            .into_iter()
+            .map(|(page, text)| PdfPageText { page, text })
+            .collect(),
+        warnings,
+    })
+}
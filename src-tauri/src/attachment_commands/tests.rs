@@ -0,0 +1,242 @@
+use super::*;
+
+#[test]
+fn serde_save_file_args() {
+    let json = r#"{"sourcePath":"/tmp/file.pdf"}"#;
+    let args: SaveAttachmentFileArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.source_path, "/tmp/file.pdf");
+}
+
+#[test]
+fn serde_read_text_args_full() {
+    let json = r#"{"path":"/tmp/doc.pdf","maxBytes":1024,"pageRange":"1-3"}"#;
+    let args: ReadAttachmentTextArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.path, "/tmp/doc.pdf");
+    assert_eq!(args.max_bytes, Some(1024));
+    assert_eq!(args.page_range, Some("1-3".to_string()));
+}
+
+#[test]
+fn serde_read_text_args_optional_defaults() {
+    let json = r#"{"path":"/tmp/doc.txt"}"#;
+    let args: ReadAttachmentTextArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.path, "/tmp/doc.txt");
+    assert_eq!(args.max_bytes, None);
+    assert_eq!(args.page_range, None);
+    assert_eq!(args.cancel_token, None);
+    assert_eq!(args.timeout_ms, None);
+}
+
+#[test]
+fn serde_result_serializes_camel_case() {
+    let result = ParseDocumentTextResult {
+        file_type: "pdf".to_string(),
+        content: "hello".to_string(),
+        truncated: false,
+        warnings: vec![],
+        cancelled: false,
+        timed_out: false,
+        frontmatter: None,
+        links: Vec::new(),
+    };
+    let json = serde_json::to_string(&result).unwrap();
+    assert!(json.contains("\"fileType\""));
+    assert!(json.contains("\"truncated\""));
+    assert!(json.contains("\"cancelled\""));
+    assert!(json.contains("\"timedOut\""));
+    assert!(!json.contains("file_type"));
+    assert!(!json.contains("frontmatter"));
+    assert!(!json.contains("links"));
+}
+
+#[test]
+fn parse_document_text_inner_precancelled() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("notes.txt");
+    std::fs::write(&file, "hello").unwrap();
+    let args = ReadAttachmentTextArgs {
+        path: file.to_string_lossy().into_owned(),
+        max_bytes: None,
+        page_range: None,
+        cancel_token: Some("t".into()),
+        strip_frontmatter: false,
+        timeout_ms: None,
+        preserve_tables: false,
+        extract_links: false,
+    };
+    let token = crate::shell_commands::CancelToken::new();
+    token.cancel();
+    let result = parse_document_text_inner(dir.path(), &args, Some(&token)).unwrap();
+    assert!(result.cancelled);
+    assert!(result.content.is_empty());
+
+    let result = parse_document_text_inner(dir.path(), &args, None).unwrap();
+    assert!(!result.cancelled);
+    assert_eq!(result.content, "hello");
+}
+
+fn parse_markdown(dir: &std::path::Path, content: &str, strip_frontmatter: bool) -> ParseDocumentTextResult {
+    let file = dir.join("note.md");
+    std::fs::write(&file, content).unwrap();
+    let args = ReadAttachmentTextArgs {
+        path: file.to_string_lossy().into_owned(),
+        max_bytes: None,
+        page_range: None,
+        cancel_token: None,
+        strip_frontmatter,
+        timeout_ms: None,
+        preserve_tables: false,
+        extract_links: false,
+    };
+    parse_document_text_inner(dir, &args, None).unwrap()
+}
+
+#[test]
+fn parse_document_text_strips_markdown_frontmatter() {
+    let dir = tempfile::tempdir().unwrap();
+    let md = "---\ntitle: Notes\ntags: [a, b]\n---\n# Heading\nbody\n";
+
+    let result = parse_markdown(dir.path(), md, true);
+    assert_eq!(result.frontmatter.as_deref(), Some("title: Notes\ntags: [a, b]"));
+    assert_eq!(result.content, "# Heading\nbody\n");
+
+    // 默认保留原始内容
+    let result = parse_markdown(dir.path(), md, false);
+    assert!(result.frontmatter.is_none());
+    assert_eq!(result.content, md);
+}
+
+#[test]
+fn parse_document_text_markdown_without_frontmatter() {
+    let dir = tempfile::tempdir().unwrap();
+    let md = "# Heading\n\n---\n\nafter rule\n";
+    let result = parse_markdown(dir.path(), md, true);
+    assert!(result.frontmatter.is_none());
+    assert_eq!(result.content, md);
+}
+
+fn partial_result(content: &str, cancelled: bool) -> ParseDocumentTextResult {
+    ParseDocumentTextResult {
+        file_type: "pdf".to_string(),
+        content: content.to_string(),
+        truncated: false,
+        warnings: vec![],
+        cancelled,
+        timed_out: false,
+        frontmatter: None,
+        links: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn parse_with_deadline_returns_partial_content_on_timeout() {
+    use std::time::Duration;
+    // 模拟逐页解析的慢 PDF：每页检查取消，返回已解析部分
+    let result = parse_with_deadline(
+        Duration::from_millis(50),
+        Duration::from_secs(2),
+        crate::shell_commands::CancelToken::new(),
+        |token| {
+            let mut content = String::new();
+            while !token.is_cancelled() {
+                content.push_str("page\n");
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            Ok(partial_result(&content, true))
+        },
+    )
+    .await
+    .unwrap();
+    assert!(result.timed_out);
+    assert!(result.cancelled);
+    assert!(result.content.starts_with("page\n"));
+    assert!(result.warnings.iter().any(|w| w.contains("超时")));
+}
+
+#[tokio::test]
+async fn parse_with_deadline_errors_when_parser_ignores_cancel() {
+    use std::time::Duration;
+    let started = std::time::Instant::now();
+    let result = parse_with_deadline(
+        Duration::from_millis(20),
+        Duration::from_millis(20),
+        crate::shell_commands::CancelToken::new(),
+        |_| {
+            std::thread::sleep(Duration::from_millis(500));
+            Ok(partial_result("late", false))
+        },
+    )
+    .await;
+    let err = result.unwrap_err();
+    assert!(err.contains("超时"), "{err}");
+    assert!(started.elapsed() < Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn parse_with_deadline_passes_fast_result_through() {
+    use std::time::Duration;
+    let result = parse_with_deadline(
+        Duration::from_secs(5),
+        Duration::from_secs(1),
+        crate::shell_commands::CancelToken::new(),
+        |_| Ok(partial_result("done", false)),
+    )
+    .await
+    .unwrap();
+    assert!(!result.timed_out);
+    assert_eq!(result.content, "done");
+}
+
+#[test]
+fn parse_pdf_pages_inner_rejects_non_pdf_and_outside_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("notes.txt");
+    std::fs::write(&file, "hello").unwrap();
+    let args = ParsePdfPagesArgs {
+        path: file.to_string_lossy().into_owned(),
+        page_range: None,
+    };
+    assert!(parse_pdf_pages_inner(dir.path(), &args).is_err());
+
+    let other = tempfile::tempdir().unwrap();
+    let err = parse_pdf_pages_inner(other.path(), &args).unwrap_err();
+    assert!(err.contains("无权"), "{err}");
+}
+
+#[test]
+fn serde_workspace_save_args() {
+    let json = r#"{"sourcePath":"/tmp/file.pdf","workspaceRoot":"/project"}"#;
+    let args: SaveAttachmentToWorkspaceArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.source_path, "/tmp/file.pdf");
+    assert_eq!(args.workspace_root, "/project");
+}
+
+#[test]
+fn serde_preprocess_args() {
+    let json = r#"{"path":"/project/file.txt","maxChars":1000}"#;
+    let args: PreprocessAttachmentArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.path, "/project/file.txt");
+    assert_eq!(args.max_chars, Some(1000));
+}
+
+#[test]
+fn serde_preprocess_result() {
+    let result = PreprocessAttachmentResult {
+        file_type: "text".to_string(),
+        content: "hello".to_string(),
+        summary: "hello".to_string(),
+        char_count: 5,
+        truncated: false,
+        warnings: vec![],
+        metadata: AttachmentMetadata {
+            line_count: Some(1),
+            ..Default::default()
+        },
+    };
+    let json = serde_json::to_string(&result).unwrap();
+    assert!(json.contains("\"fileType\""));
+    assert!(json.contains("\"charCount\""));
+    assert!(json.contains("\"lineCount\""));
+    // Optional None fields should be skipped
+    assert!(!json.contains("\"pageCount\""));
+}
@@ -0,0 +1,160 @@
+//! Write text or a workspace PNG image to the system clipboard (via `arboard`).
+
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::fs_commands::ensure_inside_workspace_exists;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyToClipboardArgs {
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Required with `image_path`; the image must live inside this workspace
+    #[serde(default)]
+    pub workspace_root: Option<String>,
+    /// PNG image path, relative to the workspace root or absolute inside it
+    #[serde(default)]
+    pub image_path: Option<String>,
+}
+
+enum ClipboardContent {
+    Text(String),
+    Image { width: usize, height: usize, rgba: Vec<u8> },
+}
+
+/// Put either `text` or the image at `imagePath` on the system clipboard.
+#[tauri::command]
+pub fn copy_to_clipboard(args: CopyToClipboardArgs) -> Result<(), String> {
+    let content = resolve_content(args)?;
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| format!("Clipboard unavailable: {e}"))?;
+    match content {
+        ClipboardContent::Text(text) => clipboard.set_text(text),
+        ClipboardContent::Image { width, height, rgba } => clipboard.set_image(arboard::ImageData {
+            width,
+            height,
+            bytes: rgba.into(),
+        }),
+    }
+    .map_err(|e| format!("Failed to write clipboard: {e}"))
+}
+
+/// Validate the arguments and load the image (if any) before touching the clipboard
+fn resolve_content(args: CopyToClipboardArgs) -> Result<ClipboardContent, String> {
+    match (args.text, args.image_path) {
+        (Some(text), None) => Ok(ClipboardContent::Text(text)),
+        (None, Some(image_path)) => {
+            let root = args
+                .workspace_root
+                .ok_or("workspaceRoot is required with imagePath")?;
+            let abs = ensure_inside_workspace_exists(&root, &image_path)
+                .map_err(|e| format!("Invalid image path {image_path}: {e:?}"))?;
+            let bytes = fs::read(&abs).map_err(|e| format!("Failed to read image: {e}"))?;
+            let (width, height, rgba) = decode_png_rgba(&bytes)?;
+            Ok(ClipboardContent::Image { width, height, rgba })
+        }
+        (Some(_), Some(_)) => Err("Provide either text or imagePath, not both".into()),
+        (None, None) => Err("Nothing to copy: provide text or imagePath".into()),
+    }
+}
+
+/// Decode a PNG into 8-bit RGBA pixels, as `arboard::ImageData` expects
+fn decode_png_rgba(bytes: &[u8]) -> Result<(usize, usize, Vec<u8>), String> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(
+        png::Transformations::normalize_to_color8() | png::Transformations::ALPHA,
+    );
+    let mut reader = decoder
+        .read_info()
+        .map_err(|e| format!("Only PNG images can be copied: {e}"))?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let frame = reader
+        .next_frame(&mut buf)
+        .map_err(|e| format!("Failed to decode PNG: {e}"))?;
+    buf.truncate(frame.buffer_size());
+    let rgba = match frame.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::GrayscaleAlpha => buf
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        other => return Err(format!("Unsupported PNG color type: {other:?}")),
+    };
+    Ok((frame.width as usize, frame.height as usize, rgba))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(text: Option<&str>, root: Option<&str>, image: Option<&str>) -> CopyToClipboardArgs {
+        CopyToClipboardArgs {
+            text: text.map(Into::into),
+            workspace_root: root.map(Into::into),
+            image_path: image.map(Into::into),
+        }
+    }
+
+    fn encode_png(width: u32, height: u32, color: png::ColorType, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(color);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(data).unwrap();
+        writer.finish().unwrap();
+        out
+    }
+
+    #[test]
+    fn decodes_rgb_png_to_rgba() {
+        let png = encode_png(2, 1, png::ColorType::Rgb, &[255, 0, 0, 0, 0, 255]);
+        let (w, h, rgba) = decode_png_rgba(&png).unwrap();
+        assert_eq!((w, h), (2, 1));
+        assert_eq!(rgba, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn rejects_invalid_argument_combinations() {
+        assert!(resolve_content(args(None, None, None)).is_err());
+        assert!(resolve_content(args(Some("a"), Some("/"), Some("x.png"))).is_err());
+        assert!(resolve_content(args(None, None, Some("x.png"))).is_err());
+        assert!(matches!(
+            resolve_content(args(Some("hi"), None, None)),
+            Ok(ClipboardContent::Text(t)) if t == "hi"
+        ));
+    }
+
+    #[test]
+    fn image_must_be_inside_workspace_and_png() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        let pixel = encode_png(1, 1, png::ColorType::Rgba, &[1, 2, 3, 4]);
+        fs::write(outside.path().join("x.png"), &pixel).unwrap();
+        let escaped = outside.path().join("x.png");
+        assert!(resolve_content(args(None, Some(root), escaped.to_str())).is_err());
+
+        fs::write(dir.path().join("fake.png"), "not a png").unwrap();
+        let err = resolve_content(args(None, Some(root), Some("fake.png"))).err().unwrap();
+        assert!(err.contains("PNG"), "{err}");
+
+        fs::write(dir.path().join("ok.png"), &pixel).unwrap();
+        assert!(matches!(
+            resolve_content(args(None, Some(root), Some("ok.png"))),
+            Ok(ClipboardContent::Image { width: 1, height: 1, .. })
+        ));
+    }
+
+    /// Needs a desktop session and overwrites the system clipboard: macOS / Windows, run manually
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    #[test]
+    #[ignore = "overwrites the system clipboard"]
+    fn copies_text_to_clipboard() {
+        copy_to_clipboard(args(Some("cove clipboard test"), None, None)).unwrap();
+        let text = arboard::Clipboard::new().unwrap().get_text().unwrap();
+        assert_eq!(text, "cove clipboard test");
+    }
+}
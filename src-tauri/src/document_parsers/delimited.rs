@@ -0,0 +1,120 @@
+//! CSV / TSV 解析：按前几行自动识别分隔符，渲染为 Markdown 表格。
+
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use super::docx_tables::render_markdown_table;
+use super::truncation::truncate_text_by_chars;
+
+/// 候选分隔符
+const DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+/// 识别分隔符时检查的非空行数
+const SNIFF_LINES: usize = 5;
+
+/// 统计引号外的分隔符个数（`"a,b"` 中的逗号不计）
+fn count_outside_quotes(line: &str, delimiter: char) -> usize {
+    let mut in_quotes = false;
+    line.chars()
+        .filter(|&c| {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            }
+            !in_quotes && c == delimiter
+        })
+        .count()
+}
+
+/// 根据样本行识别分隔符：每行出现次数一致且大于 0 的候选中，优先 `fallback`，
+/// 否则取次数最多者；都不一致时返回 `fallback`（csv 为逗号、tsv 为制表符）。
+pub(crate) fn detect_delimiter(lines: &[&str], fallback: char) -> char {
+    let sample: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|l| !l.trim().is_empty())
+        .take(SNIFF_LINES)
+        .collect();
+    let consistent: Vec<(char, usize)> = DELIMITERS
+        .iter()
+        .filter_map(|&d| {
+            let first = count_outside_quotes(sample.first()?, d);
+            let same = sample.iter().all(|l| count_outside_quotes(l, d) == first);
+            (first > 0 && same).then_some((d, first))
+        })
+        .collect();
+    if consistent.iter().any(|&(d, _)| d == fallback) {
+        return fallback;
+    }
+    consistent
+        .iter()
+        .max_by_key(|&&(_, n)| n)
+        .map_or(fallback, |&(d, _)| d)
+}
+
+/// 按分隔符拆分一行；支持双引号包裹字段与 `""` 转义（不支持跨行字段）
+pub(crate) fn split_delimited_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+pub(crate) fn parse_delimited(
+    path: &Path,
+    max_chars: usize,
+    extension: &str,
+) -> Result<(String, bool, Vec<String>), String> {
+    let file = fs::File::open(path).map_err(|e| format!("打开附件失败：{}", e))?;
+    // 读到超过字符上限即停止，避免为超大表格构建完整行数组
+    let mut lines = Vec::new();
+    let mut read_chars = 0usize;
+    let mut stopped_early = false;
+    for line in BufReader::new(file).split(b'\n') {
+        let bytes = line.map_err(|e| format!("读取附件内容失败：{}", e))?;
+        let text = String::from_utf8_lossy(&bytes).trim_end_matches('\r').to_string();
+        read_chars += text.chars().count() + 1;
+        lines.push(text);
+        if read_chars > max_chars {
+            stopped_early = true;
+            break;
+        }
+    }
+
+    let fallback = if extension == "tsv" { '\t' } else { ',' };
+    let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let delimiter = detect_delimiter(&refs, fallback);
+    let rows: Vec<Vec<String>> = refs
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            split_delimited_line(l, delimiter)
+                .into_iter()
+                .map(|f| f.trim().replace('|', "\\|"))
+                .collect()
+        })
+        .collect();
+    if rows.is_empty() {
+        return Ok(("该表格没有可读取的文本单元格。".to_string(), false, Vec::new()));
+    }
+
+    let (content, truncated) = truncate_text_by_chars(render_markdown_table(&rows), max_chars);
+    let warnings = if truncated || stopped_early {
+        vec![format!("{} 文本按字符上限截断", extension.to_ascii_uppercase())]
+    } else {
+        Vec::new()
+    };
+    Ok((content, truncated || stopped_early, warnings))
+}
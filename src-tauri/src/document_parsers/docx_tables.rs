@@ -0,0 +1,149 @@
+//! DOCX 表格保留解析：直接读取 `word/document.xml`，把 `<w:tbl>` 渲染为 Markdown 表格，
+//! 段落按原顺序输出为纯文本。文档中没有表格时回退到 `parse_docx`。
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use quick_xml::events::Event;
+use quick_xml::Reader as XmlReader;
+use zip::ZipArchive;
+
+use super::encryption::ensure_office_not_encrypted;
+use super::parsers::parse_docx;
+use super::truncation::truncate_text_by_chars;
+
+/// 正在收集的最外层表格；嵌套表格的文字并入当前单元格
+#[derive(Default)]
+struct TableState {
+    rows: Vec<Vec<String>>,
+    row: Vec<String>,
+    cell: Vec<String>,
+}
+
+pub(crate) fn parse_docx_with_tables(
+    path: &Path,
+    max_chars: usize,
+) -> Result<(String, bool, Vec<String>), String> {
+    ensure_office_not_encrypted(path, "DOCX")?;
+    let file = fs::File::open(path).map_err(|e| format!("打开 DOCX 失败：{}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("读取 DOCX 结构失败：{}", e))?;
+    let mut xml = Vec::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|e| format!("读取 DOCX 正文失败：{}", e))?
+        .read_to_end(&mut xml)
+        .map_err(|e| format!("读取 DOCX 正文失败：{}", e))?;
+
+    let Some(text) = docx_xml_to_markdown(&xml)? else {
+        return parse_docx(path, max_chars);
+    };
+    let (content, truncated) = truncate_text_by_chars(text, max_chars);
+    let warnings = if truncated {
+        vec!["DOCX 文本按字符上限截断".to_string()]
+    } else {
+        Vec::new()
+    };
+    Ok((content, truncated, warnings))
+}
+
+/// 将 `document.xml` 转为文本 + Markdown 表格；不含表格时返回 None。
+pub(crate) fn docx_xml_to_markdown(xml: &[u8]) -> Result<Option<String>, String> {
+    let mut reader = XmlReader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut out = String::new();
+    let mut paragraph = String::new();
+    let mut in_text = false;
+    let mut table_depth = 0usize;
+    let mut table = TableState::default();
+    let mut saw_table = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"w:t" => in_text = true,
+                b"w:tbl" => {
+                    table_depth += 1;
+                    saw_table = true;
+                }
+                b"w:tr" if table_depth == 1 => table.row.clear(),
+                b"w:tc" if table_depth == 1 => table.cell.clear(),
+                _ => {}
+            },
+            Ok(Event::Empty(e)) => match e.name().as_ref() {
+                b"w:tab" => paragraph.push('\t'),
+                b"w:br" | b"w:cr" => paragraph.push('\n'),
+                _ => {}
+            },
+            Ok(Event::Text(t)) if in_text => {
+                let text = t.unescape().map_err(|e| format!("解析 DOCX XML 失败：{}", e))?;
+                paragraph.push_str(&text);
+            }
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"w:t" => in_text = false,
+                b"w:p" => {
+                    let text = std::mem::take(&mut paragraph);
+                    if table_depth > 0 {
+                        if !text.trim().is_empty() {
+                            table.cell.push(text.trim().to_string());
+                        }
+                    } else {
+                        out.push_str(&text);
+                        out.push('\n');
+                    }
+                }
+                b"w:tc" if table_depth == 1 => {
+                    let cell = std::mem::take(&mut table.cell);
+                    table.row.push(markdown_cell(&cell));
+                }
+                b"w:tr" if table_depth == 1 => {
+                    let row = std::mem::take(&mut table.row);
+                    table.rows.push(row);
+                }
+                b"w:tbl" => {
+                    table_depth = table_depth.saturating_sub(1);
+                    if table_depth == 0 {
+                        out.push('\n');
+                        out.push_str(&render_markdown_table(&std::mem::take(&mut table.rows)));
+                        out.push('\n');
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(err) => return Err(format!("解析 DOCX XML 失败：{}", err)),
+        }
+        buf.clear();
+    }
+    Ok(saw_table.then_some(out))
+}
+
+/// 单元格内多段落以 `<br>` 连接，转义会破坏表格结构的 `|` 与换行
+fn markdown_cell(paragraphs: &[String]) -> String {
+    paragraphs
+        .iter()
+        .map(|p| p.replace('|', "\\|").replace('\n', "<br>"))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+/// 首行作为表头；列数按最宽的行补齐
+pub(crate) fn render_markdown_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+    let line = |row: &[String]| {
+        let cells: Vec<&str> = (0..columns)
+            .map(|i| row.get(i).map_or("", String::as_str))
+            .collect();
+        format!("| {} |\n", cells.join(" | "))
+    };
+    let mut out = line(&rows[0]);
+    out.push_str(&format!("|{}\n", " --- |".repeat(columns)));
+    for row in &rows[1..] {
+        out.push_str(&line(row));
+    }
+    out
+}
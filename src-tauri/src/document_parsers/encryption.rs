@@ -0,0 +1,53 @@
+//! 受密码保护文档的识别：统一返回以 [`FILE_ENCRYPTED`] 开头的错误，前端据此提示“文件受密码保护”，
+//! 而不是展示解析器的底层报错。
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use pdf_extract::{Document, Error as PdfError, OutputError};
+use zip::ZipArchive;
+
+/// 错误前缀，格式为 `FILE_ENCRYPTED: <说明>`
+pub(crate) const FILE_ENCRYPTED: &str = "FILE_ENCRYPTED";
+
+/// 加密的 OOXML 实为 OLE 复合文档（CFB），正文存放在 `EncryptedPackage` 流中
+const CFB_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+pub(crate) fn encrypted_error(kind: &str) -> String {
+    format!("{}: 该 {} 文件受密码保护，无法读取内容", FILE_ENCRYPTED, kind)
+}
+
+/// 转换 pdf_extract 错误：需要密码时返回 `FILE_ENCRYPTED`，其余加上 `context` 前缀
+pub(crate) fn map_pdf_error(err: OutputError, context: &str) -> String {
+    match err {
+        OutputError::PdfError(PdfError::Decryption(_)) => encrypted_error("PDF"),
+        other => format!("{}：{}", context, other),
+    }
+}
+
+/// 已加载的 PDF 无法用空密码打开（空用户密码的 PDF 仍可直接读取）
+pub(crate) fn is_pdf_password_protected(doc: &Document) -> bool {
+    doc.is_encrypted() && doc.authenticate_password("").is_err()
+}
+
+/// DOCX/XLSX/PPTX：识别 Office 加密（CFB 容器）与 ZIP 条目加密，`kind` 用于错误说明
+pub(crate) fn ensure_office_not_encrypted(path: &Path, kind: &str) -> Result<(), String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("打开 {} 失败：{}", kind, e))?;
+    let mut magic = [0u8; 8];
+    if file.read(&mut magic).map_err(|e| format!("读取 {} 失败：{}", kind, e))? == magic.len()
+        && magic == CFB_MAGIC
+    {
+        return Err(encrypted_error(kind));
+    }
+    // 不是合法 ZIP 时交给具体解析器报错
+    let Ok(mut archive) = ZipArchive::new(file) else {
+        return Ok(());
+    };
+    for i in 0..archive.len() {
+        if archive.by_index_raw(i).is_ok_and(|entry| entry.encrypted()) {
+            return Err(encrypted_error(kind));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,27 @@
+//! Markdown YAML front-matter 识别：文件开头 `---` 与其后的 `---`/`...` 之间的块。
+
+/// 拆分 front-matter 与正文。没有合法 front-matter（未闭合或不在文件开头）时返回 `(None, text)`。
+/// 返回的 front-matter 为原始 YAML 文本（不含分隔行）。
+pub(crate) fn split_frontmatter(text: &str) -> (Option<String>, &str) {
+    let body = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let Some(rest) = strip_delimiter_line(body, "---") else {
+        return (None, text);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed == "---" || trimmed == "..." {
+            let yaml = rest[..offset].trim_end_matches(['\r', '\n']).to_string();
+            return (Some(yaml), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, text)
+}
+
+/// `text` 以 `delim` 独占一行开头时，返回该行之后的内容
+fn strip_delimiter_line<'a>(text: &'a str, delim: &str) -> Option<&'a str> {
+    let rest = text.strip_prefix(delim)?;
+    let rest = rest.trim_start_matches([' ', '\t']);
+    rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n'))
+}
@@ -0,0 +1,100 @@
+//! 超链接提取：DOCX 读取 `word/_rels/document.xml.rels` 中的外部 hyperlink 关系，
+//! PDF 读取各页 `/Link` 注释的 URI 动作。结果按出现顺序去重。
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use pdf_extract::{Dictionary, Document, Object};
+use quick_xml::events::Event;
+use quick_xml::Reader as XmlReader;
+use zip::ZipArchive;
+
+const DOCX_RELS: &str = "word/_rels/document.xml.rels";
+const HYPERLINK_REL_SUFFIX: &[u8] = b"/hyperlink";
+
+fn push_unique(links: &mut Vec<String>, link: String) {
+    if !link.is_empty() && !links.contains(&link) {
+        links.push(link);
+    }
+}
+
+pub(crate) fn docx_links(path: &Path) -> Result<Vec<String>, String> {
+    let file = fs::File::open(path).map_err(|e| format!("打开 DOCX 失败：{}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("读取 DOCX 结构失败：{}", e))?;
+    let mut xml = Vec::new();
+    match archive.by_name(DOCX_RELS) {
+        Ok(mut entry) => entry
+            .read_to_end(&mut xml)
+            .map_err(|e| format!("读取 DOCX 关系文件失败：{}", e))?,
+        // 没有关系文件即没有超链接
+        Err(_) => return Ok(Vec::new()),
+    };
+    rels_hyperlinks(&xml)
+}
+
+/// 解析 `.rels`：取 Type 以 `/hyperlink` 结尾的 Relationship 的 Target
+pub(crate) fn rels_hyperlinks(xml: &[u8]) -> Result<Vec<String>, String> {
+    let mut reader = XmlReader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut links = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"Relationship" => {
+                let mut is_hyperlink = false;
+                let mut target = None;
+                for attr in e.attributes().flatten() {
+                    match attr.key.as_ref() {
+                        b"Type" => is_hyperlink = attr.value.ends_with(HYPERLINK_REL_SUFFIX),
+                        b"Target" => {
+                            let value = attr
+                                .unescape_value()
+                                .map_err(|e| format!("解析 DOCX 关系文件失败：{}", e))?;
+                            target = Some(value.into_owned());
+                        }
+                        _ => {}
+                    }
+                }
+                if let (true, Some(target)) = (is_hyperlink, target) {
+                    push_unique(&mut links, target);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(err) => return Err(format!("解析 DOCX 关系文件失败：{}", err)),
+        }
+        buf.clear();
+    }
+    Ok(links)
+}
+
+pub(crate) fn pdf_links(path: &Path) -> Result<Vec<String>, String> {
+    let bytes = fs::read(path).map_err(|e| format!("读取 PDF 失败：{}", e))?;
+    let doc = Document::load_mem(&bytes).map_err(|e| format!("解析 PDF 失败：{}", e))?;
+    let mut links = Vec::new();
+    for page_id in doc.get_pages().into_values() {
+        let Ok(annotations) = doc.get_page_annotations(page_id) else {
+            continue;
+        };
+        for annot in annotations {
+            if let Some(uri) = link_annotation_uri(&doc, annot) {
+                push_unique(&mut links, uri);
+            }
+        }
+    }
+    Ok(links)
+}
+
+/// `/Subtype /Link` 且动作为 `/S /URI` 的注释，返回其 URI
+fn link_annotation_uri(doc: &Document, annot: &Dictionary) -> Option<String> {
+    if annot.get(b"Subtype").and_then(Object::as_name).ok()? != b"Link" {
+        return None;
+    }
+    let (_, action) = doc.dereference(annot.get(b"A").ok()?).ok()?;
+    let action = action.as_dict().ok()?;
+    if action.get(b"S").and_then(Object::as_name).ok()? != b"URI" {
+        return None;
+    }
+    let (_, uri) = doc.dereference(action.get(b"URI").ok()?).ok()?;
+    Some(String::from_utf8_lossy(uri.as_str().ok()?).into_owned())
+}
@@ -1,5 +1,22 @@
+pub(crate) mod delimited;
+pub(crate) mod docx_tables;
+pub(crate) mod encryption;
+pub(crate) mod frontmatter;
+pub(crate) mod links;
+pub(crate) mod notebook;
 pub(crate) mod parsers;
+pub(crate) mod pdf;
 pub(crate) mod truncation;
 
+#[cfg(test)]
+mod test_pdf;
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod tests_delimited;
+#[cfg(test)]
+mod tests_docx_tables;
+#[cfg(test)]
+mod tests_encryption;
+#[cfg(test)]
+mod tests_links;
@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::Path;
+
+use super::truncation::truncate_text_by_chars;
+
+/// 拼接 notebook 中的多行字段（`source`/`text` 可能是字符串或字符串数组）。
+fn notebook_text(value: Option<&serde_json::Value>) -> String {
+    match value {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Array(lines)) => {
+            lines.iter().filter_map(|l| l.as_str()).collect::<String>()
+        }
+        _ => String::new(),
+    }
+}
+
+/// 提取代码单元的文本输出；图片等二进制输出只保留占位说明。
+fn notebook_outputs(cell: &serde_json::Value) -> Vec<String> {
+    let Some(outputs) = cell.get("outputs").and_then(|o| o.as_array()) else {
+        return Vec::new();
+    };
+    let mut texts = Vec::new();
+    for output in outputs {
+        match output.get("output_type").and_then(|t| t.as_str()) {
+            Some("stream") => texts.push(notebook_text(output.get("text"))),
+            Some("execute_result") | Some("display_data") => {
+                let data = output.get("data");
+                let plain = data.and_then(|d| d.get("text/plain"));
+                if plain.is_some() {
+                    texts.push(notebook_text(plain));
+                } else if data
+                    .and_then(|d| d.as_object())
+                    .is_some_and(|d| d.keys().any(|k| k.starts_with("image/")))
+                {
+                    texts.push("[图片输出已省略]".to_string());
+                }
+            }
+            Some("error") => {
+                let ename = output.get("ename").and_then(|v| v.as_str()).unwrap_or("Error");
+                let evalue = output.get("evalue").and_then(|v| v.as_str()).unwrap_or("");
+                texts.push(format!("{}: {}", ename, evalue));
+            }
+            _ => {}
+        }
+    }
+    texts.retain(|t| !t.trim().is_empty());
+    texts
+}
+
+/// 将 Jupyter notebook 转为 Markdown：markdown 单元原样保留，代码单元转为代码块，
+/// 并附带文本输出（跳过 base64 图片与元数据）。
+pub(crate) fn parse_ipynb(path: &Path, max_chars: usize) -> Result<(String, bool, Vec<String>), String> {
+    let raw = fs::read_to_string(path).map_err(|e| format!("读取 Notebook 失败：{}", e))?;
+    let nb: serde_json::Value =
+        serde_json::from_str(&raw).map_err(|e| format!("解析 Notebook JSON 失败：{}", e))?;
+    let cells = nb
+        .get("cells")
+        .and_then(|c| c.as_array())
+        .ok_or_else(|| "Notebook 缺少 cells 字段".to_string())?;
+    let metadata = nb.get("metadata");
+    let language = metadata
+        .and_then(|m| m.pointer("/kernelspec/language").or_else(|| m.pointer("/language_info/name")))
+        .and_then(|v| v.as_str())
+        .unwrap_or("python");
+
+    let mut out = String::new();
+    for cell in cells {
+        let source = notebook_text(cell.get("source"));
+        match cell.get("cell_type").and_then(|t| t.as_str()) {
+            Some("markdown") | Some("raw") => {
+                if source.trim().is_empty() {
+                    continue;
+                }
+                out.push_str(source.trim_end());
+                out.push_str("\n\n");
+            }
+            Some("code") => {
+                let outputs = notebook_outputs(cell);
+                if source.trim().is_empty() && outputs.is_empty() {
+                    continue;
+                }
+                out.push_str(&format!("```{}\n{}\n```\n\n", language, source.trim_end()));
+                if !outputs.is_empty() {
+                    let joined: Vec<&str> = outputs.iter().map(|o| o.trim_end()).collect();
+                    out.push_str(&format!("Output:\n```\n{}\n```\n\n", joined.join("\n")));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (content, truncated) = truncate_text_by_chars(out, max_chars);
+    let warnings = if truncated {
+        vec!["Notebook 文本按字符上限截断".to_string()]
+    } else {
+        Vec::new()
+    };
+    Ok((content, truncated, warnings))
+}
@@ -7,8 +7,12 @@ use quick_xml::events::Event;
 use quick_xml::Reader as XmlReader;
 use zip::ZipArchive;
 
+use super::encryption::{ensure_office_not_encrypted, map_pdf_error};
 use super::truncation::truncate_text_by_chars;
 
+pub(crate) use super::notebook::parse_ipynb;
+pub(crate) use super::pdf::{parse_pdf_by_pages, parse_pdf_cancelable};
+
 pub(crate) fn parse_plain_text(path: &Path, max_bytes: u64) -> Result<(String, bool, Vec<String>), String> {
     let meta = fs::metadata(path).map_err(|e| format!("读取附件信息失败：{}", e))?;
     let read_len = std::cmp::min(meta.len(), max_bytes) as usize;
@@ -69,7 +73,7 @@ pub(crate) fn parse_pdf(
     let mut warnings = Vec::new();
     let text = if let Some(raw_range) = page_range {
         let pages = pdf_extract::extract_text_from_mem_by_pages(&bytes)
-            .map_err(|e| format!("按页解析 PDF 失败：{}", e))?;
+            .map_err(|e| map_pdf_error(e, "按页解析 PDF 失败"))?;
         if pages.is_empty() {
             String::new()
         } else {
@@ -77,7 +81,7 @@ pub(crate) fn parse_pdf(
             if selected.is_empty() {
                 warnings.push("pageRange 无效，已回退为全文解析".to_string());
                 pdf_extract::extract_text_from_mem(&bytes)
-                    .map_err(|e| format!("解析 PDF 文本失败：{}", e))?
+                    .map_err(|e| map_pdf_error(e, "解析 PDF 文本失败"))?
             } else {
                 let mut picked = String::new();
                 for p in selected {
@@ -90,7 +94,7 @@ pub(crate) fn parse_pdf(
         }
     } else {
         pdf_extract::extract_text_from_mem(&bytes)
-            .map_err(|e| format!("解析 PDF 文本失败：{}", e))?
+            .map_err(|e| map_pdf_error(e, "解析 PDF 文本失败"))?
     };
     let (content, truncated) = truncate_text_by_chars(text, max_chars);
     if truncated {
@@ -99,7 +103,9 @@ pub(crate) fn parse_pdf(
     Ok((content, truncated, warnings))
 }
 
+
 pub(crate) fn parse_docx(path: &Path, max_chars: usize) -> Result<(String, bool, Vec<String>), String> {
+    ensure_office_not_encrypted(path, "DOCX")?;
     let text = docx_lite::extract_text(path).map_err(|e| format!("解析 DOCX 文本失败：{}", e))?;
     let (content, truncated) = truncate_text_by_chars(text, max_chars);
     let warnings = if truncated {
@@ -111,6 +117,7 @@ pub(crate) fn parse_docx(path: &Path, max_chars: usize) -> Result<(String, bool,
 }
 
 pub(crate) fn parse_xlsx(path: &Path, max_chars: usize) -> Result<(String, bool, Vec<String>), String> {
+    ensure_office_not_encrypted(path, "XLSX")?;
     let mut workbook = open_workbook_auto(path).map_err(|e| format!("打开 XLSX 失败：{}", e))?;
     let sheet_names = workbook.sheet_names().to_owned();
     if sheet_names.is_empty() {
@@ -163,6 +170,7 @@ pub(crate) fn extract_slide_index(name: &str) -> usize {
 }
 
 pub(crate) fn parse_pptx(path: &Path, max_chars: usize) -> Result<(String, bool, Vec<String>), String> {
+    ensure_office_not_encrypted(path, "PPTX")?;
     let file = fs::File::open(path).map_err(|e| format!("打开 PPTX 失败：{}", e))?;
     let mut archive = ZipArchive::new(file).map_err(|e| format!("读取 PPTX 结构失败：{}", e))?;
 
@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::Path;
+
+use super::encryption::{encrypted_error, is_pdf_password_protected, map_pdf_error};
+use super::parsers::parse_page_range;
+use super::truncation::truncate_text_by_chars;
+
+/// `(页码, 文本)`
+pub(crate) type PdfPage = (usize, String);
+
+/// 按页解析 PDF，返回 `(页码, 文本)` 列表（页码从 1 开始）。
+///
+/// 提供 `page_range` 时仅返回所选页；范围无效时回退为全部页并附带警告。
+pub(crate) fn parse_pdf_by_pages(
+    path: &Path,
+    page_range: Option<&str>,
+) -> Result<(Vec<PdfPage>, Vec<String>), String> {
+    let bytes = fs::read(path).map_err(|e| format!("读取 PDF 失败：{}", e))?;
+    let pages = pdf_extract::extract_text_from_mem_by_pages(&bytes)
+        .map_err(|e| map_pdf_error(e, "按页解析 PDF 失败"))?;
+    let mut warnings = Vec::new();
+    let selected = page_range
+        .map(|raw| parse_page_range(raw, pages.len()))
+        .unwrap_or_default();
+    if page_range.is_some() && selected.is_empty() && !pages.is_empty() {
+        warnings.push("pageRange 无效，已回退为全部页".to_string());
+    }
+    let numbered = pages.into_iter().enumerate().map(|(i, text)| (i + 1, text));
+    let result = if selected.is_empty() {
+        numbered.collect()
+    } else {
+        numbered.filter(|(p, _)| selected.binary_search(p).is_ok()).collect()
+    };
+    Ok((result, warnings))
+}
+
+/// 提取单页文本（页码从 1 开始）。
+fn extract_pdf_page(doc: &pdf_extract::Document, page: u32) -> Result<String, String> {
+    let mut s = String::new();
+    let mut output = pdf_extract::PlainTextOutput::new(&mut s);
+    pdf_extract::output_doc_page(doc, &mut output, page)
+        .map_err(|e| format!("解析 PDF 第 {} 页失败：{}", page, e))?;
+    Ok(s)
+}
+
+/// 逐页解析 PDF，每页开始前调用 `should_stop(已完成页数)`，返回 true 时中止并保留已解析内容。
+///
+/// 返回 `(content, truncated, warnings, cancelled)`。
+pub(crate) fn parse_pdf_cancelable(
+    path: &Path,
+    max_chars: usize,
+    page_range: Option<&str>,
+    mut should_stop: impl FnMut(usize) -> bool,
+) -> Result<(String, bool, Vec<String>, bool), String> {
+    let bytes = fs::read(path).map_err(|e| format!("读取 PDF 失败：{}", e))?;
+    // 加载阶段的解密错误同样按 FILE_ENCRYPTED 上报
+    let doc = pdf_extract::Document::load_mem(&bytes)
+        .map_err(|e| map_pdf_error(pdf_extract::OutputError::PdfError(e), "按页解析 PDF 失败"))?;
+    if is_pdf_password_protected(&doc) {
+        return Err(encrypted_error("PDF"));
+    }
+    let total = doc.get_pages().len();
+    let mut warnings = Vec::new();
+
+    let selected = page_range
+        .map(|raw| parse_page_range(raw, total))
+        .unwrap_or_default();
+    let labelled = !selected.is_empty();
+    if page_range.is_some() && !labelled && total > 0 {
+        warnings.push("pageRange 无效，已回退为全文解析".to_string());
+    }
+    let pages: Vec<usize> = if labelled { selected } else { (1..=total).collect() };
+
+    let mut text = String::new();
+    // 累计字符数，避免每页都重新数一遍全文
+    let mut char_count = 0;
+    let mut cancelled = false;
+    for (done, &p) in pages.iter().enumerate() {
+        if should_stop(done) {
+            cancelled = true;
+            warnings.push(format!("解析已取消，仅返回前 {} 页内容", done));
+            break;
+        }
+        let content = extract_pdf_page(&doc, p as u32)?;
+        let page_text = if labelled {
+            format!("# Page {}\n{}\n\n", p, content)
+        } else {
+            content
+        };
+        char_count += page_text.chars().count();
+        text.push_str(&page_text);
+        // 已超出字符上限，后续页无需再解析
+        if char_count > max_chars {
+            break;
+        }
+    }
+    let (content, truncated) = truncate_text_by_chars(text, max_chars);
+    if truncated {
+        warnings.push("PDF 文本按字符上限截断".to_string());
+    }
+    Ok((content, truncated, warnings, cancelled))
+}
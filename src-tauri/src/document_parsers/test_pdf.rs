@@ -0,0 +1,117 @@
+//! 测试用 PDF 生成器：每页含 `PAGE<n>` 文本，可选加密与链接注释。
+
+use std::path::Path;
+
+use pdf_extract::content::{Content, Operation};
+use pdf_extract::{dictionary, Document, EncryptionState, EncryptionVersion, Object, Permissions, Stream};
+
+pub(super) struct PdfFixture {
+    pages: usize,
+    user_password: Option<String>,
+    link_uri: Option<String>,
+}
+
+impl PdfFixture {
+    /// `pages` 页文档，第 n 页文本为 `PAGE<n>`
+    pub(super) fn pages(pages: usize) -> Self {
+        Self {
+            pages,
+            user_password: None,
+            link_uri: None,
+        }
+    }
+
+    /// 以 RC4 128 位加密，用户密码为 `user_password`（空串表示仅有所有者密码）
+    pub(super) fn encrypted(mut self, user_password: &str) -> Self {
+        self.user_password = Some(user_password.to_string());
+        self
+    }
+
+    /// 第一页添加一个 URI 链接注释和一个非链接注释
+    pub(super) fn with_link(mut self, uri: &str) -> Self {
+        self.link_uri = Some(uri.to_string());
+        self
+    }
+
+    pub(super) fn write(&self, path: &Path) {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Courier",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+        let annots: Vec<Object> = match &self.link_uri {
+            Some(uri) => {
+                let link_id = doc.add_object(dictionary! {
+                    "Type" => "Annot",
+                    "Subtype" => "Link",
+                    "Rect" => vec![0.into(), 0.into(), 100.into(), 20.into()],
+                    "A" => dictionary! { "S" => "URI", "URI" => Object::string_literal(uri.as_str()) },
+                });
+                let note_id = doc.add_object(dictionary! {
+                    "Type" => "Annot",
+                    "Subtype" => "Text",
+                    "Rect" => vec![0.into(), 0.into(), 10.into(), 10.into()],
+                });
+                vec![link_id.into(), note_id.into()]
+            }
+            None => Vec::new(),
+        };
+        let mut kids: Vec<Object> = Vec::new();
+        for i in 1..=self.pages {
+            let content = Content {
+                operations: vec![
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec!["F1".into(), 24.into()]),
+                    Operation::new("Td", vec![100.into(), 600.into()]),
+                    Operation::new("Tj", vec![Object::string_literal(format!("PAGE{i}"))]),
+                    Operation::new("ET", vec![]),
+                ],
+            };
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+            let mut page = dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            };
+            if i == 1 && !annots.is_empty() {
+                page.set("Annots", annots.clone());
+            }
+            kids.push(doc.add_object(page).into());
+        }
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => self.pages as i64,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+
+        if let Some(user_password) = &self.user_password {
+            let file_id = Object::string_literal("0123456789abcdef");
+            doc.trailer.set("ID", vec![file_id.clone(), file_id]);
+            let state = EncryptionState::try_from(EncryptionVersion::V2 {
+                document: &doc,
+                owner_password: "owner",
+                user_password,
+                key_length: 128,
+                permissions: Permissions::default(),
+            })
+            .unwrap();
+            doc.encrypt(&state).unwrap();
+        }
+        doc.save(path).unwrap();
+    }
+}
@@ -1,4 +1,6 @@
+use super::frontmatter::split_frontmatter;
 use super::parsers::*;
+use super::test_pdf::PdfFixture;
 
 #[test]
 fn page_range_basics() {
@@ -66,3 +68,151 @@ fn truncate_over_limit() {
     assert!(text.starts_with("abc"));
     assert!(text.contains("内容已截断"));
 }
+
+#[test]
+fn pdf_cancelable_parses_all_pages() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("doc.pdf");
+    PdfFixture::pages(4).write(&path);
+
+    let (content, truncated, _, cancelled) =
+        parse_pdf_cancelable(&path, 100_000, None, |_| false).unwrap();
+    assert!(!cancelled);
+    assert!(!truncated);
+    for i in 1..=4 {
+        assert!(content.contains(&format!("PAGE{i}")), "{content}");
+    }
+}
+
+#[test]
+fn pdf_cancel_mid_parse_returns_partial_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("doc.pdf");
+    PdfFixture::pages(6).write(&path);
+
+    let (content, _, warnings, cancelled) =
+        parse_pdf_cancelable(&path, 100_000, None, |done| done >= 2).unwrap();
+    assert!(cancelled);
+    assert!(content.contains("PAGE1"));
+    assert!(content.contains("PAGE2"));
+    assert!(!content.contains("PAGE3"));
+    assert!(warnings.iter().any(|w| w.contains("取消")));
+}
+
+#[test]
+fn pdf_cancelable_respects_page_range() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("doc.pdf");
+    PdfFixture::pages(5).write(&path);
+
+    let (content, _, _, cancelled) =
+        parse_pdf_cancelable(&path, 100_000, Some("2,4"), |_| false).unwrap();
+    assert!(!cancelled);
+    assert!(content.contains("# Page 2"));
+    assert!(content.contains("PAGE4"));
+    assert!(!content.contains("PAGE1"));
+}
+
+#[test]
+fn pdf_pages_returns_one_entry_per_page() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("doc.pdf");
+    PdfFixture::pages(3).write(&path);
+
+    let (pages, warnings) = parse_pdf_by_pages(&path, None).unwrap();
+    assert!(warnings.is_empty());
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[0].0, 1);
+    assert_eq!(pages[0].1.trim(), "PAGE1");
+    assert!(pages[2].1.contains("PAGE3"));
+}
+
+#[test]
+fn pdf_pages_respects_page_range() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("doc.pdf");
+    PdfFixture::pages(5).write(&path);
+
+    let (pages, _) = parse_pdf_by_pages(&path, Some("2,4-5")).unwrap();
+    let numbers: Vec<usize> = pages.iter().map(|(p, _)| *p).collect();
+    assert_eq!(numbers, vec![2, 4, 5]);
+    assert!(pages[0].1.contains("PAGE2"));
+
+    let (pages, warnings) = parse_pdf_by_pages(&path, Some("99")).unwrap();
+    assert_eq!(pages.len(), 5);
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn ipynb_extracts_markdown_code_and_text_outputs() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("analysis.ipynb");
+    let notebook = serde_json::json!({
+        "metadata": { "kernelspec": { "language": "python", "name": "python3" } },
+        "nbformat": 4,
+        "cells": [
+            { "cell_type": "markdown", "metadata": {}, "source": ["# Sales analysis\n", "Quarterly totals."] },
+            {
+                "cell_type": "code", "metadata": {}, "execution_count": 1,
+                "source": "import pandas as pd\nprint(df.sum())",
+                "outputs": [
+                    { "output_type": "stream", "name": "stdout", "text": ["total 42\n"] },
+                    { "output_type": "display_data", "metadata": {},
+                      "data": { "image/png": "iVBORw0KGgoAAAANSUhEUgAAAAEAAAAB" } }
+                ]
+            },
+            {
+                "cell_type": "code", "metadata": {}, "execution_count": 2,
+                "source": ["df.shape"],
+                "outputs": [{ "output_type": "execute_result", "execution_count": 2, "metadata": {},
+                    "data": { "text/plain": ["(10, 3)"], "text/html": ["<table></table>"] } }]
+            }
+        ]
+    });
+    std::fs::write(&path, notebook.to_string()).unwrap();
+
+    let (content, truncated, _) = parse_ipynb(&path, 100_000).unwrap();
+    assert!(!truncated);
+    assert!(content.starts_with("# Sales analysis\nQuarterly totals."), "{content}");
+    assert!(content.contains("```python\nimport pandas as pd\nprint(df.sum())\n```"));
+    assert!(content.contains("total 42"));
+    assert!(content.contains("(10, 3)"));
+    assert!(content.contains("[图片输出已省略]"));
+    assert!(!content.contains("iVBORw0KGgo"));
+    assert!(!content.contains("<table>"));
+    assert!(!content.contains("kernelspec"));
+}
+
+#[test]
+fn ipynb_respects_max_chars_and_rejects_invalid_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("big.ipynb");
+    let cells: Vec<_> = (0..50)
+        .map(|i| {
+            let source = format!("cell {i} {}", "x".repeat(100));
+            serde_json::json!({ "cell_type": "markdown", "source": source })
+        })
+        .collect();
+    std::fs::write(&path, serde_json::json!({ "cells": cells }).to_string()).unwrap();
+    let (_, truncated, warnings) = parse_ipynb(&path, 500).unwrap();
+    assert!(truncated);
+    assert_eq!(warnings.len(), 1);
+
+    std::fs::write(&path, "not json").unwrap();
+    assert!(parse_ipynb(&path, 500).is_err());
+}
+
+#[test]
+fn frontmatter_split_handles_crlf_and_dots_terminator() {
+    let (fm, body) = split_frontmatter("---\r\ntitle: x\r\n...\r\nbody\r\n");
+    assert_eq!(fm.as_deref(), Some("title: x"));
+    assert_eq!(body, "body\r\n");
+}
+
+#[test]
+fn frontmatter_split_ignores_unclosed_or_late_block() {
+    let text = "---\ntitle: x\nno closing\n";
+    assert_eq!(split_frontmatter(text), (None, text));
+    let text = "intro\n---\ntitle: x\n---\n";
+    assert_eq!(split_frontmatter(text), (None, text));
+}
@@ -0,0 +1,55 @@
+use super::delimited::{detect_delimiter, parse_delimited, split_delimited_line};
+
+#[test]
+fn tsv_fixture_renders_as_table() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.tsv");
+    std::fs::write(&path, "name\tcity\r\nAda\tLondon, UK\r\nLin\tTaipei\r\n").unwrap();
+
+    let (content, truncated, warnings) = parse_delimited(&path, 10_000, "tsv").unwrap();
+    assert!(!truncated);
+    assert!(warnings.is_empty());
+    assert_eq!(
+        content,
+        "| name | city |\n| --- | --- |\n| Ada | London, UK |\n| Lin | Taipei |\n"
+    );
+}
+
+#[test]
+fn detect_delimiter_prefers_consistent_counts() {
+    // 分号只在部分行出现，逗号每行恰好一个
+    let lines = ["name,notes", "A,x;y;z", "B,w"];
+    assert_eq!(detect_delimiter(&lines, '\t'), ',');
+    // 制表符与逗号都一致时按扩展名默认值
+    let lines = ["a\tb,c", "d\te,f"];
+    assert_eq!(detect_delimiter(&lines, '\t'), '\t');
+    assert_eq!(detect_delimiter(&lines, ','), ',');
+    // 分号分隔的 .csv（欧洲地区常见）
+    let lines = ["a;b;c", "1;2,5;3"];
+    assert_eq!(detect_delimiter(&lines, ','), ';');
+    assert_eq!(detect_delimiter(&["single"], ','), ',');
+}
+
+#[test]
+fn quoted_fields_keep_delimiters() {
+    assert_eq!(
+        split_delimited_line(r#"1,"Doe, Jane","say ""hi""""#, ','),
+        vec!["1", "Doe, Jane", r#"say "hi""#]
+    );
+    let lines = [r#"id,"a,b""#, r#"2,"c,d""#];
+    assert_eq!(detect_delimiter(&lines, '\t'), ',');
+}
+
+#[test]
+fn delimited_respects_max_chars() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("big.csv");
+    let body: String = (0..2000).map(|i| format!("{i},value-{i}\n")).collect();
+    std::fs::write(&path, body).unwrap();
+
+    let (content, truncated, warnings) = parse_delimited(&path, 4096, "csv").unwrap();
+    assert!(truncated);
+    assert_eq!(warnings, vec!["CSV 文本按字符上限截断".to_string()]);
+    assert!(content.starts_with("| 0 | value-0 |\n| --- | --- |\n"));
+    assert!(content.chars().count() < 4096 + 100);
+}
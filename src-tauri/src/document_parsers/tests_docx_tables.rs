@@ -0,0 +1,66 @@
+use std::io::Write;
+
+use zip::write::SimpleFileOptions;
+
+use super::docx_tables::{docx_xml_to_markdown, parse_docx_with_tables};
+
+fn write_docx(path: &std::path::Path, body: &str) {
+    let xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{body}</w:body></w:document>"#
+    );
+    let mut z = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+    z.start_file("word/document.xml", SimpleFileOptions::default()).unwrap();
+    z.write_all(xml.as_bytes()).unwrap();
+    z.finish().unwrap();
+}
+
+fn para(text: &str) -> String {
+    format!("<w:p><w:r><w:t xml:space=\"preserve\">{text}</w:t></w:r></w:p>")
+}
+
+fn row(cells: &[&str]) -> String {
+    let cells: String = cells.iter().map(|c| format!("<w:tc>{}</w:tc>", para(c))).collect();
+    format!("<w:tr>{cells}</w:tr>")
+}
+
+#[test]
+fn two_column_table_renders_as_markdown() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("table.docx");
+    let body = format!(
+        "{}<w:tbl>{}{}{}</w:tbl>{}",
+        para("Before"),
+        row(&["Name", "Qty"]),
+        row(&["Apple", "3"]),
+        row(&["Pear | Green", "5"]),
+        para("After"),
+    );
+    write_docx(&path, &body);
+
+    let (content, truncated, warnings) = parse_docx_with_tables(&path, 10_000).unwrap();
+    assert!(!truncated);
+    assert!(warnings.is_empty());
+    assert_eq!(
+        content,
+        "Before\n\n| Name | Qty |\n| --- | --- |\n| Apple | 3 |\n| Pear \\| Green | 5 |\n\nAfter\n"
+    );
+}
+
+#[test]
+fn ragged_rows_and_multi_paragraph_cells() {
+    let body = format!(
+        "<w:tbl>{}<w:tr><w:tc>{}{}</w:tc></w:tr></w:tbl>",
+        row(&["A", "B"]),
+        para("line1"),
+        para("line2"),
+    );
+    let md = docx_xml_to_markdown(body.as_bytes()).unwrap().unwrap();
+    assert_eq!(md, "\n| A | B |\n| --- | --- |\n| line1<br>line2 |  |\n\n");
+}
+
+#[test]
+fn document_without_tables_yields_none() {
+    let body = format!("<w:body>{}</w:body>", para("plain"));
+    assert!(docx_xml_to_markdown(body.as_bytes()).unwrap().is_none());
+}
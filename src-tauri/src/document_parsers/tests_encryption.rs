@@ -0,0 +1,69 @@
+use super::encryption::FILE_ENCRYPTED;
+use super::parsers::{parse_docx, parse_pdf, parse_pdf_by_pages, parse_pdf_cancelable, parse_pptx, parse_xlsx};
+use super::test_pdf::PdfFixture;
+
+fn assert_encrypted<T: std::fmt::Debug>(result: Result<T, String>) {
+    let err = result.unwrap_err();
+    assert!(err.starts_with(FILE_ENCRYPTED), "unexpected error: {err}");
+}
+
+#[test]
+fn password_protected_pdf_reports_file_encrypted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("locked.pdf");
+    PdfFixture::pages(1).encrypted("secret").write(&path);
+
+    assert_encrypted(parse_pdf(&path, 10_000, None));
+    assert_encrypted(parse_pdf(&path, 10_000, Some("1")));
+    assert_encrypted(parse_pdf_by_pages(&path, None));
+    assert_encrypted(parse_pdf_cancelable(&path, 10_000, None, |_| false));
+}
+
+#[test]
+fn pdf_with_empty_user_password_is_not_reported_encrypted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("owner-only.pdf");
+    PdfFixture::pages(1).encrypted("").write(&path);
+
+    assert!(parse_pdf(&path, 10_000, None).is_ok());
+    assert!(parse_pdf_cancelable(&path, 10_000, None, |_| false).is_ok());
+}
+
+#[test]
+fn encrypted_ooxml_container_reports_file_encrypted() {
+    let dir = tempfile::tempdir().unwrap();
+    // Office 加密文档：CFB 头 + EncryptedPackage 流
+    let mut bytes = vec![0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+    bytes.extend("EncryptedPackage".encode_utf16().flat_map(u16::to_le_bytes));
+    bytes.resize(1024, 0);
+    for name in ["a.docx", "a.xlsx", "a.pptx"] {
+        std::fs::write(dir.path().join(name), &bytes).unwrap();
+    }
+
+    assert_encrypted(parse_docx(&dir.path().join("a.docx"), 10_000));
+    assert_encrypted(parse_xlsx(&dir.path().join("a.xlsx"), 10_000));
+    assert_encrypted(parse_pptx(&dir.path().join("a.pptx"), 10_000));
+}
+
+#[test]
+fn zip_entry_encryption_reports_file_encrypted() {
+    use std::io::Write;
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    let mut zip = zip::ZipWriter::new(&mut buf);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored);
+    zip.start_file("word/document.xml", options).unwrap();
+    zip.write_all(b"<w:document/>").unwrap();
+    zip.finish().unwrap();
+    let mut bytes = buf.into_inner();
+    // 置位通用标志位 bit 0（加密）：本地文件头偏移 6，中央目录头偏移 8
+    bytes[6] |= 1;
+    let central = bytes.windows(4).position(|w| w == b"PK\x01\x02").unwrap();
+    bytes[central + 8] |= 1;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("zipcrypto.docx");
+    std::fs::write(&path, bytes).unwrap();
+    assert_encrypted(parse_docx(&path, 10_000));
+}
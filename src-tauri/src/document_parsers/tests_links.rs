@@ -0,0 +1,55 @@
+use std::io::Write;
+
+use zip::write::SimpleFileOptions;
+
+use super::links::{docx_links, pdf_links, rels_hyperlinks};
+use super::test_pdf::PdfFixture;
+
+const RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com/docs?a=1&amp;b=2" TargetMode="External"/>
+<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="mailto:team@example.com" TargetMode="External"/>
+<Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com/docs?a=1&amp;b=2" TargetMode="External"/>
+</Relationships>"#;
+
+#[test]
+fn rels_keep_only_hyperlinks_in_order() {
+    let links = rels_hyperlinks(RELS.as_bytes()).unwrap();
+    assert_eq!(links, vec!["https://example.com/docs?a=1&b=2", "mailto:team@example.com"]);
+}
+
+#[test]
+fn docx_links_read_document_rels() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("links.docx");
+    let mut z = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    z.start_file("word/document.xml", SimpleFileOptions::default()).unwrap();
+    z.write_all(b"<w:document/>").unwrap();
+    z.start_file("word/_rels/document.xml.rels", SimpleFileOptions::default()).unwrap();
+    z.write_all(RELS.as_bytes()).unwrap();
+    z.finish().unwrap();
+
+    let links = docx_links(&path).unwrap();
+    assert_eq!(links[0], "https://example.com/docs?a=1&b=2");
+    assert_eq!(links.len(), 2);
+}
+
+#[test]
+fn docx_without_rels_has_no_links() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("plain.docx");
+    let mut z = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    z.start_file("word/document.xml", SimpleFileOptions::default()).unwrap();
+    z.write_all(b"<w:document/>").unwrap();
+    z.finish().unwrap();
+    assert!(docx_links(&path).unwrap().is_empty());
+}
+
+#[test]
+fn pdf_links_read_uri_annotations() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("links.pdf");
+    PdfFixture::pages(1).with_link("https://example.com/report").write(&path);
+    assert_eq!(pdf_links(&path).unwrap(), vec!["https://example.com/report"]);
+}
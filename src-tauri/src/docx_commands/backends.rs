@@ -0,0 +1,99 @@
+//! 检测可用的 PDF 转换后端及各自支持的格式，供前端禁用不支持的「导出 PDF」按钮。
+
+use serde::Serialize;
+
+use super::conversion::find_office_app;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionBackend {
+    /// "officellm" | "keynote" | "pages"
+    pub backend: String,
+    /// 可转换为 PDF 的源格式（扩展名，不含点）
+    pub formats: Vec<String>,
+}
+
+/// 各后端是否已安装；与格式映射分开，便于脱离真实环境测试
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct BackendAvailability {
+    pub officellm: bool,
+    pub keynote: bool,
+    pub pages: bool,
+}
+
+/// 按可用性组装后端列表，顺序即前端的优先顺序
+pub(super) fn assemble_backends(avail: BackendAvailability) -> Vec<ConversionBackend> {
+    let table: [(bool, &str, &[&str]); 3] = [
+        (avail.officellm, "officellm", &["docx"]),
+        (avail.keynote, "keynote", &["pptx"]),
+        (avail.pages, "pages", &["pptx"]),
+    ];
+    table
+        .into_iter()
+        .filter(|(available, _, _)| *available)
+        .map(|(_, backend, formats)| ConversionBackend {
+            backend: backend.to_string(),
+            formats: formats.iter().map(|f| f.to_string()).collect(),
+        })
+        .collect()
+}
+
+/// 返回当前可用的 PDF 转换后端及其支持的格式；未安装的后端不出现在列表中。
+#[tauri::command]
+pub fn detect_conversion_backends() -> Vec<ConversionBackend> {
+    assemble_backends(BackendAvailability {
+        officellm: crate::officellm::detect::detect().available,
+        keynote: find_office_app(&["Keynote"]).is_some(),
+        pages: find_office_app(&["Pages"]).is_some(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(backends: &[ConversionBackend]) -> Vec<&str> {
+        backends.iter().map(|b| b.backend.as_str()).collect()
+    }
+
+    #[test]
+    fn no_backends_available() {
+        assert!(assemble_backends(BackendAvailability::default()).is_empty());
+    }
+
+    #[test]
+    fn all_backends_in_priority_order() {
+        let all = assemble_backends(BackendAvailability {
+            officellm: true,
+            keynote: true,
+            pages: true,
+        });
+        assert_eq!(names(&all), ["officellm", "keynote", "pages"]);
+        // 目前没有后端能转换 xlsx
+        assert!(!all.iter().any(|b| b.formats.iter().any(|f| f == "xlsx")));
+    }
+
+    #[test]
+    fn only_available_backends_listed() {
+        let backends = assemble_backends(BackendAvailability {
+            officellm: true,
+            keynote: true,
+            ..Default::default()
+        });
+        assert_eq!(names(&backends), ["officellm", "keynote"]);
+        assert_eq!(backends[0].formats, ["docx"]);
+        assert_eq!(backends[1].formats, ["pptx"]);
+    }
+
+    #[test]
+    fn serializes_backend_and_formats() {
+        let backends = assemble_backends(BackendAvailability {
+            pages: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            serde_json::to_value(&backends).unwrap(),
+            serde_json::json!([{"backend": "pages", "formats": ["pptx"]}])
+        );
+    }
+}
@@ -3,24 +3,26 @@ use std::path::{Path, PathBuf};
 
 pub(super) const MAX_CACHE_FILES: usize = 50;
 
-/// FNV-1a 64 位哈希，用于将文档字节内容映射为缓存文件名
-pub(super) fn fnv1a(data: &[u8]) -> String {
-    let mut h: u64 = 14_695_981_039_346_656_037;
-    for &b in data {
-        h ^= b as u64;
-        h = h.wrapping_mul(1_099_511_628_211);
-    }
-    format!("{h:016x}")
+/// SHA-256（hex），用于将文档字节内容映射为缓存文件名。
+/// 旧版 FNV-1a 键（16 位 hex）不再命中，由 LRU 驱逐自然淘汰。
+pub(super) fn cache_key(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(data))
+}
+
+/// PDF 磁盘缓存目录：<app_data_dir>/pdf-cache/（不创建）
+pub(crate) fn pdf_cache_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("pdf-cache")
 }
 
-/// 获取（并自动创建）PDF 磁盘缓存目录：<app_data_dir>/pdf-cache/
+/// 获取（并自动创建）PDF 磁盘缓存目录
 pub(super) fn get_cache_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     use tauri::Manager;
-    let dir = app
+    let app_data_dir = app
         .path()
         .app_data_dir()
-        .map_err(|e| format!("获取 app_data_dir 失败: {e}"))?
-        .join("pdf-cache");
+        .map_err(|e| format!("获取 app_data_dir 失败: {e}"))?;
+    let dir = pdf_cache_dir(&app_data_dir);
     fs::create_dir_all(&dir).map_err(|e| format!("创建缓存目录失败: {e}"))?;
     Ok(dir)
 }
@@ -57,36 +59,39 @@ mod tests {
     use std::time::{Duration, SystemTime};
     use tempfile::tempdir;
 
-    // ── fnv1a ────────────────────────────────────────────────────────────────
-
-    #[test]
-    fn fnv1a_empty_input() {
-        // FNV-1a offset basis = 0xcbf29ce484222325
-        assert_eq!(fnv1a(b""), "cbf29ce484222325");
-    }
+    // ── cache_key ────────────────────────────────────────────────────────────
 
     #[test]
-    fn fnv1a_known_value() {
-        // Well-known FNV-1a 64-bit hash of "hello"
-        assert_eq!(fnv1a(b"hello"), "a430d84680aabd0b");
+    fn cache_key_known_values() {
+        assert_eq!(
+            cache_key(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            cache_key(b"hello"),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
     }
 
     #[test]
-    fn fnv1a_output_is_16_char_hex() {
-        let h = fnv1a(b"test data");
-        assert_eq!(h.len(), 16);
+    fn cache_key_output_is_64_char_hex() {
+        let h = cache_key(b"test data");
+        assert_eq!(h.len(), 64);
         assert!(h.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
     #[test]
-    fn fnv1a_deterministic() {
+    fn cache_key_deterministic() {
         let input = b"determinism check";
-        assert_eq!(fnv1a(input), fnv1a(input));
+        assert_eq!(cache_key(input), cache_key(input));
     }
 
     #[test]
-    fn fnv1a_different_inputs_differ() {
-        assert_ne!(fnv1a(b"alpha"), fnv1a(b"beta"));
+    fn cache_key_distinct_inputs_give_distinct_filenames() {
+        let docs: [&[u8]; 4] = [b"alpha", b"beta", b"alpha ", b"PK\x03\x04docx"];
+        let names: std::collections::HashSet<String> =
+            docs.iter().map(|d| format!("{}.pdf", cache_key(d))).collect();
+        assert_eq!(names.len(), docs.len());
     }
 
     // ── evict_lru ────────────────────────────────────────────────────────────
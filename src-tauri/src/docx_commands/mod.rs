@@ -1,7 +1,10 @@
+mod backends;
 mod cache;
 mod commands;
 mod conversion;
 mod officellm;
 mod qmd;
 
+pub(crate) use cache::pdf_cache_dir;
+pub use backends::*;
 pub use commands::*;
@@ -4,7 +4,7 @@ use std::process::Command;
 use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine;
 
-use super::cache::{evict_lru, fnv1a, get_cache_dir};
+use super::cache::{cache_key, evict_lru, get_cache_dir};
 use super::conversion::temp_prefix;
 use crate::officellm::resolve;
 
@@ -23,7 +23,7 @@ pub(super) fn convert_docx_via_officellm(app: tauri::AppHandle, data_url: String
         .map_err(|e| format!("Base64 解码失败: {e}"))?;
 
     // ── 2. L2 磁盘缓存命中检查 ──────────────────────────────────────────────────
-    let hash = fnv1a(&bytes);
+    let hash = cache_key(&bytes);
     let cache_dir = get_cache_dir(&app)?;
     let cached_path = cache_dir.join(format!("{hash}.pdf"));
 
@@ -0,0 +1,59 @@
+//! HTTP client construction: browser-like headers and system proxy.
+
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, USER_AGENT};
+
+pub(super) const CHROME_UA: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) \
+    AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+
+pub(super) fn browser_headers() -> HeaderMap {
+    let mut h = HeaderMap::new();
+    h.insert(USER_AGENT, CHROME_UA.parse().unwrap());
+    h.insert(ACCEPT, "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"
+        .parse().unwrap());
+    h.insert(ACCEPT_LANGUAGE, "en-US,en;q=0.9".parse().unwrap());
+    h.insert(ACCEPT_ENCODING, "gzip, deflate, br".parse().unwrap());
+    h
+}
+
+#[cfg(target_os = "macos")]
+fn get_system_proxy() -> Option<reqwest::Proxy> {
+    use std::process::Command;
+    let output = Command::new("scutil").arg("--proxy").output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    if !text.contains("HTTPEnable : 1") { return None; }
+    let host = extract_scutil_value(&text, "HTTPProxy")?;
+    let port = extract_scutil_value(&text, "HTTPPort")?;
+    reqwest::Proxy::all(format!("http://{}:{}", host, port)).ok()
+}
+
+#[cfg(target_os = "macos")]
+fn extract_scutil_value(text: &str, key: &str) -> Option<String> {
+    text.lines().find_map(|line| {
+        let t = line.trim();
+        if !t.starts_with(key) { return None; }
+        let v = t.split(':').nth(1)?.trim();
+        if v.is_empty() { None } else { Some(v.to_string()) }
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn get_system_proxy() -> Option<reqwest::Proxy> { None }
+
+/// 构建 HTTP 客户端；超时按请求设置，因此同一客户端可被多个并发请求复用。
+pub(super) fn build_client() -> Result<Client, String> {
+    build_client_with(false)
+}
+
+/// `accept_invalid_certs` 为 true 时不校验 TLS 证书，仅供显式开启的单次请求使用。
+pub(super) fn build_client_with(accept_invalid_certs: bool) -> Result<Client, String> {
+    let mut builder = Client::builder().default_headers(browser_headers());
+    if let Some(proxy) = get_system_proxy() {
+        builder = builder.proxy(proxy);
+    }
+    if accept_invalid_certs {
+        log::warn!("[fetch] TLS certificate verification disabled for this request");
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    builder.build().map_err(|e| format!("HTTP client error: {}", e))
+}
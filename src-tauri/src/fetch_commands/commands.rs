@@ -0,0 +1,125 @@
+//! Tauri commands: single fetch with cancellation, and bounded-concurrency batch fetch.
+
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::Client;
+
+use super::client::build_client;
+use super::{
+    do_fetch, do_fetch_with, FetchUrlArgs, FetchUrlResult, DEFAULT_MAX_CHARS, DEFAULT_TIMEOUT_MS,
+    MAX_BODY_BYTES, MAX_TIMEOUT_MS,
+};
+use crate::shell_commands::{CancelRegistry, CancelToken};
+
+/// `fetch_urls` 同时进行的请求数上限
+pub(super) const BATCH_CONCURRENCY: usize = 4;
+/// `fetch_urls` 单次批量的 URL 数上限
+const MAX_BATCH_URLS: usize = 20;
+/// `fetch_url` 检查取消标志的间隔
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+fn effective_limits(args: &FetchUrlArgs) -> (u64, u32) {
+    let timeout_ms = args.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).min(MAX_TIMEOUT_MS);
+    let max_chars = args.max_chars.unwrap_or(DEFAULT_MAX_CHARS).min(300_000);
+    (timeout_ms, max_chars)
+}
+
+/// 以有界并发批量抓取，结果顺序与输入一致。
+pub(super) fn fetch_batch(client: &Client, args: &[FetchUrlArgs], concurrency: usize) -> Vec<FetchUrlResult> {
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let slots: Vec<std::sync::Mutex<Option<FetchUrlResult>>> =
+        args.iter().map(|_| std::sync::Mutex::new(None)).collect();
+    thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, args.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                let Some(a) = args.get(i) else { break };
+                let (timeout_ms, max_chars) = effective_limits(a);
+                // 跳过证书校验的请求使用独立客户端，共享客户端保持严格校验
+                let r = if a.danger_accept_invalid_certs {
+                    do_fetch(&a.url, timeout_ms, max_chars, a.cookies.as_deref(), a.policy())
+                } else {
+                    do_fetch_with(
+                        client, &a.url, timeout_ms, max_chars, a.cookies.as_deref(), a.policy(),
+                        MAX_BODY_BYTES,
+                    )
+                };
+                *slots[i].lock().unwrap_or_else(|e| e.into_inner()) = Some(r);
+            });
+        }
+    });
+    slots
+        .into_iter()
+        .zip(args)
+        .map(|(slot, a)| {
+            slot.into_inner()
+                .unwrap_or_else(|e| e.into_inner())
+                .unwrap_or_else(|| FetchUrlResult::err(&a.url, "Fetch error: worker failed".into()))
+        })
+        .collect()
+}
+
+/// 在后台线程抓取，等待结果、超时或取消。
+/// 取消后直接放弃接收；后台线程结束时其响应随之丢弃。
+pub(super) fn fetch_cancellable(args: FetchUrlArgs, cancel: Option<CancelToken>) -> Result<FetchUrlResult, String> {
+    let (timeout_ms, max_chars) = effective_limits(&args);
+    let url = args.url.trim().to_string();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let r = do_fetch(&args.url, timeout_ms, max_chars, args.cookies.as_deref(), args.policy());
+        let _ = tx.send(r);
+    });
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms + 2000);
+    loop {
+        if cancel.as_ref().is_some_and(|c| c.is_cancelled()) {
+            return Ok(FetchUrlResult::cancelled(&url));
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err("Fetch timed out".into());
+        }
+        match rx.recv_timeout(remaining.min(CANCEL_POLL_INTERVAL)) {
+            Ok(r) => return Ok(r),
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(e) => return Err(format!("Fetch error: {:?}", e)),
+        }
+    }
+}
+
+/// 抓取单个 URL；带 `cancelToken` 时可通过 `cancel_command` 提前放弃。
+#[tauri::command]
+pub async fn fetch_url(
+    args: FetchUrlArgs,
+    state: tauri::State<'_, Arc<CancelRegistry>>,
+) -> Result<FetchUrlResult, String> {
+    let token = args.cancel_token.as_deref().map(|key| state.register(key));
+    let token_key = args.cancel_token.clone();
+    let registry = Arc::clone(&state);
+
+    let result = tauri::async_runtime::spawn_blocking(move || fetch_cancellable(args, token))
+        .await
+        .map_err(|e| format!("Fetch error: {}", e))?;
+
+    if let Some(key) = token_key {
+        registry.remove(&key);
+    }
+    result
+}
+
+/// 批量抓取多个 URL（最多 `MAX_BATCH_URLS` 个），并发上限 `BATCH_CONCURRENCY`；
+/// 单个失败不影响其余结果，返回顺序与输入一致。
+#[tauri::command]
+pub async fn fetch_urls(args: Vec<FetchUrlArgs>) -> Result<Vec<FetchUrlResult>, String> {
+    if args.len() > MAX_BATCH_URLS {
+        return Err(format!("Too many URLs (limit {})", MAX_BATCH_URLS));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let client = build_client()?;
+        Ok(fetch_batch(&client, &args, BATCH_CONCURRENCY))
+    })
+    .await
+    .map_err(|e| format!("Fetch error: {}", e))?
+}
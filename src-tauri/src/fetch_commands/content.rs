@@ -0,0 +1,92 @@
+//! Response body handling: content-type classification, size capping, HTML cleanup.
+
+use std::io::Read;
+
+use html2md::parse_html;
+use regex::Regex;
+
+/// How the response body is turned into `content_md`, chosen from the Content-Type.
+#[derive(Debug, PartialEq)]
+pub(super) enum BodyKind {
+    Html,
+    Json,
+    Text,
+    Image,
+}
+
+/// Missing or unknown content types fall back to the HTML path (previous behavior).
+pub(super) fn classify_content_type(mime: &str) -> BodyKind {
+    if mime == "application/json" || mime.ends_with("+json") {
+        BodyKind::Json
+    } else if mime.starts_with("image/") {
+        BodyKind::Image
+    } else if mime.starts_with("text/") && mime != "text/html" {
+        BodyKind::Text
+    } else {
+        BodyKind::Html
+    }
+}
+
+/// Strip parameters (`; charset=utf-8`) and lowercase the MIME type.
+pub(super) fn mime_essence(header: &str) -> String {
+    header.split(';').next().unwrap_or("").trim().to_ascii_lowercase()
+}
+
+pub(super) fn pretty_json(text: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(text)
+        .and_then(|v| serde_json::to_string_pretty(&v))
+        .unwrap_or_else(|_| text.to_string())
+}
+
+/// Render a non-2xx body for the caller: pretty JSON, HTML as Markdown, text verbatim.
+pub(super) fn error_body_text(bytes: &[u8], kind: BodyKind) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    match kind {
+        BodyKind::Json => pretty_json(&text),
+        BodyKind::Html => html_to_clean_md(&text),
+        BodyKind::Text => text.into_owned(),
+        BodyKind::Image => String::new(),
+    }
+    .trim()
+    .to_string()
+}
+
+/// Read at most `cap` bytes from `reader`; the bool reports whether more data was available.
+pub(super) fn read_body_capped(reader: impl Read, cap: usize) -> std::io::Result<(Vec<u8>, bool)> {
+    let mut buf = Vec::new();
+    reader.take(cap as u64 + 1).read_to_end(&mut buf)?;
+    let truncated = buf.len() > cap;
+    buf.truncate(cap);
+    Ok((buf, truncated))
+}
+
+pub(super) fn is_youtube_url(url: &str) -> bool {
+    let lower = url.to_lowercase();
+    lower.contains("youtube.com") || lower.contains("youtu.be")
+}
+
+pub(super) fn extract_title(html: &str) -> Option<String> {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"(?i)<title[^>]*>([^<]*)</title>").unwrap());
+    re.captures(html).and_then(|c| c.get(1)).map(|m| m.as_str().trim().to_string())
+}
+
+pub(super) fn strip_noise_tags(html: &str) -> String {
+    static RES: std::sync::OnceLock<Vec<Regex>> = std::sync::OnceLock::new();
+    let regexes = RES.get_or_init(|| {
+        ["script", "style", "nav", "header", "footer", "noscript", "iframe"]
+            .iter()
+            .map(|tag| Regex::new(&format!(r"(?si)<{tag}[^>]*>.*?</{tag}>")).unwrap())
+            .collect()
+    });
+    let mut result = html.to_string();
+    for re in regexes {
+        result = re.replace_all(&result, "").to_string();
+    }
+    result
+}
+
+/// Strip noise tags from HTML and convert to Markdown.
+pub fn html_to_clean_md(html: &str) -> String {
+    parse_html(&strip_noise_tags(html))
+}
@@ -0,0 +1,40 @@
+//! HTML → Markdown without a network request (clipboard paste, HTML from other sources).
+
+use html2md::parse_html;
+use serde::{Deserialize, Serialize};
+
+use super::content::html_to_clean_md;
+use super::DEFAULT_MAX_CHARS;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseHtmlStringArgs {
+    pub html: String,
+    #[serde(default)]
+    pub max_chars: Option<u32>,
+    /// 先去掉 script/style/nav 等噪音标签，与 `fetch_url` 的处理一致；默认 true
+    #[serde(default)]
+    pub clean: Option<bool>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseHtmlStringResult {
+    pub content_md: String,
+    pub truncated: bool,
+}
+
+/// 将前端已有的 HTML 转为 Markdown，不发起网络请求。
+#[tauri::command]
+pub fn parse_html_string(args: ParseHtmlStringArgs) -> ParseHtmlStringResult {
+    let md = if args.clean.unwrap_or(true) {
+        html_to_clean_md(&args.html)
+    } else {
+        parse_html(&args.html)
+    };
+    let max_chars = args.max_chars.unwrap_or(DEFAULT_MAX_CHARS).min(300_000) as usize;
+    match md.char_indices().nth(max_chars) {
+        Some((cut, _)) => ParseHtmlStringResult { content_md: md[..cut].to_string(), truncated: true },
+        None => ParseHtmlStringResult { content_md: md, truncated: false },
+    }
+}
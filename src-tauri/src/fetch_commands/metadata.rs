@@ -0,0 +1,102 @@
+//! Page metadata for link previews: OpenGraph / Twitter `<meta>` tags, description, favicon.
+
+use regex::Regex;
+use reqwest::Url;
+use serde::Serialize;
+
+use super::content::extract_title;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// `og:image`，已按页面 URL 解析为绝对地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+    /// `<link rel="icon">`，缺省时为站点根目录的 `/favicon.ico`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<String>,
+}
+
+fn tag_regex(tag: &str) -> Regex {
+    Regex::new(&format!(r"(?is)<{tag}\b[^>]*>")).unwrap()
+}
+
+/// 解析单个标签的属性（键小写），支持双引号、单引号与无引号值
+fn tag_attributes(tag: &str) -> Vec<(String, String)> {
+    static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r#"([A-Za-z_:][-A-Za-z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).unwrap()
+    });
+    re.captures_iter(tag)
+        .map(|c| {
+            let value = c.get(2).or(c.get(3)).or(c.get(4)).map_or("", |m| m.as_str());
+            (c[1].to_ascii_lowercase(), decode_entities(value.trim()))
+        })
+        .collect()
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn attr<'a>(attrs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+}
+
+/// 提取页面元数据；`page_url` 用于把相对的图片、图标地址解析为绝对地址。
+/// 同一字段按 OpenGraph → Twitter → 普通 meta / `<title>` 的顺序取第一个非空值。
+pub(super) fn extract_metadata(html: &str, page_url: &str) -> PageMetadata {
+    static META: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    static LINK: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let base = Url::parse(page_url).ok();
+    let absolute = |href: &str| match &base {
+        Some(b) => b.join(href).map_or_else(|_| href.to_string(), String::from),
+        None => href.to_string(),
+    };
+
+    let mut metas: Vec<(String, String)> = Vec::new();
+    for tag in META.get_or_init(|| tag_regex("meta")).find_iter(html) {
+        let attrs = tag_attributes(tag.as_str());
+        let key = attr(&attrs, "property").or(attr(&attrs, "name"));
+        if let (Some(key), Some(content)) = (key, attr(&attrs, "content")) {
+            if !content.is_empty() {
+                metas.push((key.to_ascii_lowercase(), content.to_string()));
+            }
+        }
+    }
+    let first = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| metas.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone()))
+    };
+
+    let favicon = LINK
+        .get_or_init(|| tag_regex("link"))
+        .find_iter(html)
+        .map(|tag| tag_attributes(tag.as_str()))
+        .find(|attrs| {
+            attr(attrs, "rel").is_some_and(|rel| {
+                rel.split_whitespace().any(|r| r.eq_ignore_ascii_case("icon"))
+            })
+        })
+        .and_then(|attrs| attr(&attrs, "href").map(&absolute))
+        .or_else(|| base.as_ref().and_then(|b| b.join("/favicon.ico").ok()).map(String::from));
+
+    PageMetadata {
+        title: first(&["og:title", "twitter:title"]).or_else(|| extract_title(html)),
+        description: first(&["og:description", "twitter:description", "description"]),
+        image: first(&["og:image", "og:image:url", "twitter:image"]).map(|src| absolute(&src)),
+        site_name: first(&["og:site_name"]),
+        favicon,
+    }
+}
@@ -0,0 +1,273 @@
+//! URL fetching with browser-like headers, compression, system proxy, and noise stripping.
+
+mod client;
+mod commands;
+mod content;
+mod html;
+mod metadata;
+mod robots;
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+mod tests_metadata;
+#[cfg(test)]
+mod tests_robots;
+
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use reqwest::blocking::Client;
+use reqwest::header::{CONTENT_TYPE, USER_AGENT};
+use serde::{Deserialize, Serialize};
+
+use client::build_client_with;
+use content::{
+    classify_content_type, error_body_text, extract_title, is_youtube_url, mime_essence,
+    pretty_json, read_body_capped, BodyKind,
+};
+pub use commands::*;
+pub use content::html_to_clean_md;
+pub use html::*;
+use metadata::extract_metadata;
+pub use metadata::PageMetadata;
+use robots::check_robots;
+pub(crate) use robots::FetchPolicy;
+
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+const MAX_TIMEOUT_MS: u64 = 120_000;
+const DEFAULT_MAX_CHARS: u32 = 120_000;
+const LOW_QUALITY_THRESHOLD: usize = 100;
+/// 响应体读取硬上限（10MB），超出部分不读入内存
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+/// 图片以 data URL 返回时的大小上限（5MB）
+const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+/// 非 2xx 响应体的保留上限（64KB）
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchUrlArgs {
+    pub url: String,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub max_chars: Option<u32>,
+    #[serde(default)]
+    pub cookies: Option<String>,
+    /// 覆盖默认的浏览器 User-Agent
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// 抓取前检查 robots.txt，被禁止时返回错误
+    #[serde(default)]
+    pub respect_robots: bool,
+    /// 取消令牌：`cancel_command(token)` 会放弃进行中的请求（仅 `fetch_url` 使用）
+    #[serde(default)]
+    pub cancel_token: Option<String>,
+    /// 危险：跳过 TLS 证书校验（仅用于自签名证书的内网/开发站点），只作用于本次请求
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+    /// 仅提取页面元数据（OpenGraph、description、favicon），用于链接预览
+    #[serde(default)]
+    pub metadata_only: bool,
+}
+
+impl FetchUrlArgs {
+    fn policy(&self) -> FetchPolicy<'_> {
+        FetchPolicy {
+            user_agent: self.user_agent.as_deref(),
+            respect_robots: self.respect_robots,
+            danger_accept_invalid_certs: self.danger_accept_invalid_certs,
+            metadata_only: self.metadata_only,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FetchUrlResult {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_md: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_with_cookies: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low_quality: Option<bool>,
+    /// 响应体超过字节上限、只读取了前 `MAX_BODY_BYTES` 字节
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_truncated: Option<bool>,
+    /// 响应的 MIME 类型（不含参数），如 `text/html`、`application/json`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// 请求被 `cancel_command` 取消
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancelled: Option<bool>,
+    /// HTTP 状态码（非 2xx 时 `content_md` 为响应体）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    /// `metadata_only` 时返回的页面元数据
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<PageMetadata>,
+}
+
+impl FetchUrlResult {
+    fn err(url: &str, error: String) -> Self {
+        Self {
+            ok: false, title: None, content_md: None, truncated: None,
+            error: Some(error), source: url.to_string(),
+            retry_with_cookies: None, low_quality: None, body_truncated: None,
+            content_type: None, cancelled: None, status_code: None, metadata: None,
+        }
+    }
+
+    fn cancelled(url: &str) -> Self {
+        Self { cancelled: Some(true), ..Self::err(url, "Cancelled".into()) }
+    }
+}
+
+/// Core fetch logic.
+pub(crate) fn do_fetch(
+    url: &str, timeout_ms: u64, max_chars: u32, cookies: Option<&str>, policy: FetchPolicy,
+) -> FetchUrlResult {
+    match build_client_with(policy.danger_accept_invalid_certs) {
+        Ok(client) => {
+            do_fetch_with(&client, url, timeout_ms, max_chars, cookies, policy, MAX_BODY_BYTES)
+        }
+        Err(e) => FetchUrlResult::err(url.trim(), e),
+    }
+}
+
+fn do_fetch_with(
+    client: &Client, url: &str, timeout_ms: u64, max_chars: u32, cookies: Option<&str>,
+    policy: FetchPolicy, max_body_bytes: usize,
+) -> FetchUrlResult {
+    let url = url.trim();
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return FetchUrlResult::err(url, "Invalid URL: must start with http:// or https://".into());
+    }
+    if is_youtube_url(url) {
+        return FetchUrlResult::err(url, "YouTube transcript fetching not yet supported".into());
+    }
+
+    let user_agent = policy.effective_user_agent();
+    let timeout = Duration::from_millis(timeout_ms);
+    if policy.respect_robots && !check_robots(client, url, user_agent, timeout) {
+        return FetchUrlResult::err(url, "Disallowed by robots.txt".into());
+    }
+
+    let mut req = client.get(url).timeout(timeout).header(USER_AGENT, user_agent);
+    if let Some(cookie_str) = cookies {
+        req = req.header("Cookie", cookie_str);
+    }
+    let response = match req.send() {
+        Ok(r) => r,
+        Err(e) => {
+            let msg = e.to_string();
+            return FetchUrlResult::err(url, if msg.contains("timed out") || msg.contains("timeout") {
+                "Request timed out".into()
+            } else {
+                format!("Request failed: {}", msg)
+            });
+        }
+    };
+
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(mime_essence)
+        .filter(|m| !m.is_empty());
+    let kind = classify_content_type(content_type.as_deref().unwrap_or(""));
+
+    if !status.is_success() {
+        let code = status.as_u16();
+        let retry = matches!(code, 401 | 403 | 407) && cookies.is_none();
+        let err_msg = match code {
+            403 => "Forbidden (403)".into(), 404 => "Not found (404)".into(),
+            429 => "Rate limited (429)".into(), _ => format!("HTTP {}", code),
+        };
+        // 保留（有上限的）错误响应体：API 的 4xx/5xx 通常带有可读的错误 JSON
+        let body = read_body_capped(response, MAX_ERROR_BODY_BYTES).ok();
+        return FetchUrlResult {
+            retry_with_cookies: if retry { Some(true) } else { None },
+            status_code: Some(code),
+            body_truncated: body.as_ref().map(|(_, t)| *t),
+            content_md: body
+                .map(|(bytes, _)| error_body_text(&bytes, kind))
+                .filter(|b| !b.is_empty()),
+            content_type,
+            ..FetchUrlResult::err(url, err_msg)
+        };
+    }
+
+    // 流式读取并在上限处停止，避免超大响应体在截断前耗尽内存
+    let (bytes, body_truncated) = match read_body_capped(response, max_body_bytes) {
+        Ok(r) => r,
+        Err(e) => return FetchUrlResult::err(url, format!("Failed to read response: {}", e)),
+    };
+
+    let ok_result = |title, content_md, truncated| FetchUrlResult {
+        ok: true, title, content_md: Some(content_md), truncated: Some(truncated),
+        error: None, source: url.to_string(), retry_with_cookies: None, low_quality: None,
+        body_truncated: Some(body_truncated), content_type: content_type.clone(), cancelled: None,
+        status_code: Some(status.as_u16()), metadata: None,
+    };
+
+    if kind == BodyKind::Image {
+        if body_truncated || bytes.len() > MAX_IMAGE_BYTES {
+            return FetchUrlResult {
+                content_type: content_type.clone(),
+                ..FetchUrlResult::err(url, format!("Image too large (limit {} bytes)", MAX_IMAGE_BYTES))
+            };
+        }
+        let mime = content_type.as_deref().unwrap_or("image/png");
+        let data_url = format!("data:{};base64,{}", mime, BASE64_STANDARD.encode(&bytes));
+        return ok_result(None, data_url, false);
+    }
+
+    let text = String::from_utf8_lossy(&bytes).into_owned();
+    drop(bytes);
+
+    let (title, content_md) = match kind {
+        BodyKind::Json => (None, pretty_json(&text)),
+        BodyKind::Text => (None, text),
+        _ if policy.metadata_only => {
+            let metadata = extract_metadata(&text, url);
+            let title = metadata.title.clone();
+            return FetchUrlResult {
+                content_md: None,
+                truncated: None,
+                metadata: Some(metadata),
+                ..ok_result(title, String::new(), false)
+            };
+        }
+        _ => {
+            let title = extract_title(&text);
+            let content_md = html_to_clean_md(&text);
+            if content_md.trim().len() < LOW_QUALITY_THRESHOLD {
+                return FetchUrlResult {
+                    retry_with_cookies: if cookies.is_none() { Some(true) } else { None },
+                    low_quality: Some(true),
+                    ..ok_result(title, content_md, false)
+                };
+            }
+            (title, content_md)
+        }
+    };
+
+    let truncated = body_truncated || content_md.len() > max_chars as usize;
+    let content_md = if truncated {
+        let cut: String = content_md.chars().take(max_chars as usize).collect();
+        format!("{}\n\n(Content truncated, source: {})", cut, url)
+    } else { content_md };
+
+    ok_result(title, content_md, truncated)
+}
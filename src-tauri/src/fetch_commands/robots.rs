@@ -0,0 +1,157 @@
+//! Per-request fetch policy: User-Agent override and robots.txt checks.
+
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use reqwest::header::USER_AGENT;
+
+use super::client::CHROME_UA;
+use super::content::read_body_capped;
+
+/// robots.txt 读取上限（512KB），超出部分忽略
+const MAX_ROBOTS_BYTES: usize = 512 * 1024;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct FetchPolicy<'a> {
+    /// 覆盖默认的浏览器 UA
+    pub user_agent: Option<&'a str>,
+    /// 请求前检查目标路径是否被 robots.txt 禁止
+    pub respect_robots: bool,
+    /// 跳过 TLS 证书校验（需单独构建客户端，不复用共享客户端）
+    pub danger_accept_invalid_certs: bool,
+    /// HTML 页面只提取元数据，不转换正文为 Markdown
+    pub metadata_only: bool,
+}
+
+impl FetchPolicy<'_> {
+    pub(super) fn effective_user_agent(&self) -> &str {
+        self.user_agent.map(str::trim).filter(|s| !s.is_empty()).unwrap_or(CHROME_UA)
+    }
+}
+
+/// (User-agent 列表, 规则列表)；规则为 (是否 Allow, 路径模式)
+type Group = (Vec<String>, Vec<(bool, String)>);
+
+/// robots.txt 匹配用的产品名：UA 中第一个 `/` 或空格之前的部分
+fn product_token(ua: &str) -> String {
+    ua.split(['/', ' ']).next().unwrap_or("").to_ascii_lowercase()
+}
+
+/// 支持 `*` 通配与 `$` 结尾锚定；未锚定时只需匹配路径前缀
+fn rule_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let (p, s) = (pattern.as_bytes(), path.as_bytes());
+    let (mut pi, mut si) = (0, 0);
+    // 最近一个 `*` 的位置及其当前吞掉的位置，用于回溯
+    let mut star: Option<(usize, usize)> = None;
+    loop {
+        if pi == p.len() {
+            if !anchored || si == s.len() {
+                return true;
+            }
+        } else if p[pi] == b'*' {
+            star = Some((pi, si));
+            pi += 1;
+            continue;
+        } else if si < s.len() && p[pi] == s[si] {
+            pi += 1;
+            si += 1;
+            continue;
+        }
+        match star {
+            Some((sp, ss)) if ss < s.len() => {
+                star = Some((sp, ss + 1));
+                pi = sp + 1;
+                si = ss + 1;
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// 判断 `path`（含 query）是否允许抓取：选取匹配 UA 的分组（否则 `*`），
+/// 最长规则胜出，长度相同时 Allow 优先。
+pub(crate) fn is_allowed(robots_txt: &str, user_agent: &str, path: &str) -> bool {
+    let token = product_token(user_agent);
+    let mut groups: Vec<Group> = Vec::new();
+    let mut in_agents = false;
+    for line in robots_txt.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let (key, value) = (key.trim().to_ascii_lowercase(), value.trim());
+        match key.as_str() {
+            "user-agent" => {
+                if !in_agents {
+                    groups.push((Vec::new(), Vec::new()));
+                }
+                in_agents = true;
+                if let Some(g) = groups.last_mut() {
+                    g.0.push(value.to_ascii_lowercase());
+                }
+            }
+            "allow" | "disallow" => {
+                in_agents = false;
+                if let Some(g) = groups.last_mut() {
+                    g.1.push((key == "allow", value.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let specific: Vec<_> = groups
+        .iter()
+        .filter(|(agents, _)| agents.contains(&token))
+        .collect();
+    let selected = if specific.is_empty() {
+        groups.iter().filter(|(agents, _)| agents.iter().any(|a| a == "*")).collect()
+    } else {
+        specific
+    };
+
+    let mut best: Option<(usize, bool)> = None;
+    for (allow, pattern) in selected.iter().flat_map(|(_, rules)| rules) {
+        // 空 Disallow 表示不限制
+        if pattern.is_empty() || !rule_matches(pattern, path) {
+            continue;
+        }
+        let len = pattern.len();
+        let better = match best {
+            None => true,
+            Some((l, a)) => len > l || (len == l && *allow && !a),
+        };
+        if better {
+            best = Some((len, *allow));
+        }
+    }
+    best.map(|(_, allow)| allow).unwrap_or(true)
+}
+
+/// 抓取并检查 robots.txt；robots.txt 不存在或无法获取时视为允许。
+pub(super) fn check_robots(client: &Client, url: &str, user_agent: &str, timeout: Duration) -> bool {
+    let Ok(parsed) = reqwest::Url::parse(url) else { return true };
+    let mut path = parsed.path().to_string();
+    if let Some(q) = parsed.query() {
+        path.push('?');
+        path.push_str(q);
+    }
+    let mut robots_url = parsed.clone();
+    robots_url.set_path("/robots.txt");
+    robots_url.set_query(None);
+    robots_url.set_fragment(None);
+
+    let resp = client
+        .get(robots_url)
+        .header(USER_AGENT, user_agent)
+        .timeout(timeout)
+        .send();
+    let Ok(resp) = resp else { return true };
+    if !resp.status().is_success() {
+        return true;
+    }
+    let Ok((bytes, _)) = read_body_capped(resp, MAX_ROBOTS_BYTES) else { return true };
+    is_allowed(&String::from_utf8_lossy(&bytes), user_agent, &path)
+}
@@ -0,0 +1,294 @@
+use std::io::Read;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use reqwest::header::USER_AGENT;
+
+use super::client::{browser_headers, build_client};
+use super::content::*;
+use super::*;
+use crate::shell_commands::{CancelRegistry, CancelToken};
+
+#[test]
+fn invalid_url_returns_error() {
+    let r = do_fetch("file:///tmp/x", 1000, 1000, None, FetchPolicy::default());
+    assert!(!r.ok);
+    assert!(r.error.as_deref().unwrap().contains("http"));
+}
+
+#[test]
+fn youtube_url_returns_unsupported_error() {
+    let r = do_fetch("https://www.youtube.com/watch?v=abc", 1000, 1000, None, FetchPolicy::default());
+    assert!(!r.ok);
+    assert!(r.error.as_deref().unwrap().contains("YouTube"));
+    let r2 = do_fetch("https://youtu.be/abc", 1000, 1000, None, FetchPolicy::default());
+    assert!(!r2.ok);
+}
+
+#[test]
+fn strip_noise_tags_removes_script_and_style() {
+    let html = "<html><head><style>body{}</style></head>\
+        <body><nav>Nav</nav><p>Content</p><footer>F</footer></body></html>";
+    let cleaned = strip_noise_tags(html);
+    assert!(!cleaned.contains("<style>"));
+    assert!(!cleaned.contains("<nav>"));
+    assert!(!cleaned.contains("<footer>"));
+    assert!(cleaned.contains("<p>Content</p>"));
+}
+
+/// Serve a single HTTP response on a random local port and return its URL.
+pub(super) fn serve_once(content_type: &'static str, body: Vec<u8>) -> String {
+    serve_after(Duration::ZERO, content_type, body)
+}
+
+/// Like `serve_once`, but waits `delay` before responding.
+fn serve_after(delay: Duration, content_type: &'static str, body: Vec<u8>) -> String {
+    serve_status(delay, "200 OK", content_type, body)
+}
+
+fn serve_status(
+    delay: Duration, status: &'static str, content_type: &'static str, body: Vec<u8>,
+) -> String {
+    use std::io::Write;
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        thread::sleep(delay);
+        let mut req = [0u8; 4096];
+        let _ = stream.read(&mut req);
+        let head = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            content_type,
+            body.len()
+        );
+        let _ = stream.write_all(head.as_bytes());
+        // 客户端达到上限后会提前断开，写入失败属预期
+        for chunk in body.chunks(64 * 1024) {
+            if stream.write_all(chunk).is_err() {
+                break;
+            }
+        }
+    });
+    format!("http://{}/", addr)
+}
+
+#[test]
+fn read_body_capped_stops_at_limit() {
+    let data = [b'a'; 100];
+    let (buf, truncated) = read_body_capped(&data[..], 10).unwrap();
+    assert_eq!(buf.len(), 10);
+    assert!(truncated);
+    let (buf, truncated) = read_body_capped(&data[..], 100).unwrap();
+    assert_eq!(buf.len(), 100);
+    assert!(!truncated);
+}
+
+#[test]
+fn oversized_body_is_capped_before_conversion() {
+    let mut body = b"<html><head><title>Big</title></head><body><p>".to_vec();
+    body.extend(std::iter::repeat(b'x').take(4 * 1024 * 1024));
+    body.extend_from_slice(b"</p><p>TAIL_MARKER</p></body></html>");
+    let url = serve_once("text/html", body);
+
+    let r = do_fetch_with(&build_client().unwrap(), &url, 10_000, 300_000, None, FetchPolicy::default(), 4096);
+    assert!(r.ok, "{:?}", r.error);
+    assert_eq!(r.body_truncated, Some(true));
+    assert_eq!(r.truncated, Some(true));
+    assert_eq!(r.title.as_deref(), Some("Big"));
+    let md = r.content_md.unwrap();
+    // 转换只作用于前 4096 字节，外加截断提示
+    assert!(md.len() < 4096 + 200);
+    assert!(!md.contains("TAIL_MARKER"));
+}
+
+#[test]
+fn small_body_is_not_body_truncated() {
+    let html = format!("<html><body><p>{}</p></body></html>", "word ".repeat(50));
+    let url = serve_once("text/html", html.into_bytes());
+    let r = do_fetch(&url, 10_000, 100_000, None, FetchPolicy::default());
+    assert!(r.ok, "{:?}", r.error);
+    assert_eq!(r.body_truncated, Some(false));
+    assert_eq!(r.truncated, Some(false));
+}
+
+#[test]
+fn classify_content_types() {
+    assert_eq!(classify_content_type("text/html"), BodyKind::Html);
+    assert_eq!(classify_content_type(""), BodyKind::Html);
+    assert_eq!(classify_content_type("application/json"), BodyKind::Json);
+    assert_eq!(classify_content_type("application/ld+json"), BodyKind::Json);
+    assert_eq!(classify_content_type("text/plain"), BodyKind::Text);
+    assert_eq!(classify_content_type("image/png"), BodyKind::Image);
+    assert_eq!(mime_essence("Application/JSON; charset=utf-8"), "application/json");
+}
+
+#[test]
+fn json_response_is_pretty_printed() {
+    let url = serve_once("application/json; charset=utf-8", br#"{"a":1,"b":[true,null]}"#.to_vec());
+    let r = do_fetch(&url, 10_000, 100_000, None, FetchPolicy::default());
+    assert!(r.ok, "{:?}", r.error);
+    assert_eq!(r.content_type.as_deref(), Some("application/json"));
+    assert_eq!(r.title, None);
+    assert_eq!(r.low_quality, None);
+    let md = r.content_md.unwrap();
+    assert!(md.contains("\"a\": 1"), "{md}");
+    assert!(md.contains('\n'));
+}
+
+#[test]
+fn error_status_returns_body() {
+    let body = br#"{"error":"invalid field","field":"name"}"#.to_vec();
+    let url = serve_status(Duration::ZERO, "422 Unprocessable Entity", "application/json", body);
+    let r = do_fetch(&url, 10_000, 100_000, None, FetchPolicy::default());
+    assert!(!r.ok);
+    assert_eq!(r.error.as_deref(), Some("HTTP 422"));
+    assert_eq!(r.status_code, Some(422));
+    assert_eq!(r.content_type.as_deref(), Some("application/json"));
+    let md = r.content_md.unwrap();
+    assert!(md.contains("\"error\": \"invalid field\""), "{md}");
+}
+
+#[test]
+fn plain_text_response_is_verbatim() {
+    let body = "line <one>\n  * not markdown\n";
+    let url = serve_once("text/plain", body.as_bytes().to_vec());
+    let r = do_fetch(&url, 10_000, 100_000, None, FetchPolicy::default());
+    assert!(r.ok, "{:?}", r.error);
+    assert_eq!(r.content_type.as_deref(), Some("text/plain"));
+    assert_eq!(r.content_md.as_deref(), Some(body));
+}
+
+#[test]
+fn image_response_becomes_data_url() {
+    let url = serve_once("image/png", vec![0x89, b'P', b'N', b'G']);
+    let r = do_fetch(&url, 10_000, 100_000, None, FetchPolicy::default());
+    assert!(r.ok, "{:?}", r.error);
+    assert_eq!(r.content_md.as_deref(), Some("data:image/png;base64,iVBORw=="));
+}
+
+#[test]
+fn batch_preserves_input_order() {
+    // 先发出的请求响应更慢，完成顺序与输入顺序相反
+    let args: Vec<FetchUrlArgs> = (0..6)
+        .map(|i| {
+            let delay = Duration::from_millis(50 * (6 - i as u64));
+            FetchUrlArgs {
+                url: serve_after(delay, "text/plain", format!("body-{i}").into_bytes()),
+                timeout_ms: Some(10_000),
+                max_chars: None,
+                cookies: None,
+                user_agent: None,
+                respect_robots: false,
+                cancel_token: None,
+                danger_accept_invalid_certs: false,
+                metadata_only: false,
+            }
+        })
+        .chain(std::iter::once(FetchUrlArgs {
+            url: "ftp://example.com".into(),
+            timeout_ms: None,
+            max_chars: None,
+            cookies: None,
+            user_agent: None,
+            respect_robots: false,
+            cancel_token: None,
+            danger_accept_invalid_certs: false,
+            metadata_only: false,
+        }))
+        .collect();
+    let client = build_client().unwrap();
+    let results = fetch_batch(&client, &args, BATCH_CONCURRENCY);
+    assert_eq!(results.len(), 7);
+    for (i, r) in results.iter().take(6).enumerate() {
+        assert!(r.ok, "{:?}", r.error);
+        assert_eq!(r.source, args[i].url);
+        assert_eq!(r.content_md.as_deref(), Some(format!("body-{i}").as_str()));
+    }
+    assert!(!results[6].ok);
+}
+
+#[test]
+fn cancel_abandons_slow_fetch() {
+    let url = serve_after(Duration::from_secs(10), "text/plain", b"late".to_vec());
+    let args = FetchUrlArgs {
+        url: url.clone(),
+        timeout_ms: Some(30_000),
+        max_chars: None,
+        cookies: None,
+        user_agent: None,
+        respect_robots: false,
+        cancel_token: Some("fetch-1".into()),
+        danger_accept_invalid_certs: false,
+        metadata_only: false,
+    };
+    let registry = CancelRegistry::new();
+    let token = registry.register("fetch-1");
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        assert!(registry.cancel("fetch-1"));
+    });
+
+    let started = Instant::now();
+    let r = fetch_cancellable(args, Some(token)).unwrap();
+    canceller.join().unwrap();
+    assert!(started.elapsed() < Duration::from_secs(3));
+    assert!(!r.ok);
+    assert_eq!(r.cancelled, Some(true));
+    assert_eq!(r.source, url);
+}
+
+#[test]
+fn uncancelled_fetch_returns_result() {
+    let url = serve_once("text/plain", b"hello".to_vec());
+    let args = FetchUrlArgs {
+        url,
+        timeout_ms: Some(10_000),
+        max_chars: None,
+        cookies: None,
+        user_agent: None,
+        respect_robots: false,
+        cancel_token: None,
+        danger_accept_invalid_certs: false,
+        metadata_only: false,
+    };
+    let r = fetch_cancellable(args, Some(CancelToken::new())).unwrap();
+    assert!(r.ok, "{:?}", r.error);
+    assert!(r.cancelled.is_none());
+    assert_eq!(r.content_md.as_deref(), Some("hello"));
+}
+
+#[test]
+fn browser_headers_contain_chrome_ua() {
+    let h = browser_headers();
+    let ua = h.get(USER_AGENT).unwrap().to_str().unwrap();
+    assert!(ua.contains("Chrome"));
+}
+
+#[test]
+fn parse_html_string_converts_and_truncates() {
+    let html = "<html><script>x()</script><body><h1>Title</h1><p>Hello <b>world</b></p></body></html>";
+    let args = |max_chars| ParseHtmlStringArgs { html: html.into(), max_chars, clean: None };
+    let r = parse_html_string(args(None));
+    assert!(!r.truncated);
+    assert!(r.content_md.contains("Title") && r.content_md.contains("**world**"));
+    assert!(!r.content_md.contains("x()"));
+    let r = parse_html_string(args(Some(3)));
+    assert!(r.truncated);
+    assert_eq!(r.content_md.chars().count(), 3);
+}
+
+#[test]
+fn invalid_certs_flag_is_opt_in() {
+    let args: FetchUrlArgs = serde_json::from_str(r#"{"url":"https://intranet.local"}"#).unwrap();
+    assert!(!args.danger_accept_invalid_certs);
+    assert!(!args.policy().danger_accept_invalid_certs);
+    assert!(!FetchPolicy::default().danger_accept_invalid_certs);
+
+    let args: FetchUrlArgs =
+        serde_json::from_str(r#"{"url":"https://intranet.local","dangerAcceptInvalidCerts":true}"#)
+            .unwrap();
+    assert!(args.policy().danger_accept_invalid_certs);
+    assert!(super::client::build_client_with(true).is_ok());
+}
@@ -0,0 +1,64 @@
+use super::metadata::{extract_metadata, PageMetadata};
+
+const FIXTURE: &str = r#"<!doctype html>
+<html><head>
+  <title>Fallback title</title>
+  <meta charset="utf-8">
+  <meta property="og:title" content="Cove &amp; Friends">
+  <meta property='og:description' content='Desktop AI workspace'>
+  <meta name="description" content="Plain description">
+  <meta property="og:image" content="/img/cover.png">
+  <meta property="og:site_name" content="Cove">
+  <link rel="shortcut icon" href="/static/favicon.png">
+</head><body><p>Body is not converted.</p></body></html>"#;
+
+#[test]
+fn extracts_opengraph_fields() {
+    let meta = extract_metadata(FIXTURE, "https://example.com/blog/post");
+    assert_eq!(
+        meta,
+        PageMetadata {
+            title: Some("Cove & Friends".into()),
+            description: Some("Desktop AI workspace".into()),
+            image: Some("https://example.com/img/cover.png".into()),
+            site_name: Some("Cove".into()),
+            favicon: Some("https://example.com/static/favicon.png".into()),
+        }
+    );
+}
+
+#[test]
+fn falls_back_to_title_tag_and_meta_description() {
+    let html = "<html><head><title>Only Title</title>\
+        <META NAME=\"Description\" CONTENT=\"From meta\"></head></html>";
+    let meta = extract_metadata(html, "https://example.com/a/b");
+    assert_eq!(meta.title.as_deref(), Some("Only Title"));
+    assert_eq!(meta.description.as_deref(), Some("From meta"));
+    assert_eq!(meta.image, None);
+    assert_eq!(meta.site_name, None);
+    assert_eq!(meta.favicon.as_deref(), Some("https://example.com/favicon.ico"));
+}
+
+#[test]
+fn metadata_serializes_camel_case() {
+    let json = serde_json::to_value(extract_metadata(FIXTURE, "https://example.com/")).unwrap();
+    assert_eq!(json["siteName"], "Cove");
+    assert!(json.get("site_name").is_none());
+}
+
+#[test]
+fn metadata_only_fetch_skips_markdown() {
+    use super::client::build_client;
+    use super::*;
+
+    let url = super::tests::serve_once("text/html; charset=utf-8", FIXTURE.as_bytes().to_vec());
+    let policy = FetchPolicy { metadata_only: true, ..FetchPolicy::default() };
+    let client = build_client().unwrap();
+    let r = do_fetch_with(&client, &url, 10_000, 1000, None, policy, MAX_BODY_BYTES);
+    assert!(r.ok, "{:?}", r.error);
+    assert!(r.content_md.is_none());
+    assert_eq!(r.title.as_deref(), Some("Cove & Friends"));
+    let meta = r.metadata.unwrap();
+    assert_eq!(meta.site_name.as_deref(), Some("Cove"));
+    assert_eq!(meta.image, Some(format!("{url}img/cover.png")));
+}
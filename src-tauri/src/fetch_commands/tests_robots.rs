@@ -0,0 +1,85 @@
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use super::*;
+
+/// Serve requests by path until the test ends; records each raw request head.
+/// Unknown paths get 404.
+fn serve_routes(routes: Vec<(&'static str, &'static str)>) -> (String, Arc<Mutex<Vec<String>>>) {
+    use std::io::Write;
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_c = seen.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let mut req = [0u8; 4096];
+            let n = stream.read(&mut req).unwrap_or(0);
+            let head = String::from_utf8_lossy(&req[..n]).into_owned();
+            let path = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+            seen_c.lock().unwrap().push(head);
+            let resp = match routes.iter().find(|(p, _)| *p == path) {
+                Some((_, body)) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                ),
+                None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".into(),
+            };
+            let _ = stream.write_all(resp.as_bytes());
+        }
+    });
+    (format!("http://{}", addr), seen)
+}
+
+#[test]
+fn user_agent_override_is_sent() {
+    let (base, seen) = serve_routes(vec![("/page", "hello")]);
+    let policy = FetchPolicy { user_agent: Some("CoveBot/1.0"), ..FetchPolicy::default() };
+    let r = do_fetch(&format!("{base}/page"), 10_000, 1000, None, policy);
+    assert!(r.ok, "{:?}", r.error);
+    let heads = seen.lock().unwrap();
+    let head = heads[0].to_ascii_lowercase();
+    assert!(head.contains("user-agent: covebot/1.0"), "{head}");
+    assert!(!head.contains("chrome"));
+}
+
+#[test]
+fn robots_disallowed_path_is_blocked() {
+    let robots = "User-agent: *\nDisallow: /private\n";
+    let (base, seen) = serve_routes(vec![("/robots.txt", robots), ("/private/a", "secret"), ("/open", "ok")]);
+    let policy = FetchPolicy { respect_robots: true, ..FetchPolicy::default() };
+
+    let r = do_fetch(&format!("{base}/private/a"), 10_000, 1000, None, policy);
+    assert!(!r.ok);
+    assert_eq!(r.error.as_deref(), Some("Disallowed by robots.txt"));
+    // 被禁止时不应请求目标页面
+    assert!(seen.lock().unwrap().iter().all(|h| !h.contains("GET /private/a")));
+
+    let r = do_fetch(&format!("{base}/open"), 10_000, 1000, None, policy);
+    assert!(r.ok, "{:?}", r.error);
+}
+
+#[test]
+fn missing_robots_txt_allows_fetch() {
+    let (base, _) = serve_routes(vec![("/page", "hello")]);
+    let policy = FetchPolicy { respect_robots: true, ..FetchPolicy::default() };
+    let r = do_fetch(&format!("{base}/page"), 10_000, 1000, None, policy);
+    assert!(r.ok, "{:?}", r.error);
+}
+
+#[test]
+fn robots_rules_pick_agent_group_and_longest_match() {
+    use super::robots::is_allowed;
+    let txt = "User-agent: *\nDisallow: /\n\nUser-agent: covebot\nDisallow: /tmp/\nAllow: /tmp/ok$\n";
+    assert!(!is_allowed(txt, "OtherBot/2", "/anything"));
+    assert!(is_allowed(txt, "CoveBot/1.0", "/anything"));
+    assert!(!is_allowed(txt, "CoveBot/1.0", "/tmp/x"));
+    assert!(is_allowed(txt, "CoveBot/1.0", "/tmp/ok"));
+    assert!(!is_allowed(txt, "CoveBot/1.0", "/tmp/ok2"));
+    assert!(!is_allowed("User-agent: *\nDisallow: /*.pdf$\n", "x", "/docs/a.pdf"));
+    assert!(is_allowed("User-agent: *\nDisallow: /*.pdf$\n", "x", "/docs/a.pdf.html"));
+    assert!(is_allowed("User-agent: *\nDisallow:\n", "x", "/"));
+}
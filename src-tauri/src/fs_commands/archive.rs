@@ -0,0 +1,294 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use serde::Deserialize;
+
+use zip::write::SimpleFileOptions;
+
+use super::validation::{canonical_workspace_root, ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist};
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// unzip_archive：解压到工作区，防 zip-slip 与 zip 炸弹
+// ---------------------------------------------------------------------------
+
+/// 解压后总大小上限（1GB）
+pub(super) const UNZIP_MAX_TOTAL_BYTES: u64 = 1024 * 1024 * 1024;
+/// 条目数上限
+pub(super) const UNZIP_MAX_ENTRIES: usize = 20_000;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnzipArchiveArgs {
+    pub workspace_root: String,
+    pub archive_path: String,
+    pub dest_dir: String,
+}
+
+/// 条目名必须是纯相对路径：不能含 `..`、根或盘符。
+pub(super) fn safe_entry_path(name: &str) -> Option<PathBuf> {
+    let p = Path::new(name);
+    let mut out = PathBuf::new();
+    for c in p.components() {
+        match c {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    if out.as_os_str().is_empty() {
+        None
+    } else {
+        Some(out)
+    }
+}
+
+pub(super) fn to_rel(root: &Path, abs: &Path) -> String {
+    abs.strip_prefix(root)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| abs.to_string_lossy().into_owned())
+}
+
+/// 创建解压目录，并确认其真实位置仍在工作区内。
+///
+/// 条目路径只做了词法校验；若目标目录或其子目录是指向工作区外的符号链接，
+/// 仍可能写出工作区，因此创建前后都按 canonicalize 后的路径复查。
+pub(super) fn create_dir_inside(root: &Path, dir: &Path) -> Result<(), FsError> {
+    // 先复查最近的已存在祖先，避免在工作区外创建目录
+    let mut existing = dir;
+    while fs::symlink_metadata(existing).is_err() {
+        match existing.parent() {
+            Some(p) => existing = p,
+            None => break,
+        }
+    }
+    ensure_canonical_inside(root, existing)?;
+    fs::create_dir_all(dir)?;
+    ensure_canonical_inside(root, dir)
+}
+
+fn ensure_canonical_inside(root: &Path, path: &Path) -> Result<(), FsError> {
+    let canonical = dunce::canonicalize(path)?;
+    if !canonical.starts_with(root) {
+        return Err(FsError::OutsideWorkspace);
+    }
+    Ok(())
+}
+
+/// 准备写入文件（解压条目或 zip 输出）：父目录须在工作区内，且不经由已存在的符号链接写入。
+pub(super) fn prepare_entry_file(root: &Path, out_path: &Path) -> Result<(), FsError> {
+    if let Some(parent) = out_path.parent() {
+        create_dir_inside(root, parent)?;
+    }
+    match fs::symlink_metadata(out_path) {
+        Ok(meta) if meta.file_type().is_symlink() => Err(FsError::NotAllowed(format!(
+            "refusing to write through symlink: {}",
+            out_path.display()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Core extraction, separated from Tauri event emission for testability.
+/// Returns workspace-relative paths of the extracted files.
+pub(super) fn unzip_archive_inner(
+    args: &UnzipArchiveArgs,
+    max_total_bytes: u64,
+) -> Result<Vec<String>, FsError> {
+    let archive_abs = ensure_inside_workspace_exists(&args.workspace_root, &args.archive_path)?;
+    let dest_abs = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.dest_dir)?;
+    let root = canonical_workspace_root(&args.workspace_root)?;
+
+    let file = fs::File::open(&archive_abs)?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| FsError::NotAllowed(format!("invalid zip: {e}")))?;
+    if archive.len() > UNZIP_MAX_ENTRIES {
+        return Err(FsError::TooLarge);
+    }
+
+    // 先整体校验，任何越界条目都拒绝整个归档，避免写入一半
+    let mut declared_total: u64 = 0;
+    for i in 0..archive.len() {
+        let entry = archive
+            .by_index_raw(i)
+            .map_err(|e| FsError::Io(e.to_string()))?;
+        if safe_entry_path(entry.name()).is_none() {
+            return Err(FsError::NotAllowed(format!(
+                "entry escapes destination: {}",
+                entry.name()
+            )));
+        }
+        declared_total = declared_total.saturating_add(entry.size());
+    }
+    if declared_total > max_total_bytes {
+        return Err(FsError::TooLarge);
+    }
+
+    create_dir_inside(&root, &dest_abs)?;
+    let mut written: u64 = 0;
+    let mut created = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| FsError::Io(e.to_string()))?;
+        // 已在预检中校验
+        let Some(rel) = safe_entry_path(entry.name()) else { continue };
+        let out_path = dest_abs.join(rel);
+        if entry.is_dir() {
+            create_dir_inside(&root, &out_path)?;
+            continue;
+        }
+        // 不还原符号链接，避免借助链接写出工作区
+        if entry.is_symlink() {
+            continue;
+        }
+        prepare_entry_file(&root, &out_path)?;
+        let mut out = fs::File::create(&out_path)?;
+        // 声明的大小可能被伪造：按剩余额度限制实际解压字节数
+        let budget = max_total_bytes - written;
+        let n = io::copy(&mut (&mut entry).take(budget + 1), &mut out)?;
+        if n > budget {
+            drop(out);
+            let _ = fs::remove_file(&out_path);
+            return Err(FsError::TooLarge);
+        }
+        written += n;
+        created.push(to_rel(&root, &out_path));
+    }
+    Ok(created)
+}
+
+#[tauri::command]
+pub fn unzip_archive(app: tauri::AppHandle, args: UnzipArchiveArgs) -> Result<Vec<String>, FsError> {
+    let created = unzip_archive_inner(&args, UNZIP_MAX_TOTAL_BYTES)?;
+
+    use tauri::Emitter;
+    for path in &created {
+        let _ = app.emit(
+            crate::workspace_watcher::EVENT_WORKSPACE_FILE_CHANGED,
+            crate::workspace_watcher::WorkspaceFileChangedPayload {
+                path: path.clone(),
+                kind: crate::workspace_watcher::FileChangeKind::Create,
+            },
+        );
+    }
+
+    Ok(created)
+}
+
+// ---------------------------------------------------------------------------
+// zip_entries：将工作区文件/目录打包为 zip（确定性输出）
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZipEntriesArgs {
+    pub workspace_root: String,
+    /// 工作区内的文件或目录；条目名相对于各自的父目录
+    pub paths: Vec<String>,
+    pub zip_path: String,
+    /// 0 = 仅存储，1-9 = deflate 级别（默认 6）
+    #[serde(default)]
+    pub compression_level: Option<i64>,
+}
+
+/// 递归收集 (zip 内名称, 绝对路径)；跳过符号链接与输出文件本身。
+fn collect_zip_sources(
+    abs: &Path,
+    name: &str,
+    exclude: &Path,
+    out: &mut Vec<(String, PathBuf, bool)>,
+) -> Result<(), FsError> {
+    if abs == exclude {
+        return Ok(());
+    }
+    let meta = fs::symlink_metadata(abs)?;
+    if meta.file_type().is_symlink() {
+        return Ok(());
+    }
+    if meta.is_dir() {
+        out.push((format!("{name}/"), abs.to_path_buf(), true));
+        for entry in fs::read_dir(abs)? {
+            let entry = entry?;
+            let child = format!("{}/{}", name, entry.file_name().to_string_lossy());
+            collect_zip_sources(&entry.path(), &child, exclude, out)?;
+        }
+    } else {
+        out.push((name.to_string(), abs.to_path_buf(), false));
+    }
+    Ok(())
+}
+
+/// Core archive creation, separated from Tauri event emission for testability.
+/// Returns the workspace-relative zip path and the number of files written.
+pub(super) fn zip_entries_inner(args: &ZipEntriesArgs) -> Result<(String, usize), FsError> {
+    if args.paths.is_empty() {
+        return Err(FsError::NotAllowed("no paths to zip".into()));
+    }
+    let zip_abs = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.zip_path)?;
+    let root = canonical_workspace_root(&args.workspace_root)?;
+
+    let mut sources = Vec::new();
+    for p in &args.paths {
+        let abs = ensure_inside_workspace_exists(&args.workspace_root, p)?;
+        if abs == root {
+            return Err(FsError::NotAllowed("cannot zip the workspace root".into()));
+        }
+        let name = abs
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .ok_or_else(|| FsError::NotAllowed(format!("invalid path: {p}")))?;
+        collect_zip_sources(&abs, &name, &zip_abs, &mut sources)?;
+    }
+    // 排序保证相同输入得到相同归档
+    sources.sort_by(|a, b| a.0.cmp(&b.0));
+    if let Some(w) = sources.windows(2).find(|w| w[0].0 == w[1].0) {
+        return Err(FsError::NotAllowed(format!("duplicate entry: {}", w[0].0)));
+    }
+
+    let level = args.compression_level.unwrap_or(6).clamp(0, 9);
+    let method = if level == 0 {
+        zip::CompressionMethod::Stored
+    } else {
+        zip::CompressionMethod::Deflated
+    };
+    let opts = SimpleFileOptions::default()
+        .compression_method(method)
+        .compression_level((level > 0).then_some(level))
+        .last_modified_time(zip::DateTime::default());
+
+    // 与解压同样的检查：父目录须在工作区内，且不跟随输出路径上已有的符号链接
+    prepare_entry_file(&root, &zip_abs)?;
+    let mut zip = zip::ZipWriter::new(fs::File::create(&zip_abs)?);
+    let to_io = |e: zip::result::ZipError| FsError::Io(e.to_string());
+    let mut file_count = 0;
+    for (name, abs, is_dir) in &sources {
+        if *is_dir {
+            zip.add_directory(name.as_str(), opts.unix_permissions(0o755))
+                .map_err(to_io)?;
+        } else {
+            zip.start_file(name.as_str(), opts.unix_permissions(0o644))
+                .map_err(to_io)?;
+            io::copy(&mut fs::File::open(abs)?, &mut zip)?;
+            file_count += 1;
+        }
+    }
+    zip.finish().map_err(to_io)?.flush()?;
+
+    Ok((to_rel(&root, &zip_abs), file_count))
+}
+
+#[tauri::command]
+pub fn zip_entries(app: tauri::AppHandle, args: ZipEntriesArgs) -> Result<(), FsError> {
+    let (rel, _) = zip_entries_inner(&args)?;
+
+    use tauri::Emitter;
+    let _ = app.emit(
+        crate::workspace_watcher::EVENT_WORKSPACE_FILE_CHANGED,
+        crate::workspace_watcher::WorkspaceFileChangedPayload {
+            path: rel,
+            kind: crate::workspace_watcher::FileChangeKind::Create,
+        },
+    );
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! 内容哈希与比较后写入（compare-and-swap）：`read_file`/`read_file_raw` 传 `includeHash`
+//! 取得哈希，`write_file_if_unchanged` 仅在文件仍是该哈希时写入，避免覆盖读写之间的外部修改。
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use super::validation::ensure_inside_workspace_may_not_exist;
+use super::write::{write_file, WriteFileArgs};
+use super::FsError;
+
+/// 原始字节的 SHA-256（小写十六进制）
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+pub(super) fn file_content_hash(abs: &Path) -> Result<String, FsError> {
+    Ok(content_hash(&fs::read(abs).map_err(FsError::from)?))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteFileIfUnchangedArgs {
+    pub workspace_root: String,
+    pub path: String,
+    pub content: String,
+    /// 读取时返回的 `hash`
+    pub expected_hash: String,
+}
+
+/// 文件当前哈希与 `expectedHash` 不一致（或文件已被删除）时返回 `Conflict`，否则同 `write_file`。
+#[tauri::command]
+pub fn write_file_if_unchanged(args: WriteFileIfUnchangedArgs) -> Result<String, FsError> {
+    let abs = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.path)?;
+    if abs.is_dir() {
+        return Err(FsError::IsDirectory);
+    }
+    let current = match file_content_hash(&abs) {
+        Ok(hash) => hash,
+        Err(FsError::NotFound) => return Err(FsError::Conflict),
+        Err(e) => return Err(e),
+    };
+    if !current.eq_ignore_ascii_case(args.expected_hash.trim()) {
+        return Err(FsError::Conflict);
+    }
+    write_file(WriteFileArgs {
+        workspace_root: args.workspace_root,
+        path: args.path,
+        content: args.content,
+    })
+}
@@ -1,9 +1,10 @@
 use std::fs;
+use std::io;
 use std::path::Path;
 
 use serde::Deserialize;
 
-use super::validation::{ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist};
+use super::validation::{canonical_workspace_root, ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist};
 use super::FsError;
 
 // ---------------------------------------------------------------------------
@@ -25,7 +26,7 @@ pub(super) fn copy_entry_inner(args: &CopyEntryArgs) -> Result<String, FsError>
 
     // Use symlink_metadata to detect broken symlinks too (Path::exists follows symlinks)
     if fs::symlink_metadata(&to_abs).is_ok() {
-        return Err(FsError::NotAllowed("destination already exists".into()));
+        return Err(FsError::AlreadyExists);
     }
 
     let meta = fs::metadata(&from_abs).map_err(FsError::from)?;
@@ -37,12 +38,10 @@ pub(super) fn copy_entry_inner(args: &CopyEntryArgs) -> Result<String, FsError>
                 fs::create_dir_all(parent).map_err(FsError::from)?;
             }
         }
-        fs::copy(&from_abs, &to_abs).map_err(FsError::from)?;
+        copy_file_preserving(&from_abs, &to_abs)?;
     }
 
-    let root = Path::new(&args.workspace_root)
-        .canonicalize()
-        .map_err(FsError::from)?;
+    let root = canonical_workspace_root(&args.workspace_root)?;
     let rel = to_abs
         .strip_prefix(&root)
         .map(|p| p.to_string_lossy().replace('\\', "/"))
@@ -90,7 +89,7 @@ pub(super) fn copy_external_file_inner(args: &CopyExternalFileArgs) -> Result<St
 
     let dest = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.dest_path)?;
     if fs::symlink_metadata(&dest).is_ok() {
-        return Err(FsError::NotAllowed("destination already exists".into()));
+        return Err(FsError::AlreadyExists);
     }
 
     if let Some(parent) = dest.parent() {
@@ -102,12 +101,10 @@ pub(super) fn copy_external_file_inner(args: &CopyExternalFileArgs) -> Result<St
     if src.is_dir() {
         copy_dir_recursive(src, &dest)?;
     } else {
-        fs::copy(src, &dest).map_err(FsError::from)?;
+        copy_file_preserving(src, &dest)?;
     }
 
-    let root = Path::new(&args.workspace_root)
-        .canonicalize()
-        .map_err(FsError::from)?;
+    let root = canonical_workspace_root(&args.workspace_root)?;
     let rel = dest
         .strip_prefix(&root)
         .map(|p| p.to_string_lossy().replace('\\', "/"))
@@ -133,9 +130,23 @@ pub fn copy_external_file(app: tauri::AppHandle, args: CopyExternalFileArgs) ->
 }
 
 // ---------------------------------------------------------------------------
-// Recursive directory copy helper
+// Copy helpers (preserve mtime and permissions)
 // ---------------------------------------------------------------------------
 
+/// Apply the source's permissions and access/modified times to `dst`, so build
+/// tools keyed on mtime don't see copied files as fresh.
+fn preserve_metadata(src_meta: &fs::Metadata, dst: &Path) -> Result<(), FsError> {
+    fs::set_permissions(dst, src_meta.permissions()).map_err(FsError::from)?;
+    let atime = filetime::FileTime::from_last_access_time(src_meta);
+    let mtime = filetime::FileTime::from_last_modification_time(src_meta);
+    filetime::set_file_times(dst, atime, mtime).map_err(FsError::from)
+}
+
+pub(super) fn copy_file_preserving(src: &Path, dst: &Path) -> Result<(), FsError> {
+    fs::copy(src, dst).map_err(FsError::from)?;
+    preserve_metadata(&fs::metadata(src).map_err(FsError::from)?, dst)
+}
+
 fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), FsError> {
     fs::create_dir_all(dst).map_err(FsError::from)?;
     for entry in fs::read_dir(src).map_err(FsError::from)? {
@@ -147,8 +158,84 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), FsError> {
         if meta.is_dir() {
             copy_dir_recursive(&src_path, &dst_path)?;
         } else {
-            fs::copy(&src_path, &dst_path).map_err(FsError::from)?;
+            copy_file_preserving(&src_path, &dst_path)?;
         }
     }
-    Ok(())
+    // Last: adding entries bumps the directory mtime, and a read-only mode would block them
+    preserve_metadata(&fs::metadata(src).map_err(FsError::from)?, dst)
+}
+
+// ---------------------------------------------------------------------------
+// Cross-device move fallback
+// ---------------------------------------------------------------------------
+
+/// 跨文件系统 rename 失败（Unix `EXDEV`、Windows `ERROR_NOT_SAME_DEVICE`）
+pub(super) fn is_cross_device_error(e: &io::Error) -> bool {
+    #[cfg(unix)]
+    {
+        e.raw_os_error() == Some(libc::EXDEV)
+    }
+    #[cfg(windows)]
+    {
+        e.raw_os_error() == Some(17)
+    }
+}
+
+/// rename 失败于跨设备时退化为递归复制后删除源；复制失败时清理已写入的目标，源保持不动。
+pub(super) fn copy_then_remove(from: &Path, to: &Path) -> Result<(), FsError> {
+    let is_dir = fs::symlink_metadata(from).map_err(FsError::from)?.is_dir();
+    if let Err(e) = copy_tree_keeping_links(from, to) {
+        let _ = match fs::symlink_metadata(to) {
+            Ok(meta) if meta.is_dir() => fs::remove_dir_all(to),
+            _ => fs::remove_file(to),
+        };
+        return Err(e);
+    }
+    if is_dir {
+        fs::remove_dir_all(from).map_err(FsError::from)
+    } else {
+        fs::remove_file(from).map_err(FsError::from)
+    }
+}
+
+/// 移动用的复制：符号链接按原样重建而不跟随，既不深拷贝链接目标，也不会因循环链接无限递归。
+fn copy_tree_keeping_links(src: &Path, dst: &Path) -> Result<(), FsError> {
+    let meta = fs::symlink_metadata(src).map_err(FsError::from)?;
+    if meta.file_type().is_symlink() {
+        return copy_symlink(src, dst);
+    }
+    if !meta.is_dir() {
+        return copy_file_preserving(src, dst);
+    }
+    fs::create_dir_all(dst).map_err(FsError::from)?;
+    for entry in fs::read_dir(src).map_err(FsError::from)? {
+        let entry = entry.map_err(FsError::from)?;
+        copy_tree_keeping_links(&entry.path(), &dst.join(entry.file_name()))?;
+    }
+    preserve_metadata(&meta, dst)
+}
+
+fn copy_symlink(src: &Path, dst: &Path) -> Result<(), FsError> {
+    let target = fs::read_link(src).map_err(FsError::from)?;
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&target, dst).map_err(FsError::from)
+    }
+    #[cfg(windows)]
+    {
+        // Windows 区分文件与目录链接；悬空链接按文件链接重建
+        if fs::metadata(src).map(|m| m.is_dir()).unwrap_or(false) {
+            std::os::windows::fs::symlink_dir(&target, dst).map_err(FsError::from)
+        } else {
+            std::os::windows::fs::symlink_file(&target, dst).map_err(FsError::from)
+        }
+    }
+}
+
+pub(super) fn rename_or_copy(from: &Path, to: &Path) -> Result<(), FsError> {
+    match fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(e) if is_cross_device_error(&e) => copy_then_remove(from, to),
+        Err(e) => Err(FsError::from(e)),
+    }
 }
@@ -0,0 +1,206 @@
+//! 读取游标：服务端保持打开的 reader，按令牌分块顺序读取大文件，避免反复 offset 读取带来的 O(n²) 扫描。
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::detection::{is_binary_content, path_has_binary_extension, path_has_text_extension};
+use super::validation::ensure_inside_workspace_exists;
+use super::FsError;
+
+/// 同时打开的游标数上限
+const MAX_OPEN_CURSORS: usize = 32;
+/// 单次 `read_cursor_chunk` 的行数上限
+const MAX_CHUNK_LINES: usize = 10_000;
+/// 单行保留的字节上限，超出部分跳过（压缩过的单行文件不会整行读进内存）
+const MAX_LINE_BYTES: usize = 16 * 1024;
+/// 游标闲置超过该时长即视为被遗弃（前端未读完也未关闭），打开新游标时回收
+const CURSOR_IDLE_TTL: Duration = Duration::from_secs(10 * 60);
+
+struct OpenCursor {
+    reader: BufReader<fs::File>,
+    last_used: Instant,
+}
+
+/// 令牌到打开中 reader 的映射，作为 Tauri state（`Arc<ReadCursorRegistry>`）管理。
+pub struct ReadCursorRegistry {
+    inner: Mutex<HashMap<String, OpenCursor>>,
+    idle_ttl: Duration,
+}
+
+impl ReadCursorRegistry {
+    pub fn new() -> Self {
+        Self::with_idle_ttl(CURSOR_IDLE_TTL)
+    }
+
+    pub(super) fn with_idle_ttl(idle_ttl: Duration) -> Self {
+        Self {
+            inner: Mutex::new(HashMap::new()),
+            idle_ttl,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, OpenCursor>> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn generate_cursor_token() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    format!("cursor-{}-{}", millis, COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenReadCursorArgs {
+    pub workspace_root: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadCursorChunk {
+    /// 不含行尾换行符
+    pub lines: Vec<String>,
+    /// 已读到文件末尾；此时游标已自动关闭
+    pub eof: bool,
+}
+
+pub fn open_read_cursor_inner(registry: &ReadCursorRegistry, args: &OpenReadCursorArgs) -> Result<String, FsError> {
+    let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
+    if abs.is_dir() {
+        return Err(FsError::IsDirectory);
+    }
+    let is_known_text = path_has_text_extension(&abs);
+    if !is_known_text && path_has_binary_extension(&abs) {
+        return Err(FsError::BinaryFile);
+    }
+    if !is_known_text && is_binary_content(fs::File::open(&abs)?)? {
+        return Err(FsError::BinaryFile);
+    }
+    let reader = BufReader::new(fs::File::open(&abs)?);
+
+    let mut cursors = registry.lock();
+    cursors.retain(|_, c| c.last_used.elapsed() < registry.idle_ttl);
+    if cursors.len() >= MAX_OPEN_CURSORS {
+        return Err(FsError::NotAllowed(format!(
+            "too many open read cursors (limit {})",
+            MAX_OPEN_CURSORS
+        )));
+    }
+    let token = generate_cursor_token();
+    cursors.insert(token.clone(), OpenCursor { reader, last_used: Instant::now() });
+    Ok(token)
+}
+
+/// 读取期间游标从表中取出，文件 I/O 不持有全局锁；同一令牌的并发读取会得到 `NotFound`。
+/// 读到末尾或出错时游标不再放回，即自动关闭。
+pub fn read_cursor_chunk_inner(
+    registry: &ReadCursorRegistry,
+    token: &str,
+    max_lines: usize,
+) -> Result<ReadCursorChunk, FsError> {
+    let mut cursor = registry.lock().remove(token).ok_or(FsError::NotFound)?;
+    let chunk = read_chunk(&mut cursor.reader, max_lines)?;
+    if !chunk.eof {
+        cursor.last_used = Instant::now();
+        registry.lock().insert(token.to_string(), cursor);
+    }
+    Ok(chunk)
+}
+
+fn read_chunk(reader: &mut BufReader<fs::File>, max_lines: usize) -> io::Result<ReadCursorChunk> {
+    let mut lines = Vec::new();
+    let mut buf = Vec::new();
+    let mut eof = false;
+    while lines.len() < max_lines.clamp(1, MAX_CHUNK_LINES) {
+        buf.clear();
+        let Some(dropped) = read_line_capped(reader, &mut buf)? else {
+            eof = true;
+            break;
+        };
+        let mut line = String::from_utf8_lossy(&buf).into_owned();
+        if dropped > 0 {
+            line.push_str(&format!("[... truncated {} bytes]", dropped));
+        }
+        lines.push(line);
+    }
+    // 恰好读满 max_lines 时再探测一次，避免调用方多一次空读
+    if !eof && reader.fill_buf()?.is_empty() {
+        eof = true;
+    }
+    Ok(ReadCursorChunk { lines, eof })
+}
+
+/// 读取一行（去掉行尾 `\n` / `\r\n`），最多保留 `MAX_LINE_BYTES` 字节，其余跳过。
+/// 返回被跳过的字节数；已在文件末尾时返回 `None`。
+fn read_line_capped(reader: &mut impl BufRead, buf: &mut Vec<u8>) -> io::Result<Option<usize>> {
+    let mut dropped = 0;
+    let mut consumed_any = false;
+    let mut last_byte = None;
+    let mut ended_with_newline = false;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            break;
+        }
+        consumed_any = true;
+        let newline = available.iter().position(|&b| b == b'\n');
+        let segment = &available[..newline.unwrap_or(available.len())];
+        let keep = segment.len().min(MAX_LINE_BYTES - buf.len());
+        buf.extend_from_slice(&segment[..keep]);
+        dropped += segment.len() - keep;
+        last_byte = segment.last().copied().or(last_byte);
+        ended_with_newline = newline.is_some();
+        let used = segment.len() + usize::from(newline.is_some());
+        reader.consume(used);
+        if newline.is_some() {
+            break;
+        }
+    }
+    if !consumed_any {
+        return Ok(None);
+    }
+    if ended_with_newline && last_byte == Some(b'\r') {
+        if dropped > 0 {
+            dropped -= 1;
+        } else {
+            buf.pop();
+        }
+    }
+    Ok(Some(dropped))
+}
+
+/// 打开读取游标并返回令牌；路径在打开时校验，之后按令牌读取。
+#[tauri::command]
+pub fn open_read_cursor(
+    args: OpenReadCursorArgs,
+    state: tauri::State<'_, Arc<ReadCursorRegistry>>,
+) -> Result<String, FsError> {
+    open_read_cursor_inner(&state, &args)
+}
+
+/// 从游标处读取最多 `maxLines` 行；读到末尾时返回 `eof: true` 并自动关闭游标。
+#[tauri::command]
+pub fn read_cursor_chunk(
+    token: String,
+    max_lines: usize,
+    state: tauri::State<'_, Arc<ReadCursorRegistry>>,
+) -> Result<ReadCursorChunk, FsError> {
+    read_cursor_chunk_inner(&state, &token, max_lines)
+}
+
+/// 提前关闭游标；令牌不存在（已关闭或已读到末尾）时返回 false。
+#[tauri::command]
+pub fn close_read_cursor(token: String, state: tauri::State<'_, Arc<ReadCursorRegistry>>) -> bool {
+    state.lock().remove(&token).is_some()
+}
@@ -0,0 +1,23 @@
+//! 为可能卡住的文件系统调用（如失联的 NFS/SMB 挂载）加总超时，避免 invoke 永久挂起。
+
+use std::time::Duration;
+
+use super::FsError;
+
+/// read_file / stat_file / list_dir 的默认总超时
+pub(crate) const FS_READ_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 在 blocking 线程池执行 `f`，超过 `deadline` 返回 `FsError::Timeout`。
+///
+/// 超时后阻塞中的系统调用无法取消，线程会在调用返回后自行结束；前端已可恢复。
+pub(crate) async fn run_with_deadline<T, F>(deadline: Duration, f: F) -> Result<T, FsError>
+where
+    F: FnOnce() -> Result<T, FsError> + Send + 'static,
+    T: Send + 'static,
+{
+    let task = tauri::async_runtime::spawn_blocking(f);
+    match tokio::time::timeout(deadline, task).await {
+        Ok(joined) => joined.map_err(|e| FsError::Io(format!("spawn_blocking error: {e}")))?,
+        Err(_) => Err(FsError::Timeout),
+    }
+}
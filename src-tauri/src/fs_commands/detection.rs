@@ -59,6 +59,89 @@ pub(crate) fn is_binary_content(mut reader: impl Read) -> Result<bool, std::io::
     }
 }
 
+// ---------------------------------------------------------------------------
+// BOM 识别：UTF-8 / UTF-16LE / UTF-16BE
+// ---------------------------------------------------------------------------
+
+/// 按 BOM 识别文本编码；无 BOM 返回 None（按 UTF-8 处理）。
+pub(super) fn bom_encoding(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        Some("utf-8")
+    } else if bytes.starts_with(&[0xFF, 0xFE]) {
+        Some("utf-16le")
+    } else if bytes.starts_with(&[0xFE, 0xFF]) {
+        Some("utf-16be")
+    } else {
+        None
+    }
+}
+
+/// 若以 BOM 开头则按对应编码解码并去掉 BOM；无 BOM 返回 None。
+pub(super) fn decode_bom_text(bytes: &[u8]) -> Option<String> {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return Some(String::from_utf8_lossy(rest).into_owned());
+    }
+    let (rest, le) = if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        (rest, true)
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        (rest, false)
+    } else {
+        return None;
+    };
+    // 末尾不成对的字节忽略
+    let units = rest.chunks_exact(2).map(|c| {
+        if le {
+            u16::from_le_bytes([c[0], c[1]])
+        } else {
+            u16::from_be_bytes([c[0], c[1]])
+        }
+    });
+    Some(
+        char::decode_utf16(units)
+            .map(|r| r.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect(),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// ANSI 转义序列
+// ---------------------------------------------------------------------------
+
+/// 去掉 ANSI CSI 序列（颜色、光标移动等）与 OSC 序列（如终端超链接）。
+pub(super) fn strip_ansi_codes(text: &str) -> String {
+    static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| {
+        regex::Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)").unwrap()
+    });
+    re.replace_all(text, "").into_owned()
+}
+
+// ---------------------------------------------------------------------------
+// 密钥脱敏
+// ---------------------------------------------------------------------------
+
+/// 将 `.env` 风格 `KEY=VALUE` 行中敏感键（`*_KEY`、`*_TOKEN`、`*_SECRET`、含 `PASSWORD`）的值替换为 `***`。
+/// 键名不区分大小写，支持 `export` 前缀；其它行原样保留。
+pub(super) fn redact_env_secrets(text: &str) -> String {
+    static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let re = RE.get_or_init(|| {
+        regex::Regex::new(r"(?m)^([ \t]*(?:export[ \t]+)?([A-Za-z_][A-Za-z0-9_.]*)[ \t]*=[ \t]*)([^\r\n]+)").unwrap()
+    });
+    re.replace_all(text, |caps: &regex::Captures| {
+        let key = caps[2].to_ascii_uppercase();
+        let sensitive = key.contains("PASSWORD")
+            || ["KEY", "TOKEN", "SECRET"]
+                .iter()
+                .any(|s| key == *s || key.ends_with(&format!("_{s}")));
+        if sensitive {
+            format!("{}***", &caps[1])
+        } else {
+            caps[0].to_string()
+        }
+    })
+    .into_owned()
+}
+
 // ---------------------------------------------------------------------------
 // MIME 检测：优先 magic bytes，扩展名 fallback
 // ---------------------------------------------------------------------------
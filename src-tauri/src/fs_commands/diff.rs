@@ -0,0 +1,99 @@
+use std::fs;
+
+use serde::Deserialize;
+use similar::TextDiff;
+
+use super::deadline::{run_with_deadline, FS_READ_TIMEOUT};
+use super::read::read_text_content;
+use super::validation::{ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist};
+use super::FsError;
+
+/// 参与 diff 的单个文件大小上限（2MB）
+const DIFF_MAX_BYTES: u64 = 2 * 1024 * 1024;
+/// 默认上下文行数，与 `diff -u` 一致
+const DEFAULT_CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffFilesArgs {
+    pub workspace_root: String,
+    /// 旧文件（相对工作区根），对应 diff 中的 `-` 行
+    pub path_a: String,
+    /// 新文件（相对工作区根），对应 diff 中的 `+` 行
+    pub path_b: String,
+    /// 每个 hunk 前后的上下文行数，默认 3
+    #[serde(default)]
+    pub context_lines: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffAgainstContentArgs {
+    pub workspace_root: String,
+    /// 相对工作区根的文件路径；文件不存在时按空内容比较
+    pub path: String,
+    /// 拟写入的新内容
+    pub new_content: String,
+    #[serde(default)]
+    pub context_lines: Option<usize>,
+}
+
+fn unified_diff(old: &str, new: &str, label_a: &str, label_b: &str, context_lines: Option<usize>) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(context_lines.unwrap_or(DEFAULT_CONTEXT_LINES))
+        .header(label_a, label_b)
+        .to_string()
+}
+
+fn read_diff_side(workspace_root: &str, path: &str) -> Result<String, FsError> {
+    let abs = ensure_inside_workspace_exists(workspace_root, path)?;
+    let meta = fs::metadata(&abs).map_err(FsError::from)?;
+    if meta.is_dir() {
+        return Err(FsError::IsDirectory);
+    }
+    if meta.len() > DIFF_MAX_BYTES {
+        return Err(FsError::TooLarge);
+    }
+    read_text_content(&abs)
+}
+
+/// 两个工作区文件的 unified diff；内容相同时返回空串。
+#[tauri::command]
+pub async fn diff_files(args: DiffFilesArgs) -> Result<String, FsError> {
+    run_with_deadline(FS_READ_TIMEOUT, move || diff_files_inner(&args)).await
+}
+
+pub fn diff_files_inner(args: &DiffFilesArgs) -> Result<String, FsError> {
+    let old = read_diff_side(&args.workspace_root, &args.path_a)?;
+    let new = read_diff_side(&args.workspace_root, &args.path_b)?;
+    Ok(unified_diff(
+        &old,
+        &new,
+        &format!("a/{}", args.path_a),
+        &format!("b/{}", args.path_b),
+        args.context_lines,
+    ))
+}
+
+/// 预览写入：当前文件与 `newContent` 的 unified diff，不修改任何文件。
+#[tauri::command]
+pub async fn diff_against_content(args: DiffAgainstContentArgs) -> Result<String, FsError> {
+    run_with_deadline(FS_READ_TIMEOUT, move || diff_against_content_inner(&args)).await
+}
+
+pub fn diff_against_content_inner(args: &DiffAgainstContentArgs) -> Result<String, FsError> {
+    let abs = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.path)?;
+    let (old, label_a) = if abs.exists() {
+        (read_diff_side(&args.workspace_root, &args.path)?, format!("a/{}", args.path))
+    } else {
+        (String::new(), "/dev/null".to_string())
+    };
+    Ok(unified_diff(
+        &old,
+        &args.new_content,
+        &label_a,
+        &format!("b/{}", args.path),
+        args.context_lines,
+    ))
+}
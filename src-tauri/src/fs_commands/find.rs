@@ -0,0 +1,82 @@
+//! 文件内查找：返回每个正则匹配的位置，供预览/编辑器高亮。
+
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+
+use super::read::{load_read_content, ReadFileArgs};
+use super::FsError;
+
+/// 默认最多返回的匹配数
+const FIND_DEFAULT_MAX_MATCHES: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindInFileArgs {
+    pub workspace_root: String,
+    pub path: String,
+    /// 正则表达式，按行匹配
+    pub pattern: String,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// 匹配数上限，默认 1000
+    #[serde(default)]
+    pub max_matches: Option<usize>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FindMatch {
+    /// 行号，从 1 开始
+    pub line: usize,
+    /// 列号（按字符计），从 1 开始
+    pub column: usize,
+    /// 匹配长度（按字符计）
+    pub length: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindInFileResult {
+    pub matches: Vec<FindMatch>,
+    /// 达到 `maxMatches` 后停止查找
+    pub truncated: bool,
+}
+
+/// 与 `read_file` 相同的工作区校验、大小上限与二进制检测；空匹配不计入结果。
+#[tauri::command]
+pub fn find_in_file(args: FindInFileArgs) -> Result<FindInFileResult, FsError> {
+    if args.pattern.is_empty() {
+        return Err(FsError::NotAllowed("pattern must not be empty".into()));
+    }
+    let re = RegexBuilder::new(&args.pattern)
+        .case_insensitive(args.case_insensitive)
+        .build()
+        .map_err(|e| FsError::NotAllowed(format!("invalid regex: {}", e)))?;
+    let content = load_read_content(&ReadFileArgs {
+        workspace_root: args.workspace_root,
+        path: args.path,
+        offset: None,
+        limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
+    })?;
+    let max = args.max_matches.unwrap_or(FIND_DEFAULT_MAX_MATCHES);
+
+    let mut matches = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        for m in re.find_iter(line).filter(|m| !m.is_empty()) {
+            if matches.len() >= max {
+                return Ok(FindInFileResult { matches, truncated: true });
+            }
+            matches.push(FindMatch {
+                line: i + 1,
+                column: line[..m.start()].chars().count() + 1,
+                length: m.as_str().chars().count(),
+            });
+        }
+    }
+    Ok(FindInFileResult { matches, truncated: false })
+}
@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::validation::canonical_workspace_root;
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// workspace_root_info：读取 .git/HEAD 与 .git/config，不依赖 git 可执行文件
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRootInfoArgs {
+    pub workspace_root: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRootInfo {
+    pub is_git_repo: bool,
+    /// 仓库工作树根目录（可能是工作区的上级目录）
+    pub git_root: Option<String>,
+    /// 当前分支；detached HEAD 时为 None
+    pub branch: Option<String>,
+    /// `origin` 的 url，没有 origin 时取第一个 remote
+    pub remote_url: Option<String>,
+}
+
+/// 从 `start` 向上查找 `.git`，返回 (工作树根, git 目录)。
+/// 支持 worktree / submodule 的 `.git` 文件（`gitdir: <path>`）。
+pub(super) fn find_git_dir(start: &Path) -> Option<(PathBuf, PathBuf)> {
+    for dir in start.ancestors() {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Some((dir.to_path_buf(), dot_git));
+        }
+        if dot_git.is_file() {
+            let content = fs::read_to_string(&dot_git).ok()?;
+            let target = content.trim().strip_prefix("gitdir:")?.trim();
+            return Some((dir.to_path_buf(), dir.join(target)));
+        }
+    }
+    None
+}
+
+/// 解析 HEAD：`ref: refs/heads/<branch>` → Some(branch)，detached → None。
+pub(super) fn parse_head(head: &str) -> Option<String> {
+    let r = head.trim().strip_prefix("ref:")?.trim();
+    Some(r.strip_prefix("refs/heads/").unwrap_or(r).to_string())
+}
+
+/// 从 git config 中取 remote url，优先 `origin`。
+pub(super) fn parse_remote_url(config: &str) -> Option<String> {
+    let mut section: Option<String> = None;
+    let mut first: Option<String> = None;
+    for line in config.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            section = line
+                .trim_matches(|c| c == '[' || c == ']')
+                .strip_prefix("remote ")
+                .map(|n| n.trim().trim_matches('"').to_string());
+            continue;
+        }
+        let Some(name) = &section else { continue };
+        let Some((key, value)) = line.split_once('=') else { continue };
+        if key.trim() != "url" {
+            continue;
+        }
+        let url = value.trim().to_string();
+        if name == "origin" {
+            return Some(url);
+        }
+        first.get_or_insert(url);
+    }
+    first
+}
+
+/// worktree 的 git 目录通过 `commondir` 指向共享的 config。
+fn common_dir(git_dir: &Path) -> PathBuf {
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(c) => git_dir.join(c.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+#[tauri::command]
+pub fn workspace_root_info(args: WorkspaceRootInfoArgs) -> Result<WorkspaceRootInfo, FsError> {
+    let root = canonical_workspace_root(&args.workspace_root)?;
+    let Some((git_root, git_dir)) = find_git_dir(&root) else {
+        return Ok(WorkspaceRootInfo::default());
+    };
+    let branch = fs::read_to_string(git_dir.join("HEAD"))
+        .ok()
+        .and_then(|h| parse_head(&h));
+    let remote_url = fs::read_to_string(common_dir(&git_dir).join("config"))
+        .ok()
+        .and_then(|c| parse_remote_url(&c));
+    Ok(WorkspaceRootInfo {
+        is_git_repo: true,
+        git_root: Some(git_root.to_string_lossy().into_owned()),
+        branch,
+        remote_url,
+    })
+}
@@ -0,0 +1,127 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::validation::ensure_inside_workspace_exists;
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// detect_language：扩展名 / 文件名 / shebang → 语法高亮语言标识
+// ---------------------------------------------------------------------------
+
+const PLAINTEXT: &str = "plaintext";
+/// shebang 嗅探只读取首行所需的少量字节
+const SHEBANG_SNIFF_BYTES: usize = 256;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectLanguageArgs {
+    pub workspace_root: String,
+    pub path: String,
+}
+
+pub(super) fn language_from_extension(p: &Path) -> Option<&'static str> {
+    let ext = p.extension()?.to_str()?.to_ascii_lowercase();
+    let lang = match ext.as_str() {
+        "rs" => "rust",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "jsx",
+        "md" | "markdown" => "markdown",
+        "qmd" => "quarto",
+        "py" | "pyw" => "python",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "lua" => "lua",
+        "sh" | "bash" | "zsh" => "shell",
+        "fish" => "fish",
+        "ps1" => "powershell",
+        "json" => "json",
+        "yaml" | "yml" => "yaml",
+        "toml" => "toml",
+        "ini" | "cfg" | "conf" => "ini",
+        "xml" => "xml",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "scss" => "scss",
+        "less" => "less",
+        "sql" => "sql",
+        "graphql" | "gql" => "graphql",
+        "vue" => "vue",
+        "svelte" => "svelte",
+        "csv" => "csv",
+        "tex" => "latex",
+        "r" => "r",
+        _ => return None,
+    };
+    Some(lang)
+}
+
+fn language_from_file_name(p: &Path) -> Option<&'static str> {
+    let name = p.file_name()?.to_str()?;
+    match name {
+        "Dockerfile" => Some("dockerfile"),
+        "Makefile" | "makefile" | "GNUmakefile" => Some("makefile"),
+        "CMakeLists.txt" => Some("cmake"),
+        ".bashrc" | ".zshrc" | ".profile" | ".bash_profile" => Some("shell"),
+        _ => None,
+    }
+}
+
+/// 解析 `#!` 首行，支持 `#!/usr/bin/env python3` 与 `#!/bin/bash` 两种写法。
+pub(super) fn language_from_shebang(head: &str) -> Option<&'static str> {
+    let first = head.lines().next()?.strip_prefix("#!")?;
+    let mut parts = first.split_whitespace();
+    let mut interp = parts.next()?.rsplit('/').next()?;
+    if interp == "env" {
+        // 跳过 env 的选项（如 `env -S`）
+        interp = parts.find(|a| !a.starts_with('-'))?;
+    }
+    let interp = interp.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let lang = match interp {
+        "python" => "python",
+        "node" | "deno" | "bun" => "javascript",
+        "ts-node" => "typescript",
+        "sh" | "bash" | "zsh" | "dash" | "ksh" => "shell",
+        "fish" => "fish",
+        "ruby" => "ruby",
+        "perl" => "perl",
+        "php" => "php",
+        "lua" => "lua",
+        "pwsh" => "powershell",
+        _ => return None,
+    };
+    Some(lang)
+}
+
+/// 识别文件的语言：扩展名 → 特殊文件名 → shebang，未知时返回 `plaintext`。
+pub(super) fn detect_language_for_path(abs: &Path) -> &'static str {
+    if let Some(lang) = language_from_extension(abs).or_else(|| language_from_file_name(abs)) {
+        return lang;
+    }
+    let mut buf = [0u8; SHEBANG_SNIFF_BYTES];
+    let n = fs::File::open(abs)
+        .and_then(|mut f| f.read(&mut buf))
+        .unwrap_or(0);
+    let head = String::from_utf8_lossy(&buf[..n]);
+    language_from_shebang(&head).unwrap_or(PLAINTEXT)
+}
+
+#[tauri::command]
+pub fn detect_language(args: DetectLanguageArgs) -> Result<String, FsError> {
+    let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
+    if abs.is_dir() {
+        return Err(FsError::IsDirectory);
+    }
+    Ok(detect_language_for_path(&abs).to_string())
+}
@@ -1,10 +1,11 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use super::deadline::{run_with_deadline, FS_READ_TIMEOUT};
 use super::detection::{is_binary_content, path_has_binary_extension};
-use super::validation::ensure_inside_workspace_exists;
+use super::validation::{canonical_workspace_root, ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist, normalize_path_components};
 use super::FsError;
 
 // ---------------------------------------------------------------------------
@@ -19,23 +20,57 @@ pub struct ListDirArgs {
     pub path: String,
     /// 是否包含以 . 开头的隐藏文件，默认 true
     pub include_hidden: Option<bool>,
+    /// 条目类型过滤：`"all"`（默认）、`"files"` 或 `"dirs"`
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// 为目录条目填充 `hasChildren`，供懒加载树决定是否显示展开箭头
+    #[serde(default)]
+    pub with_children_flag: bool,
+    /// 返回规范化的绝对路径而非相对工作区根的路径
+    #[serde(default)]
+    pub absolute: bool,
+}
+
+/// 解析 `filter`，返回 (保留文件, 保留目录)
+fn parse_entry_filter(filter: Option<&str>) -> Result<(bool, bool), FsError> {
+    match filter.map(str::trim).unwrap_or("all") {
+        "" | "all" => Ok((true, true)),
+        "files" => Ok((true, false)),
+        "dirs" => Ok((false, true)),
+        other => Err(FsError::NotAllowed(format!("invalid filter: {}", other))),
+    }
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListDirEntry {
     pub name: String,
-    /// 相对工作区根的路径
+    /// 相对工作区根的路径（`absolute` 时为规范化绝对路径）
     pub path: String,
     pub is_dir: bool,
     pub mtime_secs: i64,
+    /// 仅 `withChildrenFlag` 时对目录填充：是否至少有一个未被隐藏规则排除的子项
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_children: Option<bool>,
+}
+
+/// 读到第一个可见子项即返回；目录不可读时视为无子项
+fn dir_has_children(dir: &Path, include_hidden: bool) -> bool {
+    let Ok(read) = fs::read_dir(dir) else {
+        return false;
+    };
+    read.filter_map(Result::ok)
+        .any(|e| include_hidden || !e.file_name().to_string_lossy().starts_with('.'))
 }
 
 #[tauri::command]
-pub fn list_dir(args: ListDirArgs) -> Result<Vec<ListDirEntry>, FsError> {
-    let root = Path::new(&args.workspace_root)
-        .canonicalize()
-        .map_err(|_| FsError::NotFound)?
+pub async fn list_dir(args: ListDirArgs) -> Result<Vec<ListDirEntry>, FsError> {
+    run_with_deadline(FS_READ_TIMEOUT, move || list_dir_inner(args)).await
+}
+
+pub fn list_dir_inner(args: ListDirArgs) -> Result<Vec<ListDirEntry>, FsError> {
+    let (keep_files, keep_dirs) = parse_entry_filter(args.filter.as_deref())?;
+    let root = canonical_workspace_root(&args.workspace_root)?
         .into_os_string()
         .into_string()
         .map_err(|_| FsError::Io("workspace path invalid utf-8".into()))?;
@@ -91,18 +126,28 @@ pub fn list_dir(args: ListDirArgs) -> Result<Vec<ListDirEntry>, FsError> {
         let rel = Path::new(&canonical_str)
             .strip_prefix(root_path)
             .map_err(|_| FsError::Io("strip prefix".into()))?;
-        let path = rel.to_string_lossy().replace('\\', "/");
+        let path = if args.absolute {
+            canonical_str.clone()
+        } else {
+            rel.to_string_lossy().replace('\\', "/")
+        };
         let meta = fs::metadata(&canonical_str).map_err(FsError::from)?;
         let is_dir = meta.is_dir();
+        if (is_dir && !keep_dirs) || (!is_dir && !keep_files) {
+            continue;
+        }
         let mtime_secs = meta
             .modified()
             .map(|t| t.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs() as i64)
             .unwrap_or(0);
+        let has_children = (args.with_children_flag && is_dir)
+            .then(|| dir_has_children(Path::new(&canonical_str), args.include_hidden != Some(false)));
         entries.push(ListDirEntry {
             name,
             path,
             is_dir,
             mtime_secs,
+            has_children,
         });
     }
     entries.sort_by(|a, b| {
@@ -133,12 +178,64 @@ pub struct StatFileResult {
     pub mtime_secs: i64,
     pub is_dir: bool,
     pub is_binary: bool,
+    /// 路径本身是否为 symlink（不跟随）
+    pub is_symlink: bool,
+    /// symlink 指向的路径（相对工作区根）；目标在工作区外时为 None
+    pub symlink_target: Option<String>,
+}
+
+/// 解析 symlink 自身的绝对路径（不跟随最后一段）：父目录规范化后仍需在工作区内。
+fn resolve_link_path(workspace_root: &str, path: &str) -> Result<(PathBuf, PathBuf), FsError> {
+    let root = canonical_workspace_root(workspace_root)?;
+    let lexical = ensure_inside_workspace_may_not_exist(workspace_root, path)?;
+    if lexical == root {
+        return Ok((lexical, root));
+    }
+    let (Some(parent), Some(name)) = (lexical.parent(), lexical.file_name()) else {
+        return Ok((root.clone(), root));
+    };
+    let parent = dunce::canonicalize(parent).map_err(|_| FsError::NotFound)?;
+    if !parent.starts_with(&root) {
+        return Err(FsError::OutsideWorkspace);
+    }
+    Ok((parent.join(name), root))
+}
+
+/// symlink 目标（相对工作区根）；目标逃出工作区时返回 None，不暴露外部路径。
+fn symlink_target_in_workspace(link: &Path, root: &Path) -> Option<String> {
+    let raw = fs::read_link(link).ok()?;
+    let joined = link.parent().unwrap_or(root).join(raw);
+    let target = dunce::canonicalize(&joined).unwrap_or_else(|_| normalize_path_components(&joined));
+    target
+        .strip_prefix(root)
+        .ok()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
 }
 
 #[tauri::command]
-pub fn stat_file(args: StatFileArgs) -> Result<StatFileResult, FsError> {
-    let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
-    let meta = fs::metadata(&abs).map_err(FsError::from)?;
+pub async fn stat_file(args: StatFileArgs) -> Result<StatFileResult, FsError> {
+    run_with_deadline(FS_READ_TIMEOUT, move || stat_file_inner(args)).await
+}
+
+pub fn stat_file_inner(args: StatFileArgs) -> Result<StatFileResult, FsError> {
+    let (link, root) = resolve_link_path(&args.workspace_root, &args.path)?;
+    let link_meta = fs::symlink_metadata(&link).map_err(FsError::from)?;
+    let is_symlink = link_meta.file_type().is_symlink();
+    let symlink_target = if is_symlink {
+        symlink_target_in_workspace(&link, &root)
+    } else {
+        None
+    };
+
+    // 断开的 symlink：按 symlink 自身报告，而非 NotFound
+    let (abs, meta) = match ensure_inside_workspace_exists(&args.workspace_root, &args.path) {
+        Ok(abs) => {
+            let meta = fs::metadata(&abs).map_err(FsError::from)?;
+            (abs, meta)
+        }
+        Err(FsError::NotFound) if is_symlink => (link, link_meta),
+        Err(e) => return Err(e),
+    };
     let is_dir = meta.is_dir();
     let size = meta.len();
     let mtime_secs = meta
@@ -161,5 +258,7 @@ pub fn stat_file(args: StatFileArgs) -> Result<StatFileResult, FsError> {
         mtime_secs,
         is_dir,
         is_binary,
+        is_symlink,
+        symlink_target,
     })
 }
@@ -0,0 +1,83 @@
+use std::fs;
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+
+use super::detection::{is_binary_content, path_has_binary_extension, path_has_text_extension};
+use super::validation::ensure_inside_workspace_exists;
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// file_metrics：流式统计行数/字符数，不向前端传输内容
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMetricsArgs {
+    pub workspace_root: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMetrics {
+    pub bytes: u64,
+    pub is_binary: bool,
+    /// 行数（与 read_file 的分行一致）；二进制文件为 None
+    pub lines: Option<u64>,
+    /// UTF-8 字符数；二进制文件为 None
+    pub chars: Option<u64>,
+}
+
+/// 逐块统计 (行数, 字符数)：按 UTF-8 非续字节计字符，末行无换行也计一行。
+pub(super) fn count_lines_chars(mut reader: impl Read) -> std::io::Result<(u64, u64)> {
+    let mut buf = [0u8; 64 * 1024];
+    let (mut newlines, mut chars) = (0u64, 0u64);
+    let mut last = None;
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        for &b in &buf[..n] {
+            if b == b'\n' {
+                newlines += 1;
+            }
+            if b & 0xC0 != 0x80 {
+                chars += 1;
+            }
+        }
+        last = Some(buf[n - 1]);
+    }
+    let lines = match last {
+        Some(b'\n') | None => newlines,
+        Some(_) => newlines + 1,
+    };
+    Ok((lines, chars))
+}
+
+#[tauri::command]
+pub fn file_metrics(args: FileMetricsArgs) -> Result<FileMetrics, FsError> {
+    let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
+    let meta = fs::metadata(&abs)?;
+    if meta.is_dir() {
+        return Err(FsError::IsDirectory);
+    }
+    let is_binary = !path_has_text_extension(&abs)
+        && (path_has_binary_extension(&abs) || is_binary_content(fs::File::open(&abs)?)?);
+    if is_binary {
+        return Ok(FileMetrics {
+            bytes: meta.len(),
+            is_binary,
+            lines: None,
+            chars: None,
+        });
+    }
+    let (lines, chars) = count_lines_chars(fs::File::open(&abs)?)?;
+    Ok(FileMetrics {
+        bytes: meta.len(),
+        is_binary,
+        lines: Some(lines),
+        chars: Some(chars),
+    })
+}
@@ -0,0 +1,121 @@
+use std::fs;
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+
+use super::detection::{mime_from_extension, mime_from_magic};
+use super::validation::ensure_inside_workspace_exists;
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// detect_mime：只读取文件头，magic bytes 优先、扩展名 fallback
+// ---------------------------------------------------------------------------
+
+/// magic 检测所需的文件头长度
+const MIME_SNIFF_BYTES: usize = 64;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectMimeArgs {
+    pub workspace_root: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MimeSource {
+    Magic,
+    Extension,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectMimeResult {
+    pub mime: String,
+    pub source: MimeSource,
+}
+
+#[tauri::command]
+pub fn detect_mime(args: DetectMimeArgs) -> Result<DetectMimeResult, FsError> {
+    let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
+    if abs.is_dir() {
+        return Err(FsError::IsDirectory);
+    }
+    let mut head = Vec::with_capacity(MIME_SNIFF_BYTES);
+    fs::File::open(&abs)?
+        .take(MIME_SNIFF_BYTES as u64)
+        .read_to_end(&mut head)?;
+
+    let by_ext = mime_from_extension(&abs);
+    let (mime, source) = match mime_from_magic(&head) {
+        // docx/xlsx/pptx 的 magic 都是 zip，用扩展名细化
+        Some("application/zip") if by_ext.starts_with("application/vnd.openxmlformats") => {
+            (by_ext, MimeSource::Extension)
+        }
+        Some(m) => (m, MimeSource::Magic),
+        None => (by_ext, MimeSource::Extension),
+    };
+    Ok(DetectMimeResult {
+        mime: mime.to_string(),
+        source,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// inspect_file_head：以十六进制返回文件头，便于排查"被判定为二进制"的原因
+// ---------------------------------------------------------------------------
+
+/// 文件头读取上限
+const INSPECT_HEAD_MAX_BYTES: usize = 256;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectFileHeadArgs {
+    pub workspace_root: String,
+    pub path: String,
+    /// 读取字节数，默认且最大为 256
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectFileHeadResult {
+    /// 小写十六进制，字节间以空格分隔，如 `89 50 4e 47`
+    pub hex: String,
+    /// 按 magic bytes 识别的 MIME，无法识别时为 None
+    pub mime: Option<String>,
+    /// 实际读取的字节数
+    pub bytes_read: usize,
+    /// 文件总大小
+    pub size: u64,
+}
+
+#[tauri::command]
+pub fn inspect_file_head(args: InspectFileHeadArgs) -> Result<InspectFileHeadResult, FsError> {
+    let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
+    let meta = fs::metadata(&abs)?;
+    if meta.is_dir() {
+        return Err(FsError::IsDirectory);
+    }
+    let limit = args
+        .max_bytes
+        .unwrap_or(INSPECT_HEAD_MAX_BYTES)
+        .min(INSPECT_HEAD_MAX_BYTES);
+    let mut head = Vec::with_capacity(limit);
+    fs::File::open(&abs)?
+        .take(limit as u64)
+        .read_to_end(&mut head)?;
+
+    let hex = head
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(InspectFileHeadResult {
+        hex,
+        mime: mime_from_magic(&head).map(str::to_string),
+        bytes_read: head.len(),
+        size: meta.len(),
+    })
+}
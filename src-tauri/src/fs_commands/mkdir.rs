@@ -0,0 +1,68 @@
+use std::fs;
+
+use serde::Deserialize;
+
+use super::validation::{canonical_workspace_root, ensure_inside_workspace_may_not_exist};
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// create_dir_all
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDirAllArgs {
+    pub workspace_root: String,
+    /// 相对工作区根的目录路径，可含多级（如 `a/b/c`）
+    pub path: String,
+}
+
+/// 逐级创建目录；返回最深一级新建目录的相对路径，目录已存在时返回 None。
+pub fn create_dir_all_inner(args: &CreateDirAllArgs) -> Result<Option<String>, FsError> {
+    if args.path.trim().is_empty() {
+        return Err(FsError::InvalidName);
+    }
+    let root = canonical_workspace_root(&args.workspace_root)?;
+    let target = ensure_inside_workspace_may_not_exist(&args.workspace_root, args.path.trim())?;
+    if target.is_dir() {
+        return Ok(None);
+    }
+    if target.exists() {
+        return Err(FsError::AlreadyExists);
+    }
+
+    // 最近的已存在祖先规范化后仍须在工作区内（防止经由 symlink 目录逃逸）
+    let existing = target
+        .ancestors()
+        .find(|p| p.exists())
+        .ok_or(FsError::NotFound)?;
+    let existing = dunce::canonicalize(existing).map_err(FsError::from)?;
+    if !existing.starts_with(&root) {
+        return Err(FsError::OutsideWorkspace);
+    }
+    if !existing.is_dir() {
+        return Err(FsError::NotAllowed("parent is not a directory".into()));
+    }
+
+    fs::create_dir_all(&target).map_err(FsError::from)?;
+    let rel = target
+        .strip_prefix(&root)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .map_err(|_| FsError::Io("strip prefix".into()))?;
+    Ok(Some(rel))
+}
+
+#[tauri::command]
+pub fn create_dir_all(app: tauri::AppHandle, args: CreateDirAllArgs) -> Result<(), FsError> {
+    if let Some(rel) = create_dir_all_inner(&args)? {
+        use tauri::Emitter;
+        let _ = app.emit(
+            crate::workspace_watcher::EVENT_WORKSPACE_FILE_CHANGED,
+            crate::workspace_watcher::WorkspaceFileChangedPayload {
+                path: rel,
+                kind: crate::workspace_watcher::FileChangeKind::Create,
+            },
+        );
+    }
+    Ok(())
+}
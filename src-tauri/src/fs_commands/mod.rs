@@ -1,13 +1,31 @@
 //! 文件系统 Tauri 命令：限定在工作区内，供前端 read/write/edit 工具调用。
 
+mod archive;
+mod content_hash;
 mod copy;
+mod cursor;
+mod deadline;
 mod detection;
+mod diff;
+mod find;
+mod git_info;
+mod language;
 mod list;
+mod metrics;
+mod mime;
+mod mkdir;
 mod office;
 mod office_read;
 mod office_write;
+mod preflight;
 mod read;
 mod read_absolute;
+mod read_cache;
+mod read_smart;
+mod replace;
+mod resolve;
+mod touch;
+mod untar;
 mod validation;
 mod walk;
 mod write;
@@ -17,33 +35,101 @@ mod tests;
 #[cfg(test)]
 mod tests_walk;
 #[cfg(test)]
+mod tests_write;
+#[cfg(test)]
+mod tests_archive;
+#[cfg(test)]
+mod tests_content_hash;
+#[cfg(test)]
 mod tests_copy;
 #[cfg(test)]
+mod tests_cursor;
+#[cfg(test)]
+mod tests_deadline;
+#[cfg(test)]
 mod tests_copy_external;
 #[cfg(test)]
 mod tests_detection;
 #[cfg(test)]
+mod tests_diff;
+#[cfg(test)]
+mod tests_find;
+#[cfg(test)]
+mod tests_git_info;
+#[cfg(test)]
+mod tests_language;
+#[cfg(test)]
 mod tests_list;
 #[cfg(test)]
+mod tests_list_options;
+#[cfg(test)]
+mod tests_metrics;
+#[cfg(test)]
+mod tests_mime;
+#[cfg(test)]
+mod tests_mkdir;
+#[cfg(test)]
+mod tests_office;
+#[cfg(test)]
+mod tests_preflight;
+#[cfg(test)]
 mod tests_read;
 #[cfg(test)]
 mod tests_read_absolute;
 #[cfg(test)]
+mod tests_read_ansi;
+#[cfg(test)]
+mod tests_read_cache;
+#[cfg(test)]
+mod tests_read_encoding;
+#[cfg(test)]
+mod tests_read_redact;
+#[cfg(test)]
+mod tests_read_smart;
+#[cfg(test)]
+mod tests_read_structured;
+#[cfg(test)]
+mod tests_replace;
+#[cfg(test)]
+mod tests_resolve;
+#[cfg(test)]
+mod tests_touch;
+#[cfg(test)]
+mod tests_untar;
+#[cfg(test)]
 mod tests_validation;
 
+pub use archive::*;
+pub use content_hash::*;
 pub use copy::*;
+pub use cursor::*;
+pub use diff::*;
+pub use find::*;
+pub use git_info::*;
+pub use language::*;
 pub use list::*;
+pub use metrics::*;
+pub use mime::*;
+pub use mkdir::*;
 pub use office::*;
 pub use office_read::*;
 pub use office_write::*;
+pub use preflight::*;
 pub use read::*;
 pub use read_absolute::*;
+pub use read_cache::*;
+pub use read_smart::*;
+pub use replace::*;
+pub use resolve::*;
+pub use touch::*;
+pub use untar::*;
 pub use walk::*;
 pub use write::*;
 
 pub(crate) use detection::{is_binary_content, path_has_binary_extension};
 pub(crate) use validation::ensure_inside_workspace_exists;
 pub(crate) use validation::ensure_inside_workspace_may_not_exist;
+pub(crate) use walk::{walk_workspace, WalkOptions};
 
 use serde::{Deserialize, Serialize};
 
@@ -58,14 +144,24 @@ pub enum FsError {
     OutsideWorkspace,
     /// 文件或目录不存在
     NotFound,
-    /// 权限不足或类型不符（如期望文件却是目录）
+    /// 权限不足或其它不允许的操作
     NotAllowed(String),
+    /// 期望文件却是目录
+    IsDirectory,
+    /// 目标已存在
+    AlreadyExists,
+    /// 文件/文件夹名称无效（为空或含路径分隔符）
+    InvalidName,
     /// 被判定为二进制文件，拒绝读取
     BinaryFile,
     /// 文件超过 250KB
     TooLarge,
     /// 其它 I/O 错误
     Io(String),
+    /// 操作超时（如网络挂载无响应）
+    Timeout,
+    /// 文件内容已不是预期版本（`write_file_if_unchanged` 哈希不一致）
+    Conflict,
 }
 
 impl From<std::io::Error> for FsError {
@@ -74,6 +170,7 @@ impl From<std::io::Error> for FsError {
         match e.kind() {
             ErrorKind::NotFound => FsError::NotFound,
             ErrorKind::PermissionDenied => FsError::NotAllowed(e.to_string()),
+            ErrorKind::AlreadyExists => FsError::AlreadyExists,
             _ => FsError::Io(e.to_string()),
         }
     }
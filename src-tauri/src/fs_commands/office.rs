@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::sync::Mutex;
 
 use serde::Serialize;
 
@@ -17,8 +18,39 @@ pub struct OfficeAppInfo {
     pub path: String,
 }
 
+/// 探测结果缓存：安装/卸载办公软件很少发生，由 `refresh_office_apps` 显式刷新
+static OFFICE_APPS: Mutex<Option<Vec<OfficeAppInfo>>> = Mutex::new(None);
+
+/// 返回缓存的探测结果，首次调用时探测（UI 构建右键菜单时会频繁调用）
 #[tauri::command]
 pub fn detect_office_apps() -> Vec<OfficeAppInfo> {
+    cached_or_probe(&OFFICE_APPS, probe_office_apps)
+}
+
+/// 重新探测并更新缓存
+#[tauri::command]
+pub fn refresh_office_apps() -> Vec<OfficeAppInfo> {
+    refresh_cache(&OFFICE_APPS, probe_office_apps)
+}
+
+pub(super) fn cached_or_probe(
+    cache: &Mutex<Option<Vec<OfficeAppInfo>>>,
+    probe: impl FnOnce() -> Vec<OfficeAppInfo>,
+) -> Vec<OfficeAppInfo> {
+    let mut guard = cache.lock().unwrap_or_else(|e| e.into_inner());
+    guard.get_or_insert_with(probe).clone()
+}
+
+pub(super) fn refresh_cache(
+    cache: &Mutex<Option<Vec<OfficeAppInfo>>>,
+    probe: impl FnOnce() -> Vec<OfficeAppInfo>,
+) -> Vec<OfficeAppInfo> {
+    let apps = probe();
+    *cache.lock().unwrap_or_else(|e| e.into_inner()) = Some(apps.clone());
+    apps
+}
+
+fn probe_office_apps() -> Vec<OfficeAppInfo> {
     let mut apps = Vec::new();
 
     #[cfg(target_os = "macos")]
@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::validation::canonical_workspace_root;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateWorkspaceArgs {
+    pub workspace_root: String,
+    /// 允许把文件系统根或用户 home 本身作为工作区
+    #[serde(default)]
+    pub allow_dangerous_root: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateWorkspaceResult {
+    pub valid: bool,
+    pub is_dir: bool,
+    pub writable: bool,
+    /// 文件系统根（`/`、`C:\`）或用户 home 目录本身
+    pub is_system_root: bool,
+    /// 规范化后的路径；路径不存在时为 None
+    pub canonical_path: Option<String>,
+    /// 无效时的原因
+    pub reason: Option<String>,
+}
+
+impl ValidateWorkspaceResult {
+    fn invalid(reason: &str) -> Self {
+        Self {
+            valid: false,
+            is_dir: false,
+            writable: false,
+            is_system_root: false,
+            canonical_path: None,
+            reason: Some(reason.to_string()),
+        }
+    }
+}
+
+/// 文件系统根或用户 home 本身：在这些目录上开放读写风险过大
+fn is_dangerous_root(p: &Path) -> bool {
+    if p.parent().is_none() {
+        return true;
+    }
+    dirs::home_dir()
+        .and_then(|h| dunce::canonicalize(h).ok())
+        .is_some_and(|h| h == p)
+}
+
+/// 创建并删除一个探测文件来判断目录是否可写（权限位不能反映 ACL、只读挂载等情况）
+fn probe_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".cove-write-probe-{}", std::process::id()));
+    match fs::File::options().write(true).create_new(true).open(&probe) {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+pub fn validate_workspace_inner(args: &ValidateWorkspaceArgs) -> ValidateWorkspaceResult {
+    if args.workspace_root.trim().is_empty() {
+        return ValidateWorkspaceResult::invalid("workspace path is empty");
+    }
+    let Ok(root) = canonical_workspace_root(&args.workspace_root) else {
+        return ValidateWorkspaceResult::invalid("workspace path does not exist");
+    };
+    let mut result = ValidateWorkspaceResult {
+        valid: false,
+        is_dir: root.is_dir(),
+        writable: false,
+        is_system_root: is_dangerous_root(&root),
+        canonical_path: Some(root.to_string_lossy().into_owned()),
+        reason: None,
+    };
+    if !result.is_dir {
+        result.reason = Some("workspace path is not a directory".into());
+        return result;
+    }
+    // 危险根目录不做写入探测，避免在 `/` 或 home 下留下文件
+    if result.is_system_root && !args.allow_dangerous_root {
+        result.reason = Some("workspace is a filesystem root or the home directory".into());
+        return result;
+    }
+    result.writable = probe_writable(&root);
+    if !result.writable {
+        result.reason = Some("workspace directory is not writable".into());
+        return result;
+    }
+    result.valid = true;
+    result
+}
+
+/// 选择工作区前的预检：存在、是目录、可写，且不是系统根目录或 home 本身。
+#[tauri::command]
+pub fn validate_workspace(args: ValidateWorkspaceArgs) -> ValidateWorkspaceResult {
+    validate_workspace_inner(&args)
+}
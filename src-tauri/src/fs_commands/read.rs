@@ -1,11 +1,16 @@
 use std::fs;
+use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
 use super::detection::{
-    is_binary_content, mime_from_extension, mime_from_magic, path_has_binary_extension,
+    decode_bom_text, is_binary_content, redact_env_secrets, strip_ansi_codes, mime_from_extension, mime_from_magic, path_has_binary_extension,
     path_has_text_extension, LINE_MAX_CHARS, READ_DATA_URL_MAX_BYTES, READ_MAX_BYTES,
 };
+use super::deadline::{run_with_deadline, FS_READ_TIMEOUT};
+use super::content_hash::file_content_hash;
+use super::read_cache::read_text_maybe_cached;
+use super::read_smart::PreviewSource;
 use super::validation::ensure_inside_workspace_exists;
 use super::FsError;
 
@@ -13,6 +18,23 @@ use super::FsError;
 // read_file
 // ---------------------------------------------------------------------------
 
+/// 读取文本内容：带 BOM 的文件按 UTF-8/UTF-16 解码（UTF-16 的 NUL 字节不会被误判为二进制），
+/// 否则做二进制检测后按 lossy UTF-8 读取。
+pub(super) fn read_text_content(abs: &Path) -> Result<String, FsError> {
+    let is_known_text = path_has_text_extension(abs);
+    if !is_known_text && path_has_binary_extension(abs) {
+        return Err(FsError::BinaryFile);
+    }
+    let bytes = fs::read(abs).map_err(FsError::from)?;
+    if let Some(text) = decode_bom_text(&bytes) {
+        return Ok(text);
+    }
+    if !is_known_text && is_binary_content(&bytes[..]).map_err(FsError::from)? {
+        return Err(FsError::BinaryFile);
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReadFileArgs {
@@ -22,57 +44,143 @@ pub struct ReadFileArgs {
     pub offset: Option<u64>,
     #[serde(default)]
     pub limit: Option<u64>,
+    /// 去掉 ANSI 颜色等转义序列（查看终端日志时使用）
+    #[serde(default)]
+    pub strip_ansi: bool,
+    /// 将 `.env` 风格 `KEY=VALUE` 中敏感键的值替换为 `***`，避免密钥进入模型上下文
+    #[serde(default)]
+    pub redact_secrets: bool,
+    /// 返回 `{lineNumber, text}[]` 而非带行号前缀的单个字符串，便于按行精确编辑
+    #[serde(default)]
+    pub structured: bool,
+    /// 返回 `{content, hash}`，`hash` 供 `write_file_if_unchanged` 使用
+    #[serde(default)]
+    pub include_hash: bool,
+    /// 走小文件读取缓存（agent 反复读取同一配置文件时开启）
+    #[serde(default)]
+    pub use_cache: bool,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadFileLine {
+    /// 从 1 开始
+    pub line_number: usize,
+    /// 超长行同样按 `LINE_MAX_CHARS` 截断
+    pub text: String,
+}
+
+/// 默认为带行号的字符串；`structured` 时为行数组；`includeHash` 时为 `{content, hash}`
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum ReadFileOutput {
+    Text(String),
+    Lines(Vec<ReadFileLine>),
+    Hashed { content: Box<ReadFileOutput>, hash: String },
+}
+
+/// 先于读取内容计算哈希：两者之间文件若被修改，后续写入只会误报冲突而不会覆盖未读到的修改
+fn hash_before_read(workspace_root: &str, path: &str, include: bool) -> Result<Option<String>, FsError> {
+    if !include {
+        return Ok(None);
+    }
+    file_content_hash(&ensure_inside_workspace_exists(workspace_root, path)?).map(Some)
 }
 
+fn attach_hash(output: ReadFileOutput, hash: Option<String>) -> ReadFileOutput {
+    match hash {
+        Some(hash) => ReadFileOutput::Hashed { content: Box::new(output), hash },
+        None => output,
+    }
+}
+
+/// 带总超时读取，避免网络挂载卡住时 invoke 永久挂起
 #[tauri::command]
-pub fn read_file(args: ReadFileArgs) -> Result<String, FsError> {
-    let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
+pub async fn read_file(args: ReadFileArgs) -> Result<ReadFileOutput, FsError> {
+    run_with_deadline(FS_READ_TIMEOUT, move || {
+        let hash = hash_before_read(&args.workspace_root, &args.path, args.include_hash)?;
+        let output = if args.structured {
+            read_file_lines_inner(args).map(ReadFileOutput::Lines)
+        } else {
+            read_file_inner(args).map(ReadFileOutput::Text)
+        }?;
+        Ok(attach_hash(output, hash))
+    })
+    .await
+}
+
+/// 校验路径在工作区内、是文件且不超过 `READ_MAX_BYTES` 后读取文本
+fn load_text_file(workspace_root: &str, path: &str, use_cache: bool) -> Result<String, FsError> {
+    let abs = ensure_inside_workspace_exists(workspace_root, path)?;
     let meta = fs::metadata(&abs).map_err(FsError::from)?;
     if meta.is_dir() {
-        return Err(FsError::NotAllowed("is a directory".into()));
+        return Err(FsError::IsDirectory);
     }
     if meta.len() > READ_MAX_BYTES {
         return Err(FsError::TooLarge);
     }
-    let is_known_text = path_has_text_extension(&abs);
-    if !is_known_text && path_has_binary_extension(&abs) {
-        return Err(FsError::BinaryFile);
+    read_text_maybe_cached(&abs, &meta, use_cache)
+}
+
+/// 校验路径与大小后读取文本，并按参数去除 ANSI / 脱敏
+pub(super) fn load_read_content(args: &ReadFileArgs) -> Result<String, FsError> {
+    let mut content = load_text_file(&args.workspace_root, &args.path, args.use_cache)?;
+    if args.strip_ansi {
+        content = strip_ansi_codes(&content);
     }
-    if !is_known_text {
-        let mut f = fs::File::open(&abs).map_err(FsError::from)?;
-        if is_binary_content(&mut f).map_err(FsError::from)? {
-            return Err(FsError::BinaryFile);
-        }
+    if args.redact_secrets {
+        content = redact_env_secrets(&content);
     }
-    let bytes = fs::read(&abs).map_err(FsError::from)?;
-    let content = String::from_utf8_lossy(&bytes).into_owned();
+    Ok(content)
+}
 
+pub fn read_file_inner(args: ReadFileArgs) -> Result<String, FsError> {
+    let content = load_read_content(&args)?;
     let offset = args.offset.unwrap_or(0) as usize;
     let limit = args.limit.unwrap_or(2000) as usize;
 
-    let lines: Vec<&str> = content.lines().collect();
-    let total = lines.len();
-    let from = offset.min(total);
-    let to = (from + limit).min(total);
-    let selected = &lines[from..to];
-
+    // 按需迭代，不为整个文件建立行数组
     let mut out = String::new();
-    for (i, line) in selected.iter().enumerate() {
-        let line_no = from + i + 1;
-        let prefix = format!("{:05}| ", line_no);
-        let trimmed = if line.chars().count() > LINE_MAX_CHARS {
-            let s: String = line.chars().take(LINE_MAX_CHARS).collect();
-            format!("{}[... truncated {} chars]", s, line.chars().count() - LINE_MAX_CHARS)
-        } else {
-            line.to_string()
-        };
-        out.push_str(&prefix);
-        out.push_str(&trimmed);
+    for (i, line) in content.lines().enumerate().skip(offset).take(limit) {
+        out.push_str(&format!("{:05}| ", i + 1));
+        push_truncated_line(&mut out, line);
         out.push('\n');
     }
     Ok(out)
 }
 
+/// 与 `read_file_inner` 相同的 offset/limit 与截断规则，按行返回结构化结果。
+pub fn read_file_lines_inner(args: ReadFileArgs) -> Result<Vec<ReadFileLine>, FsError> {
+    let content = load_read_content(&args)?;
+    let offset = args.offset.unwrap_or(0) as usize;
+    let limit = args.limit.unwrap_or(2000) as usize;
+
+    Ok(content
+        .lines()
+        .enumerate()
+        .skip(offset)
+        .take(limit)
+        .map(|(i, line)| {
+            let mut text = String::new();
+            push_truncated_line(&mut text, line);
+            ReadFileLine { line_number: i + 1, text }
+        })
+        .collect())
+}
+
+/// 追加一行，超过 `LINE_MAX_CHARS` 个字符时截断并注明被截掉的字符数。
+/// 只扫描一遍：先定位截断点，截断点之后的部分才计数（压缩过的 JS/CSS 常是单行数百 KB）。
+fn push_truncated_line(out: &mut String, line: &str) {
+    match line.char_indices().nth(LINE_MAX_CHARS) {
+        None => out.push_str(line),
+        Some((cut, _)) => {
+            let dropped = line[cut..].chars().count();
+            out.push_str(&line[..cut]);
+            out.push_str(&format!("[... truncated {} chars]", dropped));
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // read_file_raw
 // ---------------------------------------------------------------------------
@@ -82,30 +190,27 @@ pub fn read_file(args: ReadFileArgs) -> Result<String, FsError> {
 pub struct ReadFileRawArgs {
     pub workspace_root: String,
     pub path: String,
+    /// 去掉 ANSI 颜色等转义序列
+    #[serde(default)]
+    pub strip_ansi: bool,
+    /// 返回 `{content, hash}`，`hash` 供 `write_file_if_unchanged` 使用
+    #[serde(default)]
+    pub include_hash: bool,
+    /// 走小文件读取缓存（agent 反复读取同一配置文件时开启）
+    #[serde(default)]
+    pub use_cache: bool,
 }
 
 #[tauri::command]
-pub fn read_file_raw(args: ReadFileRawArgs) -> Result<String, FsError> {
-    let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
-    let meta = fs::metadata(&abs).map_err(FsError::from)?;
-    if meta.is_dir() {
-        return Err(FsError::NotAllowed("is a directory".into()));
-    }
-    if meta.len() > READ_MAX_BYTES {
-        return Err(FsError::TooLarge);
-    }
-    let is_known_text = path_has_text_extension(&abs);
-    if !is_known_text && path_has_binary_extension(&abs) {
-        return Err(FsError::BinaryFile);
-    }
-    if !is_known_text {
-        let mut f = fs::File::open(&abs).map_err(FsError::from)?;
-        if is_binary_content(&mut f).map_err(FsError::from)? {
-            return Err(FsError::BinaryFile);
-        }
-    }
-    let bytes = fs::read(&abs).map_err(FsError::from)?;
-    Ok(String::from_utf8_lossy(&bytes).into_owned())
+pub fn read_file_raw(args: ReadFileRawArgs) -> Result<ReadFileOutput, FsError> {
+    let hash = hash_before_read(&args.workspace_root, &args.path, args.include_hash)?;
+    let content = read_file_raw_inner(args)?;
+    Ok(attach_hash(ReadFileOutput::Text(content), hash))
+}
+
+pub fn read_file_raw_inner(args: ReadFileRawArgs) -> Result<String, FsError> {
+    let content = load_text_file(&args.workspace_root, &args.path, args.use_cache)?;
+    Ok(if args.strip_ansi { strip_ansi_codes(&content) } else { content })
 }
 
 // ---------------------------------------------------------------------------
@@ -130,16 +235,53 @@ pub fn read_file_as_data_url(args: ReadFileAsDataUrlArgs) -> Result<ReadFileAsDa
     let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
     let meta = fs::metadata(&abs).map_err(FsError::from)?;
     if meta.is_dir() {
-        return Err(FsError::NotAllowed("is a directory".into()));
+        return Err(FsError::IsDirectory);
     }
     if meta.len() > READ_DATA_URL_MAX_BYTES {
         return Err(FsError::TooLarge);
     }
     let bytes = fs::read(&abs).map_err(FsError::from)?;
-    let mime = mime_from_magic(&bytes).unwrap_or_else(|| mime_from_extension(&abs));
+    let (data_url, _) = encode_data_url(&abs, &bytes);
+    Ok(ReadFileAsDataUrlResult { data_url })
+}
+
+/// 按魔数（回退扩展名）确定 MIME，编码为 data URL。
+pub(super) fn encode_data_url(abs: &Path, bytes: &[u8]) -> (String, &'static str) {
+    let mime = mime_from_magic(bytes).unwrap_or_else(|| mime_from_extension(abs));
     use base64::engine::general_purpose::STANDARD as BASE64;
     use base64::Engine;
-    let b64 = BASE64.encode(&bytes);
-    let data_url = format!("data:{};base64,{}", mime, b64);
-    Ok(ReadFileAsDataUrlResult { data_url })
+    let b64 = BASE64.encode(bytes);
+    (format!("data:{};base64,{}", mime, b64), mime)
+}
+
+// ---------------------------------------------------------------------------
+// read_file_for_preview：一次调用完成二进制判断并返回文本或 data URL
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadFileForPreviewArgs {
+    pub workspace_root: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum FilePreview {
+    Text { content: String },
+    DataUrl { data_url: String, mime: String },
+}
+
+/// 文本分支沿用 `read_file_raw` 的大小上限与 BOM 解码，二进制分支沿用 `read_file_as_data_url` 的上限。
+#[tauri::command]
+pub fn read_file_for_preview(args: ReadFileForPreviewArgs) -> Result<FilePreview, FsError> {
+    let source = PreviewSource::load(&args.workspace_root, &args.path)?;
+    if !source.is_text {
+        let (data_url, mime) = source.into_data_url();
+        return Ok(FilePreview::DataUrl { data_url, mime: mime.to_string() });
+    }
+    if source.byte_len() > READ_MAX_BYTES {
+        return Err(FsError::TooLarge);
+    }
+    Ok(FilePreview::Text { content: source.into_text() })
 }
@@ -31,7 +31,7 @@ pub fn read_absolute_file(args: ReadAbsoluteFileArgs) -> Result<String, FsError>
     let abs = validate_absolute(&args.path)?;
     let meta = fs::metadata(abs).map_err(FsError::from)?;
     if meta.is_dir() {
-        return Err(FsError::NotAllowed("is a directory".into()));
+        return Err(FsError::IsDirectory);
     }
     if meta.len() > READ_MAX_BYTES {
         return Err(FsError::TooLarge);
@@ -58,7 +58,7 @@ pub fn read_absolute_file_as_data_url(
     let abs = validate_absolute(&args.path)?;
     let meta = fs::metadata(abs).map_err(FsError::from)?;
     if meta.is_dir() {
-        return Err(FsError::NotAllowed("is a directory".into()));
+        return Err(FsError::IsDirectory);
     }
     if meta.len() > READ_DATA_URL_MAX_BYTES {
         return Err(FsError::TooLarge);
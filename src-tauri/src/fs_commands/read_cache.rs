@@ -0,0 +1,135 @@
+//! 小文件读取缓存：agent 循环中反复读取同一配置文件时省去磁盘读取与二进制检测。
+//! 以 (路径, mtime, 大小) 判断命中，文件变更后自动失效；按条目数与总字节数双重限额，超限淘汰最久未用项。
+//! 默认关闭，读取命令传 `useCache` 时才使用；应用内写入会主动淘汰对应条目。
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use super::read::read_text_content;
+use super::FsError;
+
+/// 缓存条目数上限
+const CACHE_MAX_ENTRIES: usize = 128;
+/// 缓存内容总字节上限（4MB）
+const CACHE_MAX_TOTAL_BYTES: usize = 4 * 1024 * 1024;
+/// 超过此大小的文件不进缓存（64KB）
+const CACHE_MAX_FILE_BYTES: u64 = 64 * 1024;
+
+struct CacheEntry {
+    mtime: Option<SystemTime>,
+    size: u64,
+    content: String,
+    last_used: u64,
+}
+
+pub(super) struct ReadCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+    total_bytes: usize,
+    max_entries: usize,
+    max_total_bytes: usize,
+    tick: u64,
+}
+
+impl ReadCache {
+    pub(super) fn with_limits(max_entries: usize, max_total_bytes: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            total_bytes: 0,
+            max_entries,
+            max_total_bytes,
+            tick: 0,
+        }
+    }
+
+    /// mtime 与大小都与缓存一致时返回内容；不一致则丢弃旧条目
+    pub(super) fn get(&mut self, abs: &Path, meta: &fs::Metadata) -> Option<String> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(abs)?;
+        if entry.mtime == meta.modified().ok() && entry.size == meta.len() {
+            entry.last_used = self.tick;
+            return Some(entry.content.clone());
+        }
+        self.remove(abs);
+        None
+    }
+
+    pub(super) fn insert(&mut self, abs: &Path, meta: &fs::Metadata, content: &str) {
+        if content.len() > self.max_total_bytes || self.max_entries == 0 {
+            return;
+        }
+        self.remove(abs);
+        while self.entries.len() >= self.max_entries
+            || self.total_bytes + content.len() > self.max_total_bytes
+        {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(p, _)| p.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+        self.tick += 1;
+        self.total_bytes += content.len();
+        self.entries.insert(
+            abs.to_path_buf(),
+            CacheEntry {
+                mtime: meta.modified().ok(),
+                size: meta.len(),
+                content: content.to_string(),
+                last_used: self.tick,
+            },
+        );
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.entries.clear();
+        self.total_bytes = 0;
+    }
+
+    fn remove(&mut self, abs: &Path) {
+        if let Some(old) = self.entries.remove(abs) {
+            self.total_bytes -= old.content.len();
+        }
+    }
+}
+
+fn global_cache() -> &'static Mutex<ReadCache> {
+    static CACHE: OnceLock<Mutex<ReadCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(ReadCache::with_limits(CACHE_MAX_ENTRIES, CACHE_MAX_TOTAL_BYTES)))
+}
+
+/// `read_text_content` 的缓存版本：`use_cache` 时小文件优先走缓存，未命中时读取并写入缓存。
+pub(super) fn read_text_maybe_cached(abs: &Path, meta: &fs::Metadata, use_cache: bool) -> Result<String, FsError> {
+    if !use_cache || meta.len() > CACHE_MAX_FILE_BYTES {
+        return read_text_content(abs);
+    }
+    let cache = global_cache();
+    if let Some(hit) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(abs, meta) {
+        return Ok(hit);
+    }
+    let content = read_text_content(abs)?;
+    cache
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(abs, meta, &content);
+    Ok(content)
+}
+
+/// 应用内写入文件后丢弃其缓存：mtime 精度较粗时，同一时刻内的改写可能仍命中旧内容
+pub(super) fn evict_cached(abs: &Path) {
+    // 缓存以 canonicalize 后的路径为键，写入方可能传入经过符号链接的路径
+    let key = dunce::canonicalize(abs).unwrap_or_else(|_| abs.to_path_buf());
+    global_cache().lock().unwrap_or_else(|e| e.into_inner()).remove(&key);
+}
+
+/// 清空读取缓存（如外部工具批量改动文件后，或排查缓存问题时）
+#[tauri::command]
+pub fn clear_read_cache() {
+    global_cache().lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
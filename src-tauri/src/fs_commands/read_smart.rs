@@ -0,0 +1,88 @@
+//! 智能读取：小文本直接返回文本（附带识别出的编码），二进制或大文件才编码为 data URL，
+//! 省去预览层对常见文本文件的 base64 编解码。
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::detection::{
+    bom_encoding, decode_bom_text, is_binary_content, path_has_binary_extension,
+    path_has_text_extension, READ_DATA_URL_MAX_BYTES, READ_MAX_BYTES,
+};
+use super::read::encode_data_url;
+use super::validation::ensure_inside_workspace_exists;
+use super::FsError;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadFileSmartArgs {
+    pub workspace_root: String,
+    pub path: String,
+    /// 文本走快速路径的大小上限（字节），默认 250KB，不超过该默认值
+    #[serde(default)]
+    pub text_max_bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum SmartReadResult {
+    /// `encoding`：`utf-8`、`utf-16le` 或 `utf-16be`（依据 BOM；无 BOM 视为 UTF-8）
+    Text { content: String, encoding: String },
+    DataUrl { data_url: String, mime: String },
+}
+
+/// 文本且不超过阈值时返回文本；其余（二进制或超过阈值的文本）返回 data URL，上限同 `read_file_as_data_url`。
+#[tauri::command]
+pub fn read_file_smart(args: ReadFileSmartArgs) -> Result<SmartReadResult, FsError> {
+    let source = PreviewSource::load(&args.workspace_root, &args.path)?;
+    let text_max = args.text_max_bytes.unwrap_or(READ_MAX_BYTES).min(READ_MAX_BYTES);
+    if source.is_text && source.byte_len() <= text_max {
+        let encoding = source.bom_encoding.unwrap_or("utf-8").to_string();
+        return Ok(SmartReadResult::Text { content: source.into_text(), encoding });
+    }
+    let (data_url, mime) = source.into_data_url();
+    Ok(SmartReadResult::DataUrl { data_url, mime: mime.to_string() })
+}
+
+/// `read_file_smart` 与 `read_file_for_preview` 的公共部分：校验路径与 data URL 上限，
+/// 读取字节并判断是否为文本（有 BOM、已知文本扩展名，或非二进制扩展名且内容不像二进制）。
+pub(super) struct PreviewSource {
+    abs: PathBuf,
+    bytes: Vec<u8>,
+    pub(super) bom_encoding: Option<&'static str>,
+    pub(super) is_text: bool,
+}
+
+impl PreviewSource {
+    pub(super) fn load(workspace_root: &str, path: &str) -> Result<Self, FsError> {
+        let abs = ensure_inside_workspace_exists(workspace_root, path)?;
+        let meta = fs::metadata(&abs).map_err(FsError::from)?;
+        if meta.is_dir() {
+            return Err(FsError::IsDirectory);
+        }
+        if meta.len() > READ_DATA_URL_MAX_BYTES {
+            return Err(FsError::TooLarge);
+        }
+        let bytes = fs::read(&abs).map_err(FsError::from)?;
+        let encoding = bom_encoding(&bytes);
+        let is_text = encoding.is_some()
+            || path_has_text_extension(&abs)
+            || (!path_has_binary_extension(&abs)
+                && !is_binary_content(&bytes[..]).map_err(FsError::from)?);
+        Ok(Self { abs, bytes, bom_encoding: encoding, is_text })
+    }
+
+    pub(super) fn byte_len(&self) -> u64 {
+        self.bytes.len() as u64
+    }
+
+    /// 有 BOM 时按对应编码解码，否则按 lossy UTF-8
+    pub(super) fn into_text(self) -> String {
+        decode_bom_text(&self.bytes).unwrap_or_else(|| String::from_utf8_lossy(&self.bytes).into_owned())
+    }
+
+    pub(super) fn into_data_url(self) -> (String, &'static str) {
+        encode_data_url(&self.abs, &self.bytes)
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use regex::{NoExpand, Regex};
+use serde::{Deserialize, Serialize};
+
+use super::detection::{is_binary_content, path_has_binary_extension};
+use super::read_cache::evict_cached;
+use super::validation::{canonical_workspace_root, ensure_inside_workspace_exists};
+use super::walk::{walk_workspace, WalkOptions};
+use super::FsError;
+
+/// 单次最多处理的文件数
+const REPLACE_MAX_FILES: usize = 1000;
+/// 超过此大小的文件跳过
+const REPLACE_MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceInFilesArgs {
+    pub workspace_root: String,
+    /// 相对工作区根的 glob（如 `src/**/*.ts`），与 `paths` 二选一
+    #[serde(default)]
+    pub glob: Option<String>,
+    /// 显式文件列表（相对工作区根）
+    #[serde(default)]
+    pub paths: Option<Vec<String>>,
+    pub search: String,
+    pub replace: String,
+    /// `search` 按正则解析；否则按字面量匹配，`replace` 也不展开 `$1`
+    #[serde(default)]
+    pub regex: bool,
+    /// 只统计匹配数，不写文件
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceFileResult {
+    /// 相对工作区根的路径
+    pub path: String,
+    pub matches: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceInFilesResult {
+    /// 仅包含有匹配的文件
+    pub files: Vec<ReplaceFileResult>,
+    pub total_matches: usize,
+    /// 候选文件数超过上限被截断
+    pub truncated: bool,
+}
+
+fn build_matcher(args: &ReplaceInFilesArgs) -> Result<Regex, FsError> {
+    if args.search.is_empty() {
+        return Err(FsError::NotAllowed("search must not be empty".into()));
+    }
+    let pattern = if args.regex {
+        args.search.clone()
+    } else {
+        regex::escape(&args.search)
+    };
+    Regex::new(&pattern).map_err(|e| FsError::NotAllowed(format!("invalid regex: {}", e)))
+}
+
+/// 收集候选文件 (相对路径, 绝对路径)，返回是否截断；显式 `paths` 按规范化路径去重
+fn collect_targets(args: &ReplaceInFilesArgs, root: &Path) -> Result<(Vec<(String, PathBuf)>, bool), FsError> {
+    if let Some(paths) = &args.paths {
+        let mut out = Vec::new();
+        let mut seen = HashSet::new();
+        for p in paths.iter().take(REPLACE_MAX_FILES) {
+            let abs = ensure_inside_workspace_exists(&args.workspace_root, p)?;
+            if abs.is_dir() {
+                return Err(FsError::IsDirectory);
+            }
+            if !seen.insert(abs.clone()) {
+                continue;
+            }
+            let rel = abs
+                .strip_prefix(root)
+                .map(|r| r.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_else(|_| p.clone());
+            out.push((rel, abs));
+        }
+        return Ok((out, paths.len() > REPLACE_MAX_FILES));
+    }
+
+    let Some(glob) = args.glob.as_deref().map(str::trim).filter(|g| !g.is_empty()) else {
+        return Err(FsError::NotAllowed("either glob or paths is required".into()));
+    };
+    if Path::new(glob).is_absolute() || glob.split('/').any(|c| c == "..") {
+        return Err(FsError::OutsideWorkspace);
+    }
+    let pattern = glob::Pattern::new(glob).map_err(|e| FsError::NotAllowed(format!("invalid glob: {}", e)))?;
+    let opts = glob::MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+    let walk = walk_workspace(
+        root,
+        &WalkOptions {
+            include_dirs: false,
+            max_depth: None,
+            max_entries: REPLACE_MAX_FILES,
+            respect_ignore: true,
+        },
+        |path, _| pattern.matches_with(path, opts),
+    );
+    let targets = walk
+        .entries
+        .into_iter()
+        // symlink 解析到真实路径，避免 rename 覆盖链接本身或写出工作区
+        .filter_map(|e| {
+            let abs = dunce::canonicalize(root.join(&e.path)).ok()?;
+            abs.starts_with(root).then_some((e.path, abs))
+        })
+        .collect();
+    Ok((targets, walk.truncated))
+}
+
+/// 读取可替换的文本文件；二进制、过大或非 UTF-8 文件返回 None
+fn read_text(abs: &Path) -> Option<String> {
+    let meta = fs::metadata(abs).ok()?;
+    if !meta.is_file() || meta.len() > REPLACE_MAX_FILE_BYTES || path_has_binary_extension(abs) {
+        return None;
+    }
+    let bytes = fs::read(abs).ok()?;
+    if is_binary_content(bytes.as_slice()).unwrap_or(true) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// 先写同目录临时文件再 rename，避免中途失败留下半写的文件
+fn write_atomic(abs: &Path, content: &str) -> Result<(), FsError> {
+    let name = abs
+        .file_name()
+        .ok_or(FsError::InvalidName)?
+        .to_string_lossy();
+    let tmp = abs.with_file_name(format!(".{}.{}.cove-tmp", name, std::process::id()));
+    let written = (|| -> std::io::Result<()> {
+        let mut f = fs::File::options().write(true).create_new(true).open(&tmp)?;
+        f.write_all(content.as_bytes())?;
+        f.sync_all()?;
+        if let Ok(meta) = fs::metadata(abs) {
+            fs::set_permissions(&tmp, meta.permissions())?;
+        }
+        fs::rename(&tmp, abs)
+    })();
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(FsError::from(e));
+    }
+    Ok(())
+}
+
+/// Core logic, separated from Tauri event emission for testability.
+/// `on_written` is called with the relative path right after each file is written,
+/// so files changed before a mid-batch failure are still reported.
+pub(super) fn replace_in_files_inner(
+    args: &ReplaceInFilesArgs,
+    mut on_written: impl FnMut(&str),
+) -> Result<ReplaceInFilesResult, FsError> {
+    let matcher = build_matcher(args)?;
+    let root = canonical_workspace_root(&args.workspace_root)?;
+    let (targets, truncated) = collect_targets(args, &root)?;
+
+    let mut files = Vec::new();
+    let mut total_matches = 0;
+    for (rel, abs) in targets {
+        let Some(text) = read_text(&abs) else { continue };
+        let matches = matcher.find_iter(&text).count();
+        if matches == 0 {
+            continue;
+        }
+        if !args.dry_run {
+            let replaced = if args.regex {
+                matcher.replace_all(&text, args.replace.as_str())
+            } else {
+                matcher.replace_all(&text, NoExpand(&args.replace))
+            };
+            write_atomic(&abs, &replaced)?;
+            evict_cached(&abs);
+            on_written(&rel);
+        }
+        total_matches += matches;
+        files.push(ReplaceFileResult { path: rel, matches });
+    }
+
+    Ok(ReplaceInFilesResult {
+        files,
+        total_matches,
+        truncated,
+    })
+}
+
+#[tauri::command]
+pub fn replace_in_files(app: tauri::AppHandle, args: ReplaceInFilesArgs) -> Result<ReplaceInFilesResult, FsError> {
+    use tauri::Emitter;
+    replace_in_files_inner(&args, |path| {
+        let _ = app.emit(
+            crate::workspace_watcher::EVENT_WORKSPACE_FILE_CHANGED,
+            crate::workspace_watcher::WorkspaceFileChangedPayload {
+                path: path.to_string(),
+                kind: crate::workspace_watcher::FileChangeKind::Modify,
+            },
+        );
+    })
+}
@@ -0,0 +1,26 @@
+use serde::Deserialize;
+
+use super::validation::{canonical_workspace_root, ensure_inside_workspace_exists};
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// resolve_workspace_path
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveWorkspacePathArgs {
+    pub workspace_root: String,
+    pub path: String,
+}
+
+/// 将用户输入的路径解析为规范化的工作区相对路径（正斜杠），用于与已打开文件比对。
+#[tauri::command]
+pub fn resolve_workspace_path(args: ResolveWorkspacePathArgs) -> Result<String, FsError> {
+    let abs = ensure_inside_workspace_exists(&args.workspace_root, &args.path)?;
+    let root = canonical_workspace_root(&args.workspace_root)?;
+    let rel = abs
+        .strip_prefix(&root)
+        .map_err(|_| FsError::OutsideWorkspace)?;
+    Ok(rel.to_string_lossy().replace('\\', "/"))
+}
@@ -1,6 +1,6 @@
-use super::list::{stat_file, ListDirArgs, StatFileArgs};
+use super::list::{stat_file_inner, ListDirArgs, StatFileArgs};
 use super::read::{ReadFileArgs, ReadFileAsDataUrlArgs, ReadFileRawArgs};
-use super::read::read_file;
+use super::read::read_file_inner;
 use super::write::{write_file, WriteFileArgs};
 use super::FsError;
 
@@ -70,11 +70,16 @@ fn read_file_returns_line_numbered_content() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("hello.txt"), "line1\nline2\nline3\n").unwrap();
 
-    let out = read_file(ReadFileArgs {
+    let out = read_file_inner(ReadFileArgs {
         workspace_root: root.to_string(),
         path: "hello.txt".to_string(),
         offset: None,
         limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
     })
     .unwrap();
     assert!(out.starts_with("00001| line1\n"));
@@ -88,11 +93,16 @@ fn read_file_offset_limit() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("five.txt"), "a\nb\nc\nd\ne\n").unwrap();
 
-    let out = read_file(ReadFileArgs {
+    let out = read_file_inner(ReadFileArgs {
         workspace_root: root.to_string(),
         path: "five.txt".to_string(),
         offset: Some(1),
         limit: Some(2),
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
     })
     .unwrap();
     assert_eq!(out.trim(), "00002| b\n00003| c");
@@ -107,11 +117,16 @@ fn read_file_outside_workspace_rejected() {
     let outside_file = outside.path().join("outside.txt");
     std::fs::write(&outside_file, "x").unwrap();
 
-    let result = read_file(ReadFileArgs {
+    let result = read_file_inner(ReadFileArgs {
         workspace_root: root.to_string(),
         path: outside_file.to_str().unwrap().to_string(),
         offset: None,
         limit: Some(5),
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
     });
     assert!(matches!(result, Err(FsError::OutsideWorkspace)));
 }
@@ -122,11 +137,16 @@ fn read_file_binary_extension_rejected() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("x.png"), "not really png").unwrap();
 
-    let result = read_file(ReadFileArgs {
+    let result = read_file_inner(ReadFileArgs {
         workspace_root: root.to_string(),
         path: "x.png".to_string(),
         offset: None,
         limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
     });
     assert!(matches!(result, Err(FsError::BinaryFile)));
 }
@@ -142,7 +162,7 @@ fn write_file_creates_file_and_parent_dir() {
     let sub = dir.path().join("sub");
     assert!(!sub.exists());
 
-    write_file(WriteFileArgs {
+    let returned = write_file(WriteFileArgs {
         workspace_root: root.to_string(),
         path: "sub/nested/file.txt".to_string(),
         content: "written".to_string(),
@@ -152,6 +172,9 @@ fn write_file_creates_file_and_parent_dir() {
     let p = dir.path().join("sub/nested/file.txt");
     assert!(p.is_file());
     assert_eq!(std::fs::read_to_string(&p).unwrap(), "written");
+    // 返回规范化绝对路径，指向刚写入的文件
+    assert!(std::path::Path::new(&returned).is_absolute());
+    assert_eq!(std::path::PathBuf::from(&returned), dunce::canonicalize(&p).unwrap());
 }
 
 #[test]
@@ -194,7 +217,7 @@ fn write_file_rejects_directory_path() {
         path: "existing_dir".to_string(),
         content: "x".to_string(),
     });
-    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    assert!(matches!(result, Err(FsError::IsDirectory)));
 }
 
 // ---------------------------------------------------------------------------
@@ -207,7 +230,7 @@ fn stat_file_returns_metadata() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("f.txt"), "hello").unwrap();
 
-    let st = stat_file(StatFileArgs {
+    let st = stat_file_inner(StatFileArgs {
         workspace_root: root.to_string(),
         path: "f.txt".to_string(),
     })
@@ -216,3 +239,59 @@ fn stat_file_returns_metadata() {
     assert!(!st.is_dir);
     assert!(!st.is_binary);
 }
+
+#[cfg(unix)]
+#[test]
+fn stat_file_reports_symlink_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().to_str().unwrap();
+    std::fs::write(dir.path().join("real.txt"), "hello").unwrap();
+    std::os::unix::fs::symlink(dir.path().join("real.txt"), dir.path().join("link.txt")).unwrap();
+
+    let st = stat_file_inner(StatFileArgs {
+        workspace_root: root.to_string(),
+        path: "link.txt".to_string(),
+    })
+    .unwrap();
+    assert!(st.is_symlink);
+    assert_eq!(st.symlink_target.as_deref(), Some("real.txt"));
+    // 元数据来自目标文件
+    assert_eq!(st.size, 5);
+    assert!(!st.is_dir);
+
+    let real = stat_file_inner(StatFileArgs {
+        workspace_root: root.to_string(),
+        path: "real.txt".to_string(),
+    })
+    .unwrap();
+    assert!(!real.is_symlink);
+    assert!(real.symlink_target.is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn stat_file_reports_broken_symlink_instead_of_not_found() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().to_str().unwrap();
+    std::os::unix::fs::symlink("missing.txt", dir.path().join("broken.txt")).unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    std::os::unix::fs::symlink(outside.path().join("gone"), dir.path().join("escape")).unwrap();
+
+    let st = stat_file_inner(StatFileArgs {
+        workspace_root: root.to_string(),
+        path: "broken.txt".to_string(),
+    })
+    .unwrap();
+    assert!(st.is_symlink);
+    assert_eq!(st.symlink_target.as_deref(), Some("missing.txt"));
+    assert!(!st.is_dir);
+
+    // 指向工作区外的断链：仍报告为 symlink，但不暴露目标路径
+    let st = stat_file_inner(StatFileArgs {
+        workspace_root: root.to_string(),
+        path: "escape".to_string(),
+    })
+    .unwrap();
+    assert!(st.is_symlink);
+    assert!(st.symlink_target.is_none());
+}
@@ -0,0 +1,234 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use zip::write::SimpleFileOptions;
+
+use super::archive::{
+    unzip_archive_inner, zip_entries_inner, UnzipArchiveArgs, ZipEntriesArgs, UNZIP_MAX_TOTAL_BYTES,
+};
+use super::FsError;
+
+fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+    let mut z = zip::ZipWriter::new(fs::File::create(path).unwrap());
+    let opts = SimpleFileOptions::default();
+    for (name, data) in entries {
+        if name.ends_with('/') {
+            z.add_directory(*name, opts).unwrap();
+        } else {
+            z.start_file(*name, opts).unwrap();
+            z.write_all(data).unwrap();
+        }
+    }
+    z.finish().unwrap();
+}
+
+fn unzip_args(root: &Path, archive: &str, dest: &str) -> UnzipArchiveArgs {
+    UnzipArchiveArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        archive_path: archive.to_string(),
+        dest_dir: dest.to_string(),
+    }
+}
+
+#[test]
+fn unzip_extracts_nested_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    write_zip(
+        &dir.path().join("pkg.zip"),
+        &[("docs/", b""), ("docs/readme.md", b"# hi"), ("a/b/c.txt", b"deep")],
+    );
+
+    let mut created =
+        unzip_archive_inner(&unzip_args(dir.path(), "pkg.zip", "out"), UNZIP_MAX_TOTAL_BYTES).unwrap();
+    created.sort();
+    assert_eq!(created, vec!["out/a/b/c.txt", "out/docs/readme.md"]);
+    assert_eq!(fs::read_to_string(dir.path().join("out/a/b/c.txt")).unwrap(), "deep");
+    assert!(dir.path().join("out/docs").is_dir());
+}
+
+#[test]
+fn unzip_rejects_parent_traversal_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    write_zip(
+        &dir.path().join("evil.zip"),
+        &[("ok.txt", b"fine"), ("../../escaped.txt", b"pwned")],
+    );
+
+    let result = unzip_archive_inner(&unzip_args(dir.path(), "evil.zip", "out"), UNZIP_MAX_TOTAL_BYTES);
+    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    // 整个归档被拒绝，没有任何文件写出
+    assert!(!dir.path().join("out/ok.txt").exists());
+    assert!(!dir.path().parent().unwrap().join("escaped.txt").exists());
+}
+
+#[test]
+fn unzip_rejects_absolute_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    write_zip(&dir.path().join("abs.zip"), &[("/tmp/abs-escape.txt", b"x")]);
+    let result = unzip_archive_inner(&unzip_args(dir.path(), "abs.zip", "out"), UNZIP_MAX_TOTAL_BYTES);
+    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+}
+
+#[test]
+fn unzip_enforces_total_size_cap() {
+    let dir = tempfile::tempdir().unwrap();
+    let big = vec![0u8; 64 * 1024];
+    write_zip(&dir.path().join("bomb.zip"), &[("zeros.bin", &big)]);
+    let result = unzip_archive_inner(&unzip_args(dir.path(), "bomb.zip", "out"), 1024);
+    assert!(matches!(result, Err(FsError::TooLarge)));
+    assert!(!dir.path().join("out/zeros.bin").exists());
+}
+
+#[test]
+fn unzip_dest_outside_workspace_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    write_zip(&dir.path().join("pkg.zip"), &[("a.txt", b"a")]);
+    let result =
+        unzip_archive_inner(&unzip_args(dir.path(), "pkg.zip", "../outside"), UNZIP_MAX_TOTAL_BYTES);
+    assert!(matches!(result, Err(FsError::OutsideWorkspace)));
+}
+
+#[cfg(unix)]
+#[test]
+fn unzip_rejects_symlinked_dir_pointing_outside() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    std::os::unix::fs::symlink(outside.path(), dir.path().join("link")).unwrap();
+    write_zip(&dir.path().join("pkg.zip"), &[("link/escaped.txt", b"pwned")]);
+
+    let result = unzip_archive_inner(&unzip_args(dir.path(), "pkg.zip", "."), UNZIP_MAX_TOTAL_BYTES);
+    assert!(matches!(result, Err(FsError::OutsideWorkspace)));
+    assert!(!outside.path().join("escaped.txt").exists());
+
+    // 目标目录本身是外链时同样拒绝
+    let result = unzip_archive_inner(&unzip_args(dir.path(), "pkg.zip", "link/sub"), UNZIP_MAX_TOTAL_BYTES);
+    assert!(matches!(result, Err(FsError::OutsideWorkspace)));
+    assert!(!outside.path().join("sub").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn unzip_refuses_to_write_through_existing_symlink() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    let target = outside.path().join("victim.txt");
+    fs::write(&target, "original").unwrap();
+    fs::create_dir(dir.path().join("out")).unwrap();
+    std::os::unix::fs::symlink(&target, dir.path().join("out/a.txt")).unwrap();
+    write_zip(&dir.path().join("pkg.zip"), &[("a.txt", b"pwned")]);
+
+    let result = unzip_archive_inner(&unzip_args(dir.path(), "pkg.zip", "out"), UNZIP_MAX_TOTAL_BYTES);
+    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "original");
+}
+
+#[test]
+fn unzip_invalid_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("bad.zip"), "not a zip").unwrap();
+    let result = unzip_archive_inner(&unzip_args(dir.path(), "bad.zip", "out"), UNZIP_MAX_TOTAL_BYTES);
+    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+}
+
+// ---------------------------------------------------------------------------
+// zip_entries
+// ---------------------------------------------------------------------------
+
+fn zip_args(root: &Path, paths: &[&str], zip_path: &str, level: Option<i64>) -> ZipEntriesArgs {
+    ZipEntriesArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        paths: paths.iter().map(|p| p.to_string()).collect(),
+        zip_path: zip_path.to_string(),
+        compression_level: level,
+    }
+}
+
+#[test]
+fn zip_then_unzip_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("dist/assets")).unwrap();
+    fs::write(root.join("dist/index.html"), "<html></html>").unwrap();
+    fs::write(root.join("dist/assets/app.js"), "console.log(1)").unwrap();
+    fs::write(root.join("report.md"), "# Report").unwrap();
+
+    let (rel, count) =
+        zip_entries_inner(&zip_args(root, &["dist", "report.md"], "out/bundle.zip", None)).unwrap();
+    assert_eq!(rel, "out/bundle.zip");
+    assert_eq!(count, 3);
+
+    unzip_archive_inner(&unzip_args(root, "out/bundle.zip", "restored"), UNZIP_MAX_TOTAL_BYTES)
+        .unwrap();
+    for (orig, restored) in [
+        ("dist/index.html", "restored/dist/index.html"),
+        ("dist/assets/app.js", "restored/dist/assets/app.js"),
+        ("report.md", "restored/report.md"),
+    ] {
+        assert_eq!(
+            fs::read(root.join(orig)).unwrap(),
+            fs::read(root.join(restored)).unwrap()
+        );
+    }
+}
+
+#[test]
+fn zip_output_is_deterministic() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/b.txt"), "b").unwrap();
+    fs::write(root.join("src/a.txt"), "a").unwrap();
+
+    zip_entries_inner(&zip_args(root, &["src"], "one.zip", Some(9))).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    zip_entries_inner(&zip_args(root, &["src"], "two.zip", Some(9))).unwrap();
+    assert_eq!(fs::read(root.join("one.zip")).unwrap(), fs::read(root.join("two.zip")).unwrap());
+}
+
+#[test]
+fn zip_stored_when_level_zero() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::write(root.join("a.txt"), "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa").unwrap();
+    zip_entries_inner(&zip_args(root, &["a.txt"], "a.zip", Some(0))).unwrap();
+    let mut archive = zip::ZipArchive::new(fs::File::open(root.join("a.zip")).unwrap()).unwrap();
+    let entry = archive.by_index(0).unwrap();
+    assert_eq!(entry.compression(), zip::CompressionMethod::Stored);
+}
+
+#[test]
+fn zip_skips_output_inside_zipped_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("out")).unwrap();
+    fs::write(root.join("out/a.txt"), "a").unwrap();
+    let (_, count) = zip_entries_inner(&zip_args(root, &["out"], "out/self.zip", None)).unwrap();
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn zip_rejects_source_outside_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    fs::write(outside.path().join("secret.txt"), "s").unwrap();
+    let src = outside.path().join("secret.txt");
+    let result = zip_entries_inner(&zip_args(dir.path(), &[src.to_str().unwrap()], "x.zip", None));
+    assert!(matches!(result, Err(FsError::OutsideWorkspace)));
+    assert!(!dir.path().join("x.zip").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn zip_refuses_to_write_through_existing_symlink() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    let target = outside.path().join("victim.zip");
+    fs::write(&target, "original").unwrap();
+    fs::write(dir.path().join("a.txt"), "a").unwrap();
+    std::os::unix::fs::symlink(&target, dir.path().join("out.zip")).unwrap();
+
+    let result = zip_entries_inner(&zip_args(dir.path(), &["a.txt"], "out.zip", None));
+    assert!(result.is_err());
+    assert_eq!(fs::read_to_string(&target).unwrap(), "original");
+}
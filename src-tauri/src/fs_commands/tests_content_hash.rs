@@ -0,0 +1,108 @@
+use super::content_hash::{content_hash, write_file_if_unchanged, WriteFileIfUnchangedArgs};
+use super::read::{read_file, read_file_raw, ReadFileArgs, ReadFileOutput, ReadFileRawArgs};
+use super::FsError;
+
+fn read_hash(root: &std::path::Path, path: &str) -> String {
+    let out = read_file_raw(ReadFileRawArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path: path.to_string(),
+        strip_ansi: false,
+        include_hash: true,
+        use_cache: false,
+    })
+    .unwrap();
+    let ReadFileOutput::Hashed { content, hash } = out else {
+        panic!("expected hashed output, got {out:?}");
+    };
+    assert!(matches!(*content, ReadFileOutput::Text(_)));
+    hash
+}
+
+fn write_if_unchanged(root: &std::path::Path, path: &str, content: &str, expected_hash: &str) -> Result<String, FsError> {
+    write_file_if_unchanged(WriteFileIfUnchangedArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path: path.to_string(),
+        content: content.to_string(),
+        expected_hash: expected_hash.to_string(),
+    })
+}
+
+#[test]
+fn content_hash_is_sha256_hex() {
+    assert_eq!(
+        content_hash(b"abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn read_file_raw_without_hash_returns_plain_text() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "hello").unwrap();
+    let out = read_file_raw(ReadFileRawArgs {
+        workspace_root: dir.path().to_string_lossy().into_owned(),
+        path: "a.txt".into(),
+        strip_ansi: false,
+        include_hash: false,
+        use_cache: false,
+    })
+    .unwrap();
+    assert_eq!(out, ReadFileOutput::Text("hello".into()));
+    assert_eq!(serde_json::to_value(&out).unwrap(), "hello");
+}
+
+#[tokio::test]
+async fn read_file_include_hash_wraps_content() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "one\ntwo").unwrap();
+    let out = read_file(ReadFileArgs {
+        workspace_root: dir.path().to_string_lossy().into_owned(),
+        path: "a.txt".into(),
+        offset: None,
+        limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: true,
+        use_cache: false,
+    })
+    .await
+    .unwrap();
+    let json = serde_json::to_value(&out).unwrap();
+    assert_eq!(json["content"], "00001| one\n00002| two\n");
+    assert_eq!(json["hash"], content_hash(b"one\ntwo"));
+}
+
+#[test]
+fn write_if_unchanged_succeeds_when_hash_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "v1").unwrap();
+    let hash = read_hash(dir.path(), "a.txt");
+
+    write_if_unchanged(dir.path(), "a.txt", "v2", &hash).unwrap();
+    assert_eq!(std::fs::read_to_string(dir.path().join("a.txt")).unwrap(), "v2");
+    // 旧哈希已失效
+    assert!(matches!(write_if_unchanged(dir.path(), "a.txt", "v3", &hash), Err(FsError::Conflict)));
+}
+
+#[test]
+fn write_if_unchanged_conflicts_when_file_changed_between_read_and_write() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "v1").unwrap();
+    let hash = read_hash(dir.path(), "a.txt");
+
+    std::fs::write(dir.path().join("a.txt"), "edited elsewhere").unwrap();
+    let result = write_if_unchanged(dir.path(), "a.txt", "agent edit", &hash);
+    assert!(matches!(result, Err(FsError::Conflict)));
+    assert_eq!(std::fs::read_to_string(dir.path().join("a.txt")).unwrap(), "edited elsewhere");
+}
+
+#[test]
+fn write_if_unchanged_conflicts_when_file_deleted() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "v1").unwrap();
+    let hash = read_hash(dir.path(), "a.txt");
+    std::fs::remove_file(dir.path().join("a.txt")).unwrap();
+    assert!(matches!(write_if_unchanged(dir.path(), "a.txt", "v2", &hash), Err(FsError::Conflict)));
+    assert!(!dir.path().join("a.txt").exists());
+}
@@ -90,7 +90,7 @@ fn copy_entry_errors_when_destination_exists() {
         from_path: "src.txt".to_string(),
         to_path: "dst.txt".to_string(),
     });
-    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    assert!(matches!(result, Err(FsError::AlreadyExists)));
 }
 
 // ---------------------------------------------------------------------------
@@ -148,7 +148,7 @@ fn copy_entry_errors_when_destination_is_broken_symlink() {
         from_path: "src.txt".to_string(),
         to_path: "dst.txt".to_string(),
     });
-    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    assert!(matches!(result, Err(FsError::AlreadyExists)));
 }
 
 // ---------------------------------------------------------------------------
@@ -192,3 +192,90 @@ fn copy_entry_copies_directory_with_symlinked_subdir() {
         "top"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Metadata preservation
+// ---------------------------------------------------------------------------
+
+#[cfg(unix)]
+fn set_mode_and_mtime(path: &std::path::Path, mode: u32, secs: i64) {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+    filetime::set_file_mtime(path, filetime::FileTime::from_unix_time(secs, 0)).unwrap();
+}
+
+#[cfg(unix)]
+fn mode_and_mtime(path: &std::path::Path) -> (u32, i64) {
+    use std::os::unix::fs::PermissionsExt;
+    let meta = std::fs::metadata(path).unwrap();
+    let mtime = filetime::FileTime::from_last_modification_time(&meta);
+    (meta.permissions().mode() & 0o777, mtime.unix_seconds())
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_file_preserves_mode_and_mtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("build.sh");
+    std::fs::write(&src, "#!/bin/sh\n").unwrap();
+    set_mode_and_mtime(&src, 0o754, 1_600_000_000);
+
+    copy_entry_inner(&CopyEntryArgs {
+        workspace_root: dir.path().to_string_lossy().into_owned(),
+        from_path: "build.sh".into(),
+        to_path: "copy.sh".into(),
+    })
+    .unwrap();
+    assert_eq!(mode_and_mtime(&dir.path().join("copy.sh")), (0o754, 1_600_000_000));
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_dir_and_move_fallback_preserve_mode_and_mtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("pkg");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(src.join("lib.rs"), "").unwrap();
+    set_mode_and_mtime(&src.join("lib.rs"), 0o640, 1_500_000_000);
+    set_mode_and_mtime(&src, 0o750, 1_400_000_000);
+
+    copy_entry_inner(&CopyEntryArgs {
+        workspace_root: dir.path().to_string_lossy().into_owned(),
+        from_path: "pkg".into(),
+        to_path: "pkg-copy".into(),
+    })
+    .unwrap();
+    let copy = dir.path().join("pkg-copy");
+    assert_eq!(mode_and_mtime(&copy.join("lib.rs")), (0o640, 1_500_000_000));
+    assert_eq!(mode_and_mtime(&copy), (0o750, 1_400_000_000));
+
+    // 跨设备 move 的复制回退路径
+    let moved = dir.path().join("pkg-moved");
+    super::copy::copy_then_remove(&copy, &moved).unwrap();
+    assert!(!copy.exists());
+    assert_eq!(mode_and_mtime(&moved.join("lib.rs")), (0o640, 1_500_000_000));
+    assert_eq!(mode_and_mtime(&moved), (0o750, 1_400_000_000));
+}
+
+#[cfg(unix)]
+#[test]
+fn move_fallback_recreates_symlinks_without_following() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    std::fs::write(outside.path().join("big.bin"), "payload").unwrap();
+    let src = dir.path().join("proj");
+    std::fs::create_dir(&src).unwrap();
+    std::os::unix::fs::symlink(outside.path(), src.join("ext")).unwrap();
+    // 指向自身祖先的循环链接
+    std::os::unix::fs::symlink("..", src.join("loop")).unwrap();
+
+    let moved = dir.path().join("proj-moved");
+    super::copy::copy_then_remove(&src, &moved).unwrap();
+    assert!(!src.exists());
+    for name in ["ext", "loop"] {
+        assert!(std::fs::symlink_metadata(moved.join(name)).unwrap().file_type().is_symlink());
+    }
+    assert_eq!(std::fs::read_link(moved.join("loop")).unwrap(), std::path::Path::new(".."));
+    // 源目录删除不影响链接目标
+    assert!(outside.path().join("big.bin").exists());
+}
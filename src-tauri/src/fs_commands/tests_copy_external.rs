@@ -103,7 +103,7 @@ fn copy_external_file_errors_when_dest_exists() {
         external_path: ext_file.to_str().unwrap().to_string(),
         dest_path: "a.txt".to_string(),
     });
-    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    assert!(matches!(result, Err(FsError::AlreadyExists)));
 }
 
 // ---------------------------------------------------------------------------
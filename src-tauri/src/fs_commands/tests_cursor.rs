@@ -0,0 +1,98 @@
+use std::fs;
+use std::time::Duration;
+
+use super::cursor::{open_read_cursor_inner, read_cursor_chunk_inner, OpenReadCursorArgs, ReadCursorRegistry};
+use super::FsError;
+
+fn open(registry: &ReadCursorRegistry, root: &std::path::Path, path: &str) -> Result<String, FsError> {
+    open_read_cursor_inner(
+        registry,
+        &OpenReadCursorArgs {
+            workspace_root: root.to_string_lossy().into_owned(),
+            path: path.to_string(),
+        },
+    )
+}
+
+#[test]
+fn reads_file_in_chunks_until_eof() {
+    let dir = tempfile::tempdir().unwrap();
+    let content: String = (1..=7).map(|i| format!("line {i}\r\n")).collect();
+    fs::write(dir.path().join("big.log"), content).unwrap();
+    let registry = ReadCursorRegistry::new();
+    let token = open(&registry, dir.path(), "big.log").unwrap();
+
+    let mut all = Vec::new();
+    let mut chunks = 0;
+    loop {
+        let chunk = read_cursor_chunk_inner(&registry, &token, 3).unwrap();
+        chunks += 1;
+        all.extend(chunk.lines);
+        if chunk.eof {
+            break;
+        }
+    }
+    assert_eq!(chunks, 3);
+    assert_eq!(all, (1..=7).map(|i| format!("line {i}")).collect::<Vec<_>>());
+    // 读到末尾后游标自动关闭
+    assert!(matches!(read_cursor_chunk_inner(&registry, &token, 3), Err(FsError::NotFound)));
+}
+
+#[test]
+fn exact_multiple_reports_eof_on_last_chunk() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"), "a\nb\n").unwrap();
+    let registry = ReadCursorRegistry::new();
+    let token = open(&registry, dir.path(), "a.txt").unwrap();
+
+    let chunk = read_cursor_chunk_inner(&registry, &token, 2).unwrap();
+    assert_eq!(chunk.lines, vec!["a", "b"]);
+    assert!(chunk.eof);
+}
+
+#[test]
+fn open_rejects_outside_binary_and_too_many() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"), "a").unwrap();
+    fs::write(dir.path().join("blob.dat"), b"\0\x01\x02").unwrap();
+    let registry = ReadCursorRegistry::new();
+
+    assert!(matches!(
+        open(&registry, dir.path(), "../a.txt"),
+        Err(FsError::OutsideWorkspace) | Err(FsError::NotFound)
+    ));
+    assert!(matches!(open(&registry, dir.path(), "blob.dat"), Err(FsError::BinaryFile)));
+
+    for _ in 0..32 {
+        open(&registry, dir.path(), "a.txt").unwrap();
+    }
+    assert!(matches!(open(&registry, dir.path(), "a.txt"), Err(FsError::NotAllowed(_))));
+}
+
+#[test]
+fn overlong_line_is_truncated() {
+    let dir = tempfile::tempdir().unwrap();
+    let long = "x".repeat(20 * 1024);
+    fs::write(dir.path().join("min.js"), format!("{long}\r\nnext\n")).unwrap();
+    let registry = ReadCursorRegistry::new();
+    let token = open(&registry, dir.path(), "min.js").unwrap();
+
+    let chunk = read_cursor_chunk_inner(&registry, &token, 10).unwrap();
+    assert_eq!(chunk.lines.len(), 2);
+    let expected = format!("{}[... truncated {} bytes]", "x".repeat(16 * 1024), 4 * 1024);
+    assert_eq!(chunk.lines[0], expected);
+    assert_eq!(chunk.lines[1], "next");
+    assert!(chunk.eof);
+}
+
+#[test]
+fn idle_cursors_are_evicted_on_open() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"), "a\nb\n").unwrap();
+    let registry = ReadCursorRegistry::with_idle_ttl(Duration::ZERO);
+    let stale = open(&registry, dir.path(), "a.txt").unwrap();
+    for _ in 0..40 {
+        open(&registry, dir.path(), "a.txt").unwrap();
+    }
+    assert!(matches!(read_cursor_chunk_inner(&registry, &stale, 1), Err(FsError::NotFound)));
+}
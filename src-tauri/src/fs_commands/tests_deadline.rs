@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use super::deadline::run_with_deadline;
+use super::FsError;
+
+#[tokio::test]
+async fn slow_read_returns_timeout() {
+    // 模拟卡住的网络挂载：读取耗时远超期限
+    let result: Result<(), FsError> = run_with_deadline(Duration::from_millis(20), || {
+        std::thread::sleep(Duration::from_millis(300));
+        Ok(())
+    })
+    .await;
+    assert!(matches!(result, Err(FsError::Timeout)));
+}
+
+#[tokio::test]
+async fn fast_read_passes_result_through() {
+    let ok = run_with_deadline(Duration::from_secs(5), || Ok(42)).await;
+    assert!(matches!(ok, Ok(42)));
+    let err: Result<(), FsError> =
+        run_with_deadline(Duration::from_secs(5), || Err(FsError::NotFound)).await;
+    assert!(matches!(err, Err(FsError::NotFound)));
+}
+
+#[test]
+fn timeout_serializes_with_kind_tag() {
+    assert_eq!(serde_json::to_string(&FsError::Timeout).unwrap(), r#"{"kind":"Timeout"}"#);
+}
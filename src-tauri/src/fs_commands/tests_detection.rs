@@ -3,7 +3,7 @@ use std::path::Path;
 
 use super::detection::{
     is_binary_content, mime_from_extension, mime_from_magic, path_has_binary_extension,
-    path_has_text_extension,
+    path_has_text_extension, strip_ansi_codes,
 };
 
 // ---------------------------------------------------------------------------
@@ -185,3 +185,18 @@ fn mime_ext_unknown_returns_octet_stream() {
     assert_eq!(mime_from_extension(Path::new("a.xyz")), "application/octet-stream");
     assert_eq!(mime_from_extension(Path::new("no_ext")), "application/octet-stream");
 }
+
+// ---------------------------------------------------------------------------
+// strip_ansi_codes
+// ---------------------------------------------------------------------------
+
+#[test]
+fn strip_ansi_removes_csi_and_osc_sequences() {
+    assert_eq!(strip_ansi_codes("\x1b[31merror\x1b[0m: failed"), "error: failed");
+    assert_eq!(strip_ansi_codes("\x1b[1;32m✓\x1b[39;49m ok\x1b[2K"), "✓ ok");
+    assert_eq!(
+        strip_ansi_codes("\x1b]8;;https://x.dev\x07link\x1b]8;;\x07"),
+        "link"
+    );
+    assert_eq!(strip_ansi_codes("plain [31m text"), "plain [31m text");
+}
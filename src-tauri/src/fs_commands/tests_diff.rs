@@ -0,0 +1,97 @@
+use std::fs;
+
+use super::diff::{diff_against_content_inner, diff_files_inner, DiffAgainstContentArgs, DiffFilesArgs};
+use super::FsError;
+
+fn args(root: &std::path::Path, a: &str, b: &str) -> DiffFilesArgs {
+    DiffFilesArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path_a: a.to_string(),
+        path_b: b.to_string(),
+        context_lines: None,
+    }
+}
+
+#[test]
+fn identical_files_produce_empty_diff() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"), "same\ncontent\n").unwrap();
+    fs::write(dir.path().join("b.txt"), "same\ncontent\n").unwrap();
+    assert_eq!(diff_files_inner(&args(dir.path(), "a.txt", "b.txt")).unwrap(), "");
+}
+
+#[test]
+fn small_change_produces_unified_diff() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("old.txt"), "one\ntwo\nthree\nfour\nfive\n").unwrap();
+    fs::write(dir.path().join("new.txt"), "one\ntwo\nTHREE\nfour\nfive\n").unwrap();
+
+    let diff = diff_files_inner(&args(dir.path(), "old.txt", "new.txt")).unwrap();
+    assert!(diff.starts_with("--- a/old.txt\n+++ b/new.txt\n"), "{diff}");
+    assert!(diff.contains("\n-three\n"), "{diff}");
+    assert!(diff.contains("\n+THREE\n"), "{diff}");
+    assert!(diff.contains("\n one\n"), "{diff}");
+
+    // context_lines 控制 hunk 中的上下文行
+    let mut a = args(dir.path(), "old.txt", "new.txt");
+    a.context_lines = Some(0);
+    let diff = diff_files_inner(&a).unwrap();
+    assert!(!diff.contains(" two\n"), "{diff}");
+    assert!(diff.contains("@@ -3 +3 @@"), "{diff}");
+}
+
+#[test]
+fn missing_file_is_not_found() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"), "x\n").unwrap();
+    assert!(matches!(
+        diff_files_inner(&args(dir.path(), "a.txt", "missing.txt")),
+        Err(FsError::NotFound)
+    ));
+}
+
+#[test]
+fn binary_file_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"), "x\n").unwrap();
+    fs::write(dir.path().join("b.bin"), b"\0\x01\x02\x03").unwrap();
+    assert!(matches!(
+        diff_files_inner(&args(dir.path(), "a.txt", "b.bin")),
+        Err(FsError::BinaryFile)
+    ));
+}
+
+fn content_args(root: &std::path::Path, path: &str, new_content: &str) -> DiffAgainstContentArgs {
+    DiffAgainstContentArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path: path.to_string(),
+        new_content: new_content.to_string(),
+        context_lines: None,
+    }
+}
+
+#[test]
+fn content_diff_for_new_file_is_all_added() {
+    let dir = tempfile::tempdir().unwrap();
+    let diff = diff_against_content_inner(&content_args(dir.path(), "sub/new.txt", "a\nb\n")).unwrap();
+    assert!(diff.starts_with("--- /dev/null\n+++ b/sub/new.txt\n"), "{diff}");
+    assert!(diff.contains("\n+a\n+b\n"), "{diff}");
+    assert!(!dir.path().join("sub/new.txt").exists());
+}
+
+#[test]
+fn content_diff_unchanged_is_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"), "same\n").unwrap();
+    assert_eq!(diff_against_content_inner(&content_args(dir.path(), "a.txt", "same\n")).unwrap(), "");
+}
+
+#[test]
+fn content_diff_modified_does_not_write() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"), "keep\nold\n").unwrap();
+    let diff = diff_against_content_inner(&content_args(dir.path(), "a.txt", "keep\nnew\n")).unwrap();
+    assert!(diff.starts_with("--- a/a.txt\n+++ b/a.txt\n"), "{diff}");
+    assert!(diff.contains("\n-old\n+new\n"), "{diff}");
+    assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "keep\nold\n");
+}
@@ -0,0 +1,93 @@
+use super::find::{find_in_file, FindInFileArgs, FindMatch};
+use super::FsError;
+
+fn find(
+    dir: &std::path::Path,
+    path: &str,
+    pattern: &str,
+    case_insensitive: bool,
+    max_matches: Option<usize>,
+) -> Result<super::find::FindInFileResult, FsError> {
+    find_in_file(FindInFileArgs {
+        workspace_root: dir.to_string_lossy().into_owned(),
+        path: path.to_string(),
+        pattern: pattern.to_string(),
+        case_insensitive,
+        max_matches,
+    })
+}
+
+fn at(line: usize, column: usize, length: usize) -> FindMatch {
+    FindMatch { line, column, length }
+}
+
+#[test]
+fn find_in_file_multiple_matches_on_one_line() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "foo bar foo baz foo").unwrap();
+    let out = find(dir.path(), "a.txt", "foo", false, None).unwrap();
+    assert_eq!(out.matches, vec![at(1, 1, 3), at(1, 9, 3), at(1, 17, 3)]);
+    assert!(!out.truncated);
+}
+
+#[test]
+fn find_in_file_matches_across_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "let x = 1;\n\n  let yy = 22;\r\nfn main() {}\n").unwrap();
+    let out = find(dir.path(), "a.rs", r"let \w+", false, None).unwrap();
+    assert_eq!(out.matches, vec![at(1, 1, 5), at(3, 3, 6)]);
+}
+
+#[test]
+fn find_in_file_case_insensitive() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "Hello\nhello\nHELLO").unwrap();
+    assert_eq!(find(dir.path(), "a.txt", "hello", false, None).unwrap().matches.len(), 1);
+    let out = find(dir.path(), "a.txt", "hello", true, None).unwrap();
+    assert_eq!(out.matches, vec![at(1, 1, 5), at(2, 1, 5), at(3, 1, 5)]);
+}
+
+#[test]
+fn find_in_file_columns_count_chars() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "中文 test 中文").unwrap();
+    let out = find(dir.path(), "a.txt", "中文", false, None).unwrap();
+    assert_eq!(out.matches, vec![at(1, 1, 2), at(1, 9, 2)]);
+}
+
+#[test]
+fn find_in_file_respects_max_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "a a a\na a").unwrap();
+    let out = find(dir.path(), "a.txt", "a", false, Some(4)).unwrap();
+    assert_eq!(out.matches.len(), 4);
+    assert_eq!(out.matches[3], at(2, 1, 1));
+    assert!(out.truncated);
+
+    let out = find(dir.path(), "a.txt", "a", false, Some(5)).unwrap();
+    assert_eq!(out.matches.len(), 5);
+    assert!(!out.truncated);
+}
+
+#[test]
+fn find_in_file_skips_empty_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "abc").unwrap();
+    let out = find(dir.path(), "a.txt", "x*", false, None).unwrap();
+    assert!(out.matches.is_empty());
+}
+
+#[test]
+fn find_in_file_rejects_invalid_pattern() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "abc").unwrap();
+    assert!(matches!(find(dir.path(), "a.txt", "(", false, None), Err(FsError::NotAllowed(_))));
+    assert!(matches!(find(dir.path(), "a.txt", "", false, None), Err(FsError::NotAllowed(_))));
+}
+
+#[test]
+fn find_in_file_rejects_binary() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("data.bin"), [0u8, 1, 2, 0, 159, 146, 150]).unwrap();
+    assert!(matches!(find(dir.path(), "data.bin", "a", false, None), Err(FsError::BinaryFile)));
+}
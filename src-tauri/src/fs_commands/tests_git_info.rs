@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::Path;
+
+use super::git_info::{parse_head, parse_remote_url, workspace_root_info, WorkspaceRootInfoArgs};
+
+fn info(root: &Path) -> super::git_info::WorkspaceRootInfo {
+    workspace_root_info(WorkspaceRootInfoArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+    })
+    .unwrap()
+}
+
+fn init_repo(root: &Path, head: &str, config: &str) {
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::write(root.join(".git/HEAD"), head).unwrap();
+    fs::write(root.join(".git/config"), config).unwrap();
+}
+
+const CONFIG: &str = r#"[core]
+	repositoryformatversion = 0
+[remote "upstream"]
+	url = https://example.com/upstream.git
+[remote "origin"]
+	url = git@github.com:skyeanything/cove.git
+	fetch = +refs/heads/*:refs/remotes/origin/*
+[branch "main"]
+	remote = origin
+"#;
+
+#[test]
+fn parse_head_branch_and_detached() {
+    assert_eq!(parse_head("ref: refs/heads/main\n").as_deref(), Some("main"));
+    assert_eq!(parse_head("ref: refs/heads/feat/x\n").as_deref(), Some("feat/x"));
+    assert_eq!(parse_head("3c96ae1f0000000000000000000000000000abcd\n"), None);
+}
+
+#[test]
+fn parse_remote_prefers_origin() {
+    assert_eq!(
+        parse_remote_url(CONFIG).as_deref(),
+        Some("git@github.com:skyeanything/cove.git")
+    );
+    assert_eq!(
+        parse_remote_url("[remote \"fork\"]\n\turl = https://x/y.git\n").as_deref(),
+        Some("https://x/y.git")
+    );
+    assert_eq!(parse_remote_url("[core]\n\tbare = false\n"), None);
+}
+
+#[test]
+fn detects_branch_and_remote_in_temp_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    init_repo(dir.path(), "ref: refs/heads/feature/header\n", CONFIG);
+
+    let result = info(dir.path());
+    assert!(result.is_git_repo);
+    assert_eq!(result.branch.as_deref(), Some("feature/header"));
+    assert_eq!(
+        result.remote_url.as_deref(),
+        Some("git@github.com:skyeanything/cove.git")
+    );
+    let expected = dunce::canonicalize(dir.path()).unwrap();
+    assert_eq!(result.git_root.as_deref(), Some(expected.to_str().unwrap()));
+}
+
+#[test]
+fn workspace_in_subdirectory_finds_parent_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    init_repo(dir.path(), "ref: refs/heads/main\n", "");
+    fs::create_dir_all(dir.path().join("packages/app")).unwrap();
+
+    let result = info(&dir.path().join("packages/app"));
+    assert!(result.is_git_repo);
+    assert_eq!(result.branch.as_deref(), Some("main"));
+    assert_eq!(result.remote_url, None);
+}
+
+#[test]
+fn gitdir_file_worktree() {
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("main");
+    init_repo(&main, "ref: refs/heads/main\n", CONFIG);
+    let wt_git = main.join(".git/worktrees/wt");
+    fs::create_dir_all(&wt_git).unwrap();
+    fs::write(wt_git.join("HEAD"), "ref: refs/heads/topic\n").unwrap();
+    fs::write(wt_git.join("commondir"), "../..\n").unwrap();
+    let wt = dir.path().join("wt");
+    fs::create_dir_all(&wt).unwrap();
+    fs::write(wt.join(".git"), format!("gitdir: {}\n", wt_git.display())).unwrap();
+
+    let result = info(&wt);
+    assert!(result.is_git_repo);
+    assert_eq!(result.branch.as_deref(), Some("topic"));
+    assert_eq!(
+        result.remote_url.as_deref(),
+        Some("git@github.com:skyeanything/cove.git")
+    );
+}
+
+#[test]
+fn non_repo_returns_false() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = info(dir.path());
+    assert!(!result.is_git_repo);
+    assert!(result.git_root.is_none());
+    assert!(result.branch.is_none());
+}
+
+#[test]
+fn missing_workspace_is_not_found() {
+    let result = workspace_root_info(WorkspaceRootInfoArgs {
+        workspace_root: "/nonexistent/workspace/root".into(),
+    });
+    assert!(matches!(result, Err(super::FsError::NotFound)));
+}
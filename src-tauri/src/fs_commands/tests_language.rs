@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use super::language::{
+    detect_language, language_from_extension, language_from_shebang, DetectLanguageArgs,
+};
+use super::FsError;
+
+fn detect(root: &Path, path: &str) -> Result<String, FsError> {
+    detect_language(DetectLanguageArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path: path.to_string(),
+    })
+}
+
+#[test]
+fn extension_maps_to_language_id() {
+    assert_eq!(
+        language_from_extension(Path::new("src/main.rs")),
+        Some("rust")
+    );
+    assert_eq!(
+        language_from_extension(Path::new("app.ts")),
+        Some("typescript")
+    );
+    assert_eq!(language_from_extension(Path::new("App.TSX")), Some("tsx"));
+    assert_eq!(
+        language_from_extension(Path::new("README.md")),
+        Some("markdown")
+    );
+    assert_eq!(
+        language_from_extension(Path::new("config.yml")),
+        Some("yaml")
+    );
+    assert_eq!(language_from_extension(Path::new("data.unknownext")), None);
+}
+
+#[test]
+fn shebang_variants() {
+    assert_eq!(
+        language_from_shebang("#!/usr/bin/env python\nprint(1)"),
+        Some("python")
+    );
+    assert_eq!(
+        language_from_shebang("#!/usr/bin/env python3\n"),
+        Some("python")
+    );
+    assert_eq!(language_from_shebang("#!/bin/bash\n"), Some("shell"));
+    assert_eq!(
+        language_from_shebang("#!/usr/bin/env -S node --no-warnings\n"),
+        Some("javascript")
+    );
+    assert_eq!(language_from_shebang("no shebang here"), None);
+}
+
+#[test]
+fn detect_language_by_extension() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("lib.rs"), "fn main() {}").unwrap();
+    std::fs::write(dir.path().join("notes.md"), "# hi").unwrap();
+    assert_eq!(detect(dir.path(), "lib.rs").unwrap(), "rust");
+    assert_eq!(detect(dir.path(), "notes.md").unwrap(), "markdown");
+}
+
+#[test]
+fn detect_language_extensionless_shebang() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("run"),
+        "#!/usr/bin/env python\nprint('hi')\n",
+    )
+    .unwrap();
+    assert_eq!(detect(dir.path(), "run").unwrap(), "python");
+}
+
+#[test]
+fn detect_language_special_file_name() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Dockerfile"), "FROM alpine").unwrap();
+    assert_eq!(detect(dir.path(), "Dockerfile").unwrap(), "dockerfile");
+}
+
+#[test]
+fn detect_language_unknown_is_plaintext() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("LICENSE"), "MIT License").unwrap();
+    assert_eq!(detect(dir.path(), "LICENSE").unwrap(), "plaintext");
+}
+
+#[test]
+fn detect_language_rejects_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sub")).unwrap();
+    assert!(matches!(
+        detect(dir.path(), "sub"),
+        Err(FsError::IsDirectory)
+    ));
+}
@@ -1,4 +1,4 @@
-use super::list::{list_dir, stat_file, ListDirArgs, StatFileArgs};
+use super::list::{list_dir_inner, stat_file_inner, ListDirArgs, StatFileArgs};
 use super::FsError;
 
 // ---------------------------------------------------------------------------
@@ -12,10 +12,13 @@ fn list_dir_basic() {
     std::fs::write(dir.path().join("a.txt"), "a").unwrap();
     std::fs::write(dir.path().join("b.txt"), "b").unwrap();
 
-    let entries = list_dir(ListDirArgs {
+    let entries = list_dir_inner(ListDirArgs {
         workspace_root: root.to_string(),
         path: "".to_string(),
         include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
     })
     .unwrap();
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -30,10 +33,13 @@ fn list_dir_sorts_dirs_first() {
     std::fs::write(dir.path().join("file.txt"), "f").unwrap();
     std::fs::create_dir(dir.path().join("subdir")).unwrap();
 
-    let entries = list_dir(ListDirArgs {
+    let entries = list_dir_inner(ListDirArgs {
         workspace_root: root.to_string(),
         path: "".to_string(),
         include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
     })
     .unwrap();
     assert!(entries[0].is_dir, "first entry should be a directory");
@@ -48,10 +54,13 @@ fn list_dir_sorts_alphabetically() {
     std::fs::write(dir.path().join("a.txt"), "a").unwrap();
     std::fs::write(dir.path().join("b.txt"), "b").unwrap();
 
-    let entries = list_dir(ListDirArgs {
+    let entries = list_dir_inner(ListDirArgs {
         workspace_root: root.to_string(),
         path: "".to_string(),
         include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
     })
     .unwrap();
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -64,10 +73,13 @@ fn list_dir_empty_path_lists_root() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("root.txt"), "r").unwrap();
 
-    let entries = list_dir(ListDirArgs {
+    let entries = list_dir_inner(ListDirArgs {
         workspace_root: root.to_string(),
         path: "".to_string(),
         include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
     })
     .unwrap();
     assert_eq!(entries.len(), 1);
@@ -81,10 +93,13 @@ fn list_dir_filters_hidden() {
     std::fs::write(dir.path().join(".hidden"), "h").unwrap();
     std::fs::write(dir.path().join("visible.txt"), "v").unwrap();
 
-    let entries = list_dir(ListDirArgs {
+    let entries = list_dir_inner(ListDirArgs {
         workspace_root: root.to_string(),
         path: "".to_string(),
         include_hidden: Some(false),
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
     })
     .unwrap();
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -98,10 +113,13 @@ fn list_dir_includes_hidden_by_default() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join(".hidden"), "h").unwrap();
 
-    let entries = list_dir(ListDirArgs {
+    let entries = list_dir_inner(ListDirArgs {
         workspace_root: root.to_string(),
         path: "".to_string(),
         include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
     })
     .unwrap();
     let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
@@ -114,10 +132,13 @@ fn list_dir_empty_directory() {
     let root = dir.path().to_str().unwrap();
     std::fs::create_dir(dir.path().join("empty")).unwrap();
 
-    let entries = list_dir(ListDirArgs {
+    let entries = list_dir_inner(ListDirArgs {
         workspace_root: root.to_string(),
         path: "empty".to_string(),
         include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
     })
     .unwrap();
     assert!(entries.is_empty());
@@ -129,10 +150,13 @@ fn list_dir_rejects_file_path() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("file.txt"), "f").unwrap();
 
-    let result = list_dir(ListDirArgs {
+    let result = list_dir_inner(ListDirArgs {
         workspace_root: root.to_string(),
         path: "file.txt".to_string(),
         include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
     });
     assert!(matches!(result, Err(FsError::NotAllowed(_))));
 }
@@ -142,10 +166,13 @@ fn list_dir_outside_workspace() {
     let dir = tempfile::tempdir().unwrap();
     let root = dir.path().to_str().unwrap();
 
-    let result = list_dir(ListDirArgs {
+    let result = list_dir_inner(ListDirArgs {
         workspace_root: root.to_string(),
         path: "../../..".to_string(),
         include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
     });
     assert!(matches!(result, Err(FsError::OutsideWorkspace)));
 }
@@ -160,7 +187,7 @@ fn stat_file_directory() {
     let root = dir.path().to_str().unwrap();
     std::fs::create_dir(dir.path().join("sub")).unwrap();
 
-    let st = stat_file(StatFileArgs {
+    let st = stat_file_inner(StatFileArgs {
         workspace_root: root.to_string(),
         path: "sub".to_string(),
     })
@@ -175,7 +202,7 @@ fn stat_file_binary_by_extension() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("img.png"), "fake png").unwrap();
 
-    let st = stat_file(StatFileArgs {
+    let st = stat_file_inner(StatFileArgs {
         workspace_root: root.to_string(),
         path: "img.png".to_string(),
     })
@@ -192,7 +219,7 @@ fn stat_file_outside_workspace() {
     let outside_file = outside.path().join("outside.txt");
     std::fs::write(&outside_file, "x").unwrap();
 
-    let result = stat_file(StatFileArgs {
+    let result = stat_file_inner(StatFileArgs {
         workspace_root: root.to_string(),
         path: outside_file.to_str().unwrap().to_string(),
     });
@@ -204,7 +231,7 @@ fn stat_file_not_found() {
     let dir = tempfile::tempdir().unwrap();
     let root = dir.path().to_str().unwrap();
 
-    let result = stat_file(StatFileArgs {
+    let result = stat_file_inner(StatFileArgs {
         workspace_root: root.to_string(),
         path: "nope.txt".to_string(),
     });
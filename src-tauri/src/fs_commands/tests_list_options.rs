@@ -0,0 +1,149 @@
+use super::list::{list_dir_inner, ListDirArgs, ListDirEntry};
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// list_dir — filter / withChildrenFlag / absolute
+// ---------------------------------------------------------------------------
+
+fn list_mixed_with_filter(filter: Option<&str>) -> Result<Vec<String>, FsError> {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+    std::fs::write(dir.path().join("b.md"), "b").unwrap();
+    std::fs::create_dir(dir.path().join("sub")).unwrap();
+    std::fs::create_dir(dir.path().join("docs")).unwrap();
+
+    let entries = list_dir_inner(ListDirArgs {
+        workspace_root: dir.path().to_str().unwrap().to_string(),
+        path: "".to_string(),
+        include_hidden: None,
+        filter: filter.map(str::to_string),
+        with_children_flag: false,
+        absolute: false,
+    })?;
+    Ok(entries.into_iter().map(|e| e.name).collect())
+}
+
+#[test]
+fn list_dir_filter_all() {
+    assert_eq!(list_mixed_with_filter(None).unwrap(), vec!["docs", "sub", "a.txt", "b.md"]);
+    assert_eq!(list_mixed_with_filter(Some("all")).unwrap(), vec!["docs", "sub", "a.txt", "b.md"]);
+}
+
+#[test]
+fn list_dir_filter_files() {
+    assert_eq!(list_mixed_with_filter(Some("files")).unwrap(), vec!["a.txt", "b.md"]);
+}
+
+#[test]
+fn list_dir_filter_dirs() {
+    assert_eq!(list_mixed_with_filter(Some("dirs")).unwrap(), vec!["docs", "sub"]);
+}
+
+#[test]
+fn list_dir_filter_rejects_unknown() {
+    assert!(matches!(list_mixed_with_filter(Some("links")), Err(FsError::NotAllowed(_))));
+}
+
+#[test]
+fn list_dir_children_flag() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("empty")).unwrap();
+    std::fs::create_dir(dir.path().join("full")).unwrap();
+    std::fs::write(dir.path().join("full/x.txt"), "x").unwrap();
+    std::fs::create_dir(dir.path().join("dotonly")).unwrap();
+    std::fs::write(dir.path().join("dotonly/.keep"), "").unwrap();
+    std::fs::write(dir.path().join("file.txt"), "f").unwrap();
+
+    let list = |include_hidden, with_children_flag| {
+        list_dir_inner(ListDirArgs {
+            workspace_root: dir.path().to_str().unwrap().to_string(),
+            path: "".to_string(),
+            include_hidden,
+            filter: None,
+            with_children_flag,
+        absolute: false,
+        })
+        .unwrap()
+    };
+    let flags = |entries: Vec<ListDirEntry>| -> Vec<(String, Option<bool>)> {
+        entries.into_iter().map(|e| (e.name, e.has_children)).collect()
+    };
+
+    assert_eq!(
+        flags(list(None, true)),
+        vec![
+            ("dotonly".to_string(), Some(true)),
+            ("empty".to_string(), Some(false)),
+            ("full".to_string(), Some(true)),
+            ("file.txt".to_string(), None),
+        ]
+    );
+    // 隐藏文件被排除时，只含点文件的目录视为空
+    assert_eq!(flags(list(Some(false), true))[0], ("dotonly".to_string(), Some(false)));
+    // 未请求时不填充
+    assert!(list(None, false).iter().all(|e| e.has_children.is_none()));
+}
+
+#[test]
+fn list_dir_absolute_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sub")).unwrap();
+    std::fs::write(dir.path().join("sub/a.txt"), "a").unwrap();
+
+    let list = |absolute| {
+        list_dir_inner(ListDirArgs {
+            workspace_root: dir.path().to_str().unwrap().to_string(),
+            path: "sub".to_string(),
+            include_hidden: None,
+            filter: None,
+            with_children_flag: false,
+            absolute,
+        })
+        .unwrap()
+    };
+    assert_eq!(list(false)[0].path, "sub/a.txt");
+    let expected = dunce::canonicalize(dir.path().join("sub/a.txt")).unwrap();
+    assert_eq!(list(true)[0].path, expected.to_string_lossy());
+}
+
+// root 用户不受目录权限限制，无法构造该场景
+#[cfg(unix)]
+#[test]
+#[ignore = "requires non-root"]
+fn list_dir_unreadable_root_is_not_allowed() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = tempfile::tempdir().unwrap();
+    let locked = dir.path().join("locked");
+    std::fs::create_dir_all(locked.join("ws")).unwrap();
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+    let result = list_dir_inner(ListDirArgs {
+        workspace_root: locked.join("ws").to_str().unwrap().to_string(),
+        path: "".to_string(),
+        include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
+    });
+    std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+    assert!(matches!(result, Err(FsError::NotAllowed(_))), "got {result:?}");
+}
+
+#[cfg(unix)]
+#[test]
+fn list_dir_symlink_loop_root_is_io_error() {
+    // 与权限无关，root 用户下同样可复现
+    let dir = tempfile::tempdir().unwrap();
+    let ws = dir.path().join("ws");
+    std::os::unix::fs::symlink(&ws, &ws).unwrap();
+
+    let result = list_dir_inner(ListDirArgs {
+        workspace_root: ws.to_str().unwrap().to_string(),
+        path: "".to_string(),
+        include_hidden: None,
+        filter: None,
+        with_children_flag: false,
+        absolute: false,
+    });
+    assert!(matches!(result, Err(FsError::Io(_))), "got {result:?}");
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::Path;
+
+use super::metrics::{count_lines_chars, file_metrics, FileMetrics, FileMetricsArgs};
+use super::FsError;
+
+fn metrics(root: &Path, path: &str) -> Result<FileMetrics, FsError> {
+    file_metrics(FileMetricsArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path: path.to_string(),
+    })
+}
+
+#[test]
+fn counts_lines_chars_and_bytes_of_multiline_file() {
+    let dir = tempfile::tempdir().unwrap();
+    // 3 行，末行无换行；"é" 与 "中" 为多字节字符
+    fs::write(dir.path().join("notes.txt"), "héllo\n中文\nlast").unwrap();
+
+    let m = metrics(dir.path(), "notes.txt").unwrap();
+    assert_eq!(
+        m,
+        FileMetrics { bytes: 18, is_binary: false, lines: Some(3), chars: Some(13) }
+    );
+}
+
+#[test]
+fn trailing_newline_and_empty_file_line_counts() {
+    assert_eq!(count_lines_chars(&b"a\nb\n"[..]).unwrap(), (2, 4));
+    assert_eq!(count_lines_chars(&b""[..]).unwrap(), (0, 0));
+    // 跨越读块边界
+    let big = "x\n".repeat(70_000);
+    assert_eq!(count_lines_chars(big.as_bytes()).unwrap(), (70_000, 140_000));
+}
+
+#[test]
+fn binary_file_reports_no_line_count() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("blob.dat"), [0u8, 1, 2, 3, 0xFF, 0xFE, 0, 0]).unwrap();
+
+    let m = metrics(dir.path(), "blob.dat").unwrap();
+    assert!(m.is_binary);
+    assert_eq!(m.bytes, 8);
+    assert_eq!(m.lines, None);
+    assert_eq!(m.chars, None);
+}
+
+#[test]
+fn directory_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("sub")).unwrap();
+    assert!(matches!(metrics(dir.path(), "sub"), Err(FsError::IsDirectory)));
+}
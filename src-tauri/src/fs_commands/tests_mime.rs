@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::Path;
+
+use super::mime::{
+    detect_mime, inspect_file_head, DetectMimeArgs, DetectMimeResult, InspectFileHeadArgs,
+    MimeSource,
+};
+use super::FsError;
+
+const PNG_HEAD: &[u8] = &[
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+];
+
+fn detect(root: &Path, path: &str) -> Result<DetectMimeResult, FsError> {
+    detect_mime(DetectMimeArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path: path.to_string(),
+    })
+}
+
+#[test]
+fn mislabeled_txt_with_png_bytes_uses_magic() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("image.txt"), PNG_HEAD).unwrap();
+    let r = detect(dir.path(), "image.txt").unwrap();
+    assert_eq!(r.mime, "image/png");
+    assert_eq!(r.source, MimeSource::Magic);
+}
+
+#[test]
+fn falls_back_to_extension() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("icon.svg"), "<svg xmlns=\"http://www.w3.org/2000/svg\"/>").unwrap();
+    let r = detect(dir.path(), "icon.svg").unwrap();
+    assert_eq!(r.mime, "image/svg+xml");
+    assert_eq!(r.source, MimeSource::Extension);
+}
+
+#[test]
+fn zip_container_refined_by_office_extension() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut data = vec![0x50, 0x4B, 0x03, 0x04];
+    data.extend_from_slice(&[0u8; 16]);
+    fs::write(dir.path().join("report.docx"), &data).unwrap();
+    fs::write(dir.path().join("bundle.zip"), &data).unwrap();
+
+    let r = detect(dir.path(), "report.docx").unwrap();
+    assert!(r.mime.contains("wordprocessingml"));
+    assert_eq!(r.source, MimeSource::Extension);
+    let r = detect(dir.path(), "bundle.zip").unwrap();
+    assert_eq!(r.mime, "application/zip");
+    assert_eq!(r.source, MimeSource::Magic);
+}
+
+#[test]
+fn result_serializes_source_lowercase() {
+    let r = DetectMimeResult {
+        mime: "image/png".into(),
+        source: MimeSource::Magic,
+    };
+    let json = serde_json::to_string(&r).unwrap();
+    assert_eq!(json, r#"{"mime":"image/png","source":"magic"}"#);
+}
+
+#[test]
+fn rejects_outside_workspace_and_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    fs::write(outside.path().join("x.png"), PNG_HEAD).unwrap();
+    let outside_path = outside.path().join("x.png");
+    assert!(matches!(
+        detect(dir.path(), outside_path.to_str().unwrap()),
+        Err(FsError::OutsideWorkspace)
+    ));
+    fs::create_dir(dir.path().join("sub")).unwrap();
+    assert!(matches!(detect(dir.path(), "sub"), Err(FsError::IsDirectory)));
+}
+
+#[test]
+fn inspect_file_head_shows_png_signature() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("pic.dat"), PNG_HEAD).unwrap();
+    let r = inspect_file_head(InspectFileHeadArgs {
+        workspace_root: dir.path().to_string_lossy().into_owned(),
+        path: "pic.dat".into(),
+        max_bytes: None,
+    })
+    .unwrap();
+    assert!(r.hex.starts_with("89 50 4e 47 0d 0a 1a 0a"), "{}", r.hex);
+    assert_eq!(r.mime.as_deref(), Some("image/png"));
+    assert_eq!(r.bytes_read, PNG_HEAD.len());
+}
+
+#[test]
+fn inspect_file_head_caps_length() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("big.bin"), vec![0u8; 1000]).unwrap();
+    let inspect = |max_bytes| {
+        inspect_file_head(InspectFileHeadArgs {
+            workspace_root: dir.path().to_string_lossy().into_owned(),
+            path: "big.bin".into(),
+            max_bytes,
+        })
+        .unwrap()
+    };
+    let r = inspect(Some(10_000));
+    assert_eq!(r.bytes_read, 256);
+    assert_eq!(r.size, 1000);
+    assert_eq!(inspect(Some(4)).hex, "00 00 00 00");
+    assert!(inspect(Some(4)).mime.is_none());
+}
@@ -0,0 +1,45 @@
+use super::mkdir::{create_dir_all_inner, CreateDirAllArgs};
+use super::FsError;
+
+fn args(root: &std::path::Path, path: &str) -> CreateDirAllArgs {
+    CreateDirAllArgs {
+        workspace_root: root.to_str().unwrap().to_string(),
+        path: path.to_string(),
+    }
+}
+
+#[test]
+fn create_dir_all_creates_three_levels_in_one_call() {
+    let dir = tempfile::tempdir().unwrap();
+    let rel = create_dir_all_inner(&args(dir.path(), "a/b/c")).unwrap();
+    assert_eq!(rel.as_deref(), Some("a/b/c"));
+    assert!(dir.path().join("a/b/c").is_dir());
+
+    // 已存在：幂等，不再上报新建
+    assert_eq!(create_dir_all_inner(&args(dir.path(), "a/b/c")).unwrap(), None);
+}
+
+#[test]
+fn create_dir_all_rejects_escape_and_file_conflict() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = create_dir_all_inner(&args(dir.path(), "../outside/x"));
+    assert!(matches!(result, Err(FsError::OutsideWorkspace)));
+
+    std::fs::write(dir.path().join("f.txt"), "x").unwrap();
+    let result = create_dir_all_inner(&args(dir.path(), "f.txt/sub"));
+    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    let result = create_dir_all_inner(&args(dir.path(), "  "));
+    assert!(matches!(result, Err(FsError::InvalidName)));
+}
+
+#[cfg(unix)]
+#[test]
+fn create_dir_all_rejects_symlinked_parent_outside_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    std::os::unix::fs::symlink(outside.path(), dir.path().join("link")).unwrap();
+
+    let result = create_dir_all_inner(&args(dir.path(), "link/a/b"));
+    assert!(matches!(result, Err(FsError::OutsideWorkspace)));
+    assert!(!outside.path().join("a").exists());
+}
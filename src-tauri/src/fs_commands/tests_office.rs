@@ -0,0 +1,38 @@
+use std::cell::Cell;
+use std::sync::Mutex;
+
+use super::office::{cached_or_probe, refresh_cache, OfficeAppInfo};
+
+fn app(id: &str) -> OfficeAppInfo {
+    OfficeAppInfo { id: id.into(), name: id.into(), path: format!("/Applications/{id}.app") }
+}
+
+fn ids(apps: &[OfficeAppInfo]) -> Vec<&str> {
+    apps.iter().map(|a| a.id.as_str()).collect()
+}
+
+#[test]
+fn second_detect_returns_cached_value() {
+    let cache = Mutex::new(None);
+    let probes = Cell::new(0);
+    let probe = || {
+        probes.set(probes.get() + 1);
+        vec![app("LibreOffice")]
+    };
+
+    assert_eq!(ids(&cached_or_probe(&cache, probe)), ["LibreOffice"]);
+    assert_eq!(ids(&cached_or_probe(&cache, probe)), ["LibreOffice"]);
+    assert_eq!(probes.get(), 1);
+}
+
+#[test]
+fn refresh_reprobes_and_updates_cache() {
+    let cache = Mutex::new(None);
+    assert!(cached_or_probe(&cache, Vec::new).is_empty());
+
+    // 安装了新应用后刷新
+    let refreshed = refresh_cache(&cache, || vec![app("wpsoffice")]);
+    assert_eq!(ids(&refreshed), ["wpsoffice"]);
+    let cached = cached_or_probe(&cache, || panic!("cache should be used after refresh"));
+    assert_eq!(ids(&cached), ["wpsoffice"]);
+}
@@ -0,0 +1,63 @@
+use super::preflight::{validate_workspace_inner, ValidateWorkspaceArgs};
+use crate::test_util::with_home;
+
+fn args(root: &str, allow_dangerous_root: bool) -> ValidateWorkspaceArgs {
+    ValidateWorkspaceArgs {
+        workspace_root: root.to_string(),
+        allow_dangerous_root,
+    }
+}
+
+#[test]
+fn temp_dir_is_valid_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    let r = validate_workspace_inner(&args(dir.path().to_str().unwrap(), false));
+    assert!(r.valid, "{:?}", r.reason);
+    assert!(r.is_dir);
+    assert!(r.writable);
+    assert!(!r.is_system_root);
+    assert_eq!(
+        r.canonical_path.as_deref(),
+        Some(dunce::canonicalize(dir.path()).unwrap().to_str().unwrap())
+    );
+    // 写入探测不留下文件
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn filesystem_root_is_rejected() {
+    let r = validate_workspace_inner(&args("/", false));
+    assert!(!r.valid);
+    assert!(r.is_dir);
+    assert!(r.is_system_root);
+    assert!(!r.writable);
+    assert!(r.reason.is_some());
+}
+
+#[test]
+fn home_dir_requires_override() {
+    with_home(|home| {
+        let r = validate_workspace_inner(&args("~", false));
+        assert!(!r.valid);
+        assert!(r.is_system_root);
+
+        let r = validate_workspace_inner(&args(home.to_str().unwrap(), true));
+        assert!(r.valid, "{:?}", r.reason);
+        assert!(r.is_system_root);
+    });
+}
+
+#[test]
+fn missing_or_file_path_is_invalid() {
+    let dir = tempfile::tempdir().unwrap();
+    let r = validate_workspace_inner(&args(dir.path().join("nope").to_str().unwrap(), false));
+    assert!(!r.valid);
+    assert!(r.canonical_path.is_none());
+
+    let file = dir.path().join("f.txt");
+    std::fs::write(&file, "x").unwrap();
+    let r = validate_workspace_inner(&args(file.to_str().unwrap(), false));
+    assert!(!r.valid);
+    assert!(!r.is_dir);
+}
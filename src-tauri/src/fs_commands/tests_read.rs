@@ -1,6 +1,6 @@
 use super::read::{
-    read_file, read_file_as_data_url, read_file_raw, ReadFileArgs, ReadFileAsDataUrlArgs,
-    ReadFileRawArgs,
+    read_file_inner, read_file_as_data_url, read_file_for_preview, read_file_raw_inner, FilePreview,
+    ReadFileArgs, ReadFileAsDataUrlArgs, ReadFileForPreviewArgs, ReadFileRawArgs,
 };
 use super::FsError;
 
@@ -14,11 +14,16 @@ fn read_file_empty_file() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("empty.txt"), "").unwrap();
 
-    let out = read_file(ReadFileArgs {
+    let out = read_file_inner(ReadFileArgs {
         workspace_root: root.to_string(),
         path: "empty.txt".to_string(),
         offset: None,
         limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
     })
     .unwrap();
     assert_eq!(out, "");
@@ -30,13 +35,18 @@ fn read_file_rejects_directory() {
     let root = dir.path().to_str().unwrap();
     std::fs::create_dir(dir.path().join("sub")).unwrap();
 
-    let result = read_file(ReadFileArgs {
+    let result = read_file_inner(ReadFileArgs {
         workspace_root: root.to_string(),
         path: "sub".to_string(),
         offset: None,
         limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
     });
-    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    assert!(matches!(result, Err(FsError::IsDirectory)));
 }
 
 #[test]
@@ -46,11 +56,16 @@ fn read_file_too_large() {
     let big = vec![b'x'; 251 * 1024];
     std::fs::write(dir.path().join("big.txt"), &big).unwrap();
 
-    let result = read_file(ReadFileArgs {
+    let result = read_file_inner(ReadFileArgs {
         workspace_root: root.to_string(),
         path: "big.txt".to_string(),
         offset: None,
         limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
     });
     assert!(matches!(result, Err(FsError::TooLarge)));
 }
@@ -62,27 +77,65 @@ fn read_file_truncates_long_lines() {
     let long_line = "A".repeat(2500);
     std::fs::write(dir.path().join("long.txt"), &long_line).unwrap();
 
-    let out = read_file(ReadFileArgs {
+    let out = read_file_inner(ReadFileArgs {
         workspace_root: root.to_string(),
         path: "long.txt".to_string(),
         offset: None,
         limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
     })
     .unwrap();
     assert!(out.contains("[... truncated 500 chars]"));
 }
 
+#[test]
+fn read_file_truncates_huge_single_line_quickly() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().to_str().unwrap();
+    // 接近 READ_MAX_BYTES 的单行压缩文件，含多字节字符以验证按字符截断
+    let line = "é;".repeat(80 * 1024);
+    std::fs::write(dir.path().join("bundle.min.js"), &line).unwrap();
+
+    let started = std::time::Instant::now();
+    let out = read_file_inner(ReadFileArgs {
+        workspace_root: root.to_string(),
+        path: "bundle.min.js".to_string(),
+        offset: None,
+        limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
+    })
+    .unwrap();
+    assert!(started.elapsed() < std::time::Duration::from_secs(1));
+
+    let expected_head: String = line.chars().take(2000).collect();
+    let dropped = line.chars().count() - 2000;
+    assert_eq!(out, format!("00001| {}[... truncated {} chars]\n", expected_head, dropped));
+}
+
 #[test]
 fn read_file_text_ext_skips_binary_check() {
     let dir = tempfile::tempdir().unwrap();
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
 
-    let result = read_file(ReadFileArgs {
+    let result = read_file_inner(ReadFileArgs {
         workspace_root: root.to_string(),
         path: "main.rs".to_string(),
         offset: None,
         limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
     });
     assert!(result.is_ok());
 }
@@ -97,9 +150,12 @@ fn read_file_raw_returns_content_without_line_numbers() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("raw.txt"), "hello\nworld").unwrap();
 
-    let out = read_file_raw(ReadFileRawArgs {
+    let out = read_file_raw_inner(ReadFileRawArgs {
         workspace_root: root.to_string(),
         path: "raw.txt".to_string(),
+        strip_ansi: false,
+        include_hash: false,
+        use_cache: false,
     })
     .unwrap();
     assert_eq!(out, "hello\nworld");
@@ -111,9 +167,12 @@ fn read_file_raw_rejects_binary() {
     let root = dir.path().to_str().unwrap();
     std::fs::write(dir.path().join("bin.exe"), "fake").unwrap();
 
-    let result = read_file_raw(ReadFileRawArgs {
+    let result = read_file_raw_inner(ReadFileRawArgs {
         workspace_root: root.to_string(),
         path: "bin.exe".to_string(),
+        strip_ansi: false,
+        include_hash: false,
+        use_cache: false,
     });
     assert!(matches!(result, Err(FsError::BinaryFile)));
 }
@@ -125,9 +184,12 @@ fn read_file_raw_rejects_too_large() {
     let big = vec![b'x'; 251 * 1024];
     std::fs::write(dir.path().join("big.txt"), &big).unwrap();
 
-    let result = read_file_raw(ReadFileRawArgs {
+    let result = read_file_raw_inner(ReadFileRawArgs {
         workspace_root: root.to_string(),
         path: "big.txt".to_string(),
+        strip_ansi: false,
+        include_hash: false,
+        use_cache: false,
     });
     assert!(matches!(result, Err(FsError::TooLarge)));
 }
@@ -176,5 +238,50 @@ fn read_file_as_data_url_rejects_directory() {
         workspace_root: root.to_string(),
         path: "sub".to_string(),
     });
-    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    assert!(matches!(result, Err(FsError::IsDirectory)));
+}
+
+// ---------------------------------------------------------------------------
+// read_file_for_preview
+// ---------------------------------------------------------------------------
+
+fn preview(dir: &std::path::Path, path: &str) -> Result<FilePreview, FsError> {
+    read_file_for_preview(ReadFileForPreviewArgs {
+        workspace_root: dir.to_string_lossy().into_owned(),
+        path: path.to_string(),
+    })
+}
+
+#[test]
+fn read_file_for_preview_text_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("notes.md"), "# Title\nbody").unwrap();
+    let out = preview(dir.path(), "notes.md").unwrap();
+    assert_eq!(out, FilePreview::Text { content: "# Title\nbody".into() });
+    let json = serde_json::to_value(&out).unwrap();
+    assert_eq!(json["kind"], "text");
+    assert_eq!(json["content"], "# Title\nbody");
+}
+
+#[test]
+fn read_file_for_preview_image_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00];
+    std::fs::write(dir.path().join("pic.png"), png).unwrap();
+    let out = preview(dir.path(), "pic.png").unwrap();
+    let FilePreview::DataUrl { data_url, mime } = &out else {
+        panic!("expected data URL, got {out:?}");
+    };
+    assert_eq!(mime, "image/png");
+    assert!(data_url.starts_with("data:image/png;base64,"));
+    let json = serde_json::to_value(&out).unwrap();
+    assert_eq!(json["kind"], "dataUrl");
+    assert!(json["dataUrl"].as_str().unwrap().starts_with("data:image/png"));
+}
+
+#[test]
+fn read_file_for_preview_large_text_is_too_large() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("big.txt"), "a".repeat(300 * 1024)).unwrap();
+    assert!(matches!(preview(dir.path(), "big.txt"), Err(FsError::TooLarge)));
 }
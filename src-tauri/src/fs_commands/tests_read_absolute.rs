@@ -28,7 +28,7 @@ fn rejects_directory() {
     let result = read_absolute_file(ReadAbsoluteFileArgs {
         path: dir.path().to_str().unwrap().to_string(),
     });
-    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    assert!(matches!(result, Err(FsError::IsDirectory)));
 }
 
 #[test]
@@ -0,0 +1,44 @@
+use super::read::{read_file_inner, read_file_raw_inner, ReadFileArgs, ReadFileRawArgs};
+
+const LOG: &str = "\x1b[32mINFO\x1b[0m started\n\x1b[31mERROR\x1b[0m boom\n";
+
+fn read(root: &std::path::Path, strip_ansi: bool) -> String {
+    read_file_inner(ReadFileArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path: "build.log".to_string(),
+        offset: None,
+        limit: None,
+        strip_ansi,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
+    })
+    .unwrap()
+}
+
+#[test]
+fn read_file_strips_color_codes_when_flag_set() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("build.log"), LOG).unwrap();
+
+    assert_eq!(read(dir.path(), true), "00001| INFO started\n00002| ERROR boom\n");
+    // 默认保留原始内容
+    assert!(read(dir.path(), false).contains("\x1b[31mERROR"));
+}
+
+#[test]
+fn read_file_raw_strips_color_codes_when_flag_set() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("build.log"), LOG).unwrap();
+
+    let out = read_file_raw_inner(ReadFileRawArgs {
+        workspace_root: dir.path().to_string_lossy().into_owned(),
+        path: "build.log".to_string(),
+        strip_ansi: true,
+        include_hash: false,
+        use_cache: false,
+    })
+    .unwrap();
+    assert_eq!(out, "INFO started\nERROR boom\n");
+}
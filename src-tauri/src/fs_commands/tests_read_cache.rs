@@ -0,0 +1,96 @@
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+use super::read::{read_file_raw_inner, ReadFileRawArgs};
+use super::read_cache::ReadCache;
+use super::write::{write_file, WriteFileArgs};
+
+#[test]
+fn hit_when_mtime_and_size_unchanged() {
+    let dir = tempfile::tempdir().unwrap();
+    let p = dir.path().join("package.json");
+    fs::write(&p, "{\"name\":\"a\"}").unwrap();
+    let meta = fs::metadata(&p).unwrap();
+
+    let mut cache = ReadCache::with_limits(8, 1024);
+    assert!(cache.get(&p, &meta).is_none());
+    cache.insert(&p, &meta, "{\"name\":\"a\"}");
+    assert_eq!(cache.get(&p, &fs::metadata(&p).unwrap()).as_deref(), Some("{\"name\":\"a\"}"));
+}
+
+#[test]
+fn miss_after_modification() {
+    let dir = tempfile::tempdir().unwrap();
+    let p = dir.path().join("config.toml");
+    fs::write(&p, "a = 1").unwrap();
+    let old = SystemTime::now() - Duration::from_secs(3600);
+    fs::File::options().write(true).open(&p).unwrap().set_modified(old).unwrap();
+
+    let mut cache = ReadCache::with_limits(8, 1024);
+    cache.insert(&p, &fs::metadata(&p).unwrap(), "a = 1");
+
+    // 同样大小的内容，仅 mtime 变化也应失效
+    fs::write(&p, "a = 2").unwrap();
+    assert!(cache.get(&p, &fs::metadata(&p).unwrap()).is_none());
+}
+
+#[test]
+fn evicts_least_recently_used_over_limits() {
+    let dir = tempfile::tempdir().unwrap();
+    let paths: Vec<_> = (0..3).map(|i| dir.path().join(format!("f{i}"))).collect();
+    for p in &paths {
+        fs::write(p, "12345").unwrap();
+    }
+    let meta = |i: usize| fs::metadata(&paths[i]).unwrap();
+
+    // 条目数上限 2：插入第三个时淘汰最久未用的 f1（f0 刚被访问过）
+    let mut cache = ReadCache::with_limits(2, 1024);
+    cache.insert(&paths[0], &meta(0), "12345");
+    cache.insert(&paths[1], &meta(1), "12345");
+    assert!(cache.get(&paths[0], &meta(0)).is_some());
+    cache.insert(&paths[2], &meta(2), "12345");
+    assert!(cache.get(&paths[1], &meta(1)).is_none());
+    assert!(cache.get(&paths[0], &meta(0)).is_some());
+    assert!(cache.get(&paths[2], &meta(2)).is_some());
+
+    // 总字节上限 8：两个 5 字节内容放不下
+    let mut cache = ReadCache::with_limits(8, 8);
+    cache.insert(&paths[0], &meta(0), "12345");
+    cache.insert(&paths[1], &meta(1), "12345");
+    assert!(cache.get(&paths[0], &meta(0)).is_none());
+    assert!(cache.get(&paths[1], &meta(1)).is_some());
+
+    cache.clear();
+    assert!(cache.get(&paths[1], &meta(1)).is_none());
+}
+
+#[test]
+fn in_app_write_evicts_cached_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().to_string_lossy().into_owned();
+    let p = dir.path().join("settings.json");
+    fs::write(&p, "{\"v\":1}").unwrap();
+    let old = SystemTime::now() - Duration::from_secs(3600);
+    fs::File::options().write(true).open(&p).unwrap().set_modified(old).unwrap();
+    let read = || {
+        read_file_raw_inner(ReadFileRawArgs {
+            workspace_root: root.clone(),
+            path: "settings.json".into(),
+            strip_ansi: false,
+            include_hash: false,
+            use_cache: true,
+        })
+        .unwrap()
+    };
+    assert_eq!(read(), "{\"v\":1}");
+
+    write_file(WriteFileArgs {
+        workspace_root: root.clone(),
+        path: "settings.json".into(),
+        content: "{\"v\":2}".into(),
+    })
+    .unwrap();
+    // 模拟 mtime 精度不足：写入后 mtime 与大小都未变，仍须读到新内容
+    fs::File::options().write(true).open(&p).unwrap().set_modified(old).unwrap();
+    assert_eq!(read(), "{\"v\":2}");
+}
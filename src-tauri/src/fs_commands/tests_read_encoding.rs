@@ -0,0 +1,69 @@
+use super::read::{read_file_inner, read_file_raw_inner, ReadFileArgs, ReadFileRawArgs};
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// read_file — BOM / UTF-16
+// ---------------------------------------------------------------------------
+
+fn utf16_bytes(text: &str, le: bool) -> Vec<u8> {
+    let mut out = if le { vec![0xFF, 0xFE] } else { vec![0xFE, 0xFF] };
+    for u in text.encode_utf16() {
+        out.extend_from_slice(&if le { u.to_le_bytes() } else { u.to_be_bytes() });
+    }
+    out
+}
+
+fn read_raw(root: &std::path::Path, path: &str) -> Result<String, FsError> {
+    read_file_raw_inner(ReadFileRawArgs {
+        workspace_root: root.to_str().unwrap().to_string(),
+        path: path.to_string(),
+        strip_ansi: false,
+        include_hash: false,
+        use_cache: false,
+    })
+}
+
+#[test]
+fn read_file_decodes_utf16le_with_bom() {
+    let dir = tempfile::tempdir().unwrap();
+    // PowerShell 输出，无已知扩展名，走内容检测
+    std::fs::write(dir.path().join("out.ps-log"), utf16_bytes("héllo\r\n世界", true)).unwrap();
+
+    let out = read_file_inner(ReadFileArgs {
+        workspace_root: dir.path().to_str().unwrap().to_string(),
+        path: "out.ps-log".to_string(),
+        offset: None,
+        limit: None,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
+    })
+    .unwrap();
+    assert_eq!(out, "00001| héllo\n00002| 世界\n");
+}
+
+#[test]
+fn read_file_raw_decodes_utf16be_with_bom() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("be.txt"), utf16_bytes("big endian\n", false)).unwrap();
+    assert_eq!(read_raw(dir.path(), "be.txt").unwrap(), "big endian\n");
+}
+
+#[test]
+fn read_file_raw_strips_utf8_bom() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut bytes = vec![0xEF, 0xBB, 0xBF];
+    bytes.extend_from_slice("a,b\n1,2".as_bytes());
+    std::fs::write(dir.path().join("data.csv"), bytes).unwrap();
+    assert_eq!(read_raw(dir.path(), "data.csv").unwrap(), "a,b\n1,2");
+}
+
+#[test]
+fn read_file_utf16_without_bom_still_binary() {
+    let dir = tempfile::tempdir().unwrap();
+    let bytes: Vec<u8> = "plain".encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+    std::fs::write(dir.path().join("nobom.dat"), [bytes.as_slice(), &[0xFF, 0xFF]].concat()).unwrap();
+    assert!(matches!(read_raw(dir.path(), "nobom.dat"), Err(FsError::BinaryFile)));
+}
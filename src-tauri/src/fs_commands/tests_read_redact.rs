@@ -0,0 +1,53 @@
+use super::read::{read_file_inner, ReadFileArgs};
+
+const ENV: &str = "# comment\n\
+APP_NAME=cove\n\
+OPENAI_API_KEY=sk-abc123\n\
+export GITHUB_TOKEN = ghp_xyz\n\
+db_password=hunter2\n\
+CLIENT_SECRET=\"quoted value\"\n\
+EMPTY_TOKEN=\n\
+KEYBOARD=us\n";
+
+fn read(root: &std::path::Path, redact_secrets: bool) -> String {
+    read_file_inner(ReadFileArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path: ".env".to_string(),
+        offset: None,
+        limit: None,
+        strip_ansi: false,
+        redact_secrets,
+        structured: false,
+        include_hash: false,
+        use_cache: false,
+    })
+    .unwrap()
+}
+
+#[test]
+fn read_file_masks_secret_values_when_flag_set() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join(".env"), ENV).unwrap();
+
+    assert_eq!(
+        read(dir.path(), true),
+        "00001| # comment\n\
+         00002| APP_NAME=cove\n\
+         00003| OPENAI_API_KEY=***\n\
+         00004| export GITHUB_TOKEN = ***\n\
+         00005| db_password=***\n\
+         00006| CLIENT_SECRET=***\n\
+         00007| EMPTY_TOKEN=\n\
+         00008| KEYBOARD=us\n"
+    );
+}
+
+#[test]
+fn read_file_keeps_secrets_by_default() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join(".env"), ENV).unwrap();
+
+    let out = read(dir.path(), false);
+    assert!(out.contains("OPENAI_API_KEY=sk-abc123"));
+    assert!(out.contains("db_password=hunter2"));
+}
@@ -0,0 +1,60 @@
+use super::read_smart::{read_file_smart, ReadFileSmartArgs, SmartReadResult};
+
+fn smart(dir: &std::path::Path, path: &str, text_max_bytes: Option<u64>) -> SmartReadResult {
+    read_file_smart(ReadFileSmartArgs {
+        workspace_root: dir.to_string_lossy().into_owned(),
+        path: path.to_string(),
+        text_max_bytes,
+    })
+    .unwrap()
+}
+
+#[test]
+fn small_text_takes_fast_path() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("notes.md"), "# Title\nbody").unwrap();
+    let out = smart(dir.path(), "notes.md", None);
+    assert_eq!(
+        out,
+        SmartReadResult::Text { content: "# Title\nbody".into(), encoding: "utf-8".into() }
+    );
+    let json = serde_json::to_value(&out).unwrap();
+    assert_eq!(json["kind"], "text");
+    assert_eq!(json["encoding"], "utf-8");
+}
+
+#[test]
+fn utf16_bom_text_reports_encoding() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend("hé".encode_utf16().flat_map(|u| u.to_le_bytes()));
+    std::fs::write(dir.path().join("win.txt"), bytes).unwrap();
+    let out = smart(dir.path(), "win.txt", None);
+    assert_eq!(
+        out,
+        SmartReadResult::Text { content: "hé".into(), encoding: "utf-16le".into() }
+    );
+}
+
+#[test]
+fn binary_file_is_base64_encoded() {
+    let dir = tempfile::tempdir().unwrap();
+    let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00];
+    std::fs::write(dir.path().join("pic.png"), png).unwrap();
+    let SmartReadResult::DataUrl { data_url, mime } = smart(dir.path(), "pic.png", None) else {
+        panic!("binary file should be returned as data URL");
+    };
+    assert_eq!(mime, "image/png");
+    assert!(data_url.starts_with("data:image/png;base64,"));
+}
+
+#[test]
+fn text_over_threshold_is_base64_encoded() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("log.txt"), "a".repeat(2048)).unwrap();
+    assert!(matches!(
+        smart(dir.path(), "log.txt", Some(1024)),
+        SmartReadResult::DataUrl { .. }
+    ));
+    assert!(matches!(smart(dir.path(), "log.txt", None), SmartReadResult::Text { .. }));
+}
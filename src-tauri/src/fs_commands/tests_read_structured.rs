@@ -0,0 +1,52 @@
+use super::read::{read_file_lines_inner, ReadFileArgs, ReadFileLine, ReadFileOutput};
+
+fn read_lines(root: &std::path::Path, offset: Option<u64>, limit: Option<u64>) -> Vec<ReadFileLine> {
+    read_file_lines_inner(ReadFileArgs {
+        workspace_root: root.to_str().unwrap().to_string(),
+        path: "lines.txt".to_string(),
+        offset,
+        limit,
+        strip_ansi: false,
+        redact_secrets: false,
+        structured: true,
+        include_hash: false,
+        use_cache: false,
+    })
+    .unwrap()
+}
+
+#[test]
+fn structured_lines_keep_numbers_after_offset() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("lines.txt"), "one\ntwo\nthree\nfour\n").unwrap();
+
+    let lines = read_lines(dir.path(), Some(1), Some(2));
+    assert_eq!(
+        lines,
+        vec![
+            ReadFileLine { line_number: 2, text: "two".into() },
+            ReadFileLine { line_number: 3, text: "three".into() },
+        ]
+    );
+}
+
+#[test]
+fn structured_lines_truncate_long_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("lines.txt"), "A".repeat(2500)).unwrap();
+
+    let lines = read_lines(dir.path(), None, None);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].text.ends_with("[... truncated 500 chars]"));
+}
+
+#[test]
+fn output_serializes_as_string_or_line_array() {
+    let text = serde_json::to_value(ReadFileOutput::Text("00001| a\n".into())).unwrap();
+    assert_eq!(text, serde_json::json!("00001| a\n"));
+    let lines = ReadFileOutput::Lines(vec![ReadFileLine { line_number: 1, text: "a".into() }]);
+    assert_eq!(
+        serde_json::to_value(lines).unwrap(),
+        serde_json::json!([{ "lineNumber": 1, "text": "a" }])
+    );
+}
@@ -0,0 +1,122 @@
+use std::fs;
+
+use super::replace::{replace_in_files_inner, ReplaceInFilesArgs};
+use super::FsError;
+
+fn args(root: &std::path::Path, search: &str, replace: &str) -> ReplaceInFilesArgs {
+    ReplaceInFilesArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        glob: Some("**/*.txt".to_string()),
+        paths: None,
+        search: search.to_string(),
+        replace: replace.to_string(),
+        regex: false,
+        dry_run: true,
+    }
+}
+
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("sub")).unwrap();
+    fs::write(dir.path().join("a.txt"), "foo bar foo").unwrap();
+    fs::write(dir.path().join("sub/b.txt"), "foo\nbaz\n").unwrap();
+    fs::write(dir.path().join("c.md"), "foo").unwrap();
+    fs::write(dir.path().join("none.txt"), "nothing here").unwrap();
+    dir
+}
+
+#[test]
+fn dry_run_reports_counts_without_writing() {
+    let dir = setup();
+    let r = replace_in_files_inner(&args(dir.path(), "foo", "qux"), |_| {}).unwrap();
+
+    let mut counts: Vec<_> = r.files.iter().map(|f| (f.path.as_str(), f.matches)).collect();
+    counts.sort();
+    assert_eq!(counts, vec![("a.txt", 2), ("sub/b.txt", 1)]);
+    assert_eq!(r.total_matches, 3);
+    assert!(!r.truncated);
+    assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "foo bar foo");
+}
+
+#[test]
+fn replaces_across_multiple_files() {
+    let dir = setup();
+    let mut a = args(dir.path(), "foo", "$0-qux");
+    a.dry_run = false;
+    let r = replace_in_files_inner(&a, |_| {}).unwrap();
+    assert_eq!(r.total_matches, 3);
+
+    // 字面量模式下 replace 不展开 `$0`
+    assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "$0-qux bar $0-qux");
+    assert_eq!(fs::read_to_string(dir.path().join("sub/b.txt")).unwrap(), "$0-qux\nbaz\n");
+    // glob 之外的文件不受影响
+    assert_eq!(fs::read_to_string(dir.path().join("c.md")).unwrap(), "foo");
+    let leftovers: Vec<_> = fs::read_dir(dir.path())
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".cove-tmp"))
+        .collect();
+    assert!(leftovers.is_empty());
+}
+
+#[test]
+fn regex_with_explicit_paths_skips_binary() {
+    let dir = setup();
+    fs::write(dir.path().join("bin.txt"), b"foo\0\x01\x02").unwrap();
+    let mut a = args(dir.path(), r"f(o+)", "b$1");
+    a.glob = None;
+    a.paths = Some(vec!["c.md".into(), "bin.txt".into()]);
+    a.regex = true;
+    a.dry_run = false;
+    let r = replace_in_files_inner(&a, |_| {}).unwrap();
+
+    assert_eq!(r.files.len(), 1);
+    assert_eq!(fs::read_to_string(dir.path().join("c.md")).unwrap(), "boo");
+    assert_eq!(fs::read(dir.path().join("bin.txt")).unwrap(), b"foo\0\x01\x02");
+}
+
+#[test]
+fn rejects_invalid_input() {
+    let dir = setup();
+    assert!(matches!(
+        replace_in_files_inner(&args(dir.path(), "", "x"), |_| {}),
+        Err(FsError::NotAllowed(_))
+    ));
+    let mut a = args(dir.path(), "foo", "x");
+    a.glob = Some("../*.txt".into());
+    assert!(matches!(replace_in_files_inner(&a, |_| {}), Err(FsError::OutsideWorkspace)));
+    a.glob = None;
+    assert!(matches!(replace_in_files_inner(&a, |_| {}), Err(FsError::NotAllowed(_))));
+}
+
+#[test]
+fn explicit_paths_are_deduplicated() {
+    let dir = setup();
+    let mut a = args(dir.path(), "foo", "foofoo");
+    a.glob = None;
+    a.paths = Some(vec!["a.txt".into(), "./a.txt".into(), "sub/../a.txt".into()]);
+    a.dry_run = false;
+    let r = replace_in_files_inner(&a, |_| {}).unwrap();
+
+    assert_eq!(r.files.len(), 1);
+    assert_eq!(r.total_matches, 2);
+    assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "foofoo bar foofoo");
+}
+
+#[test]
+fn reports_files_written_before_failure() {
+    let dir = setup();
+    // 占住 sub/b.txt 的临时文件名，使第二个文件写入失败
+    let tmp = format!("sub/.b.txt.{}.cove-tmp", std::process::id());
+    fs::create_dir(dir.path().join(tmp)).unwrap();
+    let mut a = args(dir.path(), "foo", "qux");
+    a.glob = None;
+    a.paths = Some(vec!["a.txt".into(), "sub/b.txt".into()]);
+    a.dry_run = false;
+
+    let mut written = Vec::new();
+    let r = replace_in_files_inner(&a, |p| written.push(p.to_string()));
+    assert!(r.is_err());
+    assert_eq!(written, vec!["a.txt".to_string()]);
+    assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "qux bar qux");
+}
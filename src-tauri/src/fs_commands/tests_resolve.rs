@@ -0,0 +1,56 @@
+use super::resolve::{resolve_workspace_path, ResolveWorkspacePathArgs};
+use super::FsError;
+
+fn resolve(root: &str, path: &str) -> Result<String, FsError> {
+    resolve_workspace_path(ResolveWorkspacePathArgs {
+        workspace_root: root.to_string(),
+        path: path.to_string(),
+    })
+}
+
+#[test]
+fn resolve_args_deserialize_camel_case() {
+    let json = r#"{"workspaceRoot":"/ws","path":"a/b.txt"}"#;
+    let args: ResolveWorkspacePathArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.workspace_root, "/ws");
+    assert_eq!(args.path, "a/b.txt");
+}
+
+#[test]
+fn resolve_nested_path() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("src/lib")).unwrap();
+    std::fs::write(dir.path().join("src/lib/mod.rs"), "").unwrap();
+
+    let rel = resolve(dir.path().to_str().unwrap(), "./src/lib/mod.rs").unwrap();
+    assert_eq!(rel, "src/lib/mod.rs");
+}
+
+#[test]
+fn resolve_parent_segments_staying_inside() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("a")).unwrap();
+    std::fs::create_dir_all(dir.path().join("b")).unwrap();
+    std::fs::write(dir.path().join("b/file.txt"), "x").unwrap();
+
+    let rel = resolve(dir.path().to_str().unwrap(), "a/../b/file.txt").unwrap();
+    assert_eq!(rel, "b/file.txt");
+}
+
+#[test]
+fn resolve_escaping_path_rejected() {
+    let parent = tempfile::tempdir().unwrap();
+    let ws = parent.path().join("ws");
+    std::fs::create_dir_all(&ws).unwrap();
+    std::fs::write(parent.path().join("secret.txt"), "s").unwrap();
+
+    let result = resolve(ws.to_str().unwrap(), "../secret.txt");
+    assert!(matches!(result, Err(FsError::OutsideWorkspace)));
+}
+
+#[test]
+fn resolve_missing_path_not_found() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = resolve(dir.path().to_str().unwrap(), "nope.txt");
+    assert!(matches!(result, Err(FsError::NotFound)));
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+use super::touch::{touch_file_inner, TouchFileArgs};
+use super::FsError;
+
+fn args(root: &std::path::Path, path: &str) -> TouchFileArgs {
+    TouchFileArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        path: path.to_string(),
+    }
+}
+
+#[test]
+fn touch_creates_empty_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let (rel, created) = touch_file_inner(&args(dir.path(), "sub/.marker")).unwrap();
+    assert_eq!(rel, "sub/.marker");
+    assert!(created);
+    assert_eq!(fs::read(dir.path().join("sub/.marker")).unwrap(), b"");
+}
+
+#[test]
+fn touch_bumps_mtime_without_changing_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let p = dir.path().join("keep.txt");
+    fs::write(&p, "content").unwrap();
+    let old = SystemTime::now() - Duration::from_secs(3600);
+    fs::File::options().write(true).open(&p).unwrap().set_modified(old).unwrap();
+
+    let (_, created) = touch_file_inner(&args(dir.path(), "keep.txt")).unwrap();
+    assert!(!created);
+    assert_eq!(fs::read_to_string(&p).unwrap(), "content");
+    let mtime = fs::metadata(&p).unwrap().modified().unwrap();
+    assert!(mtime > old + Duration::from_secs(3000));
+}
+
+#[test]
+fn touch_rejects_directory_and_escape() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("d")).unwrap();
+    assert!(matches!(touch_file_inner(&args(dir.path(), "d")), Err(FsError::IsDirectory)));
+    assert!(matches!(
+        touch_file_inner(&args(dir.path(), "../x.txt")),
+        Err(FsError::OutsideWorkspace)
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn touch_rejects_symlink_to_outside_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    fs::write(outside.path().join("t.txt"), "x").unwrap();
+    std::os::unix::fs::symlink(outside.path().join("t.txt"), dir.path().join("link.txt")).unwrap();
+    assert!(matches!(
+        touch_file_inner(&args(dir.path(), "link.txt")),
+        Err(FsError::OutsideWorkspace)
+    ));
+}
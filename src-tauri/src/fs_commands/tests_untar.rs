@@ -0,0 +1,108 @@
+use std::fs;
+use std::io::Write;
+
+use super::untar::{untar_archive_inner, UntarArchiveArgs};
+use super::FsError;
+
+fn args(root: &std::path::Path, archive: &str, dest: &str) -> UntarArchiveArgs {
+    UntarArchiveArgs {
+        workspace_root: root.to_string_lossy().into_owned(),
+        archive_path: archive.into(),
+        dest_dir: dest.into(),
+    }
+}
+
+fn tar_bytes(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (name, data) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_entry_type(tar::EntryType::Regular);
+        // 直接写原始名称字段，以便构造绝对路径等恶意条目
+        let raw = &mut header.as_old_mut().name;
+        raw[..name.len()].copy_from_slice(name.as_bytes());
+        header.set_cksum();
+        builder.append(&header, *data).unwrap();
+    }
+    builder.into_inner().unwrap()
+}
+
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    enc.write_all(bytes).unwrap();
+    enc.finish().unwrap()
+}
+
+#[test]
+fn untar_extracts_tar_gz() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let data = gzip(&tar_bytes(&[("a.txt", b"hello"), ("sub/b.txt", b"world")]));
+    fs::write(root.join("pkg.tar.gz"), data).unwrap();
+
+    let mut created = untar_archive_inner(&args(root, "pkg.tar.gz", "out"), 1024).unwrap();
+    created.sort();
+    assert_eq!(created, vec!["out/a.txt", "out/sub/b.txt"]);
+    assert_eq!(fs::read_to_string(root.join("out/sub/b.txt")).unwrap(), "world");
+}
+
+#[test]
+fn untar_extracts_plain_tar() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::write(root.join("pkg.tar"), tar_bytes(&[("c.txt", b"plain")])).unwrap();
+
+    let created = untar_archive_inner(&args(root, "pkg.tar", "."), 1024).unwrap();
+    assert_eq!(created, vec!["c.txt"]);
+    assert_eq!(fs::read_to_string(root.join("c.txt")).unwrap(), "plain");
+}
+
+#[test]
+fn untar_rejects_absolute_entry_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let data = gzip(&tar_bytes(&[("ok.txt", b"x"), ("/tmp/evil.txt", b"pwned")]));
+    fs::write(root.join("evil.tar.gz"), data).unwrap();
+
+    let result = untar_archive_inner(&args(root, "evil.tar.gz", "out"), 1024);
+    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    // 整个归档被拒绝，未写出任何文件
+    assert!(!root.join("out").exists());
+}
+
+#[test]
+fn untar_rejects_parent_traversal_and_oversize() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::write(root.join("dots.tar"), tar_bytes(&[("../escape.txt", b"x")])).unwrap();
+    let result = untar_archive_inner(&args(root, "dots.tar", "out"), 1024);
+    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+
+    fs::write(root.join("big.tar"), tar_bytes(&[("big.bin", &[0u8; 64])])).unwrap();
+    let result = untar_archive_inner(&args(root, "big.tar", "out"), 16);
+    assert!(matches!(result, Err(FsError::TooLarge)));
+}
+
+#[cfg(unix)]
+#[test]
+fn untar_rejects_symlinked_dir_and_existing_symlink() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::os::unix::fs::symlink(outside.path(), root.join("link")).unwrap();
+    fs::write(root.join("pkg.tar"), tar_bytes(&[("link/escaped.txt", b"pwned")])).unwrap();
+    let result = untar_archive_inner(&args(root, "pkg.tar", "."), 1024);
+    assert!(matches!(result, Err(FsError::OutsideWorkspace)));
+    assert!(!outside.path().join("escaped.txt").exists());
+
+    // 输出路径上已有指向外部文件的链接：不得经由它写入
+    let victim = outside.path().join("victim.txt");
+    fs::write(&victim, "original").unwrap();
+    fs::create_dir(root.join("out")).unwrap();
+    std::os::unix::fs::symlink(&victim, root.join("out/a.txt")).unwrap();
+    fs::write(root.join("a.tar"), tar_bytes(&[("a.txt", b"pwned")])).unwrap();
+    let result = untar_archive_inner(&args(root, "a.tar", "out"), 1024);
+    assert!(matches!(result, Err(FsError::NotAllowed(_))));
+    assert_eq!(fs::read_to_string(&victim).unwrap(), "original");
+}
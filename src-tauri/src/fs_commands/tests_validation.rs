@@ -1,7 +1,7 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use super::validation::{
-    ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist,
+    canonical_workspace_root, ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist,
     normalize_path_components,
 };
 use super::FsError;
@@ -157,3 +157,108 @@ fn workspace_may_not_exist_absolute_outside() {
     let result = ensure_inside_workspace_may_not_exist(root, outside_path.to_str().unwrap());
     assert!(matches!(result, Err(FsError::OutsideWorkspace)));
 }
+
+// ---------------------------------------------------------------------------
+// Windows：verbatim (`\\?\`) 前缀与 UNC 路径
+// ---------------------------------------------------------------------------
+
+#[cfg(windows)]
+#[test]
+fn windows_nested_path_not_verbatim() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("a").join("b")).unwrap();
+    std::fs::write(dir.path().join("a").join("b").join("c.txt"), "x").unwrap();
+    let root = dir.path().to_str().unwrap();
+
+    let result = ensure_inside_workspace_exists(root, "a\\b\\c.txt").unwrap();
+    assert!(!result.to_string_lossy().starts_with(r"\\?\"));
+    assert!(result.ends_with("a\\b\\c.txt"));
+}
+
+#[cfg(windows)]
+#[test]
+fn windows_verbatim_root_accepts_nested_absolute_path() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("nested").join("deep")).unwrap();
+    std::fs::write(dir.path().join("nested").join("deep").join("f.txt"), "x").unwrap();
+
+    // root 使用 std canonicalize（带 `\\?\` 前缀），目标使用普通绝对路径
+    let verbatim_root = dir.path().canonicalize().unwrap();
+    assert!(verbatim_root.to_string_lossy().starts_with(r"\\?\"));
+    let plain_target = dunce::canonicalize(dir.path()).unwrap().join("nested\\deep\\f.txt");
+
+    let result = ensure_inside_workspace_exists(
+        verbatim_root.to_str().unwrap(),
+        plain_target.to_str().unwrap(),
+    );
+    assert!(result.is_ok());
+}
+
+#[cfg(windows)]
+#[test]
+fn windows_verbatim_absolute_path_may_not_exist() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dunce::canonicalize(dir.path()).unwrap();
+    let verbatim_target = format!(r"\\?\{}\new\nested\file.txt", root.display());
+
+    let result =
+        ensure_inside_workspace_may_not_exist(root.to_str().unwrap(), &verbatim_target).unwrap();
+    assert!(result.starts_with(&root));
+}
+
+// ---------------------------------------------------------------------------
+// canonical_workspace_root
+// ---------------------------------------------------------------------------
+
+#[test]
+fn workspace_root_trailing_slash_resolves_same() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.txt"), "x").unwrap();
+    let plain = dir.path().to_str().unwrap().to_string();
+    let slashed = format!("{plain}/");
+
+    let expected = dunce::canonicalize(dir.path()).unwrap();
+    assert_eq!(canonical_workspace_root(&plain).unwrap(), expected);
+    assert_eq!(canonical_workspace_root(&slashed).unwrap(), expected);
+    assert_eq!(
+        ensure_inside_workspace_exists(&slashed, "a.txt").unwrap(),
+        expected.join("a.txt")
+    );
+}
+
+#[test]
+fn workspace_root_expands_tilde() {
+    let Some(home) = dirs::home_dir() else { return };
+    // 需要 home 下可写；CI 沙箱中不可写时跳过
+    let Ok(dir) = tempfile::tempdir_in(&home) else { return };
+    std::fs::write(dir.path().join("a.txt"), "x").unwrap();
+    let name = dir.path().file_name().unwrap().to_str().unwrap();
+
+    let expected = dunce::canonicalize(dir.path()).unwrap();
+    assert_eq!(canonical_workspace_root(&format!("~/{name}")).unwrap(), expected);
+    assert_eq!(canonical_workspace_root(&format!("~/{name}/")).unwrap(), expected);
+    let file = ensure_inside_workspace_exists(&format!("~/{name}"), "a.txt").unwrap();
+    assert_eq!(file, expected.join("a.txt"));
+}
+
+#[test]
+fn workspace_root_missing_is_not_found() {
+    let result = canonical_workspace_root("/definitely/not/a/workspace/");
+    assert!(matches!(result, Err(FsError::NotFound)));
+}
+
+#[cfg(unix)]
+#[test]
+fn workspace_root_filesystem_root_kept() {
+    assert_eq!(canonical_workspace_root("/").unwrap(), PathBuf::from("/"));
+    assert_eq!(canonical_workspace_root("//").unwrap(), PathBuf::from("/"));
+}
+
+#[cfg(windows)]
+#[test]
+fn workspace_root_drive_root_kept() {
+    // `C:` 是“C 盘当前目录”，不能由 `C:\` 去掉分隔符得到
+    let drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".into());
+    let root = canonical_workspace_root(&format!("{drive}\\")).unwrap();
+    assert_eq!(root, PathBuf::from(format!("{drive}\\")));
+}
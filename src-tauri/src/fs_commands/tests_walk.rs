@@ -1,7 +1,7 @@
 use std::fs;
 use tempfile::TempDir;
 
-use super::walk::{WalkFileEntry, WalkFilesArgs, walk_files};
+use super::walk::{walk_workspace, WalkFileEntry, WalkFilesArgs, WalkOptions, walk_files};
 
 fn setup_workspace() -> TempDir {
     let dir = TempDir::new().unwrap();
@@ -39,8 +39,9 @@ fn walks_recursively_and_returns_files() {
         include_dirs: None,
         max_depth: None,
         max_entries: None,
+        absolute: false,
     };
-    let result = walk_files(args).unwrap();
+    let result = walk_files(args).unwrap().entries;
 
     // Should find files in subdirectories
     let p = paths(&result);
@@ -58,8 +59,9 @@ fn skips_hidden_files_and_dirs() {
         include_dirs: Some(true),
         max_depth: None,
         max_entries: None,
+        absolute: false,
     };
-    let result = walk_files(args).unwrap();
+    let result = walk_files(args).unwrap().entries;
 
     let p = paths(&result);
     assert!(!p.iter().any(|path| path.contains(".hidden")));
@@ -75,8 +77,9 @@ fn includes_dirs_when_requested() {
         include_dirs: Some(true),
         max_depth: None,
         max_entries: None,
+        absolute: false,
     };
-    let result = walk_files(args).unwrap();
+    let result = walk_files(args).unwrap().entries;
 
     let dirs: Vec<_> = result.iter().filter(|e| e.is_dir).collect();
     assert!(!dirs.is_empty());
@@ -93,8 +96,9 @@ fn excludes_dirs_by_default() {
         include_dirs: None,
         max_depth: None,
         max_entries: None,
+        absolute: false,
     };
-    let result = walk_files(args).unwrap();
+    let result = walk_files(args).unwrap().entries;
     assert!(result.iter().all(|e| !e.is_dir));
 }
 
@@ -106,8 +110,9 @@ fn respects_max_depth() {
         include_dirs: None,
         max_depth: Some(1),
         max_entries: None,
+        absolute: false,
     };
-    let result = walk_files(args).unwrap();
+    let result = walk_files(args).unwrap().entries;
     let p = paths(&result);
 
     // Depth 1 = only root-level files
@@ -125,9 +130,57 @@ fn respects_max_entries() {
         include_dirs: Some(true),
         max_depth: None,
         max_entries: Some(3),
+        absolute: false,
     };
     let result = walk_files(args).unwrap();
-    assert!(result.len() <= 3);
+    assert_eq!(result.entries.len(), 3);
+    assert!(result.truncated);
+}
+
+#[test]
+fn not_truncated_when_under_limit() {
+    let dir = setup_workspace();
+    let args = WalkFilesArgs {
+        workspace_root: dir.path().to_string_lossy().into_owned(),
+        include_dirs: None,
+        max_depth: None,
+        max_entries: Some(100),
+        absolute: false,
+    };
+    let result = walk_files(args).unwrap();
+    assert!(!result.truncated);
+}
+
+#[test]
+fn stops_walking_once_max_entries_reached() {
+    let dir = TempDir::new().unwrap();
+    // 20 层目录，每层 10 个文件
+    let mut cur = dir.path().to_path_buf();
+    for depth in 0..20 {
+        cur = cur.join(format!("d{depth}"));
+        fs::create_dir_all(&cur).unwrap();
+        for i in 0..10 {
+            fs::write(cur.join(format!("f{i}.txt")), "").unwrap();
+        }
+    }
+
+    let root = dir.path().canonicalize().unwrap();
+    let opts = WalkOptions {
+        include_dirs: false,
+        max_depth: None,
+        max_entries: 5,
+        respect_ignore: true,
+    };
+    let mut visited = 0;
+    let result = walk_workspace(&root, &opts, |_, _| {
+        visited += 1;
+        true
+    });
+
+    assert_eq!(result.entries.len(), 5);
+    assert!(result.truncated);
+    // 遍历在第 max_entries + 1 个条目处立即停止，而不是走完 200 个文件
+    assert_eq!(visited, 6);
 }
 
 #[test]
@@ -138,8 +191,9 @@ fn paths_use_forward_slashes() {
         include_dirs: None,
         max_depth: None,
         max_entries: None,
+        absolute: false,
     };
-    let result = walk_files(args).unwrap();
+    let result = walk_files(args).unwrap().entries;
     for entry in &result {
         assert!(!entry.path.contains('\\'), "path should use forward slashes: {}", entry.path);
     }
@@ -153,8 +207,9 @@ fn sorts_dirs_first_then_alphabetical() {
         include_dirs: Some(true),
         max_depth: None,
         max_entries: None,
+        absolute: false,
     };
-    let result = walk_files(args).unwrap();
+    let result = walk_files(args).unwrap().entries;
 
     // All dirs should come before all files
     let first_file = result.iter().position(|e| !e.is_dir);
@@ -171,6 +226,7 @@ fn returns_error_for_nonexistent_workspace() {
         include_dirs: None,
         max_depth: None,
         max_entries: None,
+        absolute: false,
     };
     assert!(walk_files(args).is_err());
 }
@@ -192,11 +248,37 @@ fn respects_gitignore() {
         include_dirs: Some(true),
         max_depth: None,
         max_entries: None,
+        absolute: false,
     };
-    let result = walk_files(args).unwrap();
+    let result = walk_files(args).unwrap().entries;
     let p = paths(&result);
 
     assert!(!p.contains(&"debug.log"));
     assert!(!p.contains(&"build/output.js"));
     assert!(!p.iter().any(|path| path.starts_with("build")));
 }
+
+#[test]
+fn absolute_option_returns_canonical_paths() {
+    let dir = setup_workspace();
+    let walk = |absolute| {
+        walk_files(WalkFilesArgs {
+            workspace_root: dir.path().to_string_lossy().into_owned(),
+            include_dirs: None,
+            max_depth: None,
+            max_entries: None,
+            absolute,
+        })
+        .unwrap()
+        .entries
+    };
+    let rel = walk(false);
+    let abs = walk(true);
+    let i = rel.iter().position(|e| e.path == "src/hooks/useStore.ts").unwrap();
+
+    let canonical_root = dunce::canonicalize(dir.path()).unwrap();
+    let expected = canonical_root.join("src").join("hooks").join("useStore.ts");
+    assert_eq!(abs[i].path, expected.to_string_lossy());
+    assert_eq!(abs[i].name, "useStore.ts");
+    assert!(std::path::Path::new(&abs[i].path).is_file());
+}
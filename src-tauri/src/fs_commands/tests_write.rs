@@ -0,0 +1,57 @@
+use super::copy::{copy_then_remove, is_cross_device_error};
+
+// ---------------------------------------------------------------------------
+// move_file — cross-device fallback
+// ---------------------------------------------------------------------------
+
+#[cfg(unix)]
+#[test]
+fn cross_device_error_detected_from_exdev() {
+    let exdev = std::io::Error::from_raw_os_error(libc::EXDEV);
+    assert!(is_cross_device_error(&exdev));
+    let other = std::io::Error::from_raw_os_error(libc::EACCES);
+    assert!(!is_cross_device_error(&other));
+    let custom = std::io::Error::other("x");
+    assert!(!is_cross_device_error(&custom));
+}
+
+#[test]
+fn copy_then_remove_moves_file() {
+    let src_dir = tempfile::tempdir().unwrap();
+    let dst_dir = tempfile::tempdir().unwrap();
+    let from = src_dir.path().join("a.txt");
+    let to = dst_dir.path().join("b.txt");
+    std::fs::write(&from, "hello").unwrap();
+
+    copy_then_remove(&from, &to).unwrap();
+    assert!(!from.exists());
+    assert_eq!(std::fs::read_to_string(&to).unwrap(), "hello");
+}
+
+#[test]
+fn copy_then_remove_moves_directory_recursively() {
+    let src_dir = tempfile::tempdir().unwrap();
+    let dst_dir = tempfile::tempdir().unwrap();
+    let from = src_dir.path().join("pkg");
+    std::fs::create_dir_all(from.join("nested")).unwrap();
+    std::fs::write(from.join("top.txt"), "top").unwrap();
+    std::fs::write(from.join("nested/inner.txt"), "inner").unwrap();
+    let to = dst_dir.path().join("moved");
+
+    copy_then_remove(&from, &to).unwrap();
+    assert!(!from.exists());
+    assert_eq!(std::fs::read_to_string(to.join("top.txt")).unwrap(), "top");
+    assert_eq!(std::fs::read_to_string(to.join("nested/inner.txt")).unwrap(), "inner");
+}
+
+#[test]
+fn copy_then_remove_keeps_source_when_copy_fails() {
+    let src_dir = tempfile::tempdir().unwrap();
+    let from = src_dir.path().join("a.txt");
+    std::fs::write(&from, "hello").unwrap();
+    // 目标父目录不存在，复制失败
+    let to = src_dir.path().join("missing/b.txt");
+
+    assert!(copy_then_remove(&from, &to).is_err());
+    assert_eq!(std::fs::read_to_string(&from).unwrap(), "hello");
+}
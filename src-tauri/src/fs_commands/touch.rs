@@ -0,0 +1,64 @@
+use std::fs;
+use std::time::SystemTime;
+
+use serde::Deserialize;
+
+use super::validation::{canonical_workspace_root, ensure_inside_workspace_may_not_exist};
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// touch_file：不存在则创建空文件，存在则只更新 mtime（不改内容）
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchFileArgs {
+    pub workspace_root: String,
+    pub path: String,
+}
+
+/// 返回 (相对工作区根的路径, 是否新建)。
+pub fn touch_file_inner(args: &TouchFileArgs) -> Result<(String, bool), FsError> {
+    let abs = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.path)?;
+    if abs.is_dir() {
+        return Err(FsError::IsDirectory);
+    }
+    if let Some(p) = abs.parent().filter(|p| !p.exists()) {
+        fs::create_dir_all(p)?;
+    }
+    // 父目录与已存在的目标规范化后仍须在工作区内（防止经由 symlink 写到外部）
+    let root = canonical_workspace_root(&args.workspace_root)?;
+    let parent = abs.parent().map(dunce::canonicalize).transpose()?;
+    if parent.is_some_and(|p| !p.starts_with(&root)) {
+        return Err(FsError::OutsideWorkspace);
+    }
+
+    let created = fs::symlink_metadata(&abs).is_err();
+    if !created && !dunce::canonicalize(&abs)?.starts_with(&root) {
+        return Err(FsError::OutsideWorkspace);
+    }
+    let file = fs::File::options().create(true).append(true).open(&abs)?;
+    if !created {
+        file.set_modified(SystemTime::now())?;
+    }
+    let rel = abs
+        .strip_prefix(&root)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .map_err(|_| FsError::Io("strip prefix".into()))?;
+    Ok((rel, created))
+}
+
+#[tauri::command]
+pub fn touch_file(app: tauri::AppHandle, args: TouchFileArgs) -> Result<(), FsError> {
+    let (rel, created) = touch_file_inner(&args)?;
+    use crate::workspace_watcher::FileChangeKind;
+    use tauri::Emitter;
+    let _ = app.emit(
+        crate::workspace_watcher::EVENT_WORKSPACE_FILE_CHANGED,
+        crate::workspace_watcher::WorkspaceFileChangedPayload {
+            path: rel,
+            kind: if created { FileChangeKind::Create } else { FileChangeKind::Modify },
+        },
+    );
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::fs;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::archive::{
+    create_dir_inside, prepare_entry_file, safe_entry_path, to_rel, UNZIP_MAX_ENTRIES, UNZIP_MAX_TOTAL_BYTES,
+};
+use super::validation::{canonical_workspace_root, ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist};
+use super::FsError;
+
+// ---------------------------------------------------------------------------
+// untar_archive：解压 .tar / .tar.gz 到工作区，与 unzip_archive 相同的防护
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UntarArchiveArgs {
+    pub workspace_root: String,
+    pub archive_path: String,
+    pub dest_dir: String,
+}
+
+/// 按内容判断是否 gzip（不依赖扩展名，`.tgz` 等同样处理）
+fn open_tar(path: &Path) -> Result<tar::Archive<Box<dyn Read>>, FsError> {
+    let mut file = fs::File::open(path)?;
+    let mut magic = [0u8; 2];
+    let n = file.read(&mut magic)?;
+    let file = fs::File::open(path)?;
+    let reader: Box<dyn Read> = if n == 2 && magic == [0x1f, 0x8b] {
+        Box::new(flate2::read::GzDecoder::new(BufReader::new(file)))
+    } else {
+        Box::new(BufReader::new(file))
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+fn entry_name<R: Read>(entry: &tar::Entry<R>) -> String {
+    String::from_utf8_lossy(&entry.path_bytes()).into_owned()
+}
+
+fn invalid_tar(e: io::Error) -> FsError {
+    FsError::NotAllowed(format!("invalid tar: {e}"))
+}
+
+/// Core extraction, separated from Tauri event emission for testability.
+/// Returns workspace-relative paths of the extracted files.
+pub(super) fn untar_archive_inner(
+    args: &UntarArchiveArgs,
+    max_total_bytes: u64,
+) -> Result<Vec<String>, FsError> {
+    let archive_abs = ensure_inside_workspace_exists(&args.workspace_root, &args.archive_path)?;
+    let dest_abs = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.dest_dir)?;
+    let root = canonical_workspace_root(&args.workspace_root)?;
+
+    // tar 是流式格式：先完整扫描一遍校验，任何越界条目都拒绝整个归档
+    let mut declared_total: u64 = 0;
+    let mut count = 0usize;
+    let mut archive = open_tar(&archive_abs)?;
+    for entry in archive.entries().map_err(invalid_tar)? {
+        let entry = entry.map_err(invalid_tar)?;
+        count += 1;
+        if count > UNZIP_MAX_ENTRIES {
+            return Err(FsError::TooLarge);
+        }
+        let name = entry_name(&entry);
+        if safe_entry_path(&name).is_none() {
+            return Err(FsError::NotAllowed(format!("entry escapes destination: {name}")));
+        }
+        declared_total = declared_total.saturating_add(entry.size());
+    }
+    if declared_total > max_total_bytes {
+        return Err(FsError::TooLarge);
+    }
+
+    create_dir_inside(&root, &dest_abs)?;
+    let mut written: u64 = 0;
+    let mut created = Vec::new();
+    let mut archive = open_tar(&archive_abs)?;
+    for entry in archive.entries().map_err(invalid_tar)? {
+        let mut entry = entry.map_err(invalid_tar)?;
+        let Some(rel) = safe_entry_path(&entry_name(&entry)) else { continue };
+        let out_path = dest_abs.join(rel);
+        let kind = entry.header().entry_type();
+        if kind.is_dir() {
+            create_dir_inside(&root, &out_path)?;
+            continue;
+        }
+        // 只还原普通文件：跳过符号链接、硬链接与设备文件
+        if !kind.is_file() {
+            continue;
+        }
+        // 符号链接目录或已存在的链接文件可能把写入引到工作区外
+        prepare_entry_file(&root, &out_path)?;
+        let mut out = fs::File::create(&out_path)?;
+        let budget = max_total_bytes - written;
+        let n = io::copy(&mut (&mut entry).take(budget + 1), &mut out)?;
+        if n > budget {
+            drop(out);
+            let _ = fs::remove_file(&out_path);
+            return Err(FsError::TooLarge);
+        }
+        written += n;
+        created.push(to_rel(&root, &out_path));
+    }
+    Ok(created)
+}
+
+#[tauri::command]
+pub fn untar_archive(app: tauri::AppHandle, args: UntarArchiveArgs) -> Result<Vec<String>, FsError> {
+    let created = untar_archive_inner(&args, UNZIP_MAX_TOTAL_BYTES)?;
+
+    use tauri::Emitter;
+    for path in &created {
+        let _ = app.emit(
+            crate::workspace_watcher::EVENT_WORKSPACE_FILE_CHANGED,
+            crate::workspace_watcher::WorkspaceFileChangedPayload {
+                path: path.clone(),
+                kind: crate::workspace_watcher::FileChangeKind::Create,
+            },
+        );
+    }
+
+    Ok(created)
+}
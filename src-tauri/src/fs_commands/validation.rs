@@ -17,11 +17,35 @@ pub(super) fn normalize_path_components(p: &Path) -> PathBuf {
     out
 }
 
+/// 规范化工作区根：展开 `~`、去掉末尾分隔符后 canonicalize。
+///
+/// 调用方传入的 `~/proj`、`/proj/`、`/proj` 都解析为同一路径。
+pub(crate) fn canonical_workspace_root(workspace_root: &str) -> Result<PathBuf, FsError> {
+    let expanded = crate::sandbox::expand_tilde(workspace_root.trim());
+    let root = trim_trailing_separators(&expanded);
+    // 区分权限不足与不存在，避免 UI 把无权访问误报为“未找到”
+    dunce::canonicalize(Path::new(root)).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => FsError::NotFound,
+        std::io::ErrorKind::PermissionDenied => FsError::NotAllowed(e.to_string()),
+        _ => FsError::Io(e.to_string()),
+    })
+}
+
+/// 逐个去掉末尾分隔符，但不动文件系统根或盘符根：`C:\` 去掉后变成相对当前目录的 `C:`。
+fn trim_trailing_separators(path: &str) -> &str {
+    let mut trimmed = path;
+    while trimmed.ends_with(['/', '\\']) && Path::new(trimmed).parent().is_some() {
+        trimmed = &trimmed[..trimmed.len() - 1];
+    }
+    trimmed
+}
+
 /// 路径必须存在：规范为绝对路径并校验在工作区内。
+///
+/// 使用 `dunce::canonicalize`：Windows 上两侧都去掉 `\\?\` verbatim 前缀，避免
+/// `starts_with` 因前缀不一致误判；其他平台等同 `canonicalize`。
 pub(crate) fn ensure_inside_workspace_exists(workspace_root: &str, path: &str) -> Result<PathBuf, FsError> {
-    let root = Path::new(workspace_root)
-        .canonicalize()
-        .map_err(|_| FsError::NotFound)?;
+    let root = canonical_workspace_root(workspace_root)?;
 
     let p = Path::new(path);
     let resolved = if p.is_absolute() {
@@ -29,7 +53,7 @@ pub(crate) fn ensure_inside_workspace_exists(workspace_root: &str, path: &str) -
     } else {
         root.join(path)
     };
-    let canonical = resolved.canonicalize().map_err(|e| {
+    let canonical = dunce::canonicalize(&resolved).map_err(|e| {
         if e.kind() == std::io::ErrorKind::NotFound {
             FsError::NotFound
         } else {
@@ -44,11 +68,11 @@ pub(crate) fn ensure_inside_workspace_exists(workspace_root: &str, path: &str) -
 
 /// 路径可以不存在（如写入新文件）：规范为绝对路径并校验在工作区内。
 pub(crate) fn ensure_inside_workspace_may_not_exist(workspace_root: &str, path: &str) -> Result<PathBuf, FsError> {
-    let root = Path::new(workspace_root).canonicalize().map_err(|_| FsError::NotFound)?;
+    let root = canonical_workspace_root(workspace_root)?;
 
     let p = Path::new(path);
     let resolved = if p.is_absolute() {
-        normalize_path_components(Path::new(path))
+        normalize_path_components(dunce::simplified(p))
     } else {
         normalize_path_components(&root.join(path))
     };
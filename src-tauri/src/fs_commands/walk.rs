@@ -2,6 +2,7 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use super::validation::canonical_workspace_root;
 use super::FsError;
 
 const DEFAULT_MAX_DEPTH: usize = 8;
@@ -17,6 +18,9 @@ pub struct WalkFilesArgs {
     pub max_depth: Option<usize>,
     /// Max entries returned (default 5000)
     pub max_entries: Option<usize>,
+    /// Return canonical absolute OS paths instead of workspace-relative ones (default false)
+    #[serde(default)]
+    pub absolute: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -25,34 +29,51 @@ pub struct WalkFileEntry {
     /// File/directory name (leaf)
     pub name: String,
     /// Relative path from workspace root, using `/` separators
+    /// (canonical absolute OS path when `absolute` is set)
     pub path: String,
     pub is_dir: bool,
 }
 
-#[tauri::command]
-pub fn walk_files(args: WalkFilesArgs) -> Result<Vec<WalkFileEntry>, FsError> {
-    let root = Path::new(&args.workspace_root)
-        .canonicalize()
-        .map_err(|_| FsError::NotFound)?;
-
-    let include_dirs = args.include_dirs.unwrap_or(false);
-    let max_depth = args.max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
-    let max_entries = args.max_entries.unwrap_or(DEFAULT_MAX_ENTRIES);
-
-    let walker = ignore::WalkBuilder::new(&root)
-        .max_depth(Some(max_depth))
-        .hidden(true) // respect hidden files (skip dotfiles)
-        .git_ignore(true)
-        .git_global(true)
-        .git_exclude(true)
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkFilesResult {
+    pub entries: Vec<WalkFileEntry>,
+    /// True when the walk stopped early because `max_entries` was reached
+    pub truncated: bool,
+}
+
+/// Options for the shared ignore-aware workspace traversal.
+pub(crate) struct WalkOptions {
+    pub include_dirs: bool,
+    pub max_depth: Option<usize>,
+    pub max_entries: usize,
+    /// Skip hidden files and .gitignore'd paths
+    pub respect_ignore: bool,
+}
+
+/// Core traversal shared by `walk_files` and the interpreter `glob` binding.
+///
+/// Honors hidden files and .gitignore rules when `respect_ignore` is set, never follows symlinks out of the
+/// workspace, and short-circuits as soon as one more entry than `max_entries` is
+/// accepted by `filter`, so huge trees are never fully traversed.
+pub(crate) fn walk_workspace(
+    root: &Path,
+    opts: &WalkOptions,
+    mut filter: impl FnMut(&str, bool) -> bool,
+) -> WalkFilesResult {
+    let walker = ignore::WalkBuilder::new(root)
+        .max_depth(opts.max_depth)
+        .hidden(opts.respect_ignore) // skip dotfiles
+        .ignore(opts.respect_ignore)
+        .git_ignore(opts.respect_ignore)
+        .git_global(opts.respect_ignore)
+        .git_exclude(opts.respect_ignore)
         .build();
 
     let mut entries = Vec::new();
+    let mut truncated = false;
 
     for result in walker {
-        if entries.len() >= max_entries {
-            break;
-        }
         let entry = match result {
             Ok(e) => e,
             Err(_) => continue,
@@ -64,15 +85,22 @@ pub fn walk_files(args: WalkFilesArgs) -> Result<Vec<WalkFileEntry>, FsError> {
         }
 
         let is_dir = entry.file_type().map_or(false, |ft| ft.is_dir());
-        if is_dir && !include_dirs {
+        if is_dir && !opts.include_dirs {
             continue;
         }
 
-        let rel = match entry.path().strip_prefix(&root) {
+        let rel = match entry.path().strip_prefix(root) {
             Ok(r) => r,
             Err(_) => continue,
         };
         let path = rel.to_string_lossy().replace('\\', "/");
+        if !filter(&path, is_dir) {
+            continue;
+        }
+        if entries.len() >= opts.max_entries {
+            truncated = true;
+            break;
+        }
         let name = entry
             .file_name()
             .to_string_lossy()
@@ -81,8 +109,24 @@ pub fn walk_files(args: WalkFilesArgs) -> Result<Vec<WalkFileEntry>, FsError> {
         entries.push(WalkFileEntry { name, path, is_dir });
     }
 
+    WalkFilesResult { entries, truncated }
+}
+
+#[tauri::command]
+pub fn walk_files(args: WalkFilesArgs) -> Result<WalkFilesResult, FsError> {
+    let root = canonical_workspace_root(&args.workspace_root)?;
+
+    let opts = WalkOptions {
+        include_dirs: args.include_dirs.unwrap_or(false),
+        max_depth: Some(args.max_depth.unwrap_or(DEFAULT_MAX_DEPTH)),
+        max_entries: args.max_entries.unwrap_or(DEFAULT_MAX_ENTRIES),
+        respect_ignore: true,
+    };
+
+    let mut result = walk_workspace(&root, &opts, |_, _| true);
+
     // Sort: directories first, then alphabetical by path
-    entries.sort_by(|a, b| {
+    result.entries.sort_by(|a, b| {
         match (a.is_dir, b.is_dir) {
             (true, false) => std::cmp::Ordering::Less,
             (false, true) => std::cmp::Ordering::Greater,
@@ -90,5 +134,11 @@ pub fn walk_files(args: WalkFilesArgs) -> Result<Vec<WalkFileEntry>, FsError> {
         }
     });
 
-    Ok(entries)
+    if args.absolute {
+        for entry in &mut result.entries {
+            entry.path = root.join(&entry.path).to_string_lossy().into_owned();
+        }
+    }
+
+    Ok(result)
 }
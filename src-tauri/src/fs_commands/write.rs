@@ -1,14 +1,15 @@
 use std::fs;
-use std::path::Path;
 
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 use serde::Deserialize;
 
-use super::validation::{ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist};
+use super::copy::rename_or_copy;
+use super::read_cache::evict_cached;
+use super::validation::{canonical_workspace_root, ensure_inside_workspace_exists, ensure_inside_workspace_may_not_exist};
 use super::FsError;
 
-// write_file
+// write_file：返回写入文件的规范绝对路径
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WriteFileArgs {
@@ -18,10 +19,10 @@ pub struct WriteFileArgs {
 }
 
 #[tauri::command]
-pub fn write_file(args: WriteFileArgs) -> Result<(), FsError> {
+pub fn write_file(args: WriteFileArgs) -> Result<String, FsError> {
     let abs = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.path)?;
     if abs.is_dir() {
-        return Err(FsError::NotAllowed("path is a directory".into()));
+        return Err(FsError::IsDirectory);
     }
     if let Some(parent) = abs.parent() {
         if !parent.exists() {
@@ -29,10 +30,11 @@ pub fn write_file(args: WriteFileArgs) -> Result<(), FsError> {
         }
     }
     fs::write(&abs, args.content).map_err(FsError::from)?;
-    Ok(())
+    evict_cached(&abs);
+    Ok(dunce::canonicalize(&abs)?.to_string_lossy().into_owned())
 }
 
-// create_new_file: atomic create-only, fails if file already exists
+// create_new_file: atomic create-only, fails if file already exists; returns canonical path
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateNewFileArgs {
@@ -41,22 +43,16 @@ pub struct CreateNewFileArgs {
 }
 
 #[tauri::command]
-pub fn create_new_file(args: CreateNewFileArgs) -> Result<(), FsError> {
+pub fn create_new_file(args: CreateNewFileArgs) -> Result<String, FsError> {
     let abs = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.path)?;
     if abs.is_dir() {
-        return Err(FsError::NotAllowed("path is a directory".into()));
+        return Err(FsError::IsDirectory);
     }
     if let Some(p) = abs.parent().filter(|p| !p.exists()) {
         fs::create_dir_all(p).map_err(FsError::from)?;
     }
-    fs::File::options().write(true).create_new(true).open(&abs).map_err(|e| {
-        if e.kind() == std::io::ErrorKind::AlreadyExists {
-            FsError::NotAllowed("already exists".into())
-        } else {
-            FsError::from(e)
-        }
-    })?;
-    Ok(())
+    fs::File::options().write(true).create_new(true).open(&abs).map_err(FsError::from)?;
+    Ok(dunce::canonicalize(&abs)?.to_string_lossy().into_owned())
 }
 
 // create_dir
@@ -81,14 +77,14 @@ pub fn create_dir(app: tauri::AppHandle, args: CreateDirArgs) -> Result<(), FsEr
     let parent = ensure_inside_workspace_exists(&args.workspace_root, &parent_path)?;
     let name = args.name.trim();
     if name.is_empty() || name.contains('/') || name.contains('\\') {
-        return Err(FsError::NotAllowed("invalid folder name".into()));
+        return Err(FsError::InvalidName);
     }
     let new_dir = parent.join(name);
     if new_dir.exists() {
-        return Err(FsError::NotAllowed("already exists".into()));
+        return Err(FsError::AlreadyExists);
     }
     fs::create_dir(&new_dir).map_err(FsError::from)?;
-    let root = Path::new(&args.workspace_root).canonicalize().map_err(FsError::from)?;
+    let root = canonical_workspace_root(&args.workspace_root)?;
     let rel = new_dir
         .strip_prefix(&root)
         .map(|p| p.to_string_lossy().replace('\\', "/"))
@@ -122,15 +118,18 @@ pub fn move_file(app: tauri::AppHandle, args: MoveFileArgs) -> Result<(), FsErro
         return Ok(());
     }
     if to_abs.exists() {
-        return Err(FsError::NotAllowed("destination already exists".into()));
+        return Err(FsError::AlreadyExists);
     }
     if let Some(parent) = to_abs.parent() {
         if !parent.exists() {
             fs::create_dir_all(parent).map_err(FsError::from)?;
         }
     }
-    fs::rename(&from_abs, &to_abs).map_err(FsError::from)?;
-    let root = Path::new(&args.workspace_root).canonicalize().map_err(FsError::from)?;
+    rename_or_copy(&from_abs, &to_abs)?;
+    evict_cached(&to_abs);
+    // 当前 officellm 会话打开的文档被移动时，同步会话路径，使后续 save 写入新位置
+    let _ = crate::officellm::server::rename(&from_abs, &to_abs);
+    let root = canonical_workspace_root(&args.workspace_root)?;
     let from_rel = from_abs
         .strip_prefix(&root)
         .map(|p| p.to_string_lossy().replace('\\', "/"))
@@ -268,7 +267,7 @@ pub struct WriteBinaryFileArgs {
 pub fn write_binary_file(app: tauri::AppHandle, args: WriteBinaryFileArgs) -> Result<String, FsError> {
     let abs = ensure_inside_workspace_may_not_exist(&args.workspace_root, &args.path)?;
     if abs.is_dir() {
-        return Err(FsError::NotAllowed("path is a directory".into()));
+        return Err(FsError::IsDirectory);
     }
     if let Some(parent) = abs.parent() {
         if !parent.exists() {
@@ -279,7 +278,8 @@ pub fn write_binary_file(app: tauri::AppHandle, args: WriteBinaryFileArgs) -> Re
         .decode(&args.content_base64)
         .map_err(|e| FsError::Io(format!("base64 decode failed: {e}")))?;
     fs::write(&abs, bytes).map_err(FsError::from)?;
-    let root = Path::new(&args.workspace_root).canonicalize().map_err(FsError::from)?;
+    evict_cached(&abs);
+    let root = canonical_workspace_root(&args.workspace_root)?;
     let rel = abs
         .strip_prefix(&root)
         .map(|p| p.to_string_lossy().replace('\\', "/"))
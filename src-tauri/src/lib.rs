@@ -1,12 +1,15 @@
+mod app_paths_commands;
 mod attachment_commands;
 mod git_bash_installer;
 mod clipboard_commands;
+mod clipboard_copy_commands;
 mod config_commands;
 mod cookie_commands;
 mod docx_commands;
 mod document_parsers;
 mod fetch_commands;
 mod fs_commands;
+mod log_context;
 mod lua_interpreter;
 mod workspace_ops;
 mod officellm;
@@ -14,13 +17,16 @@ mod render_commands;
 mod sandbox;
 mod sidecar;
 mod shell_commands;
+mod skill_bundle_commands;
 mod skill_commands;
+mod skill_detail_commands;
 mod skill_discovery;
 mod skill_resource_commands;
 mod soul_backup;
 mod soul_commands;
 mod soul_defaults;
 mod soul_migrate;
+mod url_commands;
 mod workspace_watcher;
 
 #[cfg(test)]
@@ -77,6 +83,7 @@ pub fn run() {
   tauri::Builder::default()
     .manage(Arc::new(workspace_watcher::WatcherState::new()))
     .manage(Arc::new(shell_commands::CancelRegistry::new()))
+    .manage(Arc::new(fs_commands::ReadCursorRegistry::new()))
     .plugin(
       tauri_plugin_sql::Builder::default()
         .add_migrations("sqlite:office-chat.db", migrations)
@@ -134,50 +141,93 @@ pub fn run() {
       Ok(())
     })
     .invoke_handler(tauri::generate_handler![
+      app_paths_commands::get_app_paths,
       attachment_commands::save_attachment_file,
       attachment_commands::save_attachment_from_base64,
+      attachment_commands::save_clipboard_image,
       attachment_commands::read_attachment_as_data_url,
       attachment_commands::parse_document_text,
+      attachment_commands::parse_pdf_pages,
       attachment_commands::save_attachment_to_workspace,
       attachment_commands::save_attachment_to_workspace_from_base64,
       attachment_commands::preprocess_attachment,
       clipboard_commands::read_clipboard_files,
+      clipboard_copy_commands::copy_to_clipboard,
       cookie_commands::get_browser_cookies,
       fetch_commands::fetch_url,
+      fetch_commands::fetch_urls,
+      fetch_commands::parse_html_string,
+      url_commands::open_url,
       render_commands::render_url,
       render_commands::render_extract_content,
       fs_commands::read_file,
       fs_commands::read_file_raw,
       fs_commands::write_file,
+      fs_commands::write_file_if_unchanged,
       fs_commands::create_new_file,
       fs_commands::write_binary_file,
       fs_commands::stat_file,
+      fs_commands::file_metrics,
+      fs_commands::detect_language,
+      fs_commands::detect_mime,
+      fs_commands::inspect_file_head,
       fs_commands::list_dir,
       fs_commands::walk_files,
+      fs_commands::resolve_workspace_path,
+      fs_commands::workspace_root_info,
       fs_commands::read_file_as_data_url,
+      fs_commands::read_file_for_preview,
+      fs_commands::read_file_smart,
+      fs_commands::find_in_file,
+      fs_commands::clear_read_cache,
+      fs_commands::validate_workspace,
+      fs_commands::open_read_cursor,
+      fs_commands::read_cursor_chunk,
+      fs_commands::close_read_cursor,
       fs_commands::read_absolute_file,
       fs_commands::read_absolute_file_as_data_url,
       fs_commands::open_with_app,
       fs_commands::detect_office_apps,
+      fs_commands::refresh_office_apps,
       fs_commands::create_dir,
+      fs_commands::create_dir_all,
+      fs_commands::touch_file,
+      fs_commands::replace_in_files,
+      fs_commands::diff_files,
+      fs_commands::diff_against_content,
       fs_commands::move_file,
       fs_commands::remove_entry,
       fs_commands::copy_entry,
       fs_commands::copy_external_file,
+      fs_commands::unzip_archive,
+      fs_commands::untar_archive,
+      fs_commands::zip_entries,
       fs_commands::reveal_in_finder,
       fs_commands::read_office_text,
       fs_commands::write_office_text,
       workspace_watcher::watch_workspace_command,
+      workspace_watcher::watcher_status,
+      workspace_watcher::pause_watching,
+      workspace_watcher::resume_watching,
       shell_commands::run_command,
+      shell_commands::run_program,
+      shell_commands::spawn_command,
       shell_commands::cancel_command,
+      shell_commands::cancel_all_commands,
+      shell_commands::read_command_audit,
       sandbox::check_sandbox_supported,
+      sandbox::sandbox_status,
       sandbox::get_sandbox_policy,
       sandbox::set_sandbox_policy,
+      sandbox::get_sandbox_preset,
+      sandbox::apply_sandbox_preset,
       lua_interpreter::run_lua,
       skill_discovery::discover_external_skills,
       skill_commands::write_skill,
+      skill_bundle_commands::write_skill_bundle,
       skill_commands::delete_skill,
       skill_commands::read_skill,
+      skill_detail_commands::read_skill_detail,
       skill_resource_commands::read_skill_resource,
       soul_commands::read_soul,
       soul_commands::write_soul,
@@ -195,6 +245,7 @@ pub fn run() {
       docx_commands::docx_to_pdf,
       docx_commands::qmd_to_pdf,
       docx_commands::pptx_to_pdf,
+      docx_commands::detect_conversion_backends,
       officellm::officellm_detect,
       officellm::officellm_init,
       officellm::officellm_call,
@@ -202,8 +253,10 @@ pub fn run() {
       officellm::officellm_create,
       officellm::officellm_save,
       officellm::officellm_close,
+      officellm::officellm_close_document,
       officellm::officellm_status,
       officellm::officellm_doctor,
+      officellm::officellm_env_info,
       officellm::officellm_list_commands,
       officellm::officellm_get_command_schema,
     ])
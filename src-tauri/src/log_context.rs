@@ -0,0 +1,25 @@
+//! 日志关联上下文：多个 agent 步骤并发执行时，用 request id 前缀区分各自的日志行。
+
+/// 日志前缀 `[req {id}] `；未提供 id（或为空）时返回空串，日志格式保持不变。
+pub(crate) fn req_prefix(request_id: Option<&str>) -> String {
+    match request_id.map(str::trim).filter(|id| !id.is_empty()) {
+        Some(id) => format!("[req {id}] "),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_includes_request_id() {
+        assert_eq!(req_prefix(Some("abc-1")), "[req abc-1] ");
+    }
+
+    #[test]
+    fn prefix_empty_without_id() {
+        assert_eq!(req_prefix(None), "");
+        assert_eq!(req_prefix(Some("  ")), "");
+    }
+}
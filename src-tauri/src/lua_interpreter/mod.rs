@@ -12,6 +12,8 @@ mod workspace;
 #[cfg(test)]
 mod tests;
 
+pub(crate) use print_capture::PrintSink;
+pub(crate) use workspace::WorkspaceAccess;
 use print_capture::PrintCapture;
 use workspace::register_workspace_fns;
 
@@ -25,6 +27,9 @@ const OFFICELLM_BRIDGE: &str = include_str!("../officellm_bridge.lua");
 const DEFAULT_MEMORY_LIMIT: usize = 64 * 1024 * 1024; // 64 MB
 const DEFAULT_TIMEOUT_MS: u64 = 30_000;
 
+/// 前端监听的事件名：streamOutput 开启时每行 print() 输出即时推送
+pub const EVENT_LUA_OUTPUT: &str = "lua-output";
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RunLuaArgs {
@@ -33,6 +38,25 @@ pub struct RunLuaArgs {
     pub file: Option<String>,
     #[serde(default)]
     pub timeout_ms: Option<u64>,
+    /// 是否逐行推送 print() 输出（`lua-output` 事件），默认 false
+    #[serde(default)]
+    pub stream_output: bool,
+    /// 流式输出事件中携带的任务标识，供前端区分并发脚本
+    #[serde(default)]
+    pub job_id: Option<String>,
+    /// 是否注册 `workspace.sh(command)`，默认 false（需显式开启）
+    #[serde(default)]
+    pub allow_shell: bool,
+    /// 只注册列出的 `workspace.*` 函数（如 `["readFile", "glob"]`），默认全部注册
+    #[serde(default)]
+    pub allow: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LuaOutputPayload {
+    pub job_id: Option<String>,
+    pub line: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -167,6 +191,8 @@ pub(crate) fn run_lua_inner(
     file: Option<&str>,
     timeout_ms: u64,
     officellm_home: Option<&std::path::Path>,
+    print_sink: Option<PrintSink>,
+    access: &WorkspaceAccess,
 ) -> Result<LuaExecutionResult, String> {
     let timeout_ms = timeout_ms.min(60_000);
     let start = Instant::now();
@@ -203,7 +229,10 @@ pub(crate) fn run_lua_inner(
     os_shim::register_os(&lua, workspace_root)
         .map_err(|e| format!("os shim setup: {e}"))?;
 
-    let print_buf = PrintCapture::new();
+    let print_buf = match print_sink {
+        Some(sink) => PrintCapture::with_sink(sink),
+        None => PrintCapture::new(),
+    };
     let print_buf_clone = print_buf.clone();
     let print_fn = lua
         .create_function(move |_, args: mlua::Variadic<LuaValue>| {
@@ -215,7 +244,7 @@ pub(crate) fn run_lua_inner(
     globals.set("print", print_fn).map_err(|e| format!("{e}"))?;
 
     register_json(&lua).map_err(|e| format!("json setup: {e}"))?;
-    register_workspace_fns(&lua, workspace_root, officellm_home)
+    register_workspace_fns(&lua, workspace_root, officellm_home, access)
         .map_err(|e| format!("workspace setup: {e}"))?;
 
     // Auto-inject officellm bridge when binary is available
@@ -268,14 +297,36 @@ pub(crate) fn run_lua_inner(
 #[tauri::command]
 pub fn run_lua(app: tauri::AppHandle, args: RunLuaArgs) -> Result<LuaExecutionResult, String> {
     let timeout_ms = args.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS);
+    let access = WorkspaceAccess {
+        allow_shell: args.allow_shell,
+        allow: args.allow.clone(),
+        audit_log: crate::shell_commands::audit_log_path(&app),
+    };
     let officellm_home = crate::officellm::resolve::resolve_bin()
         .map(|(_, is_bundled)| crate::officellm::resolve::resolve_home(is_bundled, &app))
         .transpose()?;
+    let print_sink: Option<PrintSink> = if args.stream_output {
+        let job_id = args.job_id.clone();
+        Some(Arc::new(move |line: &str| {
+            use tauri::Emitter;
+            let _ = app.emit(
+                EVENT_LUA_OUTPUT,
+                LuaOutputPayload {
+                    job_id: job_id.clone(),
+                    line: line.to_string(),
+                },
+            );
+        }))
+    } else {
+        None
+    };
     run_lua_inner(
         &args.workspace_root,
         args.code.as_deref(),
         args.file.as_deref(),
         timeout_ms,
         officellm_home.as_deref(),
+        print_sink,
+        &access,
     )
 }
@@ -1,19 +1,35 @@
 use std::sync::{Arc, Mutex};
 
+/// Callback invoked with each captured line as soon as it is printed.
+pub type PrintSink = Arc<dyn Fn(&str) + Send + Sync>;
+
 /// Thread-safe print output buffer for Lua print() capture.
 #[derive(Clone)]
 pub struct PrintCapture {
     buf: Arc<Mutex<Vec<String>>>,
+    sink: Option<PrintSink>,
 }
 
 impl PrintCapture {
     pub fn new() -> Self {
         Self {
             buf: Arc::new(Mutex::new(Vec::new())),
+            sink: None,
+        }
+    }
+
+    /// Like `new()`, but also forwards every pushed line to `sink` (incremental streaming).
+    pub fn with_sink(sink: PrintSink) -> Self {
+        Self {
+            sink: Some(sink),
+            ..Self::new()
         }
     }
 
     pub fn push(&self, line: String) {
+        if let Some(sink) = &self.sink {
+            sink(&line);
+        }
         self.buf.lock().unwrap().push(line);
     }
 
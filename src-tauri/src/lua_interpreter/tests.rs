@@ -1,10 +1,11 @@
 // FILE_SIZE_EXCEPTION: comprehensive Lua interpreter + sandbox + workspace tests
-use super::run_lua_inner;
+use super::{run_lua_inner, WorkspaceAccess};
 use std::fs;
 use tempfile::TempDir;
 
 fn run(workspace: &str, code: &str) -> super::LuaExecutionResult {
-    run_lua_inner(workspace, Some(code), None, 5_000, None).expect("run_lua_inner failed")
+    run_lua_inner(workspace, Some(code), None, 5_000, None, None, &WorkspaceAccess::default())
+        .expect("run_lua_inner failed")
 }
 
 // --- basic execution ---
@@ -49,6 +50,57 @@ fn test_print_multiple_args() {
     assert_eq!(r.output, "a\t42\ttrue");
 }
 
+#[test]
+fn test_print_sink_streams_during_execution() {
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    let dir = TempDir::new().unwrap();
+    let start = Instant::now();
+    let events: Arc<Mutex<Vec<(String, u128)>>> = Arc::new(Mutex::new(Vec::new()));
+    let events_c = events.clone();
+    let sink: super::PrintSink = Arc::new(move |line: &str| {
+        events_c
+            .lock()
+            .unwrap()
+            .push((line.to_string(), start.elapsed().as_millis()));
+    });
+    // Print first, then busy-wait ~300ms so the event clearly precedes completion.
+    let code = "print('first'); local t = os.clock(); while os.clock() - t < 0.3 do end; print('last')";
+    let r = run_lua_inner(
+        dir.path().to_str().unwrap(),
+        Some(code),
+        None,
+        5_000,
+        None,
+        Some(sink),
+        &WorkspaceAccess::default(),
+    )
+    .expect("should not fail");
+    let finished_ms = start.elapsed().as_millis();
+
+    assert!(r.error.is_none(), "{:?}", r.error);
+    assert_eq!(r.output, "first\nlast");
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].0, "first");
+    assert!(events[0].1 + 200 < finished_ms, "first line should be emitted before the script ends");
+}
+
+#[test]
+fn test_run_lua_args_stream_output_defaults() {
+    let args: super::RunLuaArgs =
+        serde_json::from_str(r#"{"workspaceRoot":"/ws","code":"return 1"}"#).unwrap();
+    assert!(!args.stream_output);
+    assert!(args.job_id.is_none());
+    let args: super::RunLuaArgs = serde_json::from_str(
+        r#"{"workspaceRoot":"/ws","code":"return 1","streamOutput":true,"jobId":"j1"}"#,
+    )
+    .unwrap();
+    assert!(args.stream_output);
+    assert_eq!(args.job_id.as_deref(), Some("j1"));
+}
+
 // --- json ---
 
 #[test]
@@ -236,6 +288,36 @@ fn test_glob_basic() {
     assert_eq!(v, vec!["a.txt", "b.txt"]);
 }
 
+#[test]
+fn test_glob_recursive() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("a/b")).unwrap();
+    fs::write(dir.path().join("root.txt"), "").unwrap();
+    fs::write(dir.path().join("a/one.txt"), "").unwrap();
+    fs::write(dir.path().join("a/b/two.txt"), "").unwrap();
+    fs::write(dir.path().join("a/b/skip.lua"), "").unwrap();
+    let r = run(
+        dir.path().to_str().unwrap(),
+        "local t = workspace.glob('**/*.txt'); table.sort(t); return table.concat(t, ',')",
+    );
+    assert!(r.error.is_none(), "{:?}", r.error);
+    assert_eq!(r.result, "a/b/two.txt,a/one.txt,root.txt");
+}
+
+#[test]
+fn test_glob_respect_ignore_option() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join(".cache")).unwrap();
+    fs::write(dir.path().join(".cache/x.txt"), "").unwrap();
+    fs::write(dir.path().join("keep.txt"), "").unwrap();
+    let r = run(
+        dir.path().to_str().unwrap(),
+        "local all = workspace.glob('**/*.txt'); local kept = workspace.glob('**/*.txt', {respectIgnore = true}); return #all .. ',' .. #kept",
+    );
+    assert!(r.error.is_none(), "{:?}", r.error);
+    assert_eq!(r.result, "2,1");
+}
+
 // --- security ---
 
 #[test]
@@ -332,6 +414,8 @@ fn test_timeout() {
         None,
         100,
         None,
+        None,
+        &WorkspaceAccess::default(),
     )
     .expect("should not fail");
     assert!(r.error.is_some());
@@ -350,6 +434,8 @@ fn test_file_execution() {
         Some("test.lua"),
         5_000,
         None,
+        None,
+        &WorkspaceAccess::default(),
     )
     .expect("should not fail");
     assert!(r.error.is_none());
@@ -371,6 +457,8 @@ fn test_file_execution_with_workspace() {
         Some("read_it.lua"),
         5_000,
         None,
+        None,
+        &WorkspaceAccess::default(),
     )
     .expect("should not fail");
     assert!(r.error.is_none());
@@ -386,6 +474,8 @@ fn test_file_outside_workspace_rejected() {
         Some("/etc/passwd"),
         5_000,
         None,
+        None,
+        &WorkspaceAccess::default(),
     );
     assert!(r.is_err());
 }
@@ -823,6 +913,8 @@ fn test_shebang_stripped_file() {
         Some("shebang.lua"),
         5_000,
         None,
+        None,
+        &WorkspaceAccess::default(),
     )
     .expect("should not fail");
     assert!(r.error.is_none(), "error: {:?}", r.error);
@@ -843,10 +935,132 @@ fn test_strip_shebang_unit() {
 
 // --- code/file exclusivity ---
 
+// --- workspace.sh ---
+
+fn run_with_shell(workspace: &str, code: &str) -> super::LuaExecutionResult {
+    let access = WorkspaceAccess { allow_shell: true, ..Default::default() };
+    run_lua_inner(workspace, Some(code), None, 5_000, None, None, &access)
+        .expect("run_lua_inner failed")
+}
+
+#[test]
+fn test_sh_unavailable_without_opt_in() {
+    let dir = TempDir::new().unwrap();
+    let r = run(dir.path().to_str().unwrap(), "return workspace.sh == nil");
+    assert_eq!(r.result, "true");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sh_runs_command() {
+    let dir = TempDir::new().unwrap();
+    let r = run_with_shell(
+        dir.path().to_str().unwrap(),
+        "local r = workspace.sh('echo hi') return r.stdout .. '|' .. r.exitCode",
+    );
+    assert!(r.error.is_none(), "error: {:?}", r.error);
+    assert_eq!(r.result, "hi\n|0");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sh_reports_exit_code_and_stderr() {
+    let dir = TempDir::new().unwrap();
+    let r = run_with_shell(
+        dir.path().to_str().unwrap(),
+        "local r = workspace.sh('echo oops >&2; exit 3') return r.stderr .. '|' .. r.exitCode",
+    );
+    assert!(r.error.is_none(), "error: {:?}", r.error);
+    assert_eq!(r.result, "oops\n|3");
+}
+
 #[test]
 fn test_neither_code_nor_file() {
     let dir = TempDir::new().unwrap();
-    let r = run_lua_inner(dir.path().to_str().unwrap(), None, None, 5_000, None);
+    let r = run_lua_inner(dir.path().to_str().unwrap(), None, None, 5_000, None, None, &WorkspaceAccess::default());
     assert!(r.is_err());
     assert!(r.unwrap_err().contains("either code or file"));
 }
+
+// --- workspace.env ---
+
+#[test]
+fn test_env_returns_allowlisted_var() {
+    let dir = TempDir::new().unwrap();
+    unsafe { std::env::set_var("NODE_ENV", "production") };
+    let r = run(dir.path().to_str().unwrap(), "return workspace.env('NODE_ENV')");
+    assert!(r.error.is_none(), "error: {:?}", r.error);
+    assert_eq!(r.result, "production");
+}
+
+#[test]
+fn test_env_hides_sensitive_and_unlisted_vars() {
+    let dir = TempDir::new().unwrap();
+    unsafe {
+        std::env::set_var("COVE_LUA_TEST_API_KEY", "sk-secret");
+        std::env::set_var("COVE_LUA_TEST_PLAIN", "visible?");
+    }
+    let r = run(
+        dir.path().to_str().unwrap(),
+        "return tostring(workspace.env('COVE_LUA_TEST_API_KEY')) .. '|' .. tostring(workspace.env('COVE_LUA_TEST_PLAIN'))",
+    );
+    assert!(r.error.is_none(), "error: {:?}", r.error);
+    assert_eq!(r.result, "nil|nil");
+    assert_eq!(super::workspace::allowlisted_env("GITHUB_TOKEN"), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sh_writes_command_audit_entry() {
+    let dir = TempDir::new().unwrap();
+    let audit = dir.path().join("audit.jsonl");
+    let access = WorkspaceAccess {
+        allow_shell: true,
+        audit_log: Some(audit.clone()),
+        ..Default::default()
+    };
+    let code = "return workspace.sh('echo audited').exitCode";
+    let ws = dir.path().to_str().unwrap();
+    let r = run_lua_inner(ws, Some(code), None, 5_000, None, None, &access).expect("run_lua_inner failed");
+    assert!(r.error.is_none(), "error: {:?}", r.error);
+    let log = fs::read_to_string(&audit).unwrap();
+    assert!(log.contains("echo audited"), "{log}");
+}
+
+// --- workspace allowlist ---
+
+fn run_with_allow(workspace: &str, code: &str, allow: &[&str]) -> super::LuaExecutionResult {
+    let access = WorkspaceAccess {
+        allow: Some(allow.iter().map(|s| s.to_string()).collect()),
+        ..Default::default()
+    };
+    run_lua_inner(workspace, Some(code), None, 5_000, None, None, &access)
+        .expect("run_lua_inner failed")
+}
+
+#[test]
+fn test_allow_denies_unlisted_workspace_fn() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("in.txt"), "hello").unwrap();
+    let ws = dir.path().to_str().unwrap();
+
+    let r = run_with_allow(ws, "return workspace.readFile('in.txt')", &["readFile"]);
+    assert!(r.error.is_none(), "error: {:?}", r.error);
+    assert_eq!(r.result, "hello");
+
+    let r = run_with_allow(ws, "workspace.writeFile('out.txt', 'x')", &["readFile"]);
+    assert!(r.error.is_some());
+    assert!(!dir.path().join("out.txt").exists());
+}
+
+#[test]
+fn test_allow_defaults_to_all_fns() {
+    let args: super::RunLuaArgs =
+        serde_json::from_str(r#"{"workspaceRoot":"/ws","code":"return 1"}"#).unwrap();
+    assert!(args.allow.is_none());
+
+    let dir = TempDir::new().unwrap();
+    let r = run(dir.path().to_str().unwrap(), "workspace.writeFile('out.txt', 'x')");
+    assert!(r.error.is_none(), "error: {:?}", r.error);
+    assert!(dir.path().join("out.txt").exists());
+}
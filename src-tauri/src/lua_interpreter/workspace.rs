@@ -1,13 +1,50 @@
-// FILE_SIZE_EXCEPTION: 11 workspace function bindings + officellm binding for Lua
+// FILE_SIZE_EXCEPTION: 12 workspace function bindings + officellm/sh bindings + allowlist for Lua
 use mlua::prelude::*;
 use std::collections::HashMap;
 
+use crate::shell_commands::{execute_limited, RunCommandArgs};
 use crate::workspace_ops;
 
+/// `workspace.sh` 的命令超时；脚本执行期间 Lua 超时钩子不生效，因此取较短值
+const SH_TIMEOUT_MS: u64 = 10_000;
+
+/// `workspace.env` 可读取的环境变量白名单，只含非敏感的运行配置
+const ENV_ALLOWLIST: &[&str] = &["NODE_ENV", "CI", "LANG", "LC_ALL", "TZ", "TERM"];
+
+/// 读取白名单内的环境变量；`*_KEY`/`*_TOKEN`/`*_SECRET` 即使误入白名单也始终返回 None
+pub(super) fn allowlisted_env(key: &str) -> Option<String> {
+    let upper = key.to_ascii_uppercase();
+    if ["_KEY", "_TOKEN", "_SECRET"].iter().any(|s| upper.ends_with(s)) {
+        return None;
+    }
+    if !ENV_ALLOWLIST.contains(&key) {
+        return None;
+    }
+    std::env::var(key).ok()
+}
+
+/// 控制脚本可用的 `workspace.*` 函数
+#[derive(Debug, Default, Clone)]
+pub(crate) struct WorkspaceAccess {
+    /// 是否注册 `workspace.sh(command)`
+    pub allow_shell: bool,
+    /// 只注册列出的函数（如 `["readFile", "glob"]`）；None 表示全部注册
+    pub allow: Option<Vec<String>>,
+    /// `workspace.sh` 写入的命令审计日志；None 表示不记录
+    pub audit_log: Option<std::path::PathBuf>,
+}
+
+impl WorkspaceAccess {
+    fn allows(&self, name: &str) -> bool {
+        self.allow.as_ref().map_or(true, |names| names.iter().any(|n| n == name))
+    }
+}
+
 pub(super) fn register_workspace_fns(
     lua: &Lua,
     workspace_root: &str,
     officellm_home: Option<&std::path::Path>,
+    access: &WorkspaceAccess,
 ) -> LuaResult<()> {
     let ws = lua.create_table()?;
     let wr = workspace_root.to_string();
@@ -114,13 +151,17 @@ pub(super) fn register_workspace_fns(
         })?,
     )?;
 
-    // glob(pattern) -> table
+    // glob(pattern, opts?) -> table；opts.respectIgnore 为 true 时跳过隐藏与 .gitignore 文件
     let wr_c = wr.clone();
     ws.set(
         "glob",
-        lua.create_function(move |lua, pattern: String| {
-            let results =
-                workspace_ops::ws_glob(&wr_c, &pattern).map_err(LuaError::runtime)?;
+        lua.create_function(move |lua, (pattern, opts): (String, Option<LuaTable>)| {
+            let respect_ignore = match opts {
+                Some(t) => t.get::<Option<bool>>("respectIgnore")?.unwrap_or(false),
+                None => false,
+            };
+            let results = workspace_ops::ws_glob(&wr_c, &pattern, respect_ignore)
+                .map_err(LuaError::runtime)?;
             let table = lua.create_table()?;
             for (i, entry) in results.iter().enumerate() {
                 table.set(i + 1, entry.as_str())?;
@@ -129,6 +170,12 @@ pub(super) fn register_workspace_fns(
         })?,
     )?;
 
+    // env(key) -> string|nil，仅限白名单变量
+    ws.set(
+        "env",
+        lua.create_function(|_, key: String| Ok(allowlisted_env(&key)))?,
+    )?;
+
     // officellm(cmd, args) -> string
     if let Some(home) = officellm_home {
         let wr_c = wr.clone();
@@ -142,6 +189,46 @@ pub(super) fn register_workspace_fns(
         )?;
     }
 
+    // sh(command) -> table {stdout, stderr, exitCode, timedOut}
+    // 与 run_command 相同的沙箱策略、并发上限与审计日志，工作目录为 workspace root
+    if access.allow_shell {
+        let wr_c = wr.clone();
+        let audit_log = access.audit_log.clone();
+        ws.set(
+            "sh",
+            lua.create_function(move |lua, command: String| {
+                let args = RunCommandArgs {
+                    workspace_root: wr_c.clone(),
+                    command,
+                    workdir: None,
+                    timeout_ms: Some(SH_TIMEOUT_MS),
+                    cancel_token: None,
+                    request_id: None,
+                    clean_env: false,
+                    output_to_file: None,
+                    login_shell: false,
+                    graceful_shutdown: false,
+                };
+                let r = execute_limited(&args, audit_log.as_deref()).map_err(LuaError::runtime)?;
+                let table = lua.create_table()?;
+                table.set("stdout", r.stdout)?;
+                table.set("stderr", r.stderr)?;
+                table.set("exitCode", r.exit_code)?;
+                table.set("timedOut", r.timed_out)?;
+                Ok(table)
+            })?,
+        )?;
+    }
+
+    // 未在 allow 中列出的函数不注册，脚本调用时报 "attempt to call a nil value"
+    let names: Vec<String> = ws
+        .pairs::<String, LuaValue>()
+        .map(|p| p.map(|(k, _)| k))
+        .collect::<LuaResult<_>>()?;
+    for name in names.iter().filter(|n| !access.allows(n)) {
+        ws.set(name.as_str(), LuaValue::Nil)?;
+    }
+
     lua.globals().set("workspace", ws)?;
     Ok(())
 }
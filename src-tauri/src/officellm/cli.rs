@@ -13,12 +13,13 @@ const DEFAULT_TIMEOUT_SECS: u64 = 120;
 ///
 /// `home` 应由调用方根据 bundled/external 模式通过 `resolve::resolve_home()` 计算。
 /// 等价于：`officellm <cmd> --result-schema v2 --strict [--key value ...]`
-/// 解析 stdout JSON 并返回 `CommandResult`。
+/// 解析 stdout JSON 并返回 `CommandResult`。`request_id` 仅用于日志关联。
 pub fn call(
     cmd: &str,
     args: &[String],
     home: &Path,
     workdir: &Path,
+    request_id: Option<&str>,
 ) -> Result<CommandResult, String> {
     super::init::wait_for_init();
 
@@ -39,7 +40,8 @@ pub fn call(
     command.stderr(std::process::Stdio::piped());
     command.stdin(std::process::Stdio::null());
 
-    log::info!("[officellm-cli] running: {cmd} with {} args", args.len());
+    let req = crate::log_context::req_prefix(request_id);
+    log::info!("{req}[officellm-cli] running: {cmd} with {} args", args.len());
 
     let mut child = command
         .spawn()
@@ -60,6 +62,7 @@ pub fn call(
         if rx.try_recv().is_ok() {
             let _ = child.kill();
             let _ = child.wait();
+            log::warn!("{req}[officellm-cli] {cmd} timed out after {}s", timeout.as_secs());
             return Err("officellm 命令执行超时（120 秒）".to_string());
         }
         std::thread::sleep(Duration::from_millis(50));
@@ -113,7 +116,7 @@ mod tests {
     #[test]
     fn call_errors_when_binary_not_found() {
         with_home_and_path_cleared(|home| {
-            let err = call("test", &[], home, home).unwrap_err();
+            let err = call("test", &[], home, home, None).unwrap_err();
             assert!(
                 err.contains("未找到 officellm"),
                 "expected '未找到 officellm' in error, got: {err}"
@@ -136,6 +139,16 @@ mod tests {
         assert_eq!(r.data, serde_json::json!("ok"));
     }
 
+    #[test]
+    fn parse_result_json_reads_metrics() {
+        let r = parse_result_json(r#"{"status":"success","data":null,"metrics":{"elapsedMs":250}}"#).unwrap();
+        let m = r.metrics.unwrap();
+        assert_eq!(m.duration_ms, Some(250.0));
+        assert_eq!(m.input_tokens, None);
+        let json = serde_json::to_value(&m).unwrap();
+        assert_eq!(json["durationMs"], 250.0);
+    }
+
     #[cfg(unix)]
     #[test]
     fn parse_output_plain_text_wrapped() {
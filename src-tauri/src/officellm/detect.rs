@@ -5,6 +5,13 @@ use std::process::Command;
 
 use super::types::DetectResult;
 
+/// Server 模式（`serve --stdio` JSON-RPC）所需的最低 officellm 版本。
+///
+/// 取自首个提供 `serve --stdio` 的 officellm 发布版（1.3.0），与 `server/` 使用的 RPC 协议对应；
+/// 打包的 sidecar 由 `scripts/pull-officellm.sh` 拉取最新发布版，不会低于此值，
+/// 该检查只针对外部安装的旧版本。`server/` 开始依赖新增的 RPC 方法时需同步提高。
+pub const MIN_SUPPORTED_VERSION: &str = "1.3.0";
+
 /// 返回已解析的 officellm 二进制路径（sidecar 优先，外部安装兜底）。
 pub fn bin_path() -> Result<PathBuf, String> {
     super::resolve::resolve_bin()
@@ -20,6 +27,8 @@ pub fn detect() -> DetectResult {
             version: None,
             path: None,
             bundled: false,
+            compatible: false,
+            min_version: MIN_SUPPORTED_VERSION.to_string(),
         };
     };
 
@@ -41,11 +50,38 @@ pub fn detect() -> DetectResult {
             }
         });
 
+    let compatible = version
+        .as_deref()
+        .is_some_and(|v| is_version_compatible(v, MIN_SUPPORTED_VERSION));
     DetectResult {
         available: true,
         version,
         path: Some(path_str),
         bundled: is_bundled,
+        compatible,
+        min_version: MIN_SUPPORTED_VERSION.to_string(),
+    }
+}
+
+/// 从 `--version` 输出中提取 `major.minor.patch`，兼容 `officellm 1.2.3`、`v1.2`、`1.2.3-beta+abc` 等形式；
+/// 缺省的 minor/patch 视为 0。
+pub fn parse_version(raw: &str) -> Option<(u64, u64, u64)> {
+    raw.split_whitespace().find_map(|token| {
+        let token = token.trim_start_matches(['v', 'V']);
+        let core = token.split(['-', '+']).next()?;
+        let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().unwrap_or(Some(0))?;
+        let patch = parts.next().unwrap_or(Some(0))?;
+        Some((major, minor, patch))
+    })
+}
+
+/// `version` 是否不低于 `min`；任一无法解析时视为不兼容
+pub fn is_version_compatible(version: &str, min: &str) -> bool {
+    match (parse_version(version), parse_version(min)) {
+        (Some(v), Some(m)) => v >= m,
+        _ => false,
     }
 }
 
@@ -67,6 +103,25 @@ mod tests {
         });
     }
 
+    #[test]
+    fn parse_version_formats() {
+        assert_eq!(parse_version("1.2.3"), Some((1, 2, 3)));
+        assert_eq!(parse_version("officellm 1.3.0"), Some((1, 3, 0)));
+        assert_eq!(parse_version("v2.0"), Some((2, 0, 0)));
+        assert_eq!(parse_version("1.4.0-beta.1+abc"), Some((1, 4, 0)));
+        assert_eq!(parse_version("officellm dev"), None);
+        assert_eq!(parse_version(""), None);
+    }
+
+    #[test]
+    fn version_compatibility_against_minimum() {
+        assert!(!is_version_compatible("1.2.0", "1.3.0"));
+        assert!(is_version_compatible("1.3.0", "1.3.0"));
+        assert!(is_version_compatible("1.10.0", "1.3.0"));
+        assert!(is_version_compatible("officellm 2.0.1", "1.3.0"));
+        assert!(!is_version_compatible("unknown", "1.3.0"));
+    }
+
     #[test]
     fn detect_unavailable_when_binary_missing() {
         with_home_and_path_cleared(|_home| {
@@ -94,6 +149,8 @@ mod tests {
             assert!(!r.bundled);
             assert_eq!(r.version.as_deref(), Some("1.2.3"));
             assert!(r.path.is_some());
+            assert!(!r.compatible);
+            assert_eq!(r.min_version, MIN_SUPPORTED_VERSION);
         });
     }
 
@@ -0,0 +1,67 @@
+//! officellm 路径诊断：汇总解析出的 home、临时目录与二进制路径。
+
+use std::path::PathBuf;
+
+use super::types::EnvInfo;
+
+/// 由二进制解析结果与 `resolve::resolve_home_for_bin()` 得到的 home 汇总诊断信息。
+/// 未找到二进制时 home 为外部安装目录，即用户安装 officellm 后将使用的目录。
+pub(super) fn build_env_info(
+    bin: Option<(PathBuf, bool)>,
+    home: Result<PathBuf, String>,
+    tmp_dir: PathBuf,
+) -> EnvInfo {
+    let is_bundled = bin.as_ref().is_some_and(|(_, bundled)| *bundled);
+    let home = match home {
+        Ok(home) => Some(home.to_string_lossy().into_owned()),
+        Err(e) => {
+            log::warn!("[officellm] resolve home failed: {e}");
+            None
+        }
+    };
+    EnvInfo {
+        home,
+        tmp_dir: tmp_dir.to_string_lossy().into_owned(),
+        bin_path: bin.map(|(path, _)| path.to_string_lossy().into_owned()),
+        is_bundled,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_resolution_populates_all_fields() {
+        let info = build_env_info(
+            Some((PathBuf::from("/app/bin/officellm"), true)),
+            Ok(PathBuf::from("/app/data/officellm")),
+            PathBuf::from("/home/u/.officellm/tmp"),
+        );
+        assert_eq!(
+            info,
+            EnvInfo {
+                home: Some("/app/data/officellm".into()),
+                tmp_dir: "/home/u/.officellm/tmp".into(),
+                bin_path: Some("/app/bin/officellm".into()),
+                is_bundled: true,
+            }
+        );
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["tmpDir"], "/home/u/.officellm/tmp");
+        assert_eq!(json["binPath"], "/app/bin/officellm");
+        assert_eq!(json["isBundled"], true);
+    }
+
+    #[test]
+    fn missing_binary_with_unresolved_home() {
+        let info = build_env_info(
+            None,
+            Err("无法获取用户 home 目录".into()),
+            PathBuf::from("/tmp"),
+        );
+        assert_eq!(info.bin_path, None);
+        assert_eq!(info.home, None);
+        assert!(!info.is_bundled);
+    }
+}
@@ -3,13 +3,14 @@
 pub mod cli;
 pub mod detect;
 pub mod env;
+mod env_info;
 pub mod init;
 pub mod resolve;
 
 pub mod server;
 pub mod types;
 
-use types::{CommandResult, DetectResult, SessionInfo};
+use types::{CommandResult, DetectResult, EnvInfo, InitCommand, SessionInfo};
 
 /// Compute the correct `OFFICELLM_HOME` for the current binary resolution.
 fn compute_home(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
@@ -24,7 +25,7 @@ async fn call_cli_in_home(
 ) -> Result<CommandResult, String> {
     let home = compute_home(&app)?;
     let cmd = cmd.to_string();
-    tauri::async_runtime::spawn_blocking(move || cli::call(&cmd, &args, &home, &home))
+    tauri::async_runtime::spawn_blocking(move || cli::call(&cmd, &args, &home, &home, None))
         .await
         .map_err(|e| format!("后台线程错误: {e}"))?
 }
@@ -44,14 +45,16 @@ pub async fn officellm_call(
     cmd: String,
     args: Vec<String>,
     workdir: String,
+    request_id: Option<String>,
 ) -> Result<CommandResult, String> {
     let home = compute_home(&app)?;
     let wd = std::path::PathBuf::from(&workdir);
     tauri::async_runtime::spawn_blocking(move || {
+        let req = request_id.as_deref();
         if server::has_session() {
-            server::call(&cmd, &args)
+            server::call(&cmd, &args, req)
         } else {
-            cli::call(&cmd, &args, &home, &wd)
+            cli::call(&cmd, &args, &home, &wd, req)
         }
     })
     .await
@@ -59,10 +62,18 @@ pub async fn officellm_call(
 }
 
 /// Server 模式：打开文档
+///
+/// `initCommands` 在打开后于同一会话中依次执行（如载入模板），返回各命令结果；
+/// 任一命令失败则关闭会话。
 #[tauri::command]
-pub async fn officellm_open(app: tauri::AppHandle, path: String) -> Result<(), String> {
+pub async fn officellm_open(
+    app: tauri::AppHandle,
+    path: String,
+    init_commands: Option<Vec<InitCommand>>,
+) -> Result<Vec<CommandResult>, String> {
     let home = compute_home(&app)?;
-    tauri::async_runtime::spawn_blocking(move || server::open(&path, &home))
+    let init = init_commands.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || server::open_with_init(&path, &home, &init))
         .await
         .map_err(|e| format!("后台线程错误: {e}"))?
 }
@@ -97,6 +108,14 @@ pub async fn officellm_close() -> Result<(), String> {
         .map_err(|e| format!("后台线程错误: {e}"))?
 }
 
+/// Server 模式：仅当活跃会话打开的是 `path` 时关闭，返回是否关闭了会话
+#[tauri::command]
+pub async fn officellm_close_document(path: String) -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || server::close_document(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| format!("后台线程错误: {e}"))?
+}
+
 /// 查询 Server 会话状态
 #[tauri::command]
 pub fn officellm_status() -> Result<Option<SessionInfo>, String> {
@@ -116,6 +135,14 @@ pub async fn officellm_doctor(app: tauri::AppHandle) -> Result<CommandResult, St
     Ok(result)
 }
 
+/// 汇总 officellm 将使用的 home、临时目录与二进制路径，便于排查路径问题
+#[tauri::command]
+pub fn officellm_env_info(app: tauri::AppHandle) -> EnvInfo {
+    let bin = resolve::resolve_bin();
+    let home = resolve::resolve_home_for_bin(bin.as_ref(), &app);
+    env_info::build_env_info(bin, home, env::tmp_dir())
+}
+
 /// 列出所有可用 officellm CLI 命令（强制 CLI 模式，不依赖 workspace）
 #[tauri::command]
 pub async fn officellm_list_commands(
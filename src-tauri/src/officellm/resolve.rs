@@ -117,6 +117,17 @@ pub fn resolve_home(is_bundled: bool, app: &tauri::AppHandle) -> Result<PathBuf,
     }
 }
 
+/// Return the `OFFICELLM_HOME` for a `resolve_bin()` result.
+///
+/// Without a binary this is the external home, where a later install will
+/// keep its state.
+pub fn resolve_home_for_bin(
+    bin: Option<&(PathBuf, bool)>,
+    app: &tauri::AppHandle,
+) -> Result<PathBuf, String> {
+    resolve_home(bin.is_some_and(|(_, is_bundled)| *is_bundled), app)
+}
+
 /// Return the `OFFICELLM_HOME` directory for bundled mode.
 ///
 /// Path: `<app_data_dir>/officellm`. The directory is created if it does not
@@ -0,0 +1,60 @@
+//! 关闭会话：无条件关闭，或仅当会话打开的是指定文档时关闭。
+
+use std::path::Path;
+
+use super::queue::IO_READY;
+use super::rename::normalize_for_compare;
+use super::{ServerSession, SESSION};
+
+/// 关闭当前会话，终止 officellm serve 进程
+pub fn close() -> Result<(), String> {
+    let session = {
+        SESSION
+            .lock()
+            .map_err(|e| format!("锁获取失败: {e}"))?
+            .take()
+    };
+    // 排队中的请求发现会话已移除后返回错误
+    IO_READY.notify_all();
+    if let Some(session) = session {
+        shutdown(session);
+    }
+    Ok(())
+}
+
+/// 仅当活跃会话打开的正是 `path` 时关闭它，返回是否关闭了会话。
+pub fn close_document(path: &Path) -> Result<bool, String> {
+    let session = {
+        let mut guard = SESSION.lock().map_err(|e| format!("锁获取失败: {e}"))?;
+        take_if_document(&mut guard, path)
+    };
+    let closed = session.is_some();
+    if closed {
+        IO_READY.notify_all();
+    }
+    if let Some(session) = session {
+        shutdown(session);
+    }
+    Ok(closed)
+}
+
+/// 会话文档路径与 `path` 一致时将其从 slot 中取出；内存文档（无路径）从不匹配。
+pub(super) fn take_if_document(slot: &mut Option<ServerSession>, path: &Path) -> Option<ServerSession> {
+    let session = slot.as_ref()?;
+    if session.document_path.is_empty()
+        || normalize_for_compare(Path::new(&session.document_path)) != normalize_for_compare(path)
+    {
+        return None;
+    }
+    slot.take()
+}
+
+/// kill 子进程并等待退出（调用方须已将 session 移出全局状态）
+fn shutdown(mut session: ServerSession) {
+    log::info!(
+        "[officellm-server] closing session for: {}",
+        session.document_path
+    );
+    let _ = session.child.kill();
+    let _ = session.child.wait();
+}
@@ -0,0 +1,74 @@
+//! 打开文档后的初始化命令：在 open 握手之后依次执行。
+//!
+//! 执行期间会话已登记但 I/O 句柄处于取出状态，不持有全局锁：其他请求排队等待，
+//! close() 可随时终止进程。
+
+use std::sync::{Condvar, Mutex};
+
+use crate::officellm::types::{CommandResult, InitCommand, JsonRpcRequest};
+use super::{send_request, ServerSession, SessionIO};
+
+/// 在 `pid` 对应的已登记会话上执行 `commands`，成功后归还句柄。
+/// 失败时若会话仍是本进程（未被 close 或替换）则将其移除并终止。
+pub(super) fn run_on_registered(
+    slot: &Mutex<Option<ServerSession>>,
+    ready: &Condvar,
+    pid: u32,
+    io: SessionIO,
+    commands: &[InitCommand],
+) -> Result<Vec<CommandResult>, String> {
+    let outcome = run_init_commands(io, commands);
+    let mut guard = slot.lock().map_err(|e| format!("锁获取失败: {e}"))?;
+    let own = guard.as_ref().is_some_and(|s| s.child.id() == pid);
+    let result = match outcome {
+        Ok((io, results)) if own => {
+            if let Some(session) = guard.as_mut() {
+                session.io = Some(io);
+            }
+            Ok(results)
+        }
+        Ok(_) => Err("会话在初始化期间已关闭".to_string()),
+        Err(e) => {
+            if own {
+                if let Some(mut session) = guard.take() {
+                    let _ = session.child.kill();
+                    let _ = session.child.wait();
+                }
+            }
+            Err(e)
+        }
+    };
+    drop(guard);
+    ready.notify_all();
+    result
+}
+
+/// 依次执行 `commands`（请求 id 紧接 open 的 1，从 2 开始）。
+/// 任一命令 I/O 失败或返回 `error`/`failure` 即中止并返回错误，由调用方终止进程。
+pub(super) fn run_init_commands(
+    mut io: SessionIO,
+    commands: &[InitCommand],
+) -> Result<(SessionIO, Vec<CommandResult>), String> {
+    let mut results = Vec::with_capacity(commands.len());
+    for (i, command) in commands.iter().enumerate() {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 2 + i as u64,
+            method: "call".to_string(),
+            params: Some(serde_json::json!({ "command": command.cmd, "args": command.args })),
+        };
+        let (next, result) = send_request(io, &request)
+            .map_err(|e| format!("初始化命令 {} 失败: {e}", command.cmd))?;
+        if matches!(result.status.as_str(), "error" | "failure") {
+            let reason = result
+                .message
+                .clone()
+                .or_else(|| result.error.clone())
+                .unwrap_or_else(|| result.status.clone());
+            return Err(format!("初始化命令 {} 失败: {reason}", command.cmd));
+        }
+        io = next;
+        results.push(result);
+    }
+    Ok((io, results))
+}
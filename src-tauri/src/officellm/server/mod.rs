@@ -2,21 +2,33 @@
 //!
 //! 并发安全设计：session 始终留在全局 SESSION 中，仅 I/O 句柄 (SessionIO)
 //! 被临时取出执行阻塞读写。close() 可随时 kill 子进程，has_session() 始终准确。
+//! 句柄被占用时后续请求按 FIFO 排队（见 `queue`）。
 
+use std::collections::VecDeque;
 use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
 use std::process::{Child, ChildStdin, ChildStdout};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::AtomicU64;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
-use super::types::{CommandResult, JsonRpcRequest, SessionInfo};
+use super::types::{CommandResult, InitCommand, JsonRpcRequest, SessionInfo};
 
+mod close;
+mod init_commands;
 mod parsing;
+mod queue;
+mod rename;
 mod spawn;
 use parsing::parse_response;
+use rename::renamed_document_path;
+pub use close::{close, close_document};
+pub use rename::rename;
 
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod tests_queue;
 
 const IO_TIMEOUT: Duration = Duration::from_secs(60);
 
@@ -29,8 +41,12 @@ struct ServerSession {
     /// I/O 句柄：Idle 时 Some，请求进行中时 None（被临时取出）
     io: Option<SessionIO>,
     document_path: String,
+    /// 文档在会话期间被移动/重命名：save() 未指定路径时需写入新路径
+    renamed: bool,
     started_at: Instant,
     next_id: AtomicU64,
+    /// 等待 I/O 句柄的请求 ticket（FIFO）
+    queue: VecDeque<u64>,
 }
 
 /// 可独立于 session 进行阻塞 I/O 的句柄
@@ -43,25 +59,46 @@ struct SessionIO {
 ///
 /// `home` 应由调用方根据 bundled/external 模式通过 `resolve::resolve_home()` 计算。
 pub fn open(path: &str, home: &std::path::Path) -> Result<(), String> {
-    let mut guard = SESSION.lock().map_err(|e| format!("锁获取失败: {e}"))?;
-    if guard.is_some() {
-        return Err("已有活跃会话，请先调用 close() 关闭".to_string());
-    }
-    log::info!("[officellm-server] opening: {path}");
-    let doc_dir = std::path::Path::new(path)
-        .parent()
-        .unwrap_or(std::path::Path::new("/"));
-    let (mut child, io) = spawn::spawn_server(home, doc_dir)?;
-    let io = send_init_request(io, "open", serde_json::json!({"path": path}))
-        .map_err(|e| { let _ = child.kill(); let _ = child.wait(); e })?;
-    *guard = Some(ServerSession {
-        child,
-        io: Some(io),
-        document_path: path.to_string(),
-        started_at: Instant::now(),
-        next_id: AtomicU64::new(2),
-    });
-    Ok(())
+    open_with_init(path, home, &[]).map(|_| ())
+}
+
+/// 打开文档后在同一会话中依次执行 `init` 命令，返回各命令结果。
+///
+/// 握手完成后会话即登记到全局状态，但 I/O 句柄在初始化期间保持取出：其他请求排队等待，
+/// close() 无需等待初始化即可终止进程。任一命令失败则终止进程并移除会话。
+pub fn open_with_init(
+    path: &str,
+    home: &std::path::Path,
+    init: &[InitCommand],
+) -> Result<Vec<CommandResult>, String> {
+    let (pid, io) = {
+        let mut guard = SESSION.lock().map_err(|e| format!("锁获取失败: {e}"))?;
+        if guard.is_some() {
+            return Err("已有活跃会话，请先调用 close() 关闭".to_string());
+        }
+        log::info!("[officellm-server] opening: {path}");
+        let doc_dir = Path::new(path)
+            .parent()
+            .unwrap_or(Path::new("/"));
+        let (mut child, io) = spawn::spawn_server(home, doc_dir)?;
+        let io = send_init_request(io, "open", serde_json::json!({"path": path}))
+            .inspect_err(|_| {
+                let _ = child.kill();
+                let _ = child.wait();
+            })?;
+        let pid = child.id();
+        *guard = Some(ServerSession {
+            child,
+            io: None,
+            document_path: path.to_string(),
+            renamed: false,
+            started_at: Instant::now(),
+            next_id: AtomicU64::new(2 + init.len() as u64),
+            queue: VecDeque::new(),
+        });
+        (pid, io)
+    };
+    init_commands::run_on_registered(&SESSION, &queue::IO_READY, pid, io, init)
 }
 
 /// 创建内存文档并启动 Server 会话。
@@ -80,39 +117,33 @@ pub fn create(
     log::info!("[officellm-server] creating in-memory document");
     let (mut child, io) = spawn::spawn_server(home, workdir)?;
     let io = send_init_request(io, "create", params.clone())
-        .map_err(|e| { let _ = child.kill(); let _ = child.wait(); e })?;
+        .inspect_err(|_| {
+            let _ = child.kill();
+            let _ = child.wait();
+        })?;
     *guard = Some(ServerSession {
         child,
         io: Some(io),
         document_path: String::new(),
+        renamed: false,
         started_at: Instant::now(),
         next_id: AtomicU64::new(2),
+        queue: VecDeque::new(),
     });
     Ok(())
 }
 
-/// 短暂持锁：取出 IO 句柄 + 分配请求 ID（session 本身留在全局状态）
+/// 取出 IO 句柄 + 分配请求 ID（session 本身留在全局状态）；句柄被占用时排队等待
 fn take_io() -> Result<(SessionIO, u64), String> {
-    let mut guard = SESSION.lock().map_err(|e| format!("锁获取失败: {e}"))?;
-    let session = guard.as_mut().ok_or("无活跃会话，请先调用 open()")?;
-    let io = session
-        .io
-        .take()
-        .ok_or("会话正在处理其他请求，请稍候")?;
-    let id = session.next_id.fetch_add(1, Ordering::Relaxed);
-    Ok((io, id))
+    queue::take_io_from(&SESSION, &queue::IO_READY, queue::queue_limits())
 }
 
-/// 短暂持锁：将 IO 句柄放回 session（session 可能已被 close 移除）
+/// 短暂持锁：将 IO 句柄放回 session 并唤醒排队的请求
 fn return_io(io: SessionIO) {
-    if let Ok(mut guard) = SESSION.lock() {
-        if let Some(session) = guard.as_mut() {
-            session.io = Some(io);
-        }
-    }
+    queue::return_io_to(&SESSION, &queue::IO_READY, io);
 }
 
-/// I/O 失败后：kill 子进程并移除 session
+/// I/O 失败后：kill 子进程并移除 session，排队中的请求随之报错
 fn kill_on_io_error() {
     if let Ok(mut guard) = SESSION.lock() {
         if let Some(mut session) = guard.take() {
@@ -120,11 +151,14 @@ fn kill_on_io_error() {
             let _ = session.child.wait();
         }
     }
+    queue::IO_READY.notify_all();
 }
 
-/// 在活跃会话中执行命令
-pub fn call(cmd: &str, args: &[String]) -> Result<CommandResult, String> {
+/// 在活跃会话中执行命令（`request_id` 仅用于日志关联）
+pub fn call(cmd: &str, args: &[String], request_id: Option<&str>) -> Result<CommandResult, String> {
+    let req = crate::log_context::req_prefix(request_id);
     let (io, id) = take_io()?;
+    log::info!("{req}[officellm-server] call #{id}: {cmd} with {} args", args.len());
     let params = serde_json::json!({ "command": cmd, "args": args });
     let request = JsonRpcRequest {
         jsonrpc: "2.0",
@@ -138,6 +172,7 @@ pub fn call(cmd: &str, args: &[String]) -> Result<CommandResult, String> {
             Ok(result)
         }
         Err(e) => {
+            log::warn!("{req}[officellm-server] call #{id} failed: {e}");
             kill_on_io_error();
             Err(e)
         }
@@ -146,6 +181,10 @@ pub fn call(cmd: &str, args: &[String]) -> Result<CommandResult, String> {
 
 /// 保存当前文档
 pub fn save(path: Option<&str>) -> Result<CommandResult, String> {
+    let path = match path {
+        Some(p) => Some(p.to_string()),
+        None => renamed_document_path(),
+    };
     let (io, id) = take_io()?;
     let params = path.map(|p| serde_json::json!({ "path": p }));
     let request = JsonRpcRequest {
@@ -166,26 +205,6 @@ pub fn save(path: Option<&str>) -> Result<CommandResult, String> {
     }
 }
 
-/// 关闭当前会话，终止 officellm serve 进程
-pub fn close() -> Result<(), String> {
-    let session = {
-        SESSION
-            .lock()
-            .map_err(|e| format!("锁获取失败: {e}"))?
-            .take()
-    };
-    let Some(mut session) = session else {
-        return Ok(());
-    };
-    log::info!(
-        "[officellm-server] closing session for: {}",
-        session.document_path
-    );
-    let _ = session.child.kill();
-    let _ = session.child.wait();
-    Ok(())
-}
-
 /// 是否有活跃会话
 pub fn has_session() -> bool {
     SESSION.lock().map(|g| g.is_some()).unwrap_or(false)
@@ -2,7 +2,7 @@
 
 use std::process::ExitStatus;
 
-use super::super::types::{CommandResult, JsonRpcResponse};
+use super::super::types::{CommandMetrics, CommandResult, JsonRpcResponse};
 
 /// 格式化进程退出状态，包含 Unix 信号信息
 pub(super) fn format_exit_status(status: &ExitStatus) -> String {
@@ -36,8 +36,13 @@ pub(super) fn parse_response(line: &str) -> Result<CommandResult, String> {
         });
     }
     let result = resp.result.unwrap_or(serde_json::Value::Null);
+    // serve 模式可能把 metrics 放在 output 同级，而不是 output 内部
+    let outer_metrics = result
+        .get("metrics")
+        .and_then(|m| serde_json::from_value::<CommandMetrics>(m.clone()).ok());
     let payload = result.get("output").cloned().unwrap_or(result);
-    if let Ok(r) = serde_json::from_value::<CommandResult>(payload.clone()) {
+    if let Ok(mut r) = serde_json::from_value::<CommandResult>(payload.clone()) {
+        r.metrics = r.metrics.or(outer_metrics);
         return Ok(r.with_error_fallback());
     }
     Ok(CommandResult {
@@ -48,6 +53,6 @@ pub(super) fn parse_response(line: &str) -> Result<CommandResult, String> {
         error: None,
         errors: Vec::new(),
         meta: None,
-        metrics: None,
+        metrics: outer_metrics,
     })
 }
@@ -0,0 +1,103 @@
+//! 请求排队：并发的 call/save 按 FIFO 等待 I/O 句柄，而不是直接报“会话正在处理其他请求”。
+//!
+//! 等待者以 ticket 登记在 `ServerSession::queue` 中，仅队首在句柄归还后取用；
+//! 会话被 close/kill 移除时唤醒所有等待者并返回错误。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use super::{ServerSession, SessionIO};
+
+/// 默认最多排队的请求数（不含正在执行的请求）
+pub(super) const DEFAULT_MAX_QUEUE_DEPTH: usize = 8;
+/// 默认单个请求的最长排队时间
+pub(super) const DEFAULT_QUEUE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// I/O 句柄归还或会话被移除时通知等待者
+pub(super) static IO_READY: Condvar = Condvar::new();
+
+/// 跨会话唯一的排队 ticket
+static NEXT_TICKET: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct QueueLimits {
+    pub max_depth: usize,
+    pub timeout: Duration,
+}
+
+impl Default for QueueLimits {
+    fn default() -> Self {
+        Self { max_depth: DEFAULT_MAX_QUEUE_DEPTH, timeout: DEFAULT_QUEUE_TIMEOUT }
+    }
+}
+
+/// 读取 general 配置中的 `officellmQueueDepth` / `officellmQueueTimeoutMs`，缺省时使用默认值。
+pub(super) fn queue_limits() -> QueueLimits {
+    let config = crate::config_commands::read_config("general".into())
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok());
+    let get = |key: &str| config.as_ref().and_then(|v| v.get(key)).and_then(|n| n.as_u64());
+    let defaults = QueueLimits::default();
+    QueueLimits {
+        max_depth: get("officellmQueueDepth").map_or(defaults.max_depth, |n| n as usize),
+        timeout: get("officellmQueueTimeoutMs")
+            .filter(|&ms| ms > 0)
+            .map_or(defaults.timeout, Duration::from_millis),
+    }
+}
+
+/// 取出 IO 句柄并分配请求 ID；句柄被占用时排队等待，直到轮到自己、超时或会话被移除。
+pub(super) fn take_io_from(
+    slot: &Mutex<Option<ServerSession>>,
+    ready: &Condvar,
+    limits: QueueLimits,
+) -> Result<(SessionIO, u64), String> {
+    let mut guard = slot.lock().map_err(|e| format!("锁获取失败: {e}"))?;
+    let session = guard.as_mut().ok_or("无活跃会话，请先调用 open()")?;
+    if session.queue.is_empty() {
+        if let Some(io) = session.io.take() {
+            return Ok((io, session.next_id.fetch_add(1, Ordering::Relaxed)));
+        }
+    }
+    if session.queue.len() >= limits.max_depth {
+        return Err(format!("请求队列已满（{} 个请求等待中），请稍候重试", session.queue.len()));
+    }
+    let ticket = NEXT_TICKET.fetch_add(1, Ordering::Relaxed);
+    session.queue.push_back(ticket);
+    let deadline = Instant::now() + limits.timeout;
+
+    loop {
+        // ticket 不在当前会话中：原会话已被 close/kill（可能已换成新会话）
+        let Some(session) = guard.as_mut().filter(|s| s.queue.contains(&ticket)) else {
+            return Err("会话已关闭，排队中的请求已取消".to_string());
+        };
+        if session.queue.front() == Some(&ticket) {
+            if let Some(io) = session.io.take() {
+                session.queue.pop_front();
+                return Ok((io, session.next_id.fetch_add(1, Ordering::Relaxed)));
+            }
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            session.queue.retain(|&t| t != ticket);
+            // 自己可能是队首，让下一个等待者接手
+            ready.notify_all();
+            return Err(format!("排队等待超时（{}s），会话仍在处理其他请求", limits.timeout.as_secs()));
+        }
+        guard = ready
+            .wait_timeout(guard, deadline - now)
+            .map_err(|e| format!("锁获取失败: {e}"))?
+            .0;
+    }
+}
+
+/// 将 IO 句柄放回 session 并唤醒等待者（session 可能已被 close 移除）
+pub(super) fn return_io_to(slot: &Mutex<Option<ServerSession>>, ready: &Condvar, io: SessionIO) {
+    if let Ok(mut guard) = slot.lock() {
+        if let Some(session) = guard.as_mut() {
+            session.io = Some(io);
+        }
+    }
+    ready.notify_all();
+}
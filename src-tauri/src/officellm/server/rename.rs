@@ -0,0 +1,56 @@
+//! 会话文档被移动/重命名时同步记录的路径。
+
+use std::path::{Path, PathBuf};
+
+use super::{ServerSession, SESSION};
+
+/// 文档被重命名过时返回其当前路径，否则 None（由 serve 进程写回原路径）
+pub(super) fn renamed_document_path() -> Option<String> {
+    let guard = SESSION.lock().ok()?;
+    let session = guard.as_ref()?;
+    session.renamed.then(|| session.document_path.clone())
+}
+
+/// 规范化路径用于比较：目标可能已被移走，因此只规范化仍存在的父目录
+pub(super) fn normalize_for_compare(path: &Path) -> PathBuf {
+    if let Ok(p) = dunce::canonicalize(path) {
+        return p;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => dunce::canonicalize(parent)
+            .map(|p| p.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// 若会话文档位于 `old`（文件本身或其所在目录）下，将其路径改写到 `new` 下。
+pub(super) fn rename_in(slot: &mut Option<ServerSession>, old: &Path, new: &Path) -> bool {
+    let Some(session) = slot.as_mut() else {
+        return false;
+    };
+    if session.document_path.is_empty() {
+        return false;
+    }
+    let doc = normalize_for_compare(Path::new(&session.document_path));
+    let old = normalize_for_compare(old);
+    let Ok(rest) = doc.strip_prefix(&old) else {
+        return false;
+    };
+    let moved = if rest.as_os_str().is_empty() { new.to_path_buf() } else { new.join(rest) };
+    log::info!(
+        "[officellm-server] document moved: {} -> {}",
+        session.document_path,
+        moved.display()
+    );
+    session.document_path = moved.to_string_lossy().into_owned();
+    session.renamed = true;
+    true
+}
+
+/// 文件移动/重命名后调用：若涉及当前会话文档，则更新会话中记录的路径，
+/// 使后续 `save()` 写入新位置。返回是否发生了更新。
+pub fn rename(old: &Path, new: &Path) -> Result<bool, String> {
+    let mut guard = SESSION.lock().map_err(|e| format!("锁获取失败: {e}"))?;
+    Ok(rename_in(&mut guard, old, new))
+}
@@ -62,6 +62,32 @@ fn parse_response_raw_value_in_output() {
     assert_eq!(r.data, serde_json::json!("just a string"));
 }
 
+#[test]
+fn parse_response_metrics_inside_output() {
+    let json = r#"{"id":1,"result":{"output":{"status":"success","data":"ok","metrics":{"durationMs":12.5,"inputTokens":40,"outputTokens":7}}}}"#;
+    let r = parse_response(json).unwrap();
+    let m = r.metrics.unwrap();
+    assert_eq!(m.duration_ms, Some(12.5));
+    assert_eq!(m.input_tokens, Some(40));
+    assert_eq!(m.output_tokens, Some(7));
+}
+
+#[test]
+fn parse_response_metrics_beside_output() {
+    let json = r#"{"id":1,"result":{"output":{"status":"success","data":"ok"},"metrics":{"duration_ms":3}}}"#;
+    let r = parse_response(json).unwrap();
+    assert_eq!(r.metrics.unwrap().duration_ms, Some(3.0));
+}
+
+#[test]
+fn parse_response_malformed_metrics_dropped() {
+    let json = r#"{"id":1,"result":{"output":{"status":"success","data":"ok","metrics":"fast"}}}"#;
+    let r = parse_response(json).unwrap();
+    assert_eq!(r.status, "success");
+    assert_eq!(r.data, serde_json::json!("ok"));
+    assert!(r.metrics.is_none());
+}
+
 // ── format_exit_status ──────────────────────────────────────────────────
 
 #[cfg(unix)]
@@ -98,3 +124,172 @@ fn has_session_false_initially() {
 fn close_without_session_is_ok() {
     assert!(super::close().is_ok());
 }
+
+// ── rename ──────────────────────────────────────────────────────────────
+
+#[cfg(unix)]
+fn fake_session(document_path: &str) -> Option<super::ServerSession> {
+    let child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+    Some(super::ServerSession {
+        child,
+        io: None,
+        document_path: document_path.to_string(),
+        renamed: false,
+        started_at: std::time::Instant::now(),
+        next_id: std::sync::atomic::AtomicU64::new(2),
+        queue: std::collections::VecDeque::new(),
+    })
+}
+
+#[cfg(unix)]
+fn kill_fake(slot: Option<super::ServerSession>) {
+    if let Some(mut s) = slot {
+        let _ = s.child.kill();
+        let _ = s.child.wait();
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn rename_updates_active_document_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dunce::canonicalize(dir.path()).unwrap();
+    let old = root.join("report.docx");
+    let new = root.join("final.docx");
+    std::fs::write(&new, "x").unwrap(); // 模拟 move 已完成
+
+    let mut slot = fake_session(old.to_str().unwrap());
+    assert!(super::rename::rename_in(&mut slot, &old, &new));
+    let session = slot.as_ref().unwrap();
+    assert_eq!(session.document_path, new.to_string_lossy());
+    assert!(session.renamed);
+    kill_fake(slot);
+}
+
+#[cfg(unix)]
+#[test]
+fn rename_follows_moved_parent_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dunce::canonicalize(dir.path()).unwrap();
+    let old_dir = root.join("drafts");
+    let new_dir = root.join("archive/drafts");
+    let mut slot = fake_session(old_dir.join("a.docx").to_str().unwrap());
+    assert!(super::rename::rename_in(&mut slot, &old_dir, &new_dir));
+    assert_eq!(
+        slot.as_ref().unwrap().document_path,
+        new_dir.join("a.docx").to_string_lossy()
+    );
+    kill_fake(slot);
+}
+
+#[cfg(unix)]
+#[test]
+fn rename_ignores_unrelated_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dunce::canonicalize(dir.path()).unwrap();
+    let doc = root.join("report.docx");
+    let mut slot = fake_session(doc.to_str().unwrap());
+    assert!(!super::rename::rename_in(&mut slot, &root.join("report.doc"), &root.join("x.doc")));
+    assert!(!super::rename::rename_in(&mut slot, &root.join("rep"), &root.join("x")));
+    assert_eq!(slot.as_ref().unwrap().document_path, doc.to_string_lossy());
+    assert!(!slot.as_ref().unwrap().renamed);
+    kill_fake(slot);
+
+    let mut empty = None;
+    assert!(!super::rename::rename_in(&mut empty, &doc, &root.join("x.docx")));
+}
+
+// ── close_document ──────────────────────────────────────────────────────
+
+#[cfg(unix)]
+#[test]
+fn take_if_document_matches_only_session_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dunce::canonicalize(dir.path()).unwrap();
+    let doc = root.join("report.docx");
+    std::fs::write(&doc, "x").unwrap();
+
+    let mut slot = fake_session(doc.to_str().unwrap());
+    assert!(super::close::take_if_document(&mut slot, &root.join("other.docx")).is_none());
+    assert!(slot.is_some(), "non-matching path must leave the session open");
+
+    // 未规范化的同一路径也应匹配
+    let taken = super::close::take_if_document(&mut slot, &dir.path().join("./report.docx"));
+    assert!(taken.is_some());
+    assert!(slot.is_none());
+    kill_fake(taken);
+}
+
+#[cfg(unix)]
+#[test]
+fn take_if_document_ignores_in_memory_session() {
+    let mut slot = fake_session("");
+    assert!(super::close::take_if_document(&mut slot, std::path::Path::new("")).is_none());
+    assert!(slot.is_some());
+    kill_fake(slot);
+}
+
+#[test]
+fn close_document_without_session_is_noop() {
+    assert_eq!(super::close_document(std::path::Path::new("/tmp/none.docx")), Ok(false));
+}
+
+// ── init commands ───────────────────────────────────────────────────────
+
+/// 假 server：对每行请求回复 `status`，并把原始请求放进 data 便于断言
+#[cfg(unix)]
+pub(super) fn fake_server(status: &str) -> (std::process::Child, super::SessionIO) {
+    fake_server_with_delay(status, "0")
+}
+
+/// 同 `fake_server`，每次回复前先 `sleep delay_secs`
+#[cfg(unix)]
+pub(super) fn fake_server_with_delay(status: &str, delay_secs: &str) -> (std::process::Child, super::SessionIO) {
+    use std::process::{Command, Stdio};
+    let script = format!(
+        r#"while IFS= read -r line; do sleep {delay_secs}; printf '{{"id":0,"result":{{"output":{{"status":"{status}","data":%s}}}}}}\n' "$line"; done"#
+    );
+    let mut child = Command::new("sh")
+        .args(["-c", &script])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let io = super::SessionIO {
+        stdin: child.stdin.take().unwrap(),
+        reader: std::io::BufReader::new(child.stdout.take().unwrap()),
+    };
+    (child, io)
+}
+
+#[cfg(unix)]
+#[test]
+fn init_commands_run_in_order_on_the_session() {
+    use crate::officellm::types::InitCommand;
+    let (mut child, io) = fake_server("success");
+    let init = [InitCommand { cmd: "apply-template".into(), args: vec!["a.dotx".into()] }];
+    let (_io, results) = super::init_commands::run_init_commands(io, &init).unwrap();
+    let _ = child.kill();
+    let _ = child.wait();
+
+    assert_eq!(results.len(), 1);
+    let request = &results[0].data;
+    assert_eq!(request["id"], 2);
+    assert_eq!(request["method"], "call");
+    assert_eq!(request["params"]["command"], "apply-template");
+    assert_eq!(request["params"]["args"], serde_json::json!(["a.dotx"]));
+}
+
+#[cfg(unix)]
+#[test]
+fn init_commands_stop_on_failure() {
+    use crate::officellm::types::InitCommand;
+    let (mut child, io) = fake_server("failure");
+    let init = [InitCommand { cmd: "apply-template".into(), args: vec![] }];
+    let Err(err) = super::init_commands::run_init_commands(io, &init) else {
+        panic!("failure status should abort initialization");
+    };
+    let _ = child.kill();
+    let _ = child.wait();
+    assert!(err.contains("apply-template"), "{err}");
+}
@@ -0,0 +1,173 @@
+#![cfg(unix)]
+
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use super::init_commands::run_on_registered;
+use super::queue::{return_io_to, take_io_from, QueueLimits};
+use super::tests::fake_server_with_delay;
+use super::{send_request, CommandResult, InitCommand, JsonRpcRequest, ServerSession, SessionIO};
+
+type Slot = Mutex<Option<ServerSession>>;
+
+fn slot_with_server(delay_secs: &str) -> Slot {
+    slot_with_status("success", delay_secs)
+}
+
+fn slot_with_status(status: &str, delay_secs: &str) -> Slot {
+    let (child, io) = fake_server_with_delay(status, delay_secs);
+    Mutex::new(Some(ServerSession {
+        child,
+        io: Some(io),
+        document_path: "/tmp/queue.docx".to_string(),
+        renamed: false,
+        started_at: Instant::now(),
+        next_id: AtomicU64::new(2),
+        queue: VecDeque::new(),
+    }))
+}
+
+fn kill(slot: &Slot) {
+    if let Some(mut s) = slot.lock().unwrap().take() {
+        let _ = s.child.kill();
+        let _ = s.child.wait();
+    }
+}
+
+/// 与 `call()` 相同的取句柄 → 请求 → 归还流程，作用于测试自己的 slot
+fn call_on(slot: &Slot, ready: &Condvar, cmd: &str, limits: QueueLimits) -> Result<CommandResult, String> {
+    let (io, id) = take_io_from(slot, ready, limits)?;
+    let request = JsonRpcRequest {
+        jsonrpc: "2.0",
+        id,
+        method: "call".to_string(),
+        params: Some(serde_json::json!({ "command": cmd, "args": [] })),
+    };
+    let (io, result) = send_request(io, &request)?;
+    return_io_to(slot, ready, io);
+    Ok(result)
+}
+
+/// 取走句柄并持有，模拟一个进行中的请求
+fn hold_io(slot: &Slot, ready: &Condvar) -> SessionIO {
+    take_io_from(slot, ready, QueueLimits::default()).unwrap().0
+}
+
+#[test]
+fn concurrent_calls_wait_their_turn() {
+    let slot = slot_with_server("0.2");
+    let ready = Condvar::new();
+    let (a, b) = std::thread::scope(|s| {
+        let a = s.spawn(|| call_on(&slot, &ready, "first", QueueLimits::default()));
+        let b = s.spawn(|| call_on(&slot, &ready, "second", QueueLimits::default()));
+        (a.join().unwrap(), b.join().unwrap())
+    });
+    kill(&slot);
+
+    let (a, b) = (a.unwrap(), b.unwrap());
+    assert_eq!(a.status, "success");
+    assert_eq!(b.status, "success");
+    let mut ids = [a.data["id"].as_u64().unwrap(), b.data["id"].as_u64().unwrap()];
+    ids.sort_unstable();
+    assert_eq!(ids, [2, 3]);
+}
+
+#[test]
+fn queue_rejects_beyond_max_depth() {
+    let slot = slot_with_server("0");
+    let ready = Condvar::new();
+    let _io = hold_io(&slot, &ready);
+    let limits = QueueLimits { max_depth: 0, timeout: Duration::from_secs(5) };
+    let Err(err) = take_io_from(&slot, &ready, limits) else {
+        panic!("queue is full, request should be rejected");
+    };
+    kill(&slot);
+    assert!(err.contains("队列已满"), "{err}");
+}
+
+#[test]
+fn queued_request_times_out() {
+    let slot = slot_with_server("0");
+    let ready = Condvar::new();
+    let _io = hold_io(&slot, &ready);
+    let limits = QueueLimits { max_depth: 4, timeout: Duration::from_millis(100) };
+    let Err(err) = take_io_from(&slot, &ready, limits) else {
+        panic!("I/O handle is never returned, request should time out");
+    };
+    assert!(err.contains("超时"), "{err}");
+    assert!(slot.lock().unwrap().as_ref().unwrap().queue.is_empty());
+    kill(&slot);
+}
+
+#[test]
+fn close_drains_queued_requests() {
+    let slot = slot_with_server("0");
+    let ready = Condvar::new();
+    let _io = hold_io(&slot, &ready);
+    let err = std::thread::scope(|s| {
+        let waiter = s.spawn(|| take_io_from(&slot, &ready, QueueLimits::default()).map(|_| ()));
+        while slot.lock().unwrap().as_ref().unwrap().queue.is_empty() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        kill(&slot);
+        ready.notify_all();
+        waiter.join().unwrap().unwrap_err()
+    });
+    assert!(err.contains("会话已关闭"), "{err}");
+}
+
+// ── init commands ──────────────────────────────────────────────────────
+
+fn session_pid(slot: &Slot) -> u32 {
+    slot.lock().unwrap().as_ref().unwrap().child.id()
+}
+
+#[test]
+fn init_holds_io_and_queued_call_runs_after() {
+    let slot = slot_with_server("0.2");
+    let ready = Condvar::new();
+    let io = hold_io(&slot, &ready);
+    let pid = session_pid(&slot);
+    let init = [InitCommand { cmd: "apply-template".into(), args: vec![] }];
+    let (init_result, call_result) = std::thread::scope(|s| {
+        let init_task = s.spawn(|| run_on_registered(&slot, &ready, pid, io, &init));
+        let call = s.spawn(|| call_on(&slot, &ready, "after-init", QueueLimits::default()));
+        (init_task.join().unwrap(), call.join().unwrap())
+    });
+    kill(&slot);
+
+    assert_eq!(init_result.unwrap()[0].data["params"]["command"], "apply-template");
+    assert_eq!(call_result.unwrap().data["params"]["command"], "after-init");
+}
+
+#[test]
+fn close_preempts_running_init() {
+    let slot = slot_with_server("0.5");
+    let ready = Condvar::new();
+    let io = hold_io(&slot, &ready);
+    let pid = session_pid(&slot);
+    let init = [InitCommand { cmd: "apply-template".into(), args: vec![] }];
+    let result = std::thread::scope(|s| {
+        let init_task = s.spawn(|| run_on_registered(&slot, &ready, pid, io, &init));
+        std::thread::sleep(Duration::from_millis(50));
+        // 初始化期间全局锁未被占用，close 可立即移除会话
+        kill(&slot);
+        init_task.join().unwrap()
+    });
+    assert!(result.is_err());
+    assert!(slot.lock().unwrap().is_none());
+}
+
+#[test]
+fn failed_init_removes_session() {
+    let slot = slot_with_status("failure", "0");
+    let ready = Condvar::new();
+    let io = hold_io(&slot, &ready);
+    let pid = session_pid(&slot);
+    let init = [InitCommand { cmd: "apply-template".into(), args: vec![] }];
+    let err = run_on_registered(&slot, &ready, pid, io, &init).unwrap_err();
+    assert!(err.contains("apply-template"), "{err}");
+    assert!(slot.lock().unwrap().is_none());
+}
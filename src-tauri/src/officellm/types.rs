@@ -16,6 +16,28 @@ pub struct CommandErrorDetail {
     pub details: Option<serde_json::Value>,
 }
 
+/// officellm 返回的单条命令性能指标；字段均可选，未知字段忽略
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandMetrics {
+    /// 命令耗时（毫秒）
+    #[serde(default, alias = "duration_ms", alias = "elapsedMs", alias = "elapsed_ms")]
+    pub duration_ms: Option<f64>,
+    #[serde(default, alias = "input_tokens")]
+    pub input_tokens: Option<u64>,
+    #[serde(default, alias = "output_tokens")]
+    pub output_tokens: Option<u64>,
+}
+
+/// 宽松解析 metrics：格式不符时丢弃为 None，而不是让整个 CommandResult 解析失败
+fn lenient_metrics<'de, D>(deserializer: D) -> Result<Option<CommandMetrics>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|v| serde_json::from_value(v).ok()))
+}
+
 /// officellm CLI / Server 命令执行结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -41,8 +63,8 @@ pub struct CommandResult {
     #[serde(default)]
     pub meta: Option<serde_json::Value>,
     /// 性能指标（可选）
-    #[serde(default)]
-    pub metrics: Option<serde_json::Value>,
+    #[serde(default, deserialize_with = "lenient_metrics")]
+    pub metrics: Option<CommandMetrics>,
 }
 
 impl CommandResult {
@@ -93,6 +115,31 @@ pub struct DetectResult {
     pub version: Option<String>,
     pub path: Option<String>,
     pub bundled: bool,
+    /// 版本可解析且不低于 `min_version`（支持 `serve --stdio` JSON-RPC）
+    pub compatible: bool,
+    /// 应用要求的最低 officellm 版本
+    pub min_version: String,
+}
+
+/// officellm 路径诊断信息（`officellm_env_info`）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvInfo {
+    /// 将使用的 `OFFICELLM_HOME`；无法解析时为 None
+    pub home: Option<String>,
+    /// 进程级 officellm 临时目录（`env::tmp_dir`）
+    pub tmp_dir: String,
+    /// 解析到的二进制路径；未找到时为 None
+    pub bin_path: Option<String>,
+    pub is_bundled: bool,
+}
+
+/// `officellm_open` 打开文档后在同一会话中立即执行的初始化命令
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitCommand {
+    pub cmd: String,
+    #[serde(default)]
+    pub args: Vec<String>,
 }
 
 /// Server 模式会话信息
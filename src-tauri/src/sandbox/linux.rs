@@ -3,7 +3,7 @@
 //! 使用 `landlock` crate 在内核层面限制文件访问。
 //! 要求内核 5.13+，不满足时 fallback（返回 None）。
 
-use super::{expand_tilde, SandboxPolicy};
+use super::{expand_tilde, SandboxPolicy, SandboxStatus};
 
 /// 检测沙箱是否可用（bwrap 是否安装）
 pub fn is_supported() -> bool {
@@ -13,6 +13,27 @@ pub fn is_supported() -> bool {
         .is_ok()
 }
 
+fn kernel_release() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/osrelease")
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|_| "unknown".into())
+}
+
+/// 沙箱状态：bwrap 依赖非特权 user namespace，不可用时附带内核版本说明原因。
+pub fn status() -> SandboxStatus {
+    let kernel = kernel_release();
+    let reason = if !is_supported() {
+        Some(format!("未安装 bwrap (bubblewrap)，内核 {kernel}"))
+    } else if std::fs::read_to_string("/proc/sys/kernel/unprivileged_userns_clone")
+        .is_ok_and(|v| v.trim() == "0")
+    {
+        Some(format!("内核 {kernel} 已禁用非特权 user namespace"))
+    } else {
+        None
+    };
+    SandboxStatus { supported: reason.is_none(), mechanism: "bwrap", reason }
+}
+
 /// 构建沙箱化命令。
 ///
 /// Linux 上使用 bwrap (bubblewrap) 作为沙箱 wrapper（如果可用），
@@ -22,6 +43,16 @@ pub fn build_command(
     cmd: &str,
     workspace_root: &str,
     policy: &SandboxPolicy,
+) -> Option<(String, Vec<String>)> {
+    let argv = ["sh".to_string(), "-c".to_string(), cmd.to_string()];
+    build_argv_command(&argv, workspace_root, policy)
+}
+
+/// 在 bwrap 中直接执行 `argv`（不经过 shell）。
+pub fn build_argv_command(
+    argv: &[String],
+    workspace_root: &str,
+    policy: &SandboxPolicy,
 ) -> Option<(String, Vec<String>)> {
     // 检查 bwrap 是否可用
     if std::process::Command::new("bwrap")
@@ -90,9 +121,7 @@ pub fn build_command(
     }
 
     // 最后添加要执行的命令
-    args.extend_from_slice(&[
-        "sh".to_string(), "-c".to_string(), cmd.to_string(),
-    ]);
+    args.extend_from_slice(argv);
 
     Some(("bwrap".to_string(), args))
 }
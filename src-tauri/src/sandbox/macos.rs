@@ -1,29 +1,42 @@
 //! macOS Seatbelt 沙箱实现：通过 sandbox-exec 在内核层面隔离 shell 命令。
 
-use super::{expand_tilde, SandboxPolicy};
+use super::{expand_tilde, SandboxPolicy, SandboxStatus};
 
 /// 检测沙箱是否可用（sandbox-exec 是否存在）
 pub fn is_supported() -> bool {
     std::path::Path::new("/usr/bin/sandbox-exec").exists()
 }
 
+/// 沙箱状态：报告 sandbox-exec 是否可用。
+pub fn status() -> SandboxStatus {
+    let supported = is_supported();
+    SandboxStatus {
+        supported,
+        mechanism: "seatbelt",
+        reason: (!supported).then(|| "/usr/bin/sandbox-exec 不存在".to_string()),
+    }
+}
+
 /// 构建 sandbox-exec 命令。
 pub fn build_command(
     cmd: &str,
     workspace_root: &str,
     policy: &SandboxPolicy,
+) -> Option<(String, Vec<String>)> {
+    let argv = ["sh".to_string(), "-c".to_string(), cmd.to_string()];
+    build_argv_command(&argv, workspace_root, policy)
+}
+
+/// 在 sandbox-exec 中直接执行 `argv`（不经过 shell）。
+pub fn build_argv_command(
+    argv: &[String],
+    workspace_root: &str,
+    policy: &SandboxPolicy,
 ) -> Option<(String, Vec<String>)> {
     let profile = generate_profile(workspace_root, policy);
-    Some((
-        "sandbox-exec".to_string(),
-        vec![
-            "-p".to_string(),
-            profile,
-            "sh".to_string(),
-            "-c".to_string(),
-            cmd.to_string(),
-        ],
-    ))
+    let mut args = vec!["-p".to_string(), profile];
+    args.extend_from_slice(argv);
+    Some(("sandbox-exec".to_string(), args))
 }
 
 /// 生成 Seatbelt S-expression profile。
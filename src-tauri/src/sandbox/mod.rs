@@ -8,10 +8,13 @@
 mod macos;
 #[cfg(target_os = "linux")]
 mod linux;
+mod presets;
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+pub use presets::{preset, PRESET_NAMES};
+
 /// 沙箱策略：描述允许/拒绝的文件路径与网络访问。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -54,6 +57,33 @@ pub fn is_sandbox_supported() -> bool {
     { false }
 }
 
+/// 沙箱可用性详情：使用的机制，以及不可用时的原因。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxStatus {
+    pub supported: bool,
+    /// `seatbelt`（macOS）、`bwrap`（Linux）或 `none`
+    pub mechanism: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// 当前平台沙箱状态
+pub fn current_status() -> SandboxStatus {
+    #[cfg(target_os = "macos")]
+    { macos::status() }
+    #[cfg(target_os = "linux")]
+    { linux::status() }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        SandboxStatus {
+            supported: false,
+            mechanism: "none",
+            reason: Some(format!("{} 平台不支持 OS 级沙箱", std::env::consts::OS)),
+        }
+    }
+}
+
 /// 构建沙箱化的命令。返回 (program, args)，若平台不支持则返回 None。
 pub fn build_sandbox_command(
     cmd: &str,
@@ -74,6 +104,26 @@ pub fn build_sandbox_command(
     }
 }
 
+/// 与 `build_sandbox_command` 相同，但直接执行 `argv`（program + args），不经过 `sh -c`。
+pub fn build_sandbox_argv(
+    argv: &[String],
+    workspace_root: &str,
+    policy: &SandboxPolicy,
+) -> Option<(String, Vec<String>)> {
+    if !policy.enabled || argv.is_empty() {
+        return None;
+    }
+    #[cfg(target_os = "macos")]
+    { macos::build_argv_command(argv, workspace_root, policy) }
+    #[cfg(target_os = "linux")]
+    { linux::build_argv_command(argv, workspace_root, policy) }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = (argv, workspace_root, policy);
+        None
+    }
+}
+
 /// 从 ~/.officellm/sandbox-policy.json 加载策略，不存在则返回默认值。
 pub fn load_policy() -> SandboxPolicy {
     let path = policy_path();
@@ -117,6 +167,11 @@ pub fn check_sandbox_supported() -> bool {
     is_sandbox_supported()
 }
 
+#[tauri::command]
+pub fn sandbox_status() -> SandboxStatus {
+    current_status()
+}
+
 #[tauri::command]
 pub fn get_sandbox_policy() -> SandboxPolicy {
     load_policy()
@@ -126,3 +181,54 @@ pub fn get_sandbox_policy() -> SandboxPolicy {
 pub fn set_sandbox_policy(policy: SandboxPolicy) -> Result<(), String> {
     save_policy(&policy)
 }
+
+/// 返回命名预设（strict / default / permissive）的策略，不写入文件
+#[tauri::command]
+pub fn get_sandbox_preset(name: String) -> Result<SandboxPolicy, String> {
+    preset(&name)
+        .ok_or_else(|| format!("未知的沙箱预设: {name}（可用: {}）", PRESET_NAMES.join(", ")))
+}
+
+/// 将命名预设写入策略文件并返回写入的策略
+#[tauri::command]
+pub fn apply_sandbox_preset(name: String) -> Result<SandboxPolicy, String> {
+    let policy = get_sandbox_preset(name)?;
+    save_policy(&policy)?;
+    Ok(policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_serializes_camel_case_and_skips_empty_reason() {
+        let s = SandboxStatus { supported: true, mechanism: "seatbelt", reason: None };
+        assert_eq!(
+            serde_json::to_string(&s).unwrap(),
+            r#"{"supported":true,"mechanism":"seatbelt"}"#
+        );
+        let s = SandboxStatus {
+            supported: false,
+            mechanism: "bwrap",
+            reason: Some("bwrap not installed".into()),
+        };
+        let json = serde_json::to_string(&s).unwrap();
+        assert!(json.contains(r#""reason":"bwrap not installed""#));
+    }
+
+    #[test]
+    fn status_mechanism_matches_platform() {
+        let s = current_status();
+        let expected = if cfg!(target_os = "macos") {
+            "seatbelt"
+        } else if cfg!(target_os = "linux") {
+            "bwrap"
+        } else {
+            "none"
+        };
+        assert_eq!(s.mechanism, expected);
+        // 不可用时必须给出原因
+        assert_eq!(s.reason.is_none(), s.supported);
+    }
+}
@@ -0,0 +1,90 @@
+//! 命名沙箱策略预设：strict / default / permissive。
+//!
+//! 预设只是生成 `SandboxPolicy` 的快捷方式；应用预设即把结果写入策略文件，之后可再手动调整。
+
+use super::SandboxPolicy;
+
+/// 可用的预设名
+pub const PRESET_NAMES: &[&str] = &["strict", "default", "permissive"];
+
+/// 按名称返回预设策略；未知名称返回 None。
+pub fn preset(name: &str) -> Option<SandboxPolicy> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "strict" => Some(strict()),
+        "default" => Some(SandboxPolicy::default()),
+        "permissive" => Some(permissive()),
+        _ => None,
+    }
+}
+
+/// 禁止网络；除默认的凭据目录外，再屏蔽常见的令牌/配置与个人文档目录
+fn strict() -> SandboxPolicy {
+    let mut policy = SandboxPolicy::default();
+    policy.deny_read.extend(
+        [
+            "~/.kube",
+            "~/.docker",
+            "~/.netrc",
+            "~/.npmrc",
+            "~/.config/gh",
+            "~/.git-credentials",
+            "~/Library/Keychains",
+            "~/Documents",
+            "~/Desktop",
+            "~/Downloads",
+        ]
+        .map(String::from),
+    );
+    policy.allow_network = false;
+    policy
+}
+
+/// 允许网络，home 下全部可读；写入仍限于工作区与临时目录
+fn permissive() -> SandboxPolicy {
+    SandboxPolicy {
+        deny_read: vec![],
+        allow_network: true,
+        ..SandboxPolicy::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_listed_name_resolves() {
+        for name in PRESET_NAMES {
+            let p = preset(name).unwrap();
+            assert!(p.enabled, "{name} should keep the sandbox enabled");
+        }
+        assert!(preset("unknown").is_none());
+        assert!(preset(" Strict ").is_some());
+    }
+
+    #[test]
+    fn strict_blocks_network_and_extends_default_reads() {
+        let p = preset("strict").unwrap();
+        assert!(!p.allow_network);
+        let default = SandboxPolicy::default();
+        assert!(default.deny_read.iter().all(|d| p.deny_read.contains(d)));
+        assert!(p.deny_read.len() > default.deny_read.len());
+        assert!(p.deny_read.contains(&"~/.kube".to_string()));
+    }
+
+    #[test]
+    fn default_matches_builtin_default() {
+        let p = preset("default").unwrap();
+        let d = SandboxPolicy::default();
+        assert_eq!(p.deny_read, d.deny_read);
+        assert_eq!(p.allow_network, d.allow_network);
+    }
+
+    #[test]
+    fn permissive_allows_network_and_home_reads() {
+        let p = preset("permissive").unwrap();
+        assert!(p.allow_network);
+        assert!(p.deny_read.is_empty());
+        assert!(p.allow_write.is_empty());
+    }
+}
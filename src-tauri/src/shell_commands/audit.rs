@@ -0,0 +1,70 @@
+//! Opt-in audit log of executed shell commands (no output is recorded).
+//!
+//! Stored as JSONL at `<app_data_dir>/command-audit.jsonl`, capped to the most
+//! recent `MAX_AUDIT_ENTRIES`. Enabled by `commandAuditLog` in `~/.cove/config/general.json`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+const AUDIT_FILE: &str = "command-audit.jsonl";
+const MAX_AUDIT_ENTRIES: usize = 1000;
+const DEFAULT_READ_LIMIT: usize = 100;
+
+/// 串行化 `append_entry` 的读-改-写，避免并发执行的命令互相覆盖记录
+static AUDIT_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// RFC 3339 UTC
+    pub timestamp: String,
+    pub command: String,
+    pub workdir: String,
+    pub exit_code: i32,
+    pub sandboxed: bool,
+    pub duration_ms: u64,
+}
+
+pub(super) fn audit_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(AUDIT_FILE)
+}
+
+/// 读取 general 配置中的 `commandAuditLog` 开关，默认关闭。
+pub(super) fn audit_enabled() -> bool {
+    crate::config_commands::read_config("general".into())
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .and_then(|v| v.get("commandAuditLog").and_then(|b| b.as_bool()))
+        .unwrap_or(false)
+}
+
+/// 追加一条记录，超出上限时只保留最近的 `MAX_AUDIT_ENTRIES` 条。
+pub(super) fn append_entry(path: &Path, entry: &AuditEntry) -> Result<(), String> {
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    let mut lines: Vec<&str> = existing.lines().filter(|l| !l.trim().is_empty()).collect();
+    let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    lines.push(&line);
+    let skip = lines.len().saturating_sub(MAX_AUDIT_ENTRIES);
+    let mut out = lines[skip..].join("\n");
+    out.push('\n');
+    fs::write(path, out).map_err(|e| e.to_string())
+}
+
+/// 读取最近 `limit` 条记录（旧 → 新），跳过无法解析的行。
+pub(super) fn read_recent(path: &Path, limit: Option<usize>) -> Vec<AuditEntry> {
+    let limit = limit.unwrap_or(DEFAULT_READ_LIMIT).min(MAX_AUDIT_ENTRIES);
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let entries: Vec<AuditEntry> = content
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect();
+    let skip = entries.len().saturating_sub(limit);
+    entries.into_iter().skip(skip).collect()
+}
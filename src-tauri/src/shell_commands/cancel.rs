@@ -52,6 +52,16 @@ impl CancelRegistry {
         }
     }
 
+    /// Signal cancellation for every registered token (global "Stop").
+    /// Returns how many tokens were signalled.
+    pub fn cancel_all(&self) -> usize {
+        let tokens = self.inner.lock().unwrap();
+        for token in tokens.values() {
+            token.cancel();
+        }
+        tokens.len()
+    }
+
     /// Remove a token after command completion.
     pub fn remove(&self, key: &str) {
         self.inner.lock().unwrap().remove(key);
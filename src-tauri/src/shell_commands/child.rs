@@ -0,0 +1,277 @@
+//! Child process spawning for shell commands: sandbox wrapper, environment and
+//! PATH setup, and process-group signals.
+
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use crate::sandbox;
+
+use super::output_file::child_stdio;
+use super::usage::{try_reap, ResourceUsage};
+
+/// How long `gracefulShutdown` waits after SIGTERM before falling back to SIGKILL.
+const TERM_GRACE: Duration = Duration::from_secs(2);
+
+/// Spawn `command` in its own process group, sandboxed when the policy allows it.
+/// Returns the child and whether it runs inside the sandbox.
+/// With `clean_env` the child starts from a minimal environment (see `apply_child_env`).
+/// With `output` both stdout and stderr go to that file instead of pipes.
+pub(super) fn spawn_child(
+    command: &str,
+    workspace_root: &str,
+    workdir_path: &str,
+    clean_env: bool,
+    output: Option<&std::fs::File>,
+) -> Result<(std::process::Child, bool), String> {
+    let path_env = build_path_env();
+
+    // On Windows, verify Git Bash is available before attempting to spawn.
+    // Installation is handled at startup; this is a fast existence check only.
+    #[cfg(windows)]
+    if crate::git_bash_installer::find_git_bash().is_none() {
+        return Err(
+            "Git Bash 未就绪。请查看应用顶部的提示安装 Git for Windows。".to_string(),
+        );
+    }
+
+    let sandbox_cmd = sandbox::build_sandbox_command(command, workspace_root, &sandbox_policy());
+    if let Some(c) = spawn_sandboxed(sandbox_cmd, workdir_path, &path_env, clean_env, output) {
+        return Ok((c, true));
+    }
+    let c = spawn_plain_command(command, workdir_path, &path_env, clean_env, output)
+        .map_err(|e| e.to_string())?;
+    Ok((c, false))
+}
+
+/// Like `spawn_child`, but runs `program` with `args` directly instead of via `sh -c`,
+/// so arguments are never subject to shell expansion.
+pub(super) fn spawn_program_child(
+    program: &str,
+    args: &[String],
+    workspace_root: &str,
+    workdir_path: &str,
+) -> Result<(std::process::Child, bool), String> {
+    if program.trim().is_empty() {
+        return Err("program must not be empty".to_string());
+    }
+    let path_env = build_path_env();
+    let mut argv = Vec::with_capacity(args.len() + 1);
+    argv.push(program.to_string());
+    argv.extend_from_slice(args);
+    let sandbox_cmd = sandbox::build_sandbox_argv(&argv, workspace_root, &sandbox_policy());
+    if let Some(c) = spawn_sandboxed(sandbox_cmd, workdir_path, &path_env, false, None) {
+        return Ok((c, true));
+    }
+    let c = spawn_command_with_pgid(program, args, workdir_path, &path_env, false, None)
+        .map_err(|e| format!("failed to spawn {program}: {e}"))?;
+    Ok((c, false))
+}
+
+fn sandbox_policy() -> sandbox::SandboxPolicy {
+    let mut policy = sandbox::load_policy();
+    policy.allow_write.extend(crate::officellm::env::sandbox_temp_whitelist());
+    policy
+}
+
+/// Spawn the sandbox wrapper; None when sandboxing is unavailable or fails to start.
+fn spawn_sandboxed(
+    sandbox_cmd: Option<(String, Vec<String>)>,
+    workdir_path: &str,
+    path_env: &str,
+    clean_env: bool,
+    output: Option<&std::fs::File>,
+) -> Option<std::process::Child> {
+    let (program, sb_args) = sandbox_cmd?;
+    spawn_command_with_pgid(&program, &sb_args, workdir_path, path_env, clean_env, output).ok()
+}
+
+/// Set the child's environment. By default it inherits the app's environment with PATH
+/// overridden; with `clean_env` it starts empty and gets only PATH, HOME and the temp-dir
+/// variables (TMPDIR/TEMP/TMP, plus OFFICELLM_TEMP when set).
+fn apply_child_env(command: &mut Command, path_env: &str, clean_env: bool) {
+    if clean_env {
+        command.env_clear();
+        if let Some(home) = dirs::home_dir() {
+            command.env("HOME", home);
+        }
+        let tmp = std::env::temp_dir();
+        command.env("TMPDIR", &tmp).env("TEMP", &tmp).env("TMP", &tmp);
+        if let Some(v) = std::env::var_os("OFFICELLM_TEMP") {
+            command.env("OFFICELLM_TEMP", v);
+        }
+        // Git Bash and Windows system tools fail to start without these
+        #[cfg(windows)]
+        for key in ["SystemRoot", "ComSpec", "USERPROFILE"] {
+            if let Some(v) = std::env::var_os(key) {
+                command.env(key, v);
+            }
+        }
+    }
+    command.env("PATH", path_env);
+}
+
+/// Build PATH with sidecar dir and platform-specific extras prepended.
+fn build_path_env() -> String {
+    let mut extra_paths: Vec<std::path::PathBuf> = Vec::new();
+
+    // Sidecar dir (bundled tools: officellm, pdftoppm, pdftotext, quarto)
+    if let Some(dir) = crate::sidecar::sidecar_dir() {
+        extra_paths.push(dir);
+    }
+
+    // ~/.local/bin (Unix convention)
+    #[cfg(not(windows))]
+    if let Some(home) = dirs::home_dir() {
+        let local_bin = home.join(".local/bin");
+        if local_bin.is_dir() {
+            extra_paths.push(local_bin);
+        }
+    }
+
+    // Windows: inject Git Bash bin dirs so Unix tools (grep, sed, awk, curl…) are available.
+    #[cfg(windows)]
+    if let Some(bash) = crate::git_bash_installer::find_git_bash() {
+        extra_paths.extend(crate::git_bash_installer::git_bash_extra_paths(&bash));
+    }
+
+    let current = std::env::var("PATH").unwrap_or_default();
+    if extra_paths.is_empty() {
+        return current;
+    }
+    let extra: Vec<String> = extra_paths
+        .iter()
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect();
+
+    #[cfg(windows)]
+    let sep = ";";
+    #[cfg(not(windows))]
+    let sep = ":";
+
+    format!("{}{sep}{current}", extra.join(sep))
+}
+
+/// Spawn a plain shell command in its own process group (Unix) or via Git Bash (Windows).
+fn spawn_plain_command(
+    cmd: &str,
+    workdir: &str,
+    path_env: &str,
+    clean_env: bool,
+    output: Option<&std::fs::File>,
+) -> std::io::Result<std::process::Child> {
+    #[cfg(unix)]
+    let (shell, shell_arg): (std::borrow::Cow<str>, &str) = ("sh".into(), "-c");
+    #[cfg(windows)]
+    let (shell, shell_arg): (std::borrow::Cow<str>, &str) = {
+        let bash = crate::git_bash_installer::find_git_bash()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "bash".to_string());
+        (bash.into(), "-c")
+    };
+
+    let (stdout, stderr) = child_stdio(output)?;
+    let mut command = Command::new(shell.as_ref());
+    command
+        .arg(shell_arg)
+        .arg(cmd)
+        .current_dir(workdir)
+        .stdout(stdout)
+        .stderr(stderr)
+        .stdin(Stdio::null());
+    apply_child_env(&mut command, path_env, clean_env);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        unsafe {
+            command.pre_exec(|| {
+                libc::setsid();
+                Ok(())
+            });
+        }
+    }
+
+    command.spawn()
+}
+
+/// Spawn a program (sandbox wrapper or direct) in its own process group (Unix).
+fn spawn_command_with_pgid(
+    program: &str,
+    sb_args: &[String],
+    workdir: &str,
+    path_env: &str,
+    clean_env: bool,
+    output: Option<&std::fs::File>,
+) -> std::io::Result<std::process::Child> {
+    let (stdout, stderr) = child_stdio(output)?;
+    let mut command = Command::new(program);
+    command
+        .args(sb_args)
+        .current_dir(workdir)
+        .stdout(stdout)
+        .stderr(stderr)
+        .stdin(Stdio::null());
+    apply_child_env(&mut command, path_env, clean_env);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        unsafe {
+            command.pre_exec(|| {
+                libc::setsid();
+                Ok(())
+            });
+        }
+    }
+
+    command.spawn()
+}
+
+/// SIGTERM the process group and wait up to `TERM_GRACE` for the child to exit.
+/// Returns its usage when it exited in time (stragglers in the group are then killed),
+/// None when it is still running and needs SIGKILL.
+pub(super) fn terminate_with_grace(
+    child: &mut std::process::Child,
+    pid: u32,
+) -> Option<Option<ResourceUsage>> {
+    if !terminate_process_group(pid) {
+        return None;
+    }
+    let deadline = std::time::Instant::now() + TERM_GRACE;
+    while std::time::Instant::now() < deadline {
+        if let Some((_, usage)) = try_reap(child) {
+            kill_process_group(pid);
+            return Some(usage);
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    None
+}
+
+/// Send SIGTERM to an entire process group (Unix); false when unsupported or it failed.
+#[cfg(unix)]
+fn terminate_process_group(pid: u32) -> bool {
+    unsafe { libc::killpg(pid as libc::pid_t, libc::SIGTERM) == 0 }
+}
+
+#[cfg(not(unix))]
+fn terminate_process_group(_pid: u32) -> bool {
+    false
+}
+
+/// Kill an entire process group via SIGKILL (Unix).
+#[cfg(unix)]
+pub(super) fn kill_process_group(pid: u32) {
+    unsafe {
+        libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+pub(super) fn kill_process_group(pid: u32) {
+    // Kill the entire process tree including child processes.
+    let _ = std::process::Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &pid.to_string()])
+        .output();
+}
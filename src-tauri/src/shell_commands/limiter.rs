@@ -0,0 +1,56 @@
+//! Global cap on concurrently executing `run_command` / `run_program` / `spawn_command` calls, so a
+//! runaway agent loop can't pile up unbounded shells and blocking threads.
+//!
+//! The limit is `maxConcurrentCommands` in `~/.cove/config/general.json` (default
+//! `DEFAULT_MAX_CONCURRENT`). Calls beyond it fail fast rather than queueing.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub(super) const DEFAULT_MAX_CONCURRENT: usize = 8;
+
+/// Process-wide limiter shared by all command invocations.
+pub(super) static COMMAND_LIMITER: CommandLimiter = CommandLimiter::new();
+
+pub(super) struct CommandLimiter {
+    active: AtomicUsize,
+}
+
+/// Held for the lifetime of one command; releases its slot on drop.
+pub(super) struct CommandPermit<'a>(&'a CommandLimiter);
+
+impl CommandLimiter {
+    pub(super) const fn new() -> Self {
+        Self { active: AtomicUsize::new(0) }
+    }
+
+    /// Take a slot if fewer than `limit` commands are running.
+    pub(super) fn try_acquire(&self, limit: usize) -> Result<CommandPermit<'_>, String> {
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < limit).then_some(n + 1))
+            .map(|_| CommandPermit(self))
+            .map_err(|running| {
+                format!("too many concurrent commands ({running} running, limit {limit}); wait for one to finish")
+            })
+    }
+
+    #[cfg(test)]
+    pub(super) fn active(&self) -> usize {
+        self.active.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for CommandPermit<'_> {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// 读取 general 配置中的 `maxConcurrentCommands`，缺省或为 0 时使用默认值。
+pub(super) fn max_concurrent() -> usize {
+    crate::config_commands::read_config("general".into())
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .and_then(|v| v.get("maxConcurrentCommands").and_then(|n| n.as_u64()))
+        .filter(|&n| n > 0)
+        .map_or(DEFAULT_MAX_CONCURRENT, |n| n as usize)
+}
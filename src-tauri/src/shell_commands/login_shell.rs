@@ -0,0 +1,31 @@
+//! `loginShell`: run the command through the user's login shell so PATH entries set up
+//! in profiles (nvm, rbenv, conda, Homebrew on Apple Silicon) are available.
+
+use std::path::Path;
+
+/// POSIX-compatible shells the command string can safely be handed to (not fish/nu).
+const LOGIN_SHELLS: &[&str] = &["bash", "zsh", "ksh", "sh", "dash"];
+
+/// Wrap `command` so the regular `sh -c` invocation re-runs it under `$SHELL -lc`
+/// (bash when `$SHELL` is unset or not POSIX-compatible). `exec` keeps the process
+/// group intact so timeout/cancel still kill the whole tree.
+pub(super) fn wrap_login_shell(command: &str) -> String {
+    format!("exec {} -lc {}", shell_quote(&login_shell_program()), shell_quote(command))
+}
+
+fn login_shell_program() -> String {
+    std::env::var("SHELL")
+        .ok()
+        .filter(|shell| {
+            Path::new(shell)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| LOGIN_SHELLS.contains(&name))
+        })
+        .unwrap_or_else(|| "bash".to_string())
+}
+
+/// Single-quote for POSIX sh: `'` becomes `'\''`.
+pub(super) fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
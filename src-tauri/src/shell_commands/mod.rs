@@ -1,12 +1,28 @@
 //! Shell command execution with cancel support for the bash frontend tool.
 
+mod audit;
 mod cancel;
+mod child;
+mod drain;
+mod limiter;
+mod login_shell;
+mod output_file;
 mod runner;
+mod spawn;
+mod usage;
 
+#[cfg(test)]
+mod tests;
+
+pub use audit::AuditEntry;
 pub use cancel::CancelRegistry;
+pub use spawn::{SpawnCommandArgs, EVENT_COMMAND_EXIT, EVENT_COMMAND_OUTPUT};
+pub(crate) use cancel::CancelToken;
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
+use tauri::Manager;
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,9 +30,20 @@ pub struct RunCommandResult {
     pub stdout: String,
     pub stderr: String,
     pub exit_code: i32,
+    /// Signal that terminated the command (Unix), e.g. 11 for SIGSEGV; `exitCode` is -1 then.
+    /// None for normal exits and when we killed it on timeout/cancel.
+    pub signal: Option<i32>,
     pub timed_out: bool,
     pub cancelled: bool,
     pub sandboxed: bool,
+    /// Peak resident set size of the command's process tree (Unix only)
+    pub max_rss_bytes: Option<u64>,
+    /// User + system CPU time (Unix only)
+    pub cpu_time_ms: Option<u64>,
+    /// Workspace-relative file that received stdout/stderr (`outputToFile`);
+    /// `stdout`/`stderr` are empty in that case
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_file: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,19 +57,84 @@ pub struct RunCommandArgs {
     pub timeout_ms: Option<u64>,
     #[serde(default)]
     pub cancel_token: Option<String>,
+    /// 日志关联 id，并发执行时作为 `[req {id}]` 前缀
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// 不继承应用环境变量，只设置 PATH、HOME 与临时目录变量
+    #[serde(default)]
+    pub clean_env: bool,
+    /// 将 stdout/stderr 写入该工作区相对路径的文件，返回空输出与文件路径（避免超大 IPC 负载）
+    #[serde(default)]
+    pub output_to_file: Option<String>,
+    /// Run via the user's login shell (`$SHELL -lc`, default bash) so profile-managed PATH
+    /// entries (nvm, rbenv, conda, Homebrew) resolve; slower to start than the `sh -c` default
+    #[serde(default)]
+    pub login_shell: bool,
+    /// On timeout or cancel, send SIGTERM and wait a short grace period before SIGKILL so the
+    /// program can flush output and clean up (Unix); default kills immediately
+    #[serde(default)]
+    pub graceful_shutdown: bool,
+}
+
+/// Structured invocation: `program` is spawned directly with `args`, never via a shell.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunProgramArgs {
+    pub workspace_root: String,
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub workdir: Option<String>,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub cancel_token: Option<String>,
+    /// 日志关联 id，并发执行时作为 `[req {id}]` 前缀
+    #[serde(default)]
+    pub request_id: Option<String>,
+}
+
+/// Where command audit entries go; None while auditing is disabled.
+pub(crate) fn audit_log_path(app: &tauri::AppHandle) -> Option<PathBuf> {
+    if !audit::audit_enabled() {
+        return None;
+    }
+    app.path().app_data_dir().ok().map(|d| audit::audit_path(&d))
+}
+
+/// Synchronous `run_command` for in-process callers (Lua `workspace.sh`): takes a
+/// `maxConcurrentCommands` slot for the duration and writes the same audit entry.
+pub(crate) fn execute_limited(
+    args: &RunCommandArgs,
+    audit_log: Option<&Path>,
+) -> Result<RunCommandResult, String> {
+    let _permit = limiter::COMMAND_LIMITER.try_acquire(limiter::max_concurrent())?;
+    runner::execute_logged(args, None, audit_log)
 }
 
+/// Fails fast with "too many concurrent commands" once `maxConcurrentCommands`
+/// (default 8, shared with `run_program`) are already running.
 #[tauri::command]
 pub async fn run_command(
+    app: tauri::AppHandle,
     args: RunCommandArgs,
     state: tauri::State<'_, Arc<CancelRegistry>>,
 ) -> Result<RunCommandResult, String> {
+    let permit = limiter::COMMAND_LIMITER.try_acquire(limiter::max_concurrent())?;
     let token = args.cancel_token.as_deref().map(|key| state.register(key));
     let token_key = args.cancel_token.clone();
     let registry = Arc::clone(&state);
+    let audit_log = audit_log_path(&app);
+
+    let output_event = args
+        .output_to_file
+        .as_deref()
+        .and_then(|rel| output_file::change_event(&args.workspace_root, rel));
 
     let result = tauri::async_runtime::spawn_blocking(move || {
-        runner::execute(&args, token)
+        let _permit = permit;
+        runner::execute_logged(&args, token, audit_log.as_deref())
     })
     .await
     .map_err(|e| format!("task join error: {e}"))?;
@@ -50,249 +142,100 @@ pub async fn run_command(
     if let Some(key) = token_key {
         registry.remove(&key);
     }
+    if let (Ok(_), Some(payload)) = (&result, output_event) {
+        use tauri::Emitter;
+        let _ = app.emit(crate::workspace_watcher::EVENT_WORKSPACE_FILE_CHANGED, payload);
+    }
     result
 }
 
+/// Run a program without `sh -c`, inside the sandbox and its own process group.
 #[tauri::command]
-pub fn cancel_command(
-    token: String,
+pub async fn run_program(
+    app: tauri::AppHandle,
+    args: RunProgramArgs,
     state: tauri::State<'_, Arc<CancelRegistry>>,
-) -> bool {
-    state.cancel(&token)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+) -> Result<RunCommandResult, String> {
+    let permit = limiter::COMMAND_LIMITER.try_acquire(limiter::max_concurrent())?;
+    let token = args.cancel_token.as_deref().map(|key| state.register(key));
+    let token_key = args.cancel_token.clone();
+    let registry = Arc::clone(&state);
+    let audit_log = audit_log_path(&app);
 
-    #[test]
-    fn args_deserialize_camel_case() {
-        let json = r#"{"workspaceRoot":"/tmp","command":"echo hi","workdir":"sub","timeoutMs":5000}"#;
-        let args: RunCommandArgs = serde_json::from_str(json).unwrap();
-        assert_eq!(args.workspace_root, "/tmp");
-        assert_eq!(args.command, "echo hi");
-        assert_eq!(args.workdir.as_deref(), Some("sub"));
-        assert_eq!(args.timeout_ms, Some(5000));
-        assert!(args.cancel_token.is_none());
-    }
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let _permit = permit;
+        runner::execute_program_logged(&args, token, audit_log.as_deref())
+    })
+    .await
+    .map_err(|e| format!("task join error: {e}"))?;
 
-    #[test]
-    fn args_defaults_for_optional_fields() {
-        let json = r#"{"workspaceRoot":"/tmp","command":"echo"}"#;
-        let args: RunCommandArgs = serde_json::from_str(json).unwrap();
-        assert!(args.workdir.is_none());
-        assert!(args.timeout_ms.is_none());
-        assert!(args.cancel_token.is_none());
+    if let Some(key) = token_key {
+        registry.remove(&key);
     }
+    result
+}
 
-    #[test]
-    fn args_with_cancel_token() {
-        let json = r#"{"workspaceRoot":"/tmp","command":"sleep 60","cancelToken":"abc-123"}"#;
-        let args: RunCommandArgs = serde_json::from_str(json).unwrap();
-        assert_eq!(args.cancel_token.as_deref(), Some("abc-123"));
-    }
+/// Most recent command audit entries (oldest first). Empty when logging was never enabled.
+#[tauri::command]
+pub fn read_command_audit(
+    app: tauri::AppHandle,
+    limit: Option<usize>,
+) -> Result<Vec<AuditEntry>, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(audit::read_recent(&audit::audit_path(&dir), limit))
+}
 
-    #[test]
-    fn result_serializes_camel_case() {
-        let r = RunCommandResult {
-            stdout: String::new(),
-            stderr: String::new(),
-            exit_code: 0,
-            timed_out: false,
-            cancelled: false,
-            sandboxed: true,
-        };
-        let json = serde_json::to_string(&r).unwrap();
-        assert!(json.contains("exitCode"));
-        assert!(json.contains("timedOut"));
-        assert!(json.contains("cancelled"));
-        assert!(json.contains("sandboxed"));
-        assert!(!json.contains("exit_code"));
-        assert!(!json.contains("timed_out"));
-    }
+/// Forwards streaming callbacks to the frontend as Tauri events.
+struct EventSink(tauri::AppHandle);
 
-    #[test]
-    fn cancel_registry_register_and_cancel() {
-        let reg = CancelRegistry::new();
-        let token = reg.register("test-1");
-        assert!(!token.is_cancelled());
-        assert!(reg.cancel("test-1"));
-        assert!(token.is_cancelled());
+impl spawn::SpawnSink for EventSink {
+    fn output(&self, payload: spawn::CommandOutputPayload) {
+        use tauri::Emitter;
+        let _ = self.0.emit(EVENT_COMMAND_OUTPUT, payload);
     }
 
-    #[test]
-    fn cancel_registry_missing_key_returns_false() {
-        let reg = CancelRegistry::new();
-        assert!(!reg.cancel("nonexistent"));
+    fn exit(&self, payload: spawn::CommandExitPayload) {
+        use tauri::Emitter;
+        let _ = self.0.emit(EVENT_COMMAND_EXIT, payload);
     }
+}
 
-    #[test]
-    fn cancel_registry_remove() {
-        let reg = CancelRegistry::new();
-        let _token = reg.register("rm-1");
-        reg.remove("rm-1");
-        assert!(!reg.cancel("rm-1"));
+/// Start a long-running command and return its cancel token immediately.
+/// Output arrives as `command-output` events; `command-exit` fires once it ends.
+/// Stop it with `cancel_command(token)`. Holds a `maxConcurrentCommands` slot until
+/// the child exits, so long-running spawns count against the same limit.
+#[tauri::command]
+pub fn spawn_command(
+    app: tauri::AppHandle,
+    args: SpawnCommandArgs,
+    state: tauri::State<'_, Arc<CancelRegistry>>,
+) -> Result<String, String> {
+    let permit = limiter::COMMAND_LIMITER.try_acquire(limiter::max_concurrent())?;
+    let key = args.cancel_token.clone().unwrap_or_else(spawn::generate_token);
+    let token = state.register(&key);
+    let registry = Arc::clone(&state);
+    let cleanup_key = key.clone();
+    let result = spawn::spawn_streaming(&args, key.clone(), token, Arc::new(EventSink(app)), move || {
+        registry.remove(&cleanup_key);
+        drop(permit);
+    });
+    if let Err(e) = result {
+        state.remove(&key);
+        return Err(e);
     }
+    Ok(key)
+}
 
-    // ── Integration tests (Unix only) ──────────────────────────────
-
-    #[cfg(unix)]
-    mod integration {
-        use super::super::*;
-        use crate::test_util::with_home;
-
-        fn run(args: RunCommandArgs) -> Result<RunCommandResult, String> {
-            runner::execute(&args, None)
-        }
-
-        #[test]
-        fn echo_captures_stdout() {
-            with_home(|_| {
-                let dir = tempfile::tempdir().unwrap();
-                let root = dir.path().canonicalize().unwrap();
-                let r = run(RunCommandArgs {
-                    workspace_root: root.to_str().unwrap().to_string(),
-                    command: "echo hello".into(),
-                    workdir: None,
-                    timeout_ms: Some(10_000),
-                    cancel_token: None,
-                }).unwrap();
-                assert_eq!(r.exit_code, 0);
-                assert_eq!(r.stdout.trim(), "hello");
-                assert!(!r.timed_out);
-                assert!(!r.cancelled);
-            });
-        }
-
-        #[test]
-        fn stderr_captured() {
-            with_home(|_| {
-                let dir = tempfile::tempdir().unwrap();
-                let root = dir.path().canonicalize().unwrap();
-                let r = run(RunCommandArgs {
-                    workspace_root: root.to_str().unwrap().to_string(),
-                    command: "echo err >&2".into(),
-                    workdir: None,
-                    timeout_ms: Some(10_000),
-                    cancel_token: None,
-                }).unwrap();
-                assert!(r.stderr.contains("err"));
-            });
-        }
-
-        #[test]
-        fn exit_code_nonzero() {
-            with_home(|_| {
-                let dir = tempfile::tempdir().unwrap();
-                let root = dir.path().canonicalize().unwrap();
-                let r = run(RunCommandArgs {
-                    workspace_root: root.to_str().unwrap().to_string(),
-                    command: "exit 42".into(),
-                    workdir: None,
-                    timeout_ms: Some(10_000),
-                    cancel_token: None,
-                }).unwrap();
-                assert_eq!(r.exit_code, 42);
-            });
-        }
-
-        #[test]
-        fn timeout_kills_long_command() {
-            with_home(|_| {
-                let dir = tempfile::tempdir().unwrap();
-                let root = dir.path().canonicalize().unwrap();
-                let r = run(RunCommandArgs {
-                    workspace_root: root.to_str().unwrap().to_string(),
-                    command: "sleep 60".into(),
-                    workdir: None,
-                    timeout_ms: Some(500),
-                    cancel_token: None,
-                }).unwrap();
-                assert!(r.timed_out);
-                assert!(!r.cancelled);
-                assert_eq!(r.exit_code, -1);
-            });
-        }
-
-        #[test]
-        fn workdir_outside_workspace_rejected() {
-            with_home(|_| {
-                let dir = tempfile::tempdir().unwrap();
-                let root = dir.path().canonicalize().unwrap();
-                let r = run(RunCommandArgs {
-                    workspace_root: root.to_str().unwrap().to_string(),
-                    command: "pwd".into(),
-                    workdir: Some("/tmp".into()),
-                    timeout_ms: Some(5_000),
-                    cancel_token: None,
-                });
-                assert!(r.is_err());
-            });
-        }
-
-        #[test]
-        fn default_workdir_is_workspace_root() {
-            with_home(|_| {
-                let dir = tempfile::tempdir().unwrap();
-                let root = dir.path().canonicalize().unwrap();
-                let r = run(RunCommandArgs {
-                    workspace_root: root.to_str().unwrap().to_string(),
-                    command: "pwd".into(),
-                    workdir: None,
-                    timeout_ms: Some(10_000),
-                    cancel_token: None,
-                }).unwrap();
-                assert_eq!(r.stdout.trim(), root.to_str().unwrap());
-            });
-        }
-
-        #[test]
-        fn cancel_stops_running_command() {
-            with_home(|_| {
-                let dir = tempfile::tempdir().unwrap();
-                let root = dir.path().canonicalize().unwrap();
-                let token = cancel::CancelToken::new();
-                let token_clone = token.clone();
-                std::thread::spawn(move || {
-                    std::thread::sleep(std::time::Duration::from_millis(200));
-                    token_clone.cancel();
-                });
-                let r = runner::execute(&RunCommandArgs {
-                    workspace_root: root.to_str().unwrap().to_string(),
-                    command: "sleep 60".into(),
-                    workdir: None,
-                    timeout_ms: Some(30_000),
-                    cancel_token: None,
-                }, Some(token)).unwrap();
-                assert!(r.cancelled);
-                assert!(!r.timed_out);
-                assert_eq!(r.exit_code, -1);
-            });
-        }
+#[tauri::command]
+pub fn cancel_command(
+    token: String,
+    state: tauri::State<'_, Arc<CancelRegistry>>,
+) -> bool {
+    state.cancel(&token)
+}
 
-        /// Regression: orphan process holding pipe FD must not block execute().
-        /// drain_pipes_with_timeout closes FDs after 3s, so total time < 7s.
-        #[test]
-        fn orphan_holding_pipe_does_not_block_drain() {
-            with_home(|_| {
-                let dir = tempfile::tempdir().unwrap();
-                let root = dir.path().canonicalize().unwrap();
-                let start = std::time::Instant::now();
-                // Orphan `sleep 300` inherits pipe FD, keeping it open after
-                // the parent shell exits. execute() must still return promptly.
-                let r = run(RunCommandArgs {
-                    workspace_root: root.to_str().unwrap().to_string(),
-                    command: "echo ok; (sleep 300 &)".into(),
-                    workdir: None,
-                    timeout_ms: Some(10_000),
-                    cancel_token: None,
-                }).unwrap();
-                let elapsed = start.elapsed();
-                assert_eq!(r.exit_code, 0);
-                assert!(!r.timed_out);
-                // Must return well before the 10s timeout — drain closes FDs after 3s.
-                assert!(elapsed.as_secs() < 7, "took {:?}, expected < 7s", elapsed);
-            });
-        }
-    }
+/// Cancel every running command (e.g. a global "Stop"), returning how many were signalled.
+#[tauri::command]
+pub fn cancel_all_commands(state: tauri::State<'_, Arc<CancelRegistry>>) -> usize {
+    state.cancel_all()
 }
@@ -0,0 +1,40 @@
+//! `outputToFile`: send a command's stdout/stderr straight into a workspace file
+//! instead of buffering it for the IPC response.
+
+use std::fs::{self, File};
+use std::process::Stdio;
+
+use crate::fs_commands::ensure_inside_workspace_may_not_exist;
+use crate::workspace_watcher::{FileChangeKind, WorkspaceFileChangedPayload};
+
+/// Validate `rel` against the workspace and create (or truncate) the file,
+/// creating missing parent directories.
+pub(super) fn open_output_file(workspace_root: &str, rel: &str) -> Result<File, String> {
+    let abs = ensure_inside_workspace_may_not_exist(workspace_root, rel)
+        .map_err(|e| format!("{:?}", e))?;
+    if abs.is_dir() {
+        return Err(format!("outputToFile points to a directory: {rel}"));
+    }
+    if let Some(parent) = abs.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+    }
+    File::create(&abs).map_err(|e| format!("failed to create output file {rel}: {e}"))
+}
+
+/// Child stdout/stderr: pipes by default, or both sharing `output` so lines interleave
+/// in the order the command wrote them.
+pub(super) fn child_stdio(output: Option<&File>) -> std::io::Result<(Stdio, Stdio)> {
+    match output {
+        None => Ok((Stdio::piped(), Stdio::piped())),
+        Some(f) => Ok((f.try_clone()?.into(), f.try_clone()?.into())),
+    }
+}
+
+/// Change event for the output file, decided before the command runs:
+/// `Create` when the file does not exist yet, `Modify` otherwise.
+pub(super) fn change_event(workspace_root: &str, rel: &str) -> Option<WorkspaceFileChangedPayload> {
+    let abs = ensure_inside_workspace_may_not_exist(workspace_root, rel).ok()?;
+    let kind = if abs.exists() { FileChangeKind::Modify } else { FileChangeKind::Create };
+    let path = rel.replace('\\', "/").trim_start_matches("./").to_string();
+    Some(WorkspaceFileChangedPayload { path, kind })
+}
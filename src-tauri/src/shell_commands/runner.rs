@@ -1,20 +1,92 @@
-// FILE_SIZE_EXCEPTION: Windows-specific platform branches (Git Bash detection, PATH separator, taskkill) add ~30 lines
-//! Core execution: spawn, poll, kill, drain for shell commands.
+//! Core execution: spawn, poll, kill, drain for shell commands (spawning lives in
+//! `child`, pipe draining in `drain`).
 
-use std::io::Read;
-use std::process::{ChildStderr, ChildStdout, Command, Stdio};
+use std::process::ExitStatus;
 use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 
 use crate::fs_commands::ensure_inside_workspace_exists;
-use crate::sandbox;
 
 use super::cancel::CancelToken;
+use super::child::{kill_process_group, spawn_child, spawn_program_child, terminate_with_grace};
+use super::drain::drain_pipes;
+use super::output_file::open_output_file;
+use super::usage::{reap, try_reap};
 use super::RunCommandArgs;
 use super::RunCommandResult;
+use super::RunProgramArgs;
+
+/// `execute`, plus an audit entry appended to `audit_log` when given.
+pub fn execute_logged(
+    args: &RunCommandArgs,
+    cancel: Option<CancelToken>,
+    audit_log: Option<&std::path::Path>,
+) -> Result<RunCommandResult, String> {
+    let req = crate::log_context::req_prefix(args.request_id.as_deref());
+    log::info!("{req}[shell] running: {}", args.command);
+    let started = std::time::Instant::now();
+    let result = execute(args, cancel)?;
+    log_finished(&req, &result, started);
+    if let Some(path) = audit_log {
+        append_audit(path, &args.command, &args.workspace_root, args.workdir.as_deref(), &result, started);
+    }
+    Ok(result)
+}
 
-const DRAIN_TIMEOUT: Duration = Duration::from_secs(3);
+/// `execute_program`, plus an audit entry (program and args joined by spaces).
+pub fn execute_program_logged(
+    args: &RunProgramArgs,
+    cancel: Option<CancelToken>,
+    audit_log: Option<&std::path::Path>,
+) -> Result<RunCommandResult, String> {
+    let req = crate::log_context::req_prefix(args.request_id.as_deref());
+    log::info!("{req}[shell] running program: {} with {} args", args.program, args.args.len());
+    let started = std::time::Instant::now();
+    let result = execute_program(args, cancel)?;
+    log_finished(&req, &result, started);
+    if let Some(path) = audit_log {
+        let mut command = args.program.clone();
+        for a in &args.args {
+            command.push(' ');
+            command.push_str(a);
+        }
+        append_audit(path, &command, &args.workspace_root, args.workdir.as_deref(), &result, started);
+    }
+    Ok(result)
+}
+
+fn log_finished(req: &str, result: &RunCommandResult, started: std::time::Instant) {
+    log::info!(
+        "{req}[shell] exit {} in {}ms (timed_out={}, cancelled={})",
+        result.exit_code,
+        started.elapsed().as_millis(),
+        result.timed_out,
+        result.cancelled
+    );
+}
+
+fn append_audit(
+    path: &std::path::Path,
+    command: &str,
+    workspace_root: &str,
+    workdir: Option<&str>,
+    result: &RunCommandResult,
+    started: std::time::Instant,
+) {
+    let workdir = std::path::Path::new(workspace_root).join(workdir.unwrap_or("."));
+    let entry = super::audit::AuditEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        command: command.to_string(),
+        workdir: workdir.to_string_lossy().into_owned(),
+        exit_code: result.exit_code,
+        sandboxed: result.sandboxed,
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    if let Err(e) = super::audit::append_entry(path, &entry) {
+        log::warn!("failed to write command audit log: {e}");
+    }
+}
 
 /// Execute a shell command with timeout and cancel support.
 pub fn execute(args: &RunCommandArgs, cancel: Option<CancelToken>) -> Result<RunCommandResult, String> {
@@ -22,43 +94,115 @@ pub fn execute(args: &RunCommandArgs, cancel: Option<CancelToken>) -> Result<Run
     let abs = ensure_inside_workspace_exists(&args.workspace_root, workdir)
         .map_err(|e| format!("{:?}", e))?;
     let workdir_path = abs.to_string_lossy().to_string();
+    if is_cancelled(cancel.as_ref()) {
+        return Ok(cancelled_before_spawn());
+    }
 
-    let timeout_ms = args.timeout_ms.unwrap_or(120_000).min(600_000);
-    let timeout = Duration::from_millis(timeout_ms);
+    let output = match args.output_to_file.as_deref() {
+        Some(rel) => Some(open_output_file(&args.workspace_root, rel)?),
+        None => None,
+    };
 
-    let path_env = build_path_env();
+    let timeout = effective_timeout(args.timeout_ms);
+    let command = if args.login_shell {
+        super::login_shell::wrap_login_shell(&args.command)
+    } else {
+        args.command.clone()
+    };
+    let (child, sandboxed) = spawn_child(
+        &command,
+        &args.workspace_root,
+        &workdir_path,
+        args.clean_env,
+        output.as_ref(),
+    )?;
+    let mut result = wait_child(child, sandboxed, timeout, cancel, args.graceful_shutdown)?;
+    if output.is_some() {
+        result.output_file = args.output_to_file.clone();
+    }
+    Ok(result)
+}
 
-    // On Windows, verify Git Bash is available before attempting to spawn.
-    // Installation is handled at startup; this is a fast existence check only.
-    #[cfg(windows)]
-    if crate::git_bash_installer::find_git_bash().is_none() {
-        return Err(
-            "Git Bash 未就绪。请查看应用顶部的提示安装 Git for Windows。".to_string(),
-        );
+/// Execute `program` with `args` directly (no shell), with timeout and cancel support.
+pub fn execute_program(
+    args: &RunProgramArgs,
+    cancel: Option<CancelToken>,
+) -> Result<RunCommandResult, String> {
+    let workdir = args.workdir.as_deref().unwrap_or(".");
+    let abs = ensure_inside_workspace_exists(&args.workspace_root, workdir)
+        .map_err(|e| format!("{:?}", e))?;
+    let workdir_path = abs.to_string_lossy().to_string();
+    if is_cancelled(cancel.as_ref()) {
+        return Ok(cancelled_before_spawn());
     }
 
-    let mut policy = sandbox::load_policy();
-    policy.allow_write.extend(crate::officellm::env::sandbox_temp_whitelist());
-    let sandbox_cmd = sandbox::build_sandbox_command(&args.command, &args.workspace_root, &policy);
+    let timeout = effective_timeout(args.timeout_ms);
+    let (child, sandboxed) =
+        spawn_program_child(&args.program, &args.args, &args.workspace_root, &workdir_path)?;
+    wait_child(child, sandboxed, timeout, cancel, false)
+}
 
-    let (mut child, sandboxed) = if let Some((program, sb_args)) = sandbox_cmd {
-        match spawn_command_with_pgid(&program, &sb_args, &workdir_path, &path_env) {
-            Ok(c) => (c, true),
-            Err(_) => {
-                let c = spawn_plain_command(&args.command, &workdir_path, &path_env)
-                    .map_err(|e| e.to_string())?;
-                (c, false)
-            }
-        }
-    } else {
-        let c = spawn_plain_command(&args.command, &workdir_path, &path_env)
-            .map_err(|e| e.to_string())?;
-        (c, false)
-    };
+fn is_cancelled(cancel: Option<&CancelToken>) -> bool {
+    cancel.is_some_and(CancelToken::is_cancelled)
+}
 
+/// Result for a cancel that arrived before the child was spawned: nothing runs.
+fn cancelled_before_spawn() -> RunCommandResult {
+    RunCommandResult {
+        stdout: String::new(),
+        stderr: String::new(),
+        exit_code: -1,
+        signal: None,
+        timed_out: false,
+        cancelled: true,
+        sandboxed: false,
+        max_rss_bytes: None,
+        cpu_time_ms: None,
+        output_file: None,
+    }
+}
+
+#[cfg(unix)]
+fn exit_signal(status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: &ExitStatus) -> Option<i32> {
+    None
+}
+
+/// `(exit_code, signal)` for a finished child. On Linux the sandboxed child is the bwrap
+/// wrapper, which reports a command killed by signal n as exit code 128+n; map that back
+/// so `signal` is set the same way with and without the sandbox.
+pub(super) fn exit_code_and_signal(status: &ExitStatus, sandboxed: bool) -> (i32, Option<i32>) {
+    if let Some(signal) = exit_signal(status) {
+        return (-1, Some(signal));
+    }
+    let code = status.code().unwrap_or(-1);
+    match code {
+        129..=192 if sandboxed && cfg!(target_os = "linux") => (-1, Some(code - 128)),
+        _ => (code, None),
+    }
+}
+
+fn effective_timeout(timeout_ms: Option<u64>) -> Duration {
+    Duration::from_millis(timeout_ms.unwrap_or(120_000).min(600_000))
+}
+
+/// Poll `child` until it exits, times out, or is cancelled; then collect its output.
+/// With `graceful` a timed-out or cancelled child gets SIGTERM and `TERM_GRACE` to exit.
+fn wait_child(
+    mut child: std::process::Child,
+    sandboxed: bool,
+    timeout: Duration,
+    cancel: Option<CancelToken>,
+    graceful: bool,
+) -> Result<RunCommandResult, String> {
     let pid = child.id();
-    let stdout = child.stdout.take().ok_or("stdout pipe")?;
-    let stderr = child.stderr.take().ok_or("stderr pipe")?;
+    // No pipes when output goes to a file (`outputToFile`)
+    let pipes = child.stdout.take().zip(child.stderr.take());
 
     // Timeout timer
     let (tx, rx) = mpsc::channel();
@@ -70,250 +214,53 @@ pub fn execute(args: &RunCommandArgs, cancel: Option<CancelToken>) -> Result<Run
     // Poll loop: check exit, timeout, and cancel
     let mut cancelled = false;
     loop {
-        if let Ok(Some(status)) = child.try_wait() {
-            let (out, err) = drain_pipes_with_timeout(stdout, stderr);
+        if let Some((status, usage)) = try_reap(&mut child) {
+            let (out, err) = drain_pipes(pipes);
+            let (exit_code, signal) = exit_code_and_signal(&status, sandboxed);
             return Ok(RunCommandResult {
                 stdout: out,
                 stderr: err,
-                exit_code: status.code().unwrap_or(-1),
+                exit_code,
+                signal,
                 timed_out: false,
                 cancelled: false,
                 sandboxed,
+                max_rss_bytes: usage.map(|u| u.max_rss_bytes),
+                cpu_time_ms: usage.map(|u| u.cpu_time_ms),
+                output_file: None,
             });
         }
         if rx.try_recv().is_ok() {
             break;
         }
-        if let Some(ref ct) = cancel {
-            if ct.is_cancelled() {
-                cancelled = true;
-                break;
-            }
+        if is_cancelled(cancel.as_ref()) {
+            cancelled = true;
+            break;
         }
         thread::sleep(Duration::from_millis(50));
     }
 
-    // Kill the entire process group, then the child directly as fallback
-    kill_process_group(pid);
-    let _ = child.kill();
-    let _ = child.wait();
+    let usage = match graceful.then(|| terminate_with_grace(&mut child, pid)).flatten() {
+        Some(usage) => usage,
+        None => {
+            // Kill the entire process group, then the child directly as fallback
+            kill_process_group(pid);
+            let _ = child.kill();
+            reap(&mut child)
+        }
+    };
 
-    let (out, err) = drain_pipes_with_timeout(stdout, stderr);
+    let (out, err) = drain_pipes(pipes);
     Ok(RunCommandResult {
         stdout: out,
         stderr: err,
         exit_code: -1,
+        signal: None,
         timed_out: !cancelled,
         cancelled,
         sandboxed,
+        max_rss_bytes: usage.map(|u| u.max_rss_bytes),
+        cpu_time_ms: usage.map(|u| u.cpu_time_ms),
+        output_file: None,
     })
 }
-
-/// Build PATH with sidecar dir and platform-specific extras prepended.
-fn build_path_env() -> String {
-    let mut extra_paths: Vec<std::path::PathBuf> = Vec::new();
-
-    // Sidecar dir (bundled tools: officellm, pdftoppm, pdftotext, quarto)
-    if let Some(dir) = crate::sidecar::sidecar_dir() {
-        extra_paths.push(dir);
-    }
-
-    // ~/.local/bin (Unix convention)
-    #[cfg(not(windows))]
-    if let Some(home) = dirs::home_dir() {
-        let local_bin = home.join(".local/bin");
-        if local_bin.is_dir() {
-            extra_paths.push(local_bin);
-        }
-    }
-
-    // Windows: inject Git Bash bin dirs so Unix tools (grep, sed, awk, curl…) are available.
-    #[cfg(windows)]
-    if let Some(bash) = crate::git_bash_installer::find_git_bash() {
-        extra_paths.extend(crate::git_bash_installer::git_bash_extra_paths(&bash));
-    }
-
-    let current = std::env::var("PATH").unwrap_or_default();
-    if extra_paths.is_empty() {
-        return current;
-    }
-    let extra: Vec<String> = extra_paths
-        .iter()
-        .map(|p| p.to_string_lossy().into_owned())
-        .collect();
-
-    #[cfg(windows)]
-    let sep = ";";
-    #[cfg(not(windows))]
-    let sep = ":";
-
-    format!("{}{sep}{current}", extra.join(sep))
-}
-
-/// Spawn a plain shell command in its own process group (Unix) or via Git Bash (Windows).
-fn spawn_plain_command(
-    cmd: &str,
-    workdir: &str,
-    path_env: &str,
-) -> std::io::Result<std::process::Child> {
-    #[cfg(unix)]
-    let (shell, shell_arg): (std::borrow::Cow<str>, &str) = ("sh".into(), "-c");
-    #[cfg(windows)]
-    let (shell, shell_arg): (std::borrow::Cow<str>, &str) = {
-        let bash = crate::git_bash_installer::find_git_bash()
-            .map(|p| p.to_string_lossy().into_owned())
-            .unwrap_or_else(|| "bash".to_string());
-        (bash.into(), "-c")
-    };
-
-    let mut command = Command::new(shell.as_ref());
-    command
-        .arg(shell_arg)
-        .arg(cmd)
-        .current_dir(workdir)
-        .env("PATH", path_env)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .stdin(Stdio::null());
-
-    #[cfg(unix)]
-    {
-        use std::os::unix::process::CommandExt;
-        unsafe {
-            command.pre_exec(|| {
-                libc::setsid();
-                Ok(())
-            });
-        }
-    }
-
-    command.spawn()
-}
-
-/// Spawn a sandboxed command in its own process group (Unix).
-fn spawn_command_with_pgid(
-    program: &str,
-    sb_args: &[String],
-    workdir: &str,
-    path_env: &str,
-) -> std::io::Result<std::process::Child> {
-    let mut command = Command::new(program);
-    command
-        .args(sb_args)
-        .current_dir(workdir)
-        .env("PATH", path_env)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .stdin(Stdio::null());
-
-    #[cfg(unix)]
-    {
-        use std::os::unix::process::CommandExt;
-        unsafe {
-            command.pre_exec(|| {
-                libc::setsid();
-                Ok(())
-            });
-        }
-    }
-
-    command.spawn()
-}
-
-/// Kill an entire process group via SIGKILL (Unix).
-#[cfg(unix)]
-fn kill_process_group(pid: u32) {
-    unsafe {
-        libc::killpg(pid as libc::pid_t, libc::SIGKILL);
-    }
-}
-
-#[cfg(not(unix))]
-fn kill_process_group(pid: u32) {
-    // Kill the entire process tree including child processes.
-    let _ = std::process::Command::new("taskkill")
-        .args(["/F", "/T", "/PID", &pid.to_string()])
-        .output();
-}
-
-/// Thin wrapper around a raw FD that implements Read but does NOT close on drop.
-/// The caller is responsible for closing the FD after the drain threads finish.
-#[cfg(unix)]
-struct RawPipeReader {
-    fd: libc::c_int,
-}
-
-#[cfg(unix)]
-impl Read for RawPipeReader {
-    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let n = unsafe {
-            libc::read(self.fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len())
-        };
-        if n < 0 { Err(std::io::Error::last_os_error()) } else { Ok(n as usize) }
-    }
-}
-
-// SAFETY: the FD is only used by the single thread that owns the RawPipeReader.
-#[cfg(unix)]
-unsafe impl Send for RawPipeReader {}
-
-/// Drain stdout/stderr pipes with a timeout to avoid blocking forever.
-/// After the timeout, FDs are closed to force any stuck reader threads to exit,
-/// preventing thread accumulation when orphan processes hold pipe handles.
-fn drain_pipes_with_timeout(stdout: ChildStdout, stderr: ChildStderr) -> (String, String) {
-    #[cfg(unix)]
-    {
-        use std::os::unix::io::IntoRawFd;
-        let out_fd = stdout.into_raw_fd();
-        let err_fd = stderr.into_raw_fd();
-
-        let (tx_out, rx_out) = mpsc::channel();
-        let (tx_err, rx_err) = mpsc::channel();
-
-        thread::spawn(move || {
-            let mut r = RawPipeReader { fd: out_fd };
-            let mut buf = String::new();
-            let _ = r.read_to_string(&mut buf);
-            let _ = tx_out.send(buf);
-        });
-        thread::spawn(move || {
-            let mut r = RawPipeReader { fd: err_fd };
-            let mut buf = String::new();
-            let _ = r.read_to_string(&mut buf);
-            let _ = tx_err.send(buf);
-        });
-
-        let out = rx_out.recv_timeout(DRAIN_TIMEOUT).unwrap_or_default();
-        let err = rx_err.recv_timeout(DRAIN_TIMEOUT).unwrap_or_default();
-
-        // Close FDs to unblock threads stuck in read(). RawPipeReader has no
-        // Drop impl, so this is the sole close — no double-close risk.
-        // If the thread already finished, this harmlessly closes an EOF pipe.
-        unsafe {
-            libc::close(out_fd);
-            libc::close(err_fd);
-        }
-        (out, err)
-    }
-
-    #[cfg(not(unix))]
-    {
-        let (tx_out, rx_out) = mpsc::channel();
-        let (tx_err, rx_err) = mpsc::channel();
-        let mut so = stdout;
-        let mut se = stderr;
-        thread::spawn(move || {
-            let mut buf = String::new();
-            let _ = so.read_to_string(&mut buf);
-            let _ = tx_out.send(buf);
-        });
-        thread::spawn(move || {
-            let mut buf = String::new();
-            let _ = se.read_to_string(&mut buf);
-            let _ = tx_err.send(buf);
-        });
-        let out = rx_out.recv_timeout(DRAIN_TIMEOUT).unwrap_or_default();
-        let err = rx_err.recv_timeout(DRAIN_TIMEOUT).unwrap_or_default();
-        (out, err)
-    }
-}
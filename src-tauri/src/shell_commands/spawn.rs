@@ -0,0 +1,139 @@
+//! Long-lived commands (`npm run dev` etc.) whose output is streamed line by line
+//! until the process exits or its cancel token is triggered.
+
+use std::io::{BufRead, BufReader, Read};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::fs_commands::ensure_inside_workspace_exists;
+
+use super::cancel::CancelToken;
+use super::child::{kill_process_group, spawn_child};
+use super::drain::DRAIN_TIMEOUT;
+
+/// 进程输出事件：每行一条
+pub const EVENT_COMMAND_OUTPUT: &str = "command-output";
+/// 进程结束事件（正常退出或被取消）
+pub const EVENT_COMMAND_EXIT: &str = "command-exit";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpawnCommandArgs {
+    pub workspace_root: String,
+    pub command: String,
+    #[serde(default)]
+    pub workdir: Option<String>,
+    /// 自定义取消令牌；缺省时自动生成并返回
+    #[serde(default)]
+    pub cancel_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandOutputPayload {
+    pub token: String,
+    /// `stdout` 或 `stderr`
+    pub stream: &'static str,
+    pub line: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandExitPayload {
+    pub token: String,
+    pub exit_code: i32,
+    pub cancelled: bool,
+    pub sandboxed: bool,
+}
+
+/// Streaming callbacks; the Tauri command forwards them as events.
+pub(crate) trait SpawnSink: Send + Sync + 'static {
+    fn output(&self, payload: CommandOutputPayload);
+    fn exit(&self, payload: CommandExitPayload);
+}
+
+pub(super) fn generate_token() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    format!("spawn-{}-{}", millis, COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Forward lines until EOF or until `closed` is set after the exit event.
+/// The returned receiver disconnects when the pump thread ends.
+fn pump_lines(
+    reader: impl Read + Send + 'static,
+    stream: &'static str,
+    token: String,
+    sink: Arc<dyn SpawnSink>,
+    closed: Arc<AtomicBool>,
+) -> mpsc::Receiver<()> {
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+    thread::spawn(move || {
+        let _done = done_tx;
+        for line in BufReader::new(reader).lines() {
+            let Ok(line) = line else { break };
+            if closed.load(Ordering::Relaxed) {
+                break;
+            }
+            sink.output(CommandOutputPayload { token: token.clone(), stream, line });
+        }
+    });
+    done_rx
+}
+
+/// Start the process and return immediately; a monitor thread streams output
+/// and reports the exit. `on_finish` runs after the exit event (registry cleanup).
+pub(crate) fn spawn_streaming(
+    args: &SpawnCommandArgs,
+    token_key: String,
+    cancel: CancelToken,
+    sink: Arc<dyn SpawnSink>,
+    on_finish: impl FnOnce() + Send + 'static,
+) -> Result<(), String> {
+    let workdir = args.workdir.as_deref().unwrap_or(".");
+    let abs = ensure_inside_workspace_exists(&args.workspace_root, workdir)
+        .map_err(|e| format!("{:?}", e))?;
+    let workdir_path = abs.to_string_lossy().to_string();
+
+    let (mut child, sandboxed) = spawn_child(&args.command, &args.workspace_root, &workdir_path, false, None)?;
+    let pid = child.id();
+    let stdout = child.stdout.take().ok_or("stdout pipe")?;
+    let stderr = child.stderr.take().ok_or("stderr pipe")?;
+
+    let closed = Arc::new(AtomicBool::new(false));
+    let out_done = pump_lines(stdout, "stdout", token_key.clone(), Arc::clone(&sink), Arc::clone(&closed));
+    let err_done = pump_lines(stderr, "stderr", token_key.clone(), Arc::clone(&sink), Arc::clone(&closed));
+
+    thread::spawn(move || {
+        let mut cancelled = false;
+        let exit_code = loop {
+            if let Ok(Some(status)) = child.try_wait() {
+                break status.code().unwrap_or(-1);
+            }
+            if cancel.is_cancelled() {
+                cancelled = true;
+                kill_process_group(pid);
+                let _ = child.kill();
+                let _ = child.wait();
+                break -1;
+            }
+            thread::sleep(Duration::from_millis(50));
+        };
+        // 先让剩余输出发完再发 exit；后台子进程可能继续持有管道，最多等待 DRAIN_TIMEOUT
+        let deadline = Instant::now() + DRAIN_TIMEOUT;
+        for done in [out_done, err_done] {
+            let _ = done.recv_timeout(deadline.saturating_duration_since(Instant::now()));
+        }
+        closed.store(true, Ordering::Relaxed);
+        sink.exit(CommandExitPayload { token: token_key, exit_code, cancelled, sandboxed });
+        on_finish();
+    });
+    Ok(())
+}
@@ -0,0 +1,893 @@
+// FILE_SIZE_EXCEPTION: unit tests + Unix integration tests for runner, spawn, audit, cancel, limiter and login shell
+use super::*;
+
+#[test]
+fn args_deserialize_camel_case() {
+    let json = r#"{"workspaceRoot":"/tmp","command":"echo hi","workdir":"sub","timeoutMs":5000}"#;
+    let args: RunCommandArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.workspace_root, "/tmp");
+    assert_eq!(args.command, "echo hi");
+    assert_eq!(args.workdir.as_deref(), Some("sub"));
+    assert_eq!(args.timeout_ms, Some(5000));
+    assert!(args.cancel_token.is_none());
+}
+
+#[test]
+fn args_defaults_for_optional_fields() {
+    let json = r#"{"workspaceRoot":"/tmp","command":"echo"}"#;
+    let args: RunCommandArgs = serde_json::from_str(json).unwrap();
+    assert!(args.workdir.is_none());
+    assert!(args.timeout_ms.is_none());
+    assert!(args.cancel_token.is_none());
+    assert!(args.request_id.is_none());
+}
+
+#[test]
+fn args_with_cancel_token() {
+    let json = r#"{"workspaceRoot":"/tmp","command":"sleep 60","cancelToken":"abc-123"}"#;
+    let args: RunCommandArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.cancel_token.as_deref(), Some("abc-123"));
+}
+
+#[test]
+fn args_with_request_id() {
+    let json = r#"{"workspaceRoot":"/tmp","command":"ls","requestId":"req-7"}"#;
+    let args: RunCommandArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.request_id.as_deref(), Some("req-7"));
+
+    let json = r#"{"workspaceRoot":"/tmp","program":"ls","requestId":"req-8"}"#;
+    let args: RunProgramArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.request_id.as_deref(), Some("req-8"));
+}
+
+#[test]
+fn result_serializes_camel_case() {
+    let r = RunCommandResult {
+        stdout: String::new(),
+        stderr: String::new(),
+        exit_code: 0,
+        signal: None,
+        timed_out: false,
+        cancelled: false,
+        sandboxed: true,
+        max_rss_bytes: None,
+        cpu_time_ms: None,
+        output_file: None,
+    };
+    let json = serde_json::to_string(&r).unwrap();
+    assert!(json.contains("exitCode"));
+    assert!(json.contains("timedOut"));
+    assert!(json.contains("cancelled"));
+    assert!(json.contains("sandboxed"));
+    assert!(!json.contains("exit_code"));
+    assert!(!json.contains("timed_out"));
+}
+
+#[test]
+fn cancel_registry_register_and_cancel() {
+    let reg = CancelRegistry::new();
+    let token = reg.register("test-1");
+    assert!(!token.is_cancelled());
+    assert!(reg.cancel("test-1"));
+    assert!(token.is_cancelled());
+}
+
+#[test]
+fn cancel_registry_missing_key_returns_false() {
+    let reg = CancelRegistry::new();
+    assert!(!reg.cancel("nonexistent"));
+}
+
+#[test]
+fn spawn_args_deserialize_camel_case() {
+    let json = r#"{"workspaceRoot":"/tmp","command":"npm run dev","cancelToken":"dev-1"}"#;
+    let args: SpawnCommandArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.command, "npm run dev");
+    assert_eq!(args.cancel_token.as_deref(), Some("dev-1"));
+    assert!(args.workdir.is_none());
+}
+
+#[test]
+fn spawn_generated_tokens_are_unique() {
+    assert_ne!(spawn::generate_token(), spawn::generate_token());
+}
+
+#[test]
+fn audit_log_is_capped_and_read_recent() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = audit::audit_path(dir.path());
+    for i in 0..1005 {
+        let entry = AuditEntry {
+            timestamp: "2026-01-01T00:00:00Z".into(),
+            command: format!("cmd {i}"),
+            workdir: "/w".into(),
+            exit_code: 0,
+            sandboxed: false,
+            duration_ms: 1,
+        };
+        audit::append_entry(&path, &entry).unwrap();
+    }
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content.lines().count(), 1000);
+    let recent = audit::read_recent(&path, Some(2));
+    assert_eq!(recent.len(), 2);
+    assert_eq!(recent[1].command, "cmd 1004");
+    assert!(audit::read_recent(&dir.path().join("missing.jsonl"), None).is_empty());
+}
+
+#[test]
+fn audit_concurrent_appends_keep_every_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = audit::audit_path(dir.path());
+    std::thread::scope(|s| {
+        for t in 0..8 {
+            let path = &path;
+            s.spawn(move || {
+                for i in 0..20 {
+                    let entry = AuditEntry {
+                        timestamp: "2026-01-01T00:00:00Z".into(),
+                        command: format!("cmd {t}-{i}"),
+                        workdir: "/w".into(),
+                        exit_code: 0,
+                        sandboxed: false,
+                        duration_ms: 1,
+                    };
+                    audit::append_entry(path, &entry).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(audit::read_recent(&path, Some(1000)).len(), 160);
+}
+
+#[test]
+fn audit_disabled_by_default() {
+    crate::test_util::with_home(|home| {
+        assert!(!audit::audit_enabled());
+        let cfg = home.join(".cove/config");
+        std::fs::create_dir_all(&cfg).unwrap();
+        std::fs::write(cfg.join("general.json"), r#"{"commandAuditLog":true}"#).unwrap();
+        assert!(audit::audit_enabled());
+    });
+}
+
+#[test]
+fn cancel_registry_remove() {
+    let reg = CancelRegistry::new();
+    let _token = reg.register("rm-1");
+    reg.remove("rm-1");
+    assert!(!reg.cancel("rm-1"));
+}
+
+#[test]
+fn cancel_registry_cancel_all() {
+    let reg = CancelRegistry::new();
+    let tokens: Vec<_> = (0..3).map(|i| reg.register(&format!("all-{i}"))).collect();
+    assert_eq!(reg.cancel_all(), 3);
+    assert!(tokens.iter().all(|t| t.is_cancelled()));
+    assert_eq!(CancelRegistry::new().cancel_all(), 0);
+}
+
+#[test]
+fn limiter_rejects_beyond_limit_and_releases_on_drop() {
+    let limiter = limiter::CommandLimiter::new();
+    let a = limiter.try_acquire(2).unwrap();
+    let _b = limiter.try_acquire(2).unwrap();
+    let Err(err) = limiter.try_acquire(2) else { panic!("third permit should be rejected") };
+    assert!(err.contains("too many concurrent commands"), "{err}");
+    drop(a);
+    assert_eq!(limiter.active(), 1);
+    assert!(limiter.try_acquire(2).is_ok());
+}
+
+#[test]
+fn limiter_bounds_concurrent_callers() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+
+    let limiter = limiter::CommandLimiter::new();
+    let peak = AtomicUsize::new(0);
+    let rejected = AtomicUsize::new(0);
+    let barrier = Barrier::new(20);
+    std::thread::scope(|s| {
+        for _ in 0..20 {
+            s.spawn(|| {
+                barrier.wait();
+                match limiter.try_acquire(4) {
+                    Ok(_permit) => {
+                        peak.fetch_max(limiter.active(), Ordering::SeqCst);
+                        std::thread::sleep(std::time::Duration::from_millis(50));
+                    }
+                    Err(_) => {
+                        rejected.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+            });
+        }
+    });
+    assert!(peak.load(Ordering::SeqCst) <= 4);
+    assert!(rejected.load(Ordering::SeqCst) > 0);
+    assert_eq!(limiter.active(), 0);
+}
+
+#[test]
+fn limiter_reads_max_concurrent_from_config() {
+    crate::test_util::with_home(|home| {
+        assert_eq!(limiter::max_concurrent(), limiter::DEFAULT_MAX_CONCURRENT);
+        let cfg = home.join(".cove/config");
+        std::fs::create_dir_all(&cfg).unwrap();
+        std::fs::write(cfg.join("general.json"), r#"{"maxConcurrentCommands":3}"#).unwrap();
+        assert_eq!(limiter::max_concurrent(), 3);
+        std::fs::write(cfg.join("general.json"), r#"{"maxConcurrentCommands":0}"#).unwrap();
+        assert_eq!(limiter::max_concurrent(), limiter::DEFAULT_MAX_CONCURRENT);
+    });
+}
+
+#[test]
+fn login_shell_wraps_quoted_command() {
+    assert_eq!(login_shell::shell_quote("it's"), r"'it'\''s'");
+    let wrapped = login_shell::wrap_login_shell("echo 'hi'");
+    assert!(wrapped.starts_with("exec '"), "{wrapped}");
+    assert!(wrapped.ends_with(r#" -lc 'echo '\''hi'\'''"#), "{wrapped}");
+}
+
+// ── Integration tests (Unix only) ──────────────────────────────
+
+#[cfg(unix)]
+mod integration {
+    use super::super::*;
+    use crate::test_util::with_home;
+
+    fn run(args: RunCommandArgs) -> Result<RunCommandResult, String> {
+        runner::execute(&args, None)
+    }
+
+    fn run_program(root: &std::path::Path, program: &str, args: &[&str]) -> RunCommandResult {
+        runner::execute_program(
+            &RunProgramArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                program: program.into(),
+                args: args.iter().map(|a| a.to_string()).collect(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+            },
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn run_program_echo_without_shell() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let r = run_program(&root, "echo", &["hi"]);
+            assert_eq!(r.exit_code, 0);
+            assert_eq!(r.stdout, "hi\n");
+
+            // 参数原样传递，不做变量展开、命令替换或分词
+            let r = run_program(&root, "echo", &["$HOME", "$(id)", "a b;c"]);
+            assert_eq!(r.stdout, "$HOME $(id) a b;c\n");
+        });
+    }
+
+    #[test]
+    fn run_program_missing_binary_errors() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let r = runner::execute_program(
+                &RunProgramArgs {
+                    workspace_root: root.to_str().unwrap().to_string(),
+                    program: "".into(),
+                    args: vec![],
+                    workdir: None,
+                    timeout_ms: None,
+                    cancel_token: None,
+                    request_id: None,
+                },
+                None,
+            );
+            assert!(r.is_err());
+        });
+    }
+
+    #[test]
+    fn echo_captures_stdout() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let r = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "echo hello".into(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            }).unwrap();
+            assert_eq!(r.exit_code, 0);
+            assert_eq!(r.stdout.trim(), "hello");
+            assert!(!r.timed_out);
+            assert!(!r.cancelled);
+        });
+    }
+
+    #[test]
+    fn clean_env_drops_ambient_vars() {
+        with_home(|home| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            unsafe { std::env::set_var("COVE_TEST_AMBIENT", "leaked") };
+            let run_env = |clean_env| {
+                run(RunCommandArgs {
+                    workspace_root: root.to_str().unwrap().to_string(),
+                    command: "echo \"[$COVE_TEST_AMBIENT][$HOME]\"".into(),
+                    workdir: None,
+                    timeout_ms: Some(10_000),
+                    cancel_token: None,
+                    request_id: None,
+                    clean_env,
+                    output_to_file: None,
+                    login_shell: false,
+                    graceful_shutdown: false,
+                })
+                .unwrap()
+            };
+            let inherited = run_env(false);
+            let clean = run_env(true);
+            unsafe { std::env::remove_var("COVE_TEST_AMBIENT") };
+
+            assert_eq!(inherited.stdout.trim(), format!("[leaked][{}]", home.display()));
+            assert_eq!(clean.stdout.trim(), format!("[][{}]", home.display()));
+        });
+    }
+
+    #[test]
+    fn reports_peak_rss_and_cpu_time() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            // 在 shell 变量中保留约 8MB 数据，抬高进程峰值内存
+            let r = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "x=$(head -c 8000000 /dev/zero | tr '\\0' a); echo ${#x}".into(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            })
+            .unwrap();
+            assert_eq!(r.exit_code, 0, "{}", r.stderr);
+            assert_eq!(r.stdout.trim(), "8000000");
+            assert!(r.max_rss_bytes.unwrap() > 0);
+            assert!(r.cpu_time_ms.is_some());
+        });
+    }
+
+    #[test]
+    fn stderr_captured() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let r = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "echo err >&2".into(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            }).unwrap();
+            assert!(r.stderr.contains("err"));
+        });
+    }
+
+    #[test]
+    fn exit_code_nonzero() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let r = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "exit 42".into(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            }).unwrap();
+            assert_eq!(r.exit_code, 42);
+        });
+    }
+
+    #[test]
+    fn timeout_kills_long_command() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let r = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "sleep 60".into(),
+                workdir: None,
+                timeout_ms: Some(500),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            }).unwrap();
+            assert!(r.timed_out);
+            assert!(!r.cancelled);
+            assert_eq!(r.exit_code, -1);
+        });
+    }
+
+    #[test]
+    fn workdir_outside_workspace_rejected() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let r = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "pwd".into(),
+                workdir: Some("/tmp".into()),
+                timeout_ms: Some(5_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            });
+            assert!(r.is_err());
+        });
+    }
+
+    #[test]
+    fn default_workdir_is_workspace_root() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let r = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "pwd".into(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            }).unwrap();
+            assert_eq!(r.stdout.trim(), root.to_str().unwrap());
+        });
+    }
+
+    #[test]
+    fn cancel_stops_running_command() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let token = cancel::CancelToken::new();
+            let token_clone = token.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                token_clone.cancel();
+            });
+            let r = runner::execute(&RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "sleep 60".into(),
+                workdir: None,
+                timeout_ms: Some(30_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            }, Some(token)).unwrap();
+            assert!(r.cancelled);
+            assert!(!r.timed_out);
+            assert_eq!(r.exit_code, -1);
+        });
+    }
+
+    /// Regression: orphan process holding pipe FD must not block execute().
+    /// drain_pipes_with_timeout closes FDs after 3s, so total time < 7s.
+    #[test]
+    fn orphan_holding_pipe_does_not_block_drain() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let start = std::time::Instant::now();
+            // Orphan `sleep 300` inherits pipe FD, keeping it open after
+            // the parent shell exits. execute() must still return promptly.
+            let r = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "echo ok; (sleep 300 &)".into(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            }).unwrap();
+            let elapsed = start.elapsed();
+            assert_eq!(r.exit_code, 0);
+            assert!(!r.timed_out);
+            // Must return well before the 10s timeout — drain closes FDs after 3s.
+            assert!(elapsed.as_secs() < 7, "took {:?}, expected < 7s", elapsed);
+        });
+    }
+
+    /// Collects streaming callbacks into a channel.
+    struct ChannelSink(std::sync::Mutex<std::sync::mpsc::Sender<String>>);
+
+    impl spawn::SpawnSink for ChannelSink {
+        fn output(&self, p: spawn::CommandOutputPayload) {
+            let _ = self.0.lock().unwrap().send(format!("{}:{}", p.stream, p.line));
+        }
+        fn exit(&self, p: spawn::CommandExitPayload) {
+            let _ = self.0.lock().unwrap().send(format!("exit:{}:{}", p.exit_code, p.cancelled));
+        }
+    }
+
+    #[test]
+    fn spawn_streams_output_until_cancelled() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let (tx, rx) = std::sync::mpsc::channel();
+            let (done_tx, done_rx) = std::sync::mpsc::channel();
+            let token = cancel::CancelToken::new();
+            let args = SpawnCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "i=0; while true; do i=$((i+1)); echo tick $i; sleep 0.05; done".into(),
+                workdir: None,
+                cancel_token: None,
+            };
+            let start = std::time::Instant::now();
+            spawn::spawn_streaming(
+                &args,
+                "t-1".into(),
+                token.clone(),
+                Arc::new(ChannelSink(std::sync::Mutex::new(tx))),
+                move || {
+                    let _ = done_tx.send(());
+                },
+            )
+            .unwrap();
+            // 立即返回，不等待进程结束
+            assert!(start.elapsed() < std::time::Duration::from_secs(2));
+
+            let wait = std::time::Duration::from_secs(10);
+            assert_eq!(rx.recv_timeout(wait).unwrap(), "stdout:tick 1");
+            assert_eq!(rx.recv_timeout(wait).unwrap(), "stdout:tick 2");
+            token.cancel();
+
+            let exit = loop {
+                let msg = rx.recv_timeout(wait).expect("exit event");
+                if msg.starts_with("exit:") {
+                    break msg;
+                }
+            };
+            assert_eq!(exit, "exit:-1:true");
+            done_rx.recv_timeout(wait).expect("on_finish called");
+        });
+    }
+
+    #[test]
+    fn spawn_reports_natural_exit() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let (tx, rx) = std::sync::mpsc::channel();
+            let args = SpawnCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "echo out; echo err >&2; exit 3".into(),
+                workdir: None,
+                cancel_token: None,
+            };
+            spawn::spawn_streaming(
+                &args,
+                "t-2".into(),
+                cancel::CancelToken::new(),
+                Arc::new(ChannelSink(std::sync::Mutex::new(tx))),
+                || {},
+            )
+            .unwrap();
+            let wait = std::time::Duration::from_secs(10);
+            let mut msgs = Vec::new();
+            loop {
+                let m = rx.recv_timeout(wait).unwrap();
+                let is_exit = m.starts_with("exit:");
+                msgs.push(m);
+                if is_exit {
+                    break;
+                }
+            }
+            assert!(msgs.contains(&"stdout:out".to_string()));
+            assert!(msgs.contains(&"stderr:err".to_string()));
+            assert_eq!(msgs.last().unwrap(), "exit:3:false");
+        });
+    }
+
+    #[test]
+    fn spawn_exit_not_blocked_by_background_child() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let (tx, rx) = std::sync::mpsc::channel();
+            let args = SpawnCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                // 后台 sleep 继承 stdout/stderr，管道在 shell 退出后仍保持打开
+                command: "sleep 30 & echo started".into(),
+                workdir: None,
+                cancel_token: None,
+            };
+            let start = std::time::Instant::now();
+            spawn::spawn_streaming(
+                &args,
+                "t-3".into(),
+                cancel::CancelToken::new(),
+                Arc::new(ChannelSink(std::sync::Mutex::new(tx))),
+                || {},
+            )
+            .unwrap();
+            let wait = std::time::Duration::from_secs(10);
+            let exit = loop {
+                let msg = rx.recv_timeout(wait).expect("exit event");
+                if msg.starts_with("exit:") {
+                    break msg;
+                }
+            };
+            assert_eq!(exit, "exit:0:false");
+            assert!(start.elapsed().as_secs() < 7, "took {:?}", start.elapsed());
+        });
+    }
+
+    #[test]
+    fn audit_entry_written_after_command() {
+        with_home(|home| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let log = home.join("app-data").join("command-audit.jsonl");
+            let args = RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "echo secret-output; exit 2".into(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: None,
+                login_shell: false,
+                graceful_shutdown: false,
+            };
+            runner::execute_logged(&args, None, Some(&log)).unwrap();
+            runner::execute_logged(&args, None, None).unwrap();
+
+            let entries = audit::read_recent(&log, None);
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].command, "echo secret-output; exit 2");
+            assert_eq!(entries[0].exit_code, 2);
+            // 只记录命令本身，不记录输出
+            let raw = std::fs::read_to_string(&log).unwrap();
+            assert!(raw.contains("\"durationMs\""));
+            assert!(!raw.contains("stdout"));
+        });
+    }
+
+    #[test]
+    fn output_to_file_writes_file_and_returns_empty_buffers() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let r = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "echo out; echo err >&2".into(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: Some("logs/run.txt".into()),
+                login_shell: false,
+                graceful_shutdown: false,
+            })
+            .unwrap();
+            assert_eq!(r.exit_code, 0);
+            assert!(r.stdout.is_empty());
+            assert!(r.stderr.is_empty());
+            assert_eq!(r.output_file.as_deref(), Some("logs/run.txt"));
+            let written = std::fs::read_to_string(root.join("logs/run.txt")).unwrap();
+            assert_eq!(written, "out\nerr\n");
+
+            // 路径必须在工作区内
+            let outside = run(RunCommandArgs {
+                workspace_root: root.to_str().unwrap().to_string(),
+                command: "echo x".into(),
+                workdir: None,
+                timeout_ms: Some(10_000),
+                cancel_token: None,
+                request_id: None,
+                clean_env: false,
+                output_to_file: Some("../escape.txt".into()),
+                login_shell: false,
+                graceful_shutdown: false,
+            });
+            assert!(outside.is_err());
+        });
+    }
+
+    #[test]
+    fn cancel_before_spawn_runs_nothing() {
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let token = cancel::CancelToken::new();
+            token.cancel();
+            let r = runner::execute(
+                &RunCommandArgs {
+                    workspace_root: root.to_str().unwrap().to_string(),
+                    command: "touch ran".into(),
+                    workdir: None,
+                    timeout_ms: Some(10_000),
+                    cancel_token: None,
+                    request_id: None,
+                    clean_env: false,
+                    output_to_file: None,
+                    login_shell: false,
+                    graceful_shutdown: false,
+                },
+                Some(token),
+            )
+            .unwrap();
+            assert!(r.cancelled);
+            assert!(!r.timed_out);
+            assert!(!root.join("ran").exists());
+        });
+    }
+
+    #[test]
+    fn signal_termination_is_reported() {
+        // 默认沙箱策略：Linux 上 bwrap 可用时经由包装进程运行
+        with_home(|_| {
+            let dir = tempfile::tempdir().unwrap();
+            let root = dir.path().canonicalize().unwrap();
+            let run_sh = |command: &str| {
+                run(RunCommandArgs {
+                    workspace_root: root.to_str().unwrap().to_string(),
+                    command: command.into(),
+                    workdir: None,
+                    timeout_ms: Some(10_000),
+                    cancel_token: None,
+                    request_id: None,
+                    clean_env: false,
+                    output_to_file: None,
+                    login_shell: false,
+                    graceful_shutdown: false,
+                })
+                .unwrap()
+            };
+
+            let r = run_sh("kill -SEGV $$");
+            assert_eq!(r.exit_code, -1);
+            assert_eq!(r.signal, Some(libc::SIGSEGV));
+            assert!(!r.timed_out && !r.cancelled);
+
+            let r = run_sh("exit 3");
+            assert_eq!(r.exit_code, 3);
+            assert_eq!(r.signal, None);
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sandboxed_exit_code_maps_back_to_signal() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+        use super::super::runner::exit_code_and_signal;
+
+        // bwrap 以 128+n 退出表示命令被信号 n 终止
+        let wrapped = ExitStatus::from_raw((128 + libc::SIGSEGV) << 8);
+        let expected = if cfg!(target_os = "linux") { (-1, Some(libc::SIGSEGV)) } else { (139, None) };
+        assert_eq!(exit_code_and_signal(&wrapped, true), expected);
+        assert_eq!(exit_code_and_signal(&wrapped, false), (139, None));
+        let killed = ExitStatus::from_raw(libc::SIGKILL);
+        assert_eq!(exit_code_and_signal(&killed, false), (-1, Some(libc::SIGKILL)));
+        assert_eq!(exit_code_and_signal(&ExitStatus::from_raw(3 << 8), true), (3, None));
+    }
+
+    #[test]
+    fn login_shell_sources_profile_path() {
+        with_home(|home| {
+            // 沙箱内不一定能读到 HOME 下的 profile，这里直接运行
+            let policy = crate::sandbox::SandboxPolicy { enabled: false, ..Default::default() };
+            crate::sandbox::save_policy(&policy).unwrap();
+            // 覆盖 bash/zsh/sh 各自读取的登录 profile
+            let export = "export PATH=\"$HOME/profile-bin:$PATH\"\n";
+            for profile in [".bash_profile", ".zprofile", ".profile"] {
+                std::fs::write(home.join(profile), export).unwrap();
+            }
+            let dir = tempfile::tempdir().unwrap();
+            let run_path = |login_shell: bool| {
+                run(RunCommandArgs {
+                    workspace_root: dir.path().to_str().unwrap().to_string(),
+                    command: "printf '%s' \"$PATH\"".into(),
+                    workdir: None,
+                    timeout_ms: Some(10_000),
+                    cancel_token: None,
+                    request_id: None,
+                    clean_env: false,
+                    output_to_file: None,
+                    login_shell,
+                    graceful_shutdown: false,
+                })
+                .unwrap()
+            };
+
+            let r = run_path(true);
+            assert_eq!(r.exit_code, 0, "stderr: {}", r.stderr);
+            assert!(r.stdout.contains("profile-bin"), "PATH: {}", r.stdout);
+            assert!(!run_path(false).stdout.contains("profile-bin"));
+        });
+    }
+
+    #[test]
+    fn graceful_shutdown_lets_trap_run_before_kill() {
+        with_home(|_| {
+            // The sandbox wrapper sits between us and the shell; signal the shell directly
+            let policy = crate::sandbox::SandboxPolicy { enabled: false, ..Default::default() };
+            crate::sandbox::save_policy(&policy).unwrap();
+            let dir = tempfile::tempdir().unwrap();
+            let run_trapped = |graceful_shutdown: bool| {
+                run(RunCommandArgs {
+                    workspace_root: dir.path().to_str().unwrap().to_string(),
+                    command: "trap 'echo cleaned up; exit 0' TERM; echo started; \
+                              while :; do sleep 0.1; done"
+                        .into(),
+                    workdir: None,
+                    timeout_ms: Some(500),
+                    cancel_token: None,
+                    request_id: None,
+                    clean_env: false,
+                    output_to_file: None,
+                    login_shell: false,
+                    graceful_shutdown,
+                })
+                .unwrap()
+            };
+
+            let r = run_trapped(true);
+            assert!(r.timed_out);
+            assert!(r.stdout.contains("started"), "stdout: {}", r.stdout);
+            assert!(r.stdout.contains("cleaned up"), "stdout: {}", r.stdout);
+
+            // Default: immediate SIGKILL, the trap never runs
+            let r = run_trapped(false);
+            assert!(r.timed_out);
+            assert!(!r.stdout.contains("cleaned up"), "stdout: {}", r.stdout);
+        });
+    }
+}
@@ -0,0 +1,56 @@
+//! Per-command resource usage (peak RSS, CPU time) collected when reaping the child.
+
+use std::process::{Child, ExitStatus};
+
+/// Peak memory and CPU time of an exited child, including descendants it waited for.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct ResourceUsage {
+    pub max_rss_bytes: u64,
+    pub cpu_time_ms: u64,
+}
+
+/// Non-blocking reap. Uses `wait4` on Unix so the usage belongs to this child only,
+/// unlike `getrusage(RUSAGE_CHILDREN)` which mixes in concurrently running commands.
+#[cfg(unix)]
+pub(super) fn try_reap(child: &mut Child) -> Option<(ExitStatus, Option<ResourceUsage>)> {
+    wait4(child.id(), libc::WNOHANG).map(|(status, usage)| (status, Some(usage)))
+}
+
+#[cfg(not(unix))]
+pub(super) fn try_reap(child: &mut Child) -> Option<(ExitStatus, Option<ResourceUsage>)> {
+    child.try_wait().ok().flatten().map(|status| (status, None))
+}
+
+/// Blocking reap after the child was killed.
+#[cfg(unix)]
+pub(super) fn reap(child: &mut Child) -> Option<ResourceUsage> {
+    wait4(child.id(), 0).map(|(_, usage)| usage)
+}
+
+#[cfg(not(unix))]
+pub(super) fn reap(child: &mut Child) -> Option<ResourceUsage> {
+    let _ = child.wait();
+    None
+}
+
+#[cfg(unix)]
+fn wait4(pid: u32, options: libc::c_int) -> Option<(ExitStatus, ResourceUsage)> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let mut status: libc::c_int = 0;
+    let mut ru: libc::rusage = unsafe { std::mem::zeroed() };
+    let reaped = unsafe { libc::wait4(pid as libc::pid_t, &mut status, options, &mut ru) };
+    if reaped <= 0 {
+        return None;
+    }
+    let timeval_ms = |tv: libc::timeval| tv.tv_sec as u64 * 1000 + tv.tv_usec as u64 / 1000;
+    // ru_maxrss is reported in bytes on macOS and in kilobytes elsewhere
+    let rss_unit = if cfg!(target_os = "macos") { 1 } else { 1024 };
+    Some((
+        ExitStatus::from_raw(status),
+        ResourceUsage {
+            max_rss_bytes: ru.ru_maxrss as u64 * rss_unit,
+            cpu_time_ms: timeval_ms(ru.ru_utime) + timeval_ms(ru.ru_stime),
+        },
+    ))
+}
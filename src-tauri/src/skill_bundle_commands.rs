@@ -0,0 +1,238 @@
+//! Write a user skill together with its resource files (helper scripts, templates, ...).
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde::Deserialize;
+
+use crate::skill_commands::{cove_skills_dir, ensure_within, validate_skill_name};
+
+const SKILL_FILENAME: &str = "SKILL.md";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillResourceFile {
+    /// Path relative to the skill directory (e.g. "scripts/run.lua")
+    pub relative_path: String,
+    pub content_base64: String,
+}
+
+/// Validate a resource path: relative, no `..`, and not SKILL.md itself
+fn validate_resource_path(relative: &str) -> Result<PathBuf, String> {
+    let mut clean = PathBuf::new();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::Normal(part) => clean.push(part),
+            Component::CurDir => {}
+            _ => return Err(format!("Path traversal not allowed: {relative}")),
+        }
+    }
+    if clean.as_os_str().is_empty() {
+        return Err("Resource path cannot be empty".into());
+    }
+    if clean == Path::new(SKILL_FILENAME) {
+        return Err("Resource path cannot overwrite SKILL.md".into());
+    }
+    Ok(clean)
+}
+
+/// Create or update a skill with resource files: ~/.cove/skills/{name}/SKILL.md plus
+/// each resource under the skill directory.
+///
+/// Every path and payload is validated and the whole bundle is staged in a temporary
+/// directory before the skill directory is touched, so invalid input leaves an existing
+/// skill unchanged. Staged files are then moved into place one at a time; this step is
+/// not atomic, and a failure partway through can leave the skill partially updated.
+#[tauri::command]
+pub fn write_skill_bundle(
+    name: String,
+    content: String,
+    resources: Vec<SkillResourceFile>,
+) -> Result<String, String> {
+    validate_skill_name(&name)?;
+    let mut files = vec![(PathBuf::from(SKILL_FILENAME), content.into_bytes())];
+    for resource in &resources {
+        let path = validate_resource_path(&resource.relative_path)?;
+        let bytes = BASE64_STANDARD
+            .decode(&resource.content_base64)
+            .map_err(|e| format!("Invalid base64 for {}: {e}", resource.relative_path))?;
+        files.push((path, bytes));
+    }
+
+    let skills_dir = cove_skills_dir()?;
+    fs::create_dir_all(&skills_dir)
+        .map_err(|e| format!("Failed to create skills directory: {e}"))?;
+    let staging = create_staging_dir(&skills_dir, &name)?;
+    let staged = stage_files(&staging, &files);
+    let result = staged.and_then(|()| {
+        // Safety: every created directory must resolve inside ~/.cove/skills/
+        let skill_dir = create_dir_within(&skills_dir, Path::new(&name))?;
+        move_into_place(&staging, &skill_dir, &files)?;
+        Ok(skill_dir.join(SKILL_FILENAME).to_string_lossy().into_owned())
+    });
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+/// Create a fresh staging directory. `create_dir` fails if the name is already taken,
+/// so concurrent bundles or a leftover from a crashed run never share one.
+fn create_staging_dir(skills_dir: &Path, name: &str) -> Result<PathBuf, String> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let staging = skills_dir.join(format!(
+        ".{name}.bundle-{}-{nanos}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir(&staging).map_err(|e| format!("Failed to create staging directory: {e}"))?;
+    Ok(staging)
+}
+
+/// Create `relative` under `root` one component at a time, resolving each step inside
+/// `root` before going deeper, so a symlink (even a dangling one) cannot make us create
+/// directories elsewhere. Returns the canonical directory.
+fn create_dir_within(root: &Path, relative: &Path) -> Result<PathBuf, String> {
+    let mut dir = ensure_within(root, root)?;
+    for component in relative.components() {
+        dir.push(component);
+        match fs::create_dir(&dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(format!("Failed to create directory: {e}")),
+        }
+        dir = ensure_within(&dir, root)?;
+    }
+    Ok(dir)
+}
+
+fn stage_files(staging: &Path, files: &[(PathBuf, Vec<u8>)]) -> Result<(), String> {
+    for (relative, bytes) in files {
+        let target = staging.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {e}"))?;
+        }
+        fs::write(&target, bytes)
+            .map_err(|e| format!("Failed to write {}: {e}", relative.display()))?;
+    }
+    Ok(())
+}
+
+/// Rename each staged file into the skill directory, creating parents through
+/// `create_dir_within` so a symlinked subdirectory cannot redirect writes outside of it.
+fn move_into_place(
+    staging: &Path,
+    skill_dir: &Path,
+    files: &[(PathBuf, Vec<u8>)],
+) -> Result<(), String> {
+    for (relative, _) in files {
+        let parent = create_dir_within(skill_dir, relative.parent().unwrap_or(Path::new("")))?;
+        let file_name = relative.file_name().ok_or("Resource path cannot be empty")?;
+        fs::rename(staging.join(relative), parent.join(file_name))
+            .map_err(|e| format!("Failed to write {}: {e}", relative.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::with_home;
+
+    fn resource(relative_path: &str, content: &str) -> SkillResourceFile {
+        SkillResourceFile {
+            relative_path: relative_path.into(),
+            content_base64: BASE64_STANDARD.encode(content),
+        }
+    }
+
+    #[test]
+    fn bundle_writes_skill_and_resources() {
+        with_home(|home| {
+            let path = write_skill_bundle(
+                "bundle".into(),
+                "---\nname: bundle\n---\n".into(),
+                vec![resource("scripts/run.lua", "print(1)"), resource("./notes.txt", "n")],
+            )
+            .unwrap();
+            let dir = home.join(".cove/skills/bundle");
+            assert_eq!(PathBuf::from(path), dir.join("SKILL.md"));
+            let read = |rel: &str| fs::read_to_string(dir.join(rel)).unwrap();
+            assert_eq!(read("SKILL.md"), "---\nname: bundle\n---\n");
+            assert_eq!(read("scripts/run.lua"), "print(1)");
+            assert_eq!(read("notes.txt"), "n");
+            // Staging directory is cleaned up
+            let entries = fs::read_dir(home.join(".cove/skills")).unwrap().count();
+            assert_eq!(entries, 1);
+        });
+    }
+
+    #[test]
+    fn bundle_rejects_traversal_without_writing() {
+        with_home(|home| {
+            let bad_paths = ["../escape.txt", "scripts/../../escape.txt", "/etc/passwd", "SKILL.md", ""];
+            for bad in bad_paths {
+                let err = write_skill_bundle(
+                    "escape".into(),
+                    "x".into(),
+                    vec![resource("ok.txt", "ok"), resource(bad, "pwned")],
+                )
+                .unwrap_err();
+                assert!(!err.is_empty(), "{bad} should be rejected");
+            }
+            assert!(!home.join(".cove/skills/escape").exists());
+            assert!(!home.join(".cove/skills/escape.txt").exists());
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn bundle_does_not_create_dirs_through_symlinks() {
+        with_home(|home| {
+            let skills = home.join(".cove/skills");
+            let outside = home.join("outside");
+            fs::create_dir_all(skills.join("nested")).unwrap();
+            fs::create_dir_all(&outside).unwrap();
+            std::os::unix::fs::symlink(&outside, skills.join("linked")).unwrap();
+            std::os::unix::fs::symlink(&outside, skills.join("nested/scripts")).unwrap();
+
+            let linked = write_skill_bundle("linked".into(), "x".into(), vec![]);
+            assert!(linked.is_err());
+            let nested = write_skill_bundle(
+                "nested".into(),
+                "x".into(),
+                vec![resource("scripts/lib/run.lua", "print(1)")],
+            );
+            assert!(nested.is_err());
+            assert_eq!(fs::read_dir(&outside).unwrap().count(), 0);
+        });
+    }
+
+    #[test]
+    fn staging_dirs_are_unique() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = create_staging_dir(dir.path(), "s").unwrap();
+        let b = create_staging_dir(dir.path(), "s").unwrap();
+        assert_ne!(a, b);
+        assert!(a.is_dir() && b.is_dir());
+    }
+
+    #[test]
+    fn bundle_rejects_invalid_base64() {
+        with_home(|home| {
+            let bad = SkillResourceFile {
+                relative_path: "a.txt".into(),
+                content_base64: "***".into(),
+            };
+            assert!(write_skill_bundle("b64".into(), "x".into(), vec![bad]).is_err());
+            assert!(!home.join(".cove/skills/b64").exists());
+        });
+    }
+}
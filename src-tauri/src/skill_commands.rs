@@ -1,10 +1,10 @@
 //! Skill CRUD: create / update / delete user skills in ~/.cove/skills/
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Validate skill name: lowercase alphanumeric + hyphens only
-fn validate_skill_name(name: &str) -> Result<(), String> {
+pub(crate) fn validate_skill_name(name: &str) -> Result<(), String> {
     if name.is_empty() {
         return Err("Skill name cannot be empty".into());
     }
@@ -25,11 +25,26 @@ fn validate_skill_name(name: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn cove_skills_dir() -> Result<PathBuf, String> {
+pub(crate) fn cove_skills_dir() -> Result<PathBuf, String> {
     let home = home_dir().ok_or("Cannot determine home directory")?;
     Ok(home.join(".cove").join("skills"))
 }
 
+/// Canonicalize `path` and ensure it resolves inside `base` (symlinks included).
+/// Returns the canonical path.
+pub(crate) fn ensure_within(path: &Path, base: &Path) -> Result<PathBuf, String> {
+    let canonical = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {e}"))?;
+    let canonical_base = base
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve base path: {e}"))?;
+    if !canonical.starts_with(&canonical_base) {
+        return Err("Path traversal detected".into());
+    }
+    Ok(canonical)
+}
+
 fn home_dir() -> Option<PathBuf> {
     #[cfg(unix)]
     { return std::env::var_os("HOME").map(PathBuf::from); }
@@ -49,15 +64,7 @@ pub fn write_skill(name: String, content: String) -> Result<String, String> {
         .map_err(|e| format!("Failed to create skill directory: {e}"))?;
 
     // Safety: ensure the resolved path is actually inside ~/.cove/skills/
-    let canonical = skill_dir
-        .canonicalize()
-        .map_err(|e| format!("Failed to resolve path: {e}"))?;
-    let canonical_base = skills_dir
-        .canonicalize()
-        .map_err(|e| format!("Failed to resolve base path: {e}"))?;
-    if !canonical.starts_with(&canonical_base) {
-        return Err("Path traversal detected".into());
-    }
+    let canonical = ensure_within(&skill_dir, &skills_dir)?;
 
     let skill_path = canonical.join("SKILL.md");
     fs::write(&skill_path, &content)
@@ -78,14 +85,22 @@ pub fn delete_skill(name: String) -> Result<(), String> {
     }
 
     // Safety: ensure the path is actually inside ~/.cove/skills/
-    let canonical = skill_dir
-        .canonicalize()
-        .map_err(|e| format!("Failed to resolve path: {e}"))?;
-    let canonical_base = skills_dir
-        .canonicalize()
-        .map_err(|e| format!("Failed to resolve base path: {e}"))?;
-    if !canonical.starts_with(&canonical_base) {
-        return Err("Path traversal detected".into());
+    ensure_within(&skill_dir, &skills_dir)?;
+
+    // Safety: only remove a real directory that looks like a skill, never a symlink to one
+    let meta = fs::symlink_metadata(&skill_dir)
+        .map_err(|e| format!("Failed to inspect skill directory: {e}"))?;
+    if meta.file_type().is_symlink() || !meta.is_dir() {
+        return Err(format!(
+            "Refusing to delete {}: not a real directory",
+            skill_dir.display()
+        ));
+    }
+    if !skill_dir.join("SKILL.md").is_file() {
+        return Err(format!(
+            "Refusing to delete {}: no SKILL.md found",
+            skill_dir.display()
+        ));
     }
 
     fs::remove_dir_all(&skill_dir)
@@ -191,6 +206,34 @@ mod tests {
         });
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn delete_refuses_symlinked_skill_dir() {
+        with_home(|home| {
+            write_skill("real".into(), "x".into()).unwrap();
+            let skills = home.join(".cove/skills");
+            std::os::unix::fs::symlink(skills.join("real"), skills.join("alias")).unwrap();
+
+            let err = delete_skill("alias".into()).unwrap_err();
+            assert!(err.contains("not a real directory"), "got: {err}");
+            assert!(skills.join("real/SKILL.md").is_file());
+            assert!(skills.join("alias").symlink_metadata().is_ok());
+        });
+    }
+
+    #[test]
+    fn delete_refuses_dir_without_skill_md() {
+        with_home(|home| {
+            let dir = home.join(".cove/skills/not-a-skill");
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("keep.txt"), "data").unwrap();
+
+            let err = delete_skill("not-a-skill".into()).unwrap_err();
+            assert!(err.contains("SKILL.md"), "got: {err}");
+            assert!(dir.join("keep.txt").is_file());
+        });
+    }
+
     #[test]
     fn delete_nonexistent_returns_error() {
         with_home(|_| {
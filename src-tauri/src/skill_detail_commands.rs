@@ -0,0 +1,222 @@
+//! Read a user skill together with its parsed frontmatter and resource file listing.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::document_parsers::frontmatter::split_frontmatter;
+use crate::skill_commands::{cove_skills_dir, validate_skill_name};
+
+const SKILL_FILENAME: &str = "SKILL.md";
+const MAX_RESOURCE_ENTRIES: usize = 200;
+const MAX_RESOURCE_DEPTH: usize = 3;
+
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillMeta {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillResource {
+    /// Path relative to the skill directory, forward slashes (e.g. "resources/GUIDE.md")
+    pub name: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillDetail {
+    pub content: String,
+    pub meta: SkillMeta,
+    pub resources: Vec<SkillResource>,
+}
+
+/// Read ~/.cove/skills/{name}/SKILL.md with parsed frontmatter and the other files in the skill dir
+#[tauri::command]
+pub fn read_skill_detail(name: String) -> Result<SkillDetail, String> {
+    validate_skill_name(&name)?;
+    let skill_dir = cove_skills_dir()?.join(&name);
+    let skill_path = skill_dir.join(SKILL_FILENAME);
+    if !skill_path.is_file() {
+        return Err(format!("Skill not found: {}", skill_path.display()));
+    }
+
+    let content = fs::read_to_string(&skill_path)
+        .map_err(|e| format!("Failed to read SKILL.md: {e}"))?;
+    let meta = split_frontmatter(&content)
+        .0
+        .map(|yaml| parse_skill_meta(&yaml))
+        .unwrap_or_default();
+    let mut resources = Vec::new();
+    walk_resources(&skill_dir, &skill_dir, 0, &mut resources);
+    resources.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(SkillDetail { content, meta, resources })
+}
+
+/// Minimal frontmatter reader for `name`, `description` and `tags`.
+/// Tags may be an inline list (`[a, b]`), a block list (`- a`) or a comma-separated string.
+fn parse_skill_meta(yaml: &str) -> SkillMeta {
+    let mut meta = SkillMeta::default();
+    let mut in_tags_block = false;
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+        if in_tags_block {
+            if let Some(item) = trimmed.strip_prefix("- ") {
+                push_tag(&mut meta.tags, item);
+                continue;
+            }
+            if trimmed.is_empty() {
+                continue;
+            }
+            in_tags_block = false;
+        }
+        // Nested keys (indented) belong to other mappings
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "name" => meta.name = non_empty(unquote(value)),
+            "description" => meta.description = non_empty(unquote(value)),
+            "tags" if value.is_empty() => in_tags_block = true,
+            "tags" => {
+                let list = value
+                    .strip_prefix('[')
+                    .and_then(|v| v.strip_suffix(']'))
+                    .unwrap_or(value);
+                for item in list.split(',') {
+                    push_tag(&mut meta.tags, item);
+                }
+            }
+            _ => {}
+        }
+    }
+    meta
+}
+
+fn push_tag(tags: &mut Vec<String>, raw: &str) {
+    if let Some(tag) = non_empty(unquote(raw.trim())) {
+        tags.push(tag);
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    let quoted = value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')));
+    if quoted {
+        &value[1..value.len() - 1]
+    } else {
+        value
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Collect every file except the top-level SKILL.md; hidden entries are skipped
+fn walk_resources(dir: &Path, base: &Path, depth: usize, out: &mut Vec<SkillResource>) {
+    if depth >= MAX_RESOURCE_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        if out.len() >= MAX_RESOURCE_ENTRIES {
+            return;
+        }
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else { continue };
+        if meta.is_dir() {
+            walk_resources(&path, base, depth + 1, out);
+        } else if meta.is_file() && !(depth == 0 && entry.file_name() == SKILL_FILENAME) {
+            if let Ok(rel) = path.strip_prefix(base) {
+                out.push(SkillResource {
+                    name: rel.to_string_lossy().replace('\\', "/"),
+                    size: meta.len(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill_commands::write_skill;
+    use crate::test_util::with_home;
+
+    const SKILL_MD: &str = "---\nname: report-writer\ndescription: \"Writes reports\"\ntags: [docs, 'office']\n---\n# Report writer\n";
+
+    #[test]
+    fn parse_skill_meta_reads_inline_and_block_tags() {
+        let meta = parse_skill_meta("name: a\ndescription: Does things\ntags:\n  - one\n  - \"two\"\nother: x");
+        assert_eq!(meta.name.as_deref(), Some("a"));
+        assert_eq!(meta.description.as_deref(), Some("Does things"));
+        assert_eq!(meta.tags, vec!["one", "two"]);
+
+        let meta = parse_skill_meta("tags: x, y\nmetadata:\n  name: nested");
+        assert_eq!(meta.name, None);
+        assert_eq!(meta.tags, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn detail_without_resources() {
+        with_home(|_| {
+            write_skill("report-writer".into(), SKILL_MD.into()).unwrap();
+            let detail = read_skill_detail("report-writer".into()).unwrap();
+            assert_eq!(detail.content, SKILL_MD);
+            assert_eq!(
+                detail.meta,
+                SkillMeta {
+                    name: Some("report-writer".into()),
+                    description: Some("Writes reports".into()),
+                    tags: vec!["docs".into(), "office".into()],
+                }
+            );
+            assert!(detail.resources.is_empty());
+        });
+    }
+
+    #[test]
+    fn detail_lists_resource_files() {
+        with_home(|home| {
+            write_skill("with-res".into(), "no frontmatter".into()).unwrap();
+            let dir = home.join(".cove/skills/with-res");
+            fs::create_dir_all(dir.join("resources")).unwrap();
+            fs::write(dir.join("resources/GUIDE.md"), "guide").unwrap();
+            fs::write(dir.join("template.json"), "{}").unwrap();
+            fs::write(dir.join(".DS_Store"), "x").unwrap();
+
+            let detail = read_skill_detail("with-res".into()).unwrap();
+            assert_eq!(detail.meta, SkillMeta::default());
+            assert_eq!(
+                detail.resources,
+                vec![
+                    SkillResource { name: "resources/GUIDE.md".into(), size: 5 },
+                    SkillResource { name: "template.json".into(), size: 2 },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn detail_rejects_missing_or_invalid_skill() {
+        with_home(|_| {
+            assert!(read_skill_detail("no-exist".into()).is_err());
+            assert!(read_skill_detail("../bad".into()).is_err());
+        });
+    }
+}
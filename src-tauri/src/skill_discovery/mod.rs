@@ -28,7 +28,7 @@ pub struct ExternalSkillEntry {
     pub name: String,
     /// SKILL.md 的绝对路径
     pub path: String,
-    /// 文件内容（原始，前端解析 frontmatter）
+    /// 文件内容（原始，前端解析 frontmatter）；`loadContent: false` 时为空，由前端按需读取
     pub content: String,
     /// Absolute path to the skill directory (parent of SKILL.md)
     #[serde(default)]
@@ -114,13 +114,14 @@ fn walk_resources(dir: &Path, base: &Path, depth: usize, out: &mut Vec<String>)
     }
 }
 
-fn scan_skill_root(root: &Path, source: &str) -> Vec<ExternalSkillEntry> {
+/// `load_content` 为 false 时不读取 SKILL.md，`content` 留空
+fn scan_skill_root(root: &Path, source: &str, load_content: bool) -> Vec<ExternalSkillEntry> {
     let mut out = Vec::new();
 
     // 平铺布局：SKILL.md 直接位于 root（如 ~/.officellm/skills/SKILL.md）
     let flat_md = root.join(SKILL_FILENAME);
     if flat_md.is_file() {
-        if let Ok(content) = read_skill_file(&flat_md) {
+        if let Ok(content) = load_skill_content(&flat_md, load_content) {
             let name = root
                 .file_name()
                 .map(|n| n.to_string_lossy().into_owned())
@@ -148,11 +149,15 @@ fn scan_skill_root(root: &Path, source: &str) -> Vec<ExternalSkillEntry> {
             continue;
         }
         let name = entry.file_name().to_string_lossy().into_owned();
+        // 跳过隐藏目录（如 skill_bundle_commands 写入中的 `.{name}.bundle-…` 暂存目录）
+        if name.starts_with('.') {
+            continue;
+        }
         let skill_md = path.join(SKILL_FILENAME);
         if !skill_md.is_file() {
             continue;
         }
-        let content = match read_skill_file(&skill_md) {
+        let content = match load_skill_content(&skill_md, load_content) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -169,6 +174,14 @@ fn scan_skill_root(root: &Path, source: &str) -> Vec<ExternalSkillEntry> {
     out
 }
 
+fn load_skill_content(path: &Path, load_content: bool) -> Result<String, std::io::Error> {
+    if load_content {
+        read_skill_file(path)
+    } else {
+        Ok(String::new())
+    }
+}
+
 fn read_skill_file(path: &Path) -> Result<String, std::io::Error> {
     let meta = fs::metadata(path)?;
     if meta.len() > MAX_SKILL_BYTES {
@@ -200,16 +213,19 @@ fn bundled_officellm_skills(app: &tauri::AppHandle) -> Option<PathBuf> {
 
 /// 发现本机 skills：先扫内置默认目录，再扫用户配置的 custom_roots（支持 ~ 展开）。
 /// workspace_path 存在时额外扫描工作区下的 .claude/skills、.agents/skills。
+/// `load_content` 为 false 时只返回路径等元信息，`content` 为空（默认 true）。
 #[tauri::command]
 pub fn discover_external_skills(
     app: tauri::AppHandle,
     workspace_path: Option<String>,
     custom_roots: Option<Vec<String>>,
+    load_content: Option<bool>,
 ) -> Result<Vec<ExternalSkillEntry>, String> {
     discover_skills_impl(
         bundled_officellm_skills(&app),
         workspace_path,
         custom_roots,
+        load_content.unwrap_or(true),
     )
 }
 
@@ -218,6 +234,7 @@ fn discover_skills_impl(
     bundled_officellm_skills_root: Option<PathBuf>,
     workspace_path: Option<String>,
     custom_roots: Option<Vec<String>>,
+    load_content: bool,
 ) -> Result<Vec<ExternalSkillEntry>, String> {
     let mut all = Vec::new();
 
@@ -225,7 +242,7 @@ fn discover_skills_impl(
         for (subdir, source) in DEFAULT_SKILL_ROOTS {
             let root = home.join(subdir);
             if root.is_dir() {
-                all.extend(scan_skill_root(&root, source));
+                all.extend(scan_skill_root(&root, source, load_content));
             }
         }
     }
@@ -235,14 +252,14 @@ fn discover_skills_impl(
     // Source "office-bundled" distinguishes from user-installed "office" so
     // the frontend can hide bundled skills from UI while keeping them active.
     if let Some(ref root) = bundled_officellm_skills_root {
-        all.extend(scan_skill_root(root, "office-bundled"));
+        all.extend(scan_skill_root(root, "office-bundled", load_content));
     }
 
     if let Some(roots) = custom_roots {
         for path in roots {
             let root = expand_path(&path);
             if root.is_dir() {
-                all.extend(scan_skill_root(&root, "custom"));
+                all.extend(scan_skill_root(&root, "custom", load_content));
             }
         }
     }
@@ -253,7 +270,7 @@ fn discover_skills_impl(
             for (subdir, source) in [(".claude/skills", "claude"), (".agents/skills", "agents")] {
                 let root = ws.join(subdir);
                 if root.is_dir() {
-                    all.extend(scan_skill_root(&root, source));
+                    all.extend(scan_skill_root(&root, source, load_content));
                 }
             }
         }
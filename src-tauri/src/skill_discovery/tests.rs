@@ -13,7 +13,7 @@ fn scan_nested_layout() {
     let td = tempfile::TempDir::new().unwrap();
     let root = td.path();
     write_md(&root.join("my-skill"), "---\nname: my-skill\n---");
-    let found = scan_skill_root(root, "test");
+    let found = scan_skill_root(root, "test", true);
     assert_eq!(found.len(), 1);
     assert_eq!(found[0].name, "my-skill");
     assert_eq!(found[0].source, "test");
@@ -25,7 +25,7 @@ fn scan_flat_layout() {
     let td = tempfile::TempDir::new().unwrap();
     let root = td.path().join("skills");
     write_md(&root, "flat content");
-    let found = scan_skill_root(&root, "src");
+    let found = scan_skill_root(&root, "src", true);
     assert_eq!(found.len(), 1);
     assert_eq!(found[0].name, "skills");
     assert_eq!(found[0].content, "flat content");
@@ -37,19 +37,19 @@ fn scan_mixed_layout() {
     let root = td.path().join("mix");
     write_md(&root, "flat");
     write_md(&root.join("nested"), "nested");
-    let found = scan_skill_root(&root, "s");
+    let found = scan_skill_root(&root, "s", true);
     assert_eq!(found.len(), 2);
 }
 
 #[test]
 fn scan_empty_dir() {
     let td = tempfile::TempDir::new().unwrap();
-    assert!(scan_skill_root(td.path(), "x").is_empty());
+    assert!(scan_skill_root(td.path(), "x", true).is_empty());
 }
 
 #[test]
 fn scan_nonexistent_dir() {
-    assert!(scan_skill_root(Path::new("/no/such/dir"), "x").is_empty());
+    assert!(scan_skill_root(Path::new("/no/such/dir"), "x", true).is_empty());
 }
 
 #[test]
@@ -59,7 +59,9 @@ fn scan_skips_non_dir_entries() {
     fs::write(td.path().join("not-a-dir"), "x").unwrap();
     // subdir without SKILL.md — should be skipped
     fs::create_dir(td.path().join("empty-sub")).unwrap();
-    assert!(scan_skill_root(td.path(), "x").is_empty());
+    // hidden dir (e.g. in-progress bundle staging) — should be skipped
+    write_md(&td.path().join(".demo.bundle-1-2-3"), "staged");
+    assert!(scan_skill_root(td.path(), "x", true).is_empty());
 }
 
 // --- read_skill_file ---
@@ -116,7 +118,7 @@ fn discover_scans_default_roots() {
         // Create a skill in ~/.cove/skills/cove-skill/SKILL.md
         write_md(&home.join(".cove/skills/cove-skill"), "cove skill");
 
-        let result = discover_skills_impl(None, None, None).unwrap();
+        let result = discover_skills_impl(None, None, None, true).unwrap();
         assert!(result.len() >= 2);
 
         let claude = result.iter().find(|e| e.name == "my-skill");
@@ -140,6 +142,7 @@ fn discover_scans_custom_roots() {
             None,
             None,
             Some(vec![custom.to_string_lossy().into_owned()]),
+            true,
         )
         .unwrap();
 
@@ -162,6 +165,7 @@ fn discover_scans_workspace_path() {
             None,
             Some(ws.to_string_lossy().into_owned()),
             None,
+            true,
         )
         .unwrap();
 
@@ -179,7 +183,7 @@ fn discover_scans_workspace_path() {
 fn discover_skips_empty_workspace_path() {
     with_home(|_| {
         // Empty string workspace_path should be filtered out
-        let result = discover_skills_impl(None, Some(String::new()), None).unwrap();
+        let result = discover_skills_impl(None, Some(String::new()), None, true).unwrap();
         // Should still succeed (just scanning default roots, which are empty in tempdir)
         assert!(result.is_empty());
     });
@@ -192,6 +196,7 @@ fn discover_skips_nonexistent_custom_root() {
             None,
             None,
             Some(vec!["/no/such/path".into()]),
+            true,
         )
         .unwrap();
         // Non-existent custom root is silently skipped (not an error)
@@ -206,12 +211,7 @@ fn discover_scans_bundled_officellm_skills() {
         let bundled_skills = td.path().join("officellm-skills");
         write_md(&bundled_skills.join("OfficeLLM"), "bundled officellm skill");
 
-        let result = discover_skills_impl(
-            Some(bundled_skills),
-            None,
-            None,
-        )
-        .unwrap();
+        let result = discover_skills_impl(Some(bundled_skills), None, None, true).unwrap();
 
         let entry = result.iter().find(|e| e.name == "OfficeLLM");
         assert!(entry.is_some(), "should find bundled officellm skill");
@@ -230,6 +230,7 @@ fn discover_custom_roots_with_tilde() {
             None,
             None,
             Some(vec!["~/my-skills".into()]),
+            true,
         )
         .unwrap();
 
@@ -251,7 +252,7 @@ fn scan_discovers_resource_paths() {
     fs::write(res_dir.join("GUIDE.md"), "guide").unwrap();
     fs::write(res_dir.join("schema.json"), "{}").unwrap();
 
-    let found = scan_skill_root(td.path(), "test");
+    let found = scan_skill_root(td.path(), "test", true);
     assert_eq!(found.len(), 1);
     let entry = &found[0];
     assert!(!entry.skill_dir.is_empty());
@@ -265,7 +266,7 @@ fn scan_ignores_skill_md_in_resource_paths() {
     let skill = td.path().join("s");
     write_md(&skill, "content");
 
-    let found = scan_skill_root(td.path(), "test");
+    let found = scan_skill_root(td.path(), "test", true);
     assert_eq!(found.len(), 1);
     // SKILL.md is not a resource — it has no allowed extension match under resources/
     assert!(!found[0].resource_paths.iter().any(|p| p.contains("SKILL.md")));
@@ -280,7 +281,19 @@ fn scan_nested_resources() {
     fs::create_dir_all(&nested).unwrap();
     fs::write(nested.join("bar.json"), "{}").unwrap();
 
-    let found = scan_skill_root(td.path(), "test");
+    let found = scan_skill_root(td.path(), "test", true);
     assert_eq!(found.len(), 1);
     assert!(found[0].resource_paths.contains(&"resources/sub/bar.json".to_string()));
 }
+
+#[test]
+fn discover_without_content_still_returns_entries() {
+    with_home(|home| {
+        write_md(&home.join(".cove/skills/lazy/resources"), "guide");
+        write_md(&home.join(".cove/skills/lazy"), "---\nname: lazy\n---\nbody");
+        let result = discover_skills_impl(None, None, None, false).unwrap();
+        let entry = result.iter().find(|e| e.name == "lazy").expect("entry returned");
+        assert!(entry.content.is_empty());
+        assert_eq!(entry.resource_paths, vec!["resources/SKILL.md"]);
+    });
+}
@@ -0,0 +1,100 @@
+//! Open external URLs in the default browser on behalf of the agent.
+//!
+//! Only `http`/`https` are allowed; `file:`, `javascript:` and other schemes are
+//! rejected before anything is handed to the opener plugin.
+
+use reqwest::Url;
+use tauri_plugin_opener::OpenerExt;
+
+const ALLOWED_SCHEMES: &[&str] = &["http", "https"];
+
+/// Host matches an allowlist entry exactly or as a subdomain (`docs.rs` allows `foo.docs.rs`).
+fn host_allowed(host: &str, allowed_hosts: &[String]) -> bool {
+    let host = host.to_ascii_lowercase();
+    allowed_hosts.iter().any(|h| {
+        let h = h.trim().trim_start_matches("*.").to_ascii_lowercase();
+        !h.is_empty() && (host == h || host.ends_with(&format!(".{h}")))
+    })
+}
+
+/// Validate the URL scheme and optional host allowlist; returns the normalized URL.
+fn validate_url(url: &str, allowed_hosts: Option<&[String]>) -> Result<String, String> {
+    let parsed = Url::parse(url.trim()).map_err(|e| format!("Invalid URL: {e}"))?;
+    if !ALLOWED_SCHEMES.contains(&parsed.scheme()) {
+        return Err(format!("URL scheme not allowed: {}", parsed.scheme()));
+    }
+    let host = parsed.host_str().ok_or("URL has no host")?;
+    if let Some(list) = allowed_hosts {
+        if !host_allowed(host, list) {
+            return Err(format!("Host not in allowlist: {host}"));
+        }
+    }
+    Ok(parsed.to_string())
+}
+
+/// Open an http(s) URL in the system browser. `allowed_hosts`, when given, restricts the target host.
+#[tauri::command]
+pub fn open_url(
+    app: tauri::AppHandle,
+    url: String,
+    allowed_hosts: Option<Vec<String>>,
+) -> Result<(), String> {
+    let url = validate_url(&url, allowed_hosts.as_deref())?;
+    app.opener()
+        .open_url(url, None::<&str>)
+        .map_err(|e| format!("Failed to open URL: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_http_and_https() {
+        assert!(validate_url("https://docs.rs/mlua", None).is_ok());
+        assert!(validate_url("http://example.com/a?b=c", None).is_ok());
+    }
+
+    #[test]
+    fn rejects_disallowed_schemes() {
+        for url in [
+            "file:///etc/passwd",
+            "javascript:alert(1)",
+            "data:text/html,<b>x</b>",
+            "ftp://example.com/file",
+            "mailto:someone@example.com",
+        ] {
+            let err = validate_url(url, None).unwrap_err();
+            assert!(err.contains("scheme not allowed"), "{url}: {err}");
+        }
+    }
+
+    #[test]
+    fn scheme_check_is_case_insensitive() {
+        assert!(validate_url("HTTPS://EXAMPLE.COM", None).is_ok());
+        assert!(validate_url("JavaScript:alert(1)", None).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_url() {
+        assert!(validate_url("not a url", None).unwrap_err().contains("Invalid URL"));
+        assert!(validate_url("", None).is_err());
+    }
+
+    #[test]
+    fn allowlist_matches_host_and_subdomains() {
+        let list = vec!["docs.rs".to_string(), "*.github.com".to_string()];
+        assert!(validate_url("https://docs.rs/x", Some(&list)).is_ok());
+        assert!(validate_url("https://crates.docs.rs/x", Some(&list)).is_ok());
+        assert!(validate_url("https://gist.github.com/x", Some(&list)).is_ok());
+        assert!(validate_url("https://evil-docs.rs/x", Some(&list)).is_err());
+        assert!(validate_url("https://example.com", Some(&list))
+            .unwrap_err()
+            .contains("allowlist"));
+    }
+
+    #[test]
+    fn empty_allowlist_rejects_everything() {
+        assert!(validate_url("https://docs.rs", Some(&[])).is_err());
+    }
+}
@@ -1,8 +1,13 @@
 use std::path::Path;
 
+use crate::fs_commands::{walk_workspace, WalkOptions};
+
 const GLOB_LIMIT: usize = 1000;
 
-pub fn ws_glob(workspace_root: &str, pattern: &str) -> Result<Vec<String>, String> {
+/// Match `pattern` against workspace entries using the same traversal as
+/// `walk_files`. Hidden and .gitignore'd paths are included unless
+/// `respect_ignore` is set, matching the original `glob`-based behavior.
+pub fn ws_glob(workspace_root: &str, pattern: &str, respect_ignore: bool) -> Result<Vec<String>, String> {
     if Path::new(pattern).is_absolute() {
         return Err("absolute glob patterns not allowed".to_string());
     }
@@ -13,28 +18,35 @@ pub fn ws_glob(workspace_root: &str, pattern: &str) -> Result<Vec<String>, Strin
         return Err("glob pattern must not contain parent traversal".to_string());
     }
 
-    let root = Path::new(workspace_root);
-    let full_pattern = root.join(pattern).to_string_lossy().into_owned();
-    let paths = glob::glob(&full_pattern).map_err(|e| e.to_string())?;
+    let pattern = pattern.trim_start_matches("./");
+    let matcher = glob::Pattern::new(pattern).map_err(|e| e.to_string())?;
+    let match_opts = glob::MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
 
-    let canonical_root = root.canonicalize().map_err(|e| e.to_string())?;
+    let canonical_root = Path::new(workspace_root)
+        .canonicalize()
+        .map_err(|e| e.to_string())?;
+    let opts = WalkOptions {
+        include_dirs: true,
+        max_depth: pattern_depth(pattern),
+        max_entries: GLOB_LIMIT,
+        respect_ignore,
+    };
+    let result = walk_workspace(&canonical_root, &opts, |rel, _| {
+        matcher.matches_with(rel, match_opts)
+    });
+    Ok(result.entries.into_iter().map(|e| e.path).collect())
+}
 
-    let mut results = Vec::new();
-    for entry in paths {
-        let p = entry.map_err(|e| e.to_string())?;
-        let canonical = match p.canonicalize() {
-            Ok(c) => c,
-            Err(_) => continue,
-        };
-        if !canonical.starts_with(&canonical_root) {
-            continue;
-        }
-        if let Ok(rel) = canonical.strip_prefix(&canonical_root) {
-            results.push(rel.to_string_lossy().into_owned());
-        }
-        if results.len() >= GLOB_LIMIT {
-            break;
-        }
+/// Deepest level a match can sit at: one per path segment, unbounded with `**`.
+pub(super) fn pattern_depth(pattern: &str) -> Option<usize> {
+    let segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty() && *s != ".").collect();
+    if segments.iter().any(|s| s.contains("**")) {
+        None
+    } else {
+        Some(segments.len())
     }
-    Ok(results)
 }
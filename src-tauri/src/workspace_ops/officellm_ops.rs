@@ -80,7 +80,7 @@ pub fn ws_officellm(
                 })
                 .collect();
             if crate::officellm::server::has_session() {
-                crate::officellm::server::call(cmd, &cli_args)
+                crate::officellm::server::call(cmd, &cli_args, None)
                     .map(|r| serde_json::to_value(&r).unwrap_or(serde_json::Value::Null))
             } else {
                 crate::officellm::cli::call(
@@ -88,6 +88,7 @@ pub fn ws_officellm(
                     &cli_args,
                     officellm_home,
                     Path::new(workspace_root),
+                    None,
                 )
                 .map(|r| serde_json::to_value(&r).unwrap_or(serde_json::Value::Null))
             }
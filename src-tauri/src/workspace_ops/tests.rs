@@ -218,7 +218,7 @@ fn test_glob_basic() {
     fs::write(dir.path().join("a.txt"), "").unwrap();
     fs::write(dir.path().join("b.txt"), "").unwrap();
     fs::write(dir.path().join("c.rs"), "").unwrap();
-    let mut files = ws_glob(wr, "*.txt").unwrap();
+    let mut files = ws_glob(wr, "*.txt", false).unwrap();
     files.sort();
     assert_eq!(files, vec!["a.txt", "b.txt"]);
 }
@@ -230,7 +230,7 @@ fn test_glob_nested() {
     fs::create_dir_all(dir.path().join("src/lib")).unwrap();
     fs::write(dir.path().join("src/a.ts"), "").unwrap();
     fs::write(dir.path().join("src/lib/b.ts"), "").unwrap();
-    let mut files = ws_glob(wr, "src/**/*.ts").unwrap();
+    let mut files = ws_glob(wr, "src/**/*.ts", false).unwrap();
     files.sort();
     assert_eq!(files, vec!["src/a.ts", "src/lib/b.ts"]);
 }
@@ -242,7 +242,7 @@ fn test_glob_limit() {
     for i in 0..1005 {
         fs::write(dir.path().join(format!("{i:04}.txt")), "").unwrap();
     }
-    let files = ws_glob(wr, "*.txt").unwrap();
+    let files = ws_glob(wr, "*.txt", false).unwrap();
     assert_eq!(files.len(), 1000);
 }
 
@@ -250,7 +250,7 @@ fn test_glob_limit() {
 fn test_glob_absolute_pattern_rejected() {
     let dir = TempDir::new().unwrap();
     let wr = dir.path().to_str().unwrap();
-    let err = ws_glob(wr, "/etc/*").unwrap_err();
+    let err = ws_glob(wr, "/etc/*", false).unwrap_err();
     assert!(err.contains("absolute glob patterns not allowed"));
 }
 
@@ -258,7 +258,7 @@ fn test_glob_absolute_pattern_rejected() {
 fn test_glob_parent_traversal_rejected() {
     let dir = TempDir::new().unwrap();
     let wr = dir.path().to_str().unwrap();
-    let err = ws_glob(wr, "../*").unwrap_err();
+    let err = ws_glob(wr, "../*", false).unwrap_err();
     assert!(err.contains("parent traversal"));
 }
 
@@ -266,6 +266,55 @@ fn test_glob_parent_traversal_rejected() {
 fn test_glob_nested_parent_traversal_rejected() {
     let dir = TempDir::new().unwrap();
     let wr = dir.path().to_str().unwrap();
-    let err = ws_glob(wr, "sub/../../*.txt").unwrap_err();
+    let err = ws_glob(wr, "sub/../../*.txt", false).unwrap_err();
     assert!(err.contains("parent traversal"));
 }
+
+#[test]
+fn test_glob_recursive_double_star() {
+    let dir = TempDir::new().unwrap();
+    let wr = dir.path().to_str().unwrap();
+    fs::create_dir_all(dir.path().join("docs/notes")).unwrap();
+    fs::write(dir.path().join("top.txt"), "").unwrap();
+    fs::write(dir.path().join("docs/a.txt"), "").unwrap();
+    fs::write(dir.path().join("docs/notes/b.txt"), "").unwrap();
+    fs::write(dir.path().join("docs/c.md"), "").unwrap();
+    let mut files = ws_glob(wr, "**/*.txt", false).unwrap();
+    files.sort();
+    assert_eq!(files, vec!["docs/a.txt", "docs/notes/b.txt", "top.txt"]);
+}
+
+#[test]
+fn test_glob_skips_hidden_and_gitignored() {
+    let dir = TempDir::new().unwrap();
+    let wr = dir.path().to_str().unwrap();
+    fs::create_dir_all(dir.path().join(".git")).unwrap();
+    fs::create_dir_all(dir.path().join(".cache")).unwrap();
+    fs::create_dir_all(dir.path().join("node_modules/pkg")).unwrap();
+    fs::write(dir.path().join(".gitignore"), "node_modules/\n").unwrap();
+    fs::write(dir.path().join(".cache/x.txt"), "").unwrap();
+    fs::write(dir.path().join("node_modules/pkg/y.txt"), "").unwrap();
+    fs::write(dir.path().join("keep.txt"), "").unwrap();
+    let files = ws_glob(wr, "**/*.txt", true).unwrap();
+    assert_eq!(files, vec!["keep.txt"]);
+
+    // 默认与原 glob 行为一致：隐藏与被忽略的文件照常匹配
+    let mut files = ws_glob(wr, "**/*.txt", false).unwrap();
+    files.sort();
+    assert_eq!(files, vec![".cache/x.txt", "keep.txt", "node_modules/pkg/y.txt"]);
+}
+
+#[test]
+fn test_glob_depth_follows_pattern() {
+    use super::glob_ops::pattern_depth;
+    assert_eq!(pattern_depth("*.txt"), Some(1));
+    assert_eq!(pattern_depth("./src/*/*.ts"), Some(3));
+    assert_eq!(pattern_depth("src/**/*.ts"), None);
+
+    let dir = TempDir::new().unwrap();
+    let wr = dir.path().to_str().unwrap();
+    fs::create_dir_all(dir.path().join("a/b")).unwrap();
+    fs::write(dir.path().join("a/one.txt"), "").unwrap();
+    fs::write(dir.path().join("a/b/two.txt"), "").unwrap();
+    assert_eq!(ws_glob(wr, "*/*.txt", false).unwrap(), vec!["a/one.txt"]);
+}
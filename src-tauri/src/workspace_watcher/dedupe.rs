@@ -0,0 +1,87 @@
+//! 基于内容哈希的去重：编辑器重写相同内容时不再发送 Modify 事件。
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hasher;
+use std::path::Path;
+
+use super::FileChangeKind;
+
+/// 超过此大小的文件不计算哈希，照常发送事件
+const DEDUPE_MAX_BYTES: u64 = 1024 * 1024;
+/// 最多记住的路径数（LRU）
+const DEDUPE_CAPACITY: usize = 256;
+
+/// 计算文件内容哈希；文件不存在、过大或读取失败时返回 None
+pub(super) fn content_hash(abs: &Path) -> Option<u64> {
+    let meta = std::fs::metadata(abs).ok()?;
+    if !meta.is_file() || meta.len() > DEDUPE_MAX_BYTES {
+        return None;
+    }
+    let bytes = std::fs::read(abs).ok()?;
+    let mut hasher = DefaultHasher::new();
+    hasher.write(&bytes);
+    Some(hasher.finish())
+}
+
+/// path → 上次发送时的内容哈希，容量满时淘汰最久未用的条目
+pub(super) struct ContentDeduper {
+    hashes: HashMap<String, u64>,
+    order: VecDeque<String>,
+    capacity: usize,
+}
+
+impl ContentDeduper {
+    pub(super) fn new() -> Self {
+        Self::with_capacity(DEDUPE_CAPACITY)
+    }
+
+    pub(super) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            hashes: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// 记录本次哈希；若为 Modify 且与上次记录相同则返回 false（应抑制）
+    pub(super) fn check(&mut self, path: &str, kind: FileChangeKind, hash: Option<u64>) -> bool {
+        let Some(hash) = hash else {
+            self.forget(path);
+            return true;
+        };
+        if matches!(kind, FileChangeKind::Remove) {
+            self.forget(path);
+            return true;
+        }
+        let unchanged = self.hashes.get(path) == Some(&hash);
+        self.remember(path, hash);
+        !(unchanged && matches!(kind, FileChangeKind::Modify))
+    }
+
+    /// 读取 `abs` 的内容并判断是否需要发送事件
+    pub(super) fn should_emit(&mut self, path: &str, kind: FileChangeKind, abs: &Path) -> bool {
+        let hash = match kind {
+            FileChangeKind::Remove => None,
+            _ => content_hash(abs),
+        };
+        self.check(path, kind, hash)
+    }
+
+    fn remember(&mut self, path: &str, hash: u64) {
+        if self.hashes.insert(path.to_string(), hash).is_some() {
+            self.order.retain(|p| p != path);
+        } else if self.hashes.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.hashes.remove(&oldest);
+            }
+        }
+        self.order.push_back(path.to_string());
+    }
+
+    fn forget(&mut self, path: &str) {
+        if self.hashes.remove(path).is_some() {
+            self.order.retain(|p| p != path);
+        }
+    }
+}
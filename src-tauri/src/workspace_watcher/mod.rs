@@ -0,0 +1,298 @@
+//! 工作区文件监听：递归 watch + 防抖，向前端发送 workspace-file-changed 事件。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use notify::{recommended_watcher, Event, EventKind, RecommendedWatcher};
+use serde::Serialize;
+use tauri::Emitter;
+
+mod dedupe;
+mod pause;
+mod watches;
+
+#[cfg(test)]
+mod tests;
+
+use dedupe::ContentDeduper;
+use pause::flush_pending;
+pub use pause::*;
+use watches::register_watches;
+
+const DEBOUNCE_MS: u64 = 400;
+
+/// 前端监听的事件名
+pub const EVENT_WORKSPACE_FILE_CHANGED: &str = "workspace-file-changed";
+/// 监听未能完整建立（如触达系统 inotify 上限）时发送，UI 可据此提示用户
+pub const EVENT_WATCHER_DEGRADED: &str = "watcher-degraded";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatcherDegradedPayload {
+    pub root: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceFileChangedPayload {
+    /// 相对工作区根的路径（正斜杠）
+    pub path: String,
+    pub kind: FileChangeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileChangeKind {
+    Create,
+    Modify,
+    Remove,
+    Rename,
+}
+
+/// notify 6.x 无 Rename 变体，重命名通常以 Modify 上报
+fn kind_from_event(e: &Event) -> Option<FileChangeKind> {
+    match &e.kind {
+        EventKind::Create(_) => Some(FileChangeKind::Create),
+        EventKind::Modify(_) => Some(FileChangeKind::Modify),
+        EventKind::Remove(_) => Some(FileChangeKind::Remove),
+        _ => None,
+    }
+}
+
+/// 忽略的目录名（不向上递归匹配，仅当前段）
+const IGNORE_DIRS: &[&str] = &["node_modules", ".git", "target", "dist", ".next", ".turbo", "build"];
+
+fn is_ignored(path: &Path, workspace_root: &Path) -> bool {
+    let path = path.strip_prefix(workspace_root).unwrap_or(path);
+    path.components().any(|c| {
+        if let std::path::Component::Normal(name) = c {
+            IGNORE_DIRS.contains(&name.to_string_lossy().as_ref())
+        } else {
+            false
+        }
+    })
+}
+
+fn to_relative_path(root: &Path, abs: &Path) -> Option<String> {
+    let rel = abs.strip_prefix(root).ok()?;
+    Some(rel.to_string_lossy().replace('\\', "/"))
+}
+
+/// 扩展名过滤：`extensions` 为已规范化（小写、无前导点）的列表，None 表示不过滤
+fn matches_extension(path: &Path, extensions: Option<&[String]>) -> bool {
+    let Some(exts) = extensions else {
+        return true;
+    };
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|e| exts.contains(&e))
+}
+
+/// 规范化前端传入的扩展名（`.MD` → `md`），空列表视为不过滤
+fn normalize_extensions(extensions: Option<Vec<String>>) -> Option<Vec<String>> {
+    let exts: Vec<String> = extensions?
+        .iter()
+        .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect();
+    (!exts.is_empty()).then_some(exts)
+}
+
+/// 从事件中收集 (相对路径, kind)
+fn collect_paths(
+    e: &Event,
+    workspace_root: &Path,
+    extensions: Option<&[String]>,
+) -> Vec<(String, FileChangeKind)> {
+    let kind = match kind_from_event(e) {
+        Some(k) => k,
+        None => return vec![],
+    };
+    let mut out = Vec::new();
+    for p in &e.paths {
+        if is_ignored(p, workspace_root) || !matches_extension(p, extensions) {
+            continue;
+        }
+        if let Some(rel) = to_relative_path(workspace_root, p) {
+            out.push((rel, kind));
+        }
+    }
+    out
+}
+
+/// 当前生效的 watcher 及其监听根目录；drop 即停止监听。
+pub struct ActiveWatcher {
+    _watcher: RecommendedWatcher,
+    pub root: PathBuf,
+}
+
+pub struct WatcherState {
+    pub watcher: Mutex<Option<ActiveWatcher>>,
+    /// `pause_watching` 期间缓冲变更，`resume_watching` 时汇总发送
+    pub paused: PauseState,
+}
+
+impl WatcherState {
+    pub fn new() -> Self {
+        Self {
+            watcher: Mutex::new(None),
+            paused: PauseState::default(),
+        }
+    }
+}
+
+/// 启动对 workspace_root 的监听；若此前已有监听则先停止再开新的，暂停状态随之清除。
+pub fn watch_workspace(
+    app_handle: tauri::AppHandle,
+    state: Arc<WatcherState>,
+    workspace_root: PathBuf,
+    max_depth: Option<usize>,
+    dedupe_unchanged: bool,
+    extensions: Option<Vec<String>>,
+) -> Result<(), String> {
+    {
+        let mut guard = state.watcher.lock().map_err(|e| e.to_string())?;
+        *guard = None;
+    }
+    state.paused.clear();
+
+    let (tx, rx) = mpsc::channel::<(String, FileChangeKind)>();
+
+    let root = workspace_root.clone();
+    let mut watcher = recommended_watcher(move |res: Result<Event, notify::Error>| {
+        if let Ok(e) = res {
+            for (rel, kind) in collect_paths(&e, &root, extensions.as_deref()) {
+                let _ = tx.send((rel, kind));
+            }
+        }
+    })
+    .map_err(|e| e.to_string())?;
+
+    if let Some(reason) = register_watches(&mut watcher, &workspace_root, max_depth)? {
+        let _ = app_handle.emit(
+            EVENT_WATCHER_DEGRADED,
+            WatcherDegradedPayload {
+                root: workspace_root.to_string_lossy().into_owned(),
+                reason,
+            },
+        );
+    }
+
+    {
+        let mut guard = state.watcher.lock().map_err(|e| e.to_string())?;
+        *guard = Some(ActiveWatcher {
+            _watcher: watcher,
+            root: workspace_root.clone(),
+        });
+    }
+
+    // 防抖线程：收集 DEBOUNCE_MS 内的 (path, kind)，同一 path 只保留最后一次 kind，再 emit
+    std::thread::spawn(move || {
+        let mut pending: HashMap<String, FileChangeKind> = HashMap::new();
+        let mut deduper = dedupe_unchanged.then(ContentDeduper::new);
+        let timeout = Duration::from_millis(DEBOUNCE_MS);
+        loop {
+            match rx.recv_timeout(timeout) {
+                Ok((path, kind)) => {
+                    pending.insert(path, kind);
+                }
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                    let emit = |payload: WorkspaceFileChangedPayload| {
+                        let _ = app_handle.emit(EVENT_WORKSPACE_FILE_CHANGED, payload);
+                    };
+                    let root = workspace_root.as_path();
+                    flush_pending(&mut pending, deduper.as_mut(), root, &state.paused, emit);
+                }
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// 停止监听（drop 当前 watcher），并清除暂停状态
+pub fn stop_watching(state: &WatcherState) {
+    if let Ok(mut guard) = state.watcher.lock() {
+        *guard = None;
+    }
+    state.paused.clear();
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatcherStatus {
+    pub active: bool,
+    pub roots: Vec<String>,
+    /// 是否处于 `pause_watching` 暂停中
+    pub paused: bool,
+}
+
+pub fn watcher_status_of(state: &WatcherState) -> WatcherStatus {
+    let roots: Vec<String> = state
+        .watcher
+        .lock()
+        .map(|guard| {
+            guard
+                .iter()
+                .map(|w| w.root.to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+    WatcherStatus {
+        active: !roots.is_empty(),
+        roots,
+        paused: state.paused.is_paused(),
+    }
+}
+
+/// 查询当前监听状态，便于排查前端漏收事件的问题
+#[tauri::command]
+pub fn watcher_status(state: tauri::State<Arc<WatcherState>>) -> WatcherStatus {
+    watcher_status_of(state.inner())
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchWorkspaceArgs {
+    pub workspace_root: String,
+    /// 限制监听深度，避免超深目录树耗尽系统监听数；默认递归监听全部
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    /// 内容未变化的 Modify 事件不再发送（编辑器原样重写时避免预览重复刷新）
+    #[serde(default)]
+    pub dedupe_unchanged: bool,
+    /// 只发送这些扩展名的变更（如 `["md"]`，不区分大小写、可带点）；默认不过滤
+    #[serde(default)]
+    pub extensions: Option<Vec<String>>,
+}
+
+#[tauri::command]
+pub fn watch_workspace_command(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<Arc<WatcherState>>,
+    args: WatchWorkspaceArgs,
+) -> Result<(), String> {
+    let root = args.workspace_root.trim();
+    if root.is_empty() {
+        stop_watching(state.inner().as_ref());
+        return Ok(());
+    }
+    let path = PathBuf::from(root);
+    if !path.is_dir() {
+        return Err("workspace_root 不是有效目录".into());
+    }
+    let canonical = path.canonicalize().map_err(|e| e.to_string())?;
+    watch_workspace(
+        app_handle,
+        state.inner().clone(),
+        canonical,
+        args.max_depth,
+        args.dedupe_unchanged,
+        normalize_extensions(args.extensions),
+    )
+}
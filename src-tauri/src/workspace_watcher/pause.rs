@@ -0,0 +1,127 @@
+//! 暂停/恢复：批量写入期间不逐条发送变更事件，恢复时合并为一次汇总事件。
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::Emitter;
+
+use super::dedupe::ContentDeduper;
+use super::{FileChangeKind, WatcherState, WorkspaceFileChangedPayload};
+
+/// 恢复监听时发送的汇总事件
+pub const EVENT_WORKSPACE_WATCH_RESUMED: &str = "workspace-watch-resumed";
+/// 暂停期间最多记录的路径数；超出后只标记 `overflowed`
+const PAUSE_BUFFER_LIMIT: usize = 10_000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatcherResumedPayload {
+    /// 暂停期间变更过的路径，同一 path 只保留最后一次 kind，按路径排序
+    pub changes: Vec<WorkspaceFileChangedPayload>,
+    /// 变更过多未全部记录，前端应整体刷新工作区
+    pub overflowed: bool,
+}
+
+#[derive(Default)]
+struct PauseBuffer {
+    changes: BTreeMap<String, FileChangeKind>,
+    overflowed: bool,
+}
+
+/// 暂停状态；`None` 表示未暂停
+#[derive(Default)]
+pub struct PauseState {
+    buffer: Mutex<Option<PauseBuffer>>,
+}
+
+impl PauseState {
+    pub fn is_paused(&self) -> bool {
+        self.buffer.lock().map(|b| b.is_some()).unwrap_or(false)
+    }
+
+    /// 开始暂停；重复调用保留已缓冲的变更
+    pub fn pause(&self) {
+        if let Ok(mut guard) = self.buffer.lock() {
+            guard.get_or_insert_with(PauseBuffer::default);
+        }
+    }
+
+    /// 结束暂停并取出汇总；未暂停时返回 None
+    pub fn resume(&self) -> Option<WatcherResumedPayload> {
+        let buffer = self.buffer.lock().ok()?.take()?;
+        let changes = buffer
+            .changes
+            .into_iter()
+            .map(|(path, kind)| WorkspaceFileChangedPayload { path, kind })
+            .collect();
+        Some(WatcherResumedPayload {
+            changes,
+            overflowed: buffer.overflowed,
+        })
+    }
+
+    /// 丢弃暂停状态与已缓冲的变更：监听重启或停止后，旧监听期间的汇总已无意义，
+    /// 也避免前端漏调 `resume_watching` 时新的监听一直处于暂停中
+    pub fn clear(&self) {
+        if let Ok(mut guard) = self.buffer.lock() {
+            *guard = None;
+        }
+    }
+
+    /// 暂停中则缓冲该变更并返回 true，调用方不再发送事件
+    pub(super) fn intercept(&self, path: &str, kind: FileChangeKind) -> bool {
+        let Ok(mut guard) = self.buffer.lock() else {
+            return false;
+        };
+        let Some(buffer) = guard.as_mut() else {
+            return false;
+        };
+        if buffer.changes.len() < PAUSE_BUFFER_LIMIT || buffer.changes.contains_key(path) {
+            buffer.changes.insert(path.to_string(), kind);
+        } else {
+            buffer.overflowed = true;
+        }
+        true
+    }
+}
+
+/// 发送防抖窗口内累积的变更；内容未变的 Modify 被去重，暂停期间的变更转入缓冲
+pub(super) fn flush_pending(
+    pending: &mut HashMap<String, FileChangeKind>,
+    mut deduper: Option<&mut ContentDeduper>,
+    workspace_root: &Path,
+    pause: &PauseState,
+    mut emit: impl FnMut(WorkspaceFileChangedPayload),
+) {
+    for (path, kind) in pending.drain() {
+        if let Some(d) = deduper.as_mut() {
+            if !d.should_emit(&path, kind, &workspace_root.join(&path)) {
+                continue;
+            }
+        }
+        if pause.intercept(&path, kind) {
+            continue;
+        }
+        emit(WorkspaceFileChangedPayload { path, kind });
+    }
+}
+
+/// 暂停发送 workspace-file-changed 事件（监听本身保持运行，变更被缓冲）
+#[tauri::command]
+pub fn pause_watching(state: tauri::State<Arc<WatcherState>>) {
+    state.paused.pause();
+}
+
+/// 恢复发送事件，并以一次 workspace-watch-resumed 事件发送暂停期间的变更汇总。
+/// 恢复前最后一个防抖窗口内的变更仍会在防抖后单独发送。
+#[tauri::command]
+pub fn resume_watching(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<Arc<WatcherState>>,
+) -> Option<WatcherResumedPayload> {
+    let summary = state.paused.resume()?;
+    let _ = app_handle.emit(EVENT_WORKSPACE_WATCH_RESUMED, summary.clone());
+    Some(summary)
+}
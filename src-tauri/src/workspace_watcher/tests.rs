@@ -0,0 +1,290 @@
+use super::pause::flush_pending;
+use super::watches::register_watches;
+use super::*;
+use notify::event::{AccessKind, CreateKind, ModifyKind, RemoveKind};
+use notify::{RecursiveMode, Watcher};
+
+#[test]
+fn kind_from_event_maps_create_modify_remove() {
+    let create = Event::new(EventKind::Create(CreateKind::File));
+    assert!(matches!(kind_from_event(&create), Some(FileChangeKind::Create)));
+    let modify = Event::new(EventKind::Modify(ModifyKind::Any));
+    assert!(matches!(kind_from_event(&modify), Some(FileChangeKind::Modify)));
+    let remove = Event::new(EventKind::Remove(RemoveKind::File));
+    assert!(matches!(kind_from_event(&remove), Some(FileChangeKind::Remove)));
+}
+
+#[test]
+fn kind_from_event_returns_none_for_access_and_other() {
+    let access = Event::new(EventKind::Access(AccessKind::Read));
+    assert!(kind_from_event(&access).is_none());
+    let other = Event::new(EventKind::Other);
+    assert!(kind_from_event(&other).is_none());
+}
+
+#[test]
+fn is_ignored_filters_all_ignore_dirs() {
+    let root = Path::new("/workspace");
+    for dir in IGNORE_DIRS {
+        let p = root.join(dir).join("file.rs");
+        assert!(is_ignored(&p, root), "{dir} should be ignored");
+    }
+}
+
+#[test]
+fn is_ignored_passes_normal_paths() {
+    let root = Path::new("/workspace");
+    assert!(!is_ignored(&root.join("src/main.rs"), root));
+    assert!(!is_ignored(&root.join("README.md"), root));
+}
+
+#[test]
+fn is_ignored_catches_nested_ignored_dir() {
+    let root = Path::new("/workspace");
+    let p = root.join("packages/foo/node_modules/bar/index.js");
+    assert!(is_ignored(&p, root));
+}
+
+#[test]
+fn to_relative_path_inside_root() {
+    let root = Path::new("/workspace");
+    assert_eq!(
+        to_relative_path(root, &root.join("src/main.rs")),
+        Some("src/main.rs".into())
+    );
+}
+
+#[test]
+fn to_relative_path_outside_root_returns_none() {
+    let root = Path::new("/workspace");
+    assert_eq!(to_relative_path(root, Path::new("/other/file.rs")), None);
+}
+
+#[test]
+fn collect_paths_filters_ignored_and_converts() {
+    let root = Path::new("/workspace");
+    let mut e = Event::new(EventKind::Create(CreateKind::File));
+    e.paths = vec![
+        root.join("src/main.rs"),
+        root.join("node_modules/foo/bar.js"),
+        root.join("lib/util.rs"),
+    ];
+    let result = collect_paths(&e, root, None);
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0].0, "src/main.rs");
+    assert_eq!(result[1].0, "lib/util.rs");
+}
+
+#[test]
+fn collect_paths_returns_empty_for_access_event() {
+    let root = Path::new("/workspace");
+    let mut e = Event::new(EventKind::Access(AccessKind::Read));
+    e.paths = vec![root.join("src/main.rs")];
+    assert!(collect_paths(&e, root, None).is_empty());
+}
+
+#[test]
+fn collect_paths_filters_by_extension() {
+    let root = Path::new("/workspace");
+    let mut e = Event::new(EventKind::Modify(ModifyKind::Any));
+    e.paths = vec![root.join("notes/readme.MD"), root.join("logs/app.log")];
+    let exts = normalize_extensions(Some(vec![".md".into()])).unwrap();
+    let result = collect_paths(&e, root, Some(&exts));
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].0, "notes/readme.MD");
+}
+
+#[test]
+fn normalize_extensions_treats_empty_as_unfiltered() {
+    assert_eq!(normalize_extensions(None), None);
+    assert_eq!(normalize_extensions(Some(vec![" ".into(), ".".into()])), None);
+    assert_eq!(normalize_extensions(Some(vec!["Md".into()])), Some(vec!["md".to_string()]));
+}
+
+#[test]
+fn watcher_state_new_is_none() {
+    let s = WatcherState::new();
+    assert!(s.watcher.lock().unwrap().is_none());
+}
+
+#[test]
+fn stop_watching_no_panic_on_empty_state() {
+    let s = WatcherState::new();
+    stop_watching(&s);
+}
+
+#[test]
+fn watcher_status_reflects_active_and_stopped() {
+    let s = WatcherState::new();
+    assert_eq!(
+        watcher_status_of(&s),
+        WatcherStatus { active: false, roots: vec![], paused: false }
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut watcher = recommended_watcher(|_: Result<Event, notify::Error>| {}).unwrap();
+    watcher.watch(dir.path(), RecursiveMode::Recursive).unwrap();
+    *s.watcher.lock().unwrap() = Some(ActiveWatcher {
+        _watcher: watcher,
+        root: dir.path().to_path_buf(),
+    });
+    let status = watcher_status_of(&s);
+    assert!(status.active);
+    assert_eq!(status.roots, vec![dir.path().to_string_lossy().into_owned()]);
+
+    stop_watching(&s);
+    assert!(!watcher_status_of(&s).active);
+}
+
+#[test]
+fn watch_workspace_args_deserialize_camel_case() {
+    let json = r#"{"workspaceRoot":"/tmp/ws"}"#;
+    let args: WatchWorkspaceArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.workspace_root, "/tmp/ws");
+    assert_eq!(args.max_depth, None);
+
+    let json = r#"{"workspaceRoot":"/tmp/ws","maxDepth":2}"#;
+    let args: WatchWorkspaceArgs = serde_json::from_str(json).unwrap();
+    assert_eq!(args.max_depth, Some(2));
+}
+
+/// 记录 watch 调用，并在注册 `limit` 个之后模拟 inotify 上限错误
+struct LimitedWatcher {
+    limit: usize,
+    watched: Vec<(PathBuf, RecursiveMode)>,
+}
+
+impl Watcher for LimitedWatcher {
+    fn new<F: notify::EventHandler>(_: F, _: notify::Config) -> notify::Result<Self> {
+        Ok(Self { limit: usize::MAX, watched: Vec::new() })
+    }
+    fn watch(&mut self, path: &Path, mode: RecursiveMode) -> notify::Result<()> {
+        if self.watched.len() >= self.limit {
+            return Err(notify::Error::new(notify::ErrorKind::MaxFilesWatch));
+        }
+        self.watched.push((path.to_path_buf(), mode));
+        Ok(())
+    }
+    fn unwatch(&mut self, _: &Path) -> notify::Result<()> {
+        Ok(())
+    }
+    fn kind() -> notify::WatcherKind {
+        notify::WatcherKind::NullWatcher
+    }
+}
+
+fn deep_tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("a/b/c/d")).unwrap();
+    std::fs::create_dir_all(dir.path().join("e")).unwrap();
+    std::fs::create_dir_all(dir.path().join("node_modules/pkg")).unwrap();
+    dir
+}
+
+#[test]
+fn register_watches_respects_max_depth_and_ignores() {
+    let dir = deep_tree();
+    let mut w = LimitedWatcher { limit: usize::MAX, watched: Vec::new() };
+    let degraded = register_watches(&mut w, dir.path(), Some(1)).unwrap();
+    assert!(degraded.is_none());
+    let mut rels: Vec<String> = w
+        .watched
+        .iter()
+        .map(|(p, mode)| {
+            assert_eq!(*mode, RecursiveMode::NonRecursive);
+            to_relative_path(dir.path(), p).unwrap()
+        })
+        .collect();
+    rels.sort();
+    assert_eq!(rels, vec!["", "a", "e"]);
+}
+
+#[test]
+fn register_watches_reports_degraded_when_limit_hit() {
+    let dir = deep_tree();
+    let mut w = LimitedWatcher { limit: 2, watched: Vec::new() };
+    let degraded = register_watches(&mut w, dir.path(), Some(10)).unwrap();
+    assert!(degraded.unwrap().contains("limit"));
+    assert_eq!(w.watched.len(), 2);
+
+    // 递归模式触达上限时退回仅监听根目录
+    let mut w = LimitedWatcher { limit: 0, watched: Vec::new() };
+    assert!(register_watches(&mut w, dir.path(), None).unwrap().is_some());
+}
+
+#[test]
+fn deduper_suppresses_modify_with_identical_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("a.txt");
+    let mut d = super::dedupe::ContentDeduper::new();
+
+    std::fs::write(&file, "hello").unwrap();
+    assert!(d.should_emit("a.txt", FileChangeKind::Modify, &file));
+    // 原样重写：哈希不变，抑制
+    std::fs::write(&file, "hello").unwrap();
+    assert!(!d.should_emit("a.txt", FileChangeKind::Modify, &file));
+    std::fs::write(&file, "world").unwrap();
+    assert!(d.should_emit("a.txt", FileChangeKind::Modify, &file));
+}
+
+#[test]
+fn deduper_check_resets_on_remove_and_evicts_oldest() {
+    let mut d = super::dedupe::ContentDeduper::with_capacity(2);
+    assert!(d.check("a", FileChangeKind::Create, Some(1)));
+    assert!(!d.check("a", FileChangeKind::Modify, Some(1)));
+    assert!(d.check("a", FileChangeKind::Remove, None));
+    assert!(d.check("a", FileChangeKind::Modify, Some(1)));
+    // 无法计算哈希（过大/不可读）时总是发送
+    assert!(d.check("big", FileChangeKind::Modify, None));
+    assert!(d.check("big", FileChangeKind::Modify, None));
+
+    d.check("b", FileChangeKind::Modify, Some(2));
+    d.check("c", FileChangeKind::Modify, Some(3));
+    // 容量 2：最早的 "a" 已被淘汰
+    assert!(d.check("a", FileChangeKind::Modify, Some(1)));
+    assert!(!d.check("c", FileChangeKind::Modify, Some(3)));
+}
+
+fn pending_of(changes: &[(&str, FileChangeKind)]) -> HashMap<String, FileChangeKind> {
+    changes.iter().map(|(p, k)| (p.to_string(), *k)).collect()
+}
+
+#[test]
+fn paused_watcher_suppresses_events_and_coalesces_on_resume() {
+    let state = WatcherState::new();
+    let root = Path::new("/workspace");
+    let mut emitted = Vec::new();
+
+    state.paused.pause();
+    assert!(watcher_status_of(&state).paused);
+    let mut pending = pending_of(&[("b.txt", FileChangeKind::Create), ("a.txt", FileChangeKind::Modify)]);
+    flush_pending(&mut pending, None, root, &state.paused, |p| emitted.push(p.path));
+    let mut pending = pending_of(&[("b.txt", FileChangeKind::Remove)]);
+    flush_pending(&mut pending, None, root, &state.paused, |p| emitted.push(p.path));
+    assert!(emitted.is_empty(), "events must be suppressed while paused");
+
+    let summary = state.paused.resume().unwrap();
+    assert!(!summary.overflowed);
+    let changes: Vec<(&str, FileChangeKind)> =
+        summary.changes.iter().map(|c| (c.path.as_str(), c.kind)).collect();
+    assert_eq!(changes, vec![("a.txt", FileChangeKind::Modify), ("b.txt", FileChangeKind::Remove)]);
+    assert!(!watcher_status_of(&state).paused);
+
+    // 恢复后照常逐条发送；未暂停时 resume 返回 None
+    let mut pending = pending_of(&[("c.txt", FileChangeKind::Create)]);
+    flush_pending(&mut pending, None, root, &state.paused, |p| emitted.push(p.path));
+    assert_eq!(emitted, vec!["c.txt".to_string()]);
+    assert!(state.paused.resume().is_none());
+}
+
+#[test]
+fn stop_watching_clears_pause() {
+    let state = WatcherState::new();
+    state.paused.pause();
+    let mut pending = pending_of(&[("a.txt", FileChangeKind::Modify)]);
+    flush_pending(&mut pending, None, Path::new("/workspace"), &state.paused, |_| {});
+
+    stop_watching(&state);
+    assert!(!watcher_status_of(&state).paused);
+    assert!(state.paused.resume().is_none(), "buffered changes are discarded");
+}
@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use notify::{RecursiveMode, Watcher};
+
+use super::is_ignored;
+
+/// 注册监听目录。`max_depth` 为 None 时递归监听整个工作区；否则按目录逐个非递归监听，
+/// 深度不超过 `max_depth`（0 = 仅根目录），跳过忽略目录。之后新建的目录不会自动加入。
+///
+/// 触达系统监听上限时不报错，而是保留已建立的监听并返回降级原因。
+pub(super) fn register_watches<W: Watcher>(
+    watcher: &mut W,
+    root: &Path,
+    max_depth: Option<usize>,
+) -> Result<Option<String>, String> {
+    let is_limit = |e: &notify::Error| matches!(e.kind, notify::ErrorKind::MaxFilesWatch);
+    let Some(max_depth) = max_depth else {
+        return match watcher.watch(root, RecursiveMode::Recursive) {
+            Ok(()) => Ok(None),
+            Err(e) if is_limit(&e) => {
+                // 退回仅监听根目录，至少保留顶层事件
+                let _ = watcher.watch(root, RecursiveMode::NonRecursive);
+                Ok(Some(e.to_string()))
+            }
+            Err(e) => Err(e.to_string()),
+        };
+    };
+
+    let mut stack = vec![(root.to_path_buf(), 0usize)];
+    while let Some((dir, depth)) = stack.pop() {
+        match watcher.watch(&dir, RecursiveMode::NonRecursive) {
+            Ok(()) => {}
+            Err(e) if is_limit(&e) => return Ok(Some(e.to_string())),
+            Err(e) if dir == root => return Err(e.to_string()),
+            Err(_) => continue,
+        }
+        if depth >= max_depth {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir && !is_ignored(&path, root) {
+                stack.push((path, depth + 1));
+            }
+        }
+    }
+    Ok(None)
+}
@@ -6,7 +6,7 @@
  *
  * 缓存策略（两级）：
  *   L1 内存缓存（模块 Map，按命令名隔离）：同 session 内命中时零延迟。
- *   L2 磁盘缓存（Rust 端，FNV-1a 哈希键）：跨 session 命中时极快。
+ *   L2 磁盘缓存（Rust 端，SHA-256 哈希键）：跨 session 命中时极快。
  *   首次预览：后台线程异步转换，UI 始终响应，完成后自动渲染。
  */
 import { useEffect, useRef, useState } from "react";
@@ -101,6 +101,17 @@ describe("useFileTreeDialogs", () => {
       expect(result.current.newFileParentPath).toBe("");
     });
 
+    it("sets file-already-exists error for AlreadyExists kind", async () => {
+      mockInvoke.mockRejectedValueOnce({ kind: "AlreadyExists" });
+
+      const { result } = renderDialogs();
+      act(() => result.current.onNewFile(""));
+      act(() => result.current.setNewFileName("existing.txt"));
+      await act(async () => result.current.handleNewFileConfirm());
+
+      expect(result.current.newFileError).toBe("explorer.fileAlreadyExists");
+    });
+
     it("builds path correctly for root parent (empty string)", async () => {
       mockInvoke.mockResolvedValueOnce(undefined);
 
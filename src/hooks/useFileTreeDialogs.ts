@@ -12,6 +12,20 @@ interface UseFileTreeDialogsParams {
   t: TranslateFn;
 }
 
+/** Backend reports `{ kind: "AlreadyExists" }`; older errors only carry a message */
+function isAlreadyExistsError(err: unknown): boolean {
+  if (typeof err === "object" && err != null && "kind" in err) {
+    if ((err as { kind: unknown }).kind === "AlreadyExists") return true;
+  }
+  return /already exists|已存在/i.test(errorMessage(err));
+}
+
+function errorMessage(err: unknown): string {
+  return typeof err === "object" && err != null && "message" in err
+    ? String((err as { message: string }).message)
+    : String(err);
+}
+
 export interface DeleteTarget {
   path: string;
   name: string;
@@ -66,12 +80,7 @@ export function useFileTreeDialogs({
         }
       })
       .catch((err: unknown) => {
-        const msg =
-          typeof err === "object" && err != null && "message" in err
-            ? String((err as { message: string }).message)
-            : String(err);
-        const isAlreadyExists = /already exists|已存在/i.test(msg);
-        setNewFolderError(isAlreadyExists ? t("explorer.folderAlreadyExists") : msg);
+        setNewFolderError(isAlreadyExistsError(err) ? t("explorer.folderAlreadyExists") : errorMessage(err));
       });
   }, [workspaceRoot, newFolderParentPath, newFolderName, t, setExpandedDirs]);
 
@@ -107,12 +116,7 @@ export function useFileTreeDialogs({
         }
       })
       .catch((err: unknown) => {
-        const msg =
-          typeof err === "object" && err != null && "message" in err
-            ? String((err as { message: string }).message)
-            : String(err);
-        const isAlreadyExists = /already exists|已存在/i.test(msg);
-        setNewFileError(isAlreadyExists ? t("explorer.fileAlreadyExists") : msg);
+        setNewFileError(isAlreadyExistsError(err) ? t("explorer.fileAlreadyExists") : errorMessage(err));
       });
   }, [workspaceRoot, newFileParentPath, newFileName, t, setExpandedDirs]);
 
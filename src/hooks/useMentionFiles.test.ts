@@ -27,7 +27,7 @@ describe("useMentionFiles", () => {
 
   it("loads files from workspace when enabled", async () => {
     setupTauriMocks({
-      walk_files: () => MOCK_ENTRIES,
+      walk_files: () => ({ entries: MOCK_ENTRIES, truncated: false }),
     });
 
     const { result } = renderHook(() => useMentionFiles("/workspace", "", true));
@@ -50,7 +50,7 @@ describe("useMentionFiles", () => {
 
   it("filters results by query on name", async () => {
     setupTauriMocks({
-      walk_files: () => MOCK_ENTRIES,
+      walk_files: () => ({ entries: MOCK_ENTRIES, truncated: false }),
     });
 
     const { result } = renderHook(() => useMentionFiles("/workspace", "pack", true));
@@ -64,7 +64,7 @@ describe("useMentionFiles", () => {
 
   it("filters case-insensitively", async () => {
     setupTauriMocks({
-      walk_files: () => MOCK_ENTRIES,
+      walk_files: () => ({ entries: MOCK_ENTRIES, truncated: false }),
     });
 
     const { result } = renderHook(() => useMentionFiles("/workspace", "READ", true));
@@ -78,7 +78,7 @@ describe("useMentionFiles", () => {
 
   it("filters by path for subdirectory search", async () => {
     setupTauriMocks({
-      walk_files: () => MOCK_ENTRIES,
+      walk_files: () => ({ entries: MOCK_ENTRIES, truncated: false }),
     });
 
     const { result } = renderHook(() => useMentionFiles("/workspace", "src/comp", true));
@@ -93,7 +93,7 @@ describe("useMentionFiles", () => {
 
   it("includes parentDir for subdirectory entries", async () => {
     setupTauriMocks({
-      walk_files: () => MOCK_ENTRIES,
+      walk_files: () => ({ entries: MOCK_ENTRIES, truncated: false }),
     });
 
     const { result } = renderHook(() => useMentionFiles("/workspace", "App", true));
@@ -107,7 +107,7 @@ describe("useMentionFiles", () => {
 
   it("sets empty parentDir for root-level files", async () => {
     setupTauriMocks({
-      walk_files: () => MOCK_ENTRIES,
+      walk_files: () => ({ entries: MOCK_ENTRIES, truncated: false }),
     });
 
     const { result } = renderHook(() => useMentionFiles("/workspace", "package", true));
@@ -127,7 +127,7 @@ describe("useMentionFiles", () => {
     }));
 
     setupTauriMocks({
-      walk_files: () => manyEntries,
+      walk_files: () => ({ entries: manyEntries, truncated: false }),
     });
 
     const { result } = renderHook(() => useMentionFiles("/workspace", "", true));
@@ -159,7 +159,7 @@ describe("useMentionFiles", () => {
     setupTauriMocks({
       walk_files: () => {
         callCount++;
-        return callCount === 1 ? MOCK_ENTRIES : NEW_ENTRIES;
+        return { entries: callCount === 1 ? MOCK_ENTRIES : NEW_ENTRIES, truncated: false };
       },
     });
 
@@ -194,7 +194,7 @@ describe("useMentionFiles", () => {
     setupTauriMocks({
       walk_files: () => {
         callCount++;
-        return callCount === 1 ? OLD_ENTRIES : NEW_ENTRIES;
+        return { entries: callCount === 1 ? OLD_ENTRIES : NEW_ENTRIES, truncated: false };
       },
     });
 
@@ -224,7 +224,7 @@ describe("useMentionFiles", () => {
 
   it("preserves isDir in entries", async () => {
     setupTauriMocks({
-      walk_files: () => MOCK_ENTRIES,
+      walk_files: () => ({ entries: MOCK_ENTRIES, truncated: false }),
     });
 
     const { result } = renderHook(() => useMentionFiles("/workspace", "src", true));
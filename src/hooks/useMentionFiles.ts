@@ -16,6 +16,11 @@ interface WalkFileEntry {
   isDir: boolean;
 }
 
+interface WalkFilesResult {
+  entries: WalkFileEntry[];
+  truncated: boolean;
+}
+
 const MAX_RESULTS = 10;
 
 /** Binary extensions to exclude from @mention — everything else is allowed. */
@@ -95,7 +100,7 @@ export function useMentionFiles(
       setEntries([]);
 
       try {
-        const raw = await invoke<WalkFileEntry[]>("walk_files", {
+        const { entries: raw } = await invoke<WalkFilesResult>("walk_files", {
           args: { workspaceRoot: workspacePath, includeDirs: true },
         });
         if (cancelled) return;
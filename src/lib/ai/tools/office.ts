@@ -17,6 +17,12 @@ interface CommandErrorDetail {
   details?: unknown;
 }
 
+interface CommandMetrics {
+  durationMs?: number | null;
+  inputTokens?: number | null;
+  outputTokens?: number | null;
+}
+
 interface CommandResult {
   status: string;
   code?: string | null;
@@ -25,7 +31,7 @@ interface CommandResult {
   error?: string | null;
   errors?: CommandErrorDetail[];
   meta?: unknown;
-  metrics?: unknown;
+  metrics?: CommandMetrics | null;
 }
 
 interface LuaExecutionResult {
@@ -46,6 +46,7 @@ describe("readTool", () => {
         path: "src/main.ts",
         offset: undefined,
         limit: 2000,
+        useCache: true,
       },
     });
     expect(mockRecordRead).toHaveBeenCalledWith("conv-123", "/workspace/src/main.ts");
@@ -56,7 +57,7 @@ describe("readTool", () => {
     await exec({ filePath: "a.ts", offset: 10, limit: 50 });
 
     expect(mockInvoke).toHaveBeenCalledWith("read_file", {
-      args: { workspaceRoot: "/workspace", path: "a.ts", offset: 10, limit: 50 },
+      args: { workspaceRoot: "/workspace", path: "a.ts", offset: 10, limit: 50, useCache: true },
     });
   });
 
@@ -124,6 +125,14 @@ describe("readTool", () => {
     expect(result).toContain("250KB");
   });
 
+  it("handles Timeout error", async () => {
+    mockInvoke.mockRejectedValue({ kind: "Timeout" });
+
+    const result = await exec({ filePath: "mnt/share/report.txt" });
+
+    expect(result).toContain("超时");
+  });
+
   it("handles NotAllowed with message", async () => {
     mockInvoke.mockRejectedValue({ kind: "NotAllowed", message: ".env is blocked" });
 
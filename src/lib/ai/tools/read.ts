@@ -49,6 +49,10 @@ function handleFsError(err: unknown, filePath: string): string {
       return "文件超过 250KB 上限，请使用 offset/limit 分段读取。";
     case "NotAllowed":
       return err.message ? `无法读取：${err.message}` : "无法读取该路径。";
+    case "IsDirectory":
+      return `该路径是目录，无法读取：${filePath}`;
+    case "Timeout":
+      return "读取超时，文件所在的磁盘或网络挂载可能无响应，请稍后重试。";
     default:
       return err.message ? `错误：${err.message}` : `错误：${err.kind}`;
   }
@@ -90,6 +94,7 @@ export const readTool = tool({
           path: filePath,
           offset: offset ?? undefined,
           limit: limit ?? DEFAULT_LIMIT,
+          useCache: true,
         },
       });
       if (sessionId) recordRead(sessionId, resolved);
@@ -120,6 +120,7 @@ export const writeTool = tool({
     } catch (err) {
       if (isFsError(err)) {
         if (err.kind === "OutsideWorkspace") return "该路径不在当前工作区内。";
+        if (err.kind === "IsDirectory") return "该路径是目录，无法写入。";
         if (err.kind === "NotAllowed") return err.message ?? "无法写入该路径。";
         return err.message ? `错误：${err.message}` : `错误：${err.kind}`;
       }
@@ -17,6 +17,8 @@ export interface LayoutConfig {
 export interface GeneralConfig {
   locale: "zh" | "en";
   sendShortcut: "enter" | "modifierEnter";
+  /** Record every agent shell command (no output) to the app data dir */
+  commandAuditLog: boolean;
 }
 
 export interface SkillsConfig {
@@ -40,6 +42,7 @@ export const CONFIG_DEFAULTS = {
   general: {
     locale: "zh",
     sendShortcut: "enter",
+    commandAuditLog: false,
   } satisfies GeneralConfig,
   skills: {
     enabled: [],